@@ -5,12 +5,13 @@ use binius_core::{
 	challenger::HashChallenger,
 	oracle::{CommittedBatchSpec, CommittedId, CompositePolyOracle, MultilinearOracleSet},
 	polynomial::{
+		composition::{LinearFactor, LinearProductComposition},
 		CompositionPoly, EvaluationDomain, MultilinearComposite, MultilinearExtension,
 		MultilinearPoly,
 	},
 	protocols::{
 		test_utils::{transform_poly, TestProductComposition},
-		zerocheck::{prove, Error as ZerocheckError, ZerocheckClaim},
+		zerocheck::{prove, prove_univariate_skip, Error as ZerocheckError, ZerocheckClaim},
 	},
 	Step,
 };
@@ -19,7 +20,10 @@ use binius_field::{
 	ExtensionField, Field, PackedField, TowerField,
 };
 use binius_hash::GroestlHasher;
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use criterion::{
+	criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion,
+	Throughput,
+};
 use rand::{rngs::ThreadRng, thread_rng};
 use std::{fmt::Debug, mem, sync::Arc};
 
@@ -270,6 +274,133 @@ fn zerocheck_128b_monomial_basis_with_arc(c: &mut Criterion) {
 	}
 }
 
+fn zerocheck_128b_by_degree(c: &mut Criterion) {
+	let n_vars = 14;
+	let mut rng = thread_rng();
+
+	let mut group = c.benchmark_group("Zerocheck 128b product composition by degree");
+	for degree in 2..=8 {
+		// The generic evaluator is the baseline for the evaluator on the linear factors
+		bench_zerocheck_by_degree(
+			&mut group,
+			"generic",
+			&mut rng,
+			n_vars,
+			TestProductComposition::new(degree),
+		);
+		bench_zerocheck_by_degree(
+			&mut group,
+			"linear_factors",
+			&mut rng,
+			n_vars,
+			LinearProductComposition::new(
+				degree,
+				(0..degree).map(LinearFactor::variable).collect(),
+			)
+			.unwrap(),
+		);
+	}
+}
+
+fn bench_zerocheck_by_degree<C>(
+	group: &mut BenchmarkGroup<WallTime>,
+	name: &str,
+	rng: &mut ThreadRng,
+	n_vars: usize,
+	composition: C,
+) where
+	C: CompositionPoly<BinaryField128b> + Clone + 'static,
+{
+	type FTower = BinaryField128b;
+
+	let degree = composition.degree();
+	let domain = EvaluationDomain::<BinaryField32b>::new(degree + 1).unwrap();
+
+	let n = 1 << n_vars;
+	group.throughput(Throughput::Bytes((n * degree * mem::size_of::<FTower>()) as u64));
+	group.bench_with_input(BenchmarkId::new(name, degree), &degree, |b, &degree| {
+		let multilinears = make_multilinears::<FTower>(rng, n_vars, degree)
+			.into_iter()
+			.map(|m| m.specialize_arc_dyn::<FTower>())
+			.collect::<Vec<_>>();
+
+		let poly = MultilinearComposite::new(n_vars, composition.clone(), multilinears).unwrap();
+
+		let zerocheck_claim = make_zerocheck_claim(&poly).unwrap();
+		let zerocheck_witness = poly;
+		let prove_challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+
+		b.iter(|| {
+			prove::<FTower, FTower, BinaryField32b, _, _>(
+				&zerocheck_claim,
+				zerocheck_witness.clone(),
+				&domain,
+				prove_challenger.clone(),
+				|_| 1,
+			)
+		});
+	});
+}
+
+fn zerocheck_128b_univariate_skip_over_1b(c: &mut Criterion) {
+	type FTower = BinaryField128b;
+	type FS = BinaryField8b;
+
+	let n_multilinears = 3;
+	let skip_vars = 3;
+	let composition = TestProductComposition::new(n_multilinears);
+
+	let domain = EvaluationDomain::<FS>::new(n_multilinears + 1).unwrap();
+	let skip_domain =
+		EvaluationDomain::<FS>::new(n_multilinears * ((1 << skip_vars) - 1) + 1).unwrap();
+
+	let mut rng = thread_rng();
+
+	let mut group = c.benchmark_group("Zerocheck 128b over 1b, univariate skip");
+	for &n_vars in [13, 14, 15, 16].iter() {
+		let n = 1 << n_vars;
+		group.throughput(Throughput::Bytes((n * n_multilinears * mem::size_of::<FTower>()) as u64));
+
+		let multilinears = make_multilinears::<BinaryField1b>(&mut rng, n_vars, n_multilinears)
+			.into_iter()
+			.map(|m| m.specialize_arc_dyn::<FTower>())
+			.collect::<Vec<_>>();
+		let zerocheck_witness =
+			MultilinearComposite::new(n_vars, composition.clone(), multilinears).unwrap();
+		let zerocheck_claim = make_zerocheck_claim(&zerocheck_witness).unwrap();
+		let prove_challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+
+		group.bench_with_input(BenchmarkId::new("prove", n_vars), &n_vars, |b, _| {
+			b.iter(|| {
+				prove::<FTower, FTower, FS, _, _>(
+					&zerocheck_claim,
+					zerocheck_witness.clone(),
+					&domain,
+					prove_challenger.clone(),
+					|_| 8,
+				)
+			});
+		});
+		group.bench_with_input(
+			BenchmarkId::new("prove_univariate_skip", n_vars),
+			&n_vars,
+			|b, _| {
+				b.iter(|| {
+					prove_univariate_skip::<FTower, FTower, FS, _, _>(
+						&zerocheck_claim,
+						zerocheck_witness.clone(),
+						skip_vars,
+						&skip_domain,
+						&domain,
+						prove_challenger.clone(),
+						|_| 8,
+					)
+				});
+			},
+		);
+	}
+}
+
 /// Given a zerocheck witness, make zerocheck claim
 pub fn make_zerocheck_claim<F, C, M>(
 	poly: &MultilinearComposite<F, C, M>,
@@ -307,6 +438,8 @@ criterion_group!(
 	zerocheck_128b_over_1b,
 	zerocheck_128b_over_8b,
 	zerocheck_128b_tower_basis_32b_domain,
-	zerocheck_128b_tower_basis_8b_domain
+	zerocheck_128b_tower_basis_8b_domain,
+	zerocheck_128b_by_degree,
+	zerocheck_128b_univariate_skip_over_1b
 );
 criterion_main!(zerocheck);
@@ -1,8 +1,12 @@
 // Copyright 2023-2024 Ulvetanna Inc.
 // Copyright (c) 2022-2023 The Plonky3 Authors
 
-use binius_field::{ExtensionField, Field, PackedExtensionField, PackedField};
-use binius_hash::Hasher;
+use super::domain_separation::{label_scalars, DomainSeparator};
+use crate::merkle_tree::MerkleCap;
+use binius_field::{
+	BinaryField32b, BinaryField8b, ExtensionField, Field, PackedExtensionField, PackedField,
+};
+use binius_hash::{Hasher, Vision32bPermutation};
 use bytemuck::{bytes_of, AnyBitPattern, Pod};
 pub use p3_challenger::{CanObserve, CanSample, CanSampleBits};
 use p3_symmetric::CryptographicPermutation;
@@ -129,7 +133,17 @@ where
 	}
 }
 
-// Duplex challenger for CryptographicPermutation that can fill upto RATE Elements
+/// A duplex sponge challenger over a cryptographic permutation.
+///
+/// Observed elements are buffered and added into the first `RATE` elements of the sponge state,
+/// permuting whenever the buffer is full. Sampling squeezes elements from the rate portion of the
+/// state, and permutes it, absorbing any buffered input, only when input is pending or all `RATE`
+/// squeezed elements have been consumed. The remaining `STATE_SIZE - RATE` elements form the
+/// capacity and are never directly observed or sampled.
+///
+/// Extension field elements are absorbed and squeezed as their coordinates over `F`, so no
+/// conversion to bytes is needed. This makes the challenger cheap to evaluate in an arithmetic
+/// circuit over `F`, which is the intended use for recursive verification.
 #[derive(Clone)]
 pub struct DuplexChallenger<F, H, const RATE: usize, const STATE_SIZE: usize>
 where
@@ -242,6 +256,20 @@ where
 	}
 }
 
+/// Observing a Merkle cap observes its nodes in order.
+impl<F: Field, H, PE> CanObserve<MerkleCap<PE>> for HashChallenger<F, H>
+where
+	F: Field,
+	H: Hasher<F>,
+	H::Digest: PackedField<Scalar = F>,
+	PE: PackedExtensionField<F>,
+	PE::Scalar: ExtensionField<F>,
+{
+	fn observe(&mut self, value: MerkleCap<PE>) {
+		self.observe_scalars(PE::cast_to_bases(&value.0));
+	}
+}
+
 impl<F: Field, H, FO> CanSample<FO> for HashChallenger<F, H>
 where
 	F: Field,
@@ -278,6 +306,20 @@ where
 	}
 }
 
+/// Observing a Merkle cap observes its nodes in order.
+impl<F: Field, H, const RATE: usize, const STATE_SIZE: usize, PE> CanObserve<MerkleCap<PE>>
+	for DuplexChallenger<F, H, RATE, STATE_SIZE>
+where
+	F: Field,
+	H: CryptographicPermutation<[F; STATE_SIZE]>,
+	PE: PackedExtensionField<F>,
+	PE::Scalar: ExtensionField<F>,
+{
+	fn observe(&mut self, value: MerkleCap<PE>) {
+		self.observe_scalars(PE::cast_to_bases(&value.0));
+	}
+}
+
 impl<F: Field, H, const RATE: usize, const STATE_SIZE: usize, FO> CanSample<FO>
 	for DuplexChallenger<F, H, RATE, STATE_SIZE>
 where
@@ -334,6 +376,33 @@ where
 	}
 }
 
+impl<F, H> DomainSeparator for HashChallenger<F, H>
+where
+	F: Field + From<BinaryField8b>,
+	H: Hasher<F>,
+	H::Digest: PackedField<Scalar = F>,
+{
+	fn observe_label(&mut self, label: &[u8]) {
+		self.observe_scalars(&label_scalars(label));
+	}
+}
+
+impl<F, H, const RATE: usize, const STATE_SIZE: usize> DomainSeparator
+	for DuplexChallenger<F, H, RATE, STATE_SIZE>
+where
+	F: Field + From<BinaryField8b>,
+	H: CryptographicPermutation<[F; STATE_SIZE]>,
+{
+	fn observe_label(&mut self, label: &[u8]) {
+		self.observe_scalars(&label_scalars(label));
+	}
+}
+
+/// A duplex sponge challenger over the Vision Mark-32 permutation.
+///
+/// The sponge has a rate of 16 and a capacity of 8 [`BinaryField32b`] elements.
+pub type Vision32bChallenger = DuplexChallenger<BinaryField32b, Vision32bPermutation, 16, 24>;
+
 /// Extract a range of bits from a byte array
 fn get_bits_le(bytes: &[u8], bit_range: Range<usize>) -> u32 {
 	let start_byte = bit_range.start / 8;
@@ -360,10 +429,9 @@ fn get_bits_le(bytes: &[u8], bit_range: Range<usize>) -> u32 {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use binius_field::{
-		BinaryField128b, BinaryField32b, BinaryField64b, BinaryField8b, PackedBinaryField4x64b,
-	};
-	use binius_hash::{GroestlHasher, Vision32bPermutation};
+	use binius_field::{BinaryField128b, BinaryField64b, PackedBinaryField4x64b};
+	use binius_hash::GroestlHasher;
+	use p3_symmetric::Permutation;
 	use rand::{thread_rng, Rng};
 
 	#[test]
@@ -391,8 +459,6 @@ mod tests {
 		let _: BinaryField128b = challenger.sample();
 	}
 
-	type Vision32bChallenger = DuplexChallenger<BinaryField32b, Vision32bPermutation, 16, 24>;
-
 	#[test]
 	fn test_duplex_challenger_can_sample_ext_field() {
 		let mut challenger = Vision32bChallenger::new();
@@ -434,4 +500,49 @@ mod tests {
 			assert_eq!(output, last << first_bits | first);
 		}
 	}
+
+	#[test]
+	fn test_vision_challenger_matches_reference_sponge() {
+		type F = BinaryField32b;
+
+		// Run a fixed script covering base and extension field elements, sampling bits, and
+		// observing after sampling.
+		let mut challenger = Vision32bChallenger::new();
+		let input_128b = BinaryField128b::new(0x0123456789abcdef_fedcba9876543210);
+		challenger.observe_slice(&[F::new(1), F::new(2)]);
+		let sample_32b: F = challenger.sample();
+		challenger.observe(input_128b);
+		let bits = challenger.sample_bits(20);
+		challenger.observe_slice(&[F::new(3); 20]);
+		let sample_128b: BinaryField128b = challenger.sample();
+
+		// The same script on a plain duplex sponge: every squeeze after an absorption adds the
+		// pending input into the first rate elements of the state and permutes, a full rate of
+		// input is absorbed immediately, and squeezed elements are read from the start of the
+		// state.
+		let permutation = Vision32bPermutation::default();
+		let mut state = [F::ZERO; 24];
+		let absorb = |state: &mut [F; 24], input: &[F]| {
+			assert!(input.len() <= 16);
+			for (state_i, &input_i) in state.iter_mut().zip(input) {
+				*state_i += input_i;
+			}
+			permutation.permute_mut(state);
+		};
+
+		absorb(&mut state, &[F::new(1), F::new(2)]);
+		assert_eq!(sample_32b, state[0]);
+
+		let input_limbs = [0x76543210, 0xfedcba98, 0x89abcdef, 0x01234567].map(F::new);
+		assert_eq!(
+			<BinaryField128b as ExtensionField<F>>::iter_bases(&input_128b).collect::<Vec<_>>(),
+			input_limbs
+		);
+		absorb(&mut state, &input_limbs);
+		assert_eq!(bits, state[0].val() as usize & ((1 << 20) - 1));
+
+		absorb(&mut state, &[F::new(3); 16]);
+		absorb(&mut state, &[F::new(3); 4]);
+		assert_eq!(sample_128b, BinaryField128b::from_bases(&state[..4]).unwrap());
+	}
 }
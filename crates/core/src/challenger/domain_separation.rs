@@ -0,0 +1,188 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Domain separation for Fiat-Shamir challengers.
+//!
+//! A challenger absorbs a flat stream of field elements, so two sub-protocols that observe the
+//! same values derive the same challenges. The [`DomainSeparator`] trait lets protocols bind each
+//! message and challenge to a label naming its role, such as `"zerocheck.round"` or
+//! `"pcs.mixing"`, and lets an application bind the whole proof to a domain tag describing the
+//! statement being proven. Labels are absorbed together with their length, so distinct sequences
+//! of labels are never absorbed identically.
+//!
+//! Labels only affect the challenger state. They are not part of the proof, and a
+//! [`TranscriptWriter`](super::TranscriptWriter) forwards them to its challenger without writing anything.
+
+use super::{CanObserve, CanSample, CanSampleBits};
+use crate::serialization::SerializeBytes;
+use binius_field::BinaryField8b;
+
+/// A challenger that can absorb domain separation labels.
+pub trait DomainSeparator {
+	/// Absorb a label.
+	fn observe_label(&mut self, label: &[u8]);
+
+	/// Absorb the initial domain tag of a protocol instance.
+	///
+	/// The tag consists of the protocol name and the canonical encoding of the statement, which
+	/// should include everything both parties agree on before the protocol starts, such as the
+	/// oracle set and the protocol parameters.
+	fn observe_domain_tag<S: SerializeBytes + ?Sized>(&mut self, protocol: &str, statement: &S) {
+		let mut statement_bytes = Vec::new();
+		statement.serialize(&mut statement_bytes);
+		self.observe_label(protocol.as_bytes());
+		self.observe_label(&statement_bytes);
+	}
+
+	/// Observe a value after absorbing the label.
+	fn observe_labeled<T>(&mut self, label: &str, value: T)
+	where
+		Self: CanObserve<T>,
+	{
+		self.observe_label(label.as_bytes());
+		self.observe(value);
+	}
+
+	/// Observe a slice of values after absorbing the label.
+	fn observe_slice_labeled<T: Clone>(&mut self, label: &str, values: &[T])
+	where
+		Self: CanObserve<T>,
+	{
+		self.observe_label(label.as_bytes());
+		self.observe_slice(values);
+	}
+
+	/// Sample a value after absorbing the label.
+	fn sample_labeled<T>(&mut self, label: &str) -> T
+	where
+		Self: CanSample<T>,
+	{
+		self.observe_label(label.as_bytes());
+		self.sample()
+	}
+
+	/// Sample `n` values after absorbing the label.
+	fn sample_vec_labeled<T>(&mut self, label: &str, n: usize) -> Vec<T>
+	where
+		Self: CanSample<T>,
+	{
+		self.observe_label(label.as_bytes());
+		self.sample_vec(n)
+	}
+
+	/// Sample a usize with `bits` bits after absorbing the label.
+	fn sample_bits_labeled(&mut self, label: &str, bits: usize) -> usize
+	where
+		Self: CanSampleBits<usize>,
+	{
+		self.observe_label(label.as_bytes());
+		self.sample_bits(bits)
+	}
+}
+
+/// Encode a label as a sequence of field elements, prefixed by its length in bytes.
+pub(super) fn label_scalars<F: From<BinaryField8b>>(label: &[u8]) -> Vec<F> {
+	let mut bytes = Vec::with_capacity(label.len() + 8);
+	label.len().serialize(&mut bytes);
+	bytes.extend_from_slice(label);
+	bytes
+		.into_iter()
+		.map(|byte| BinaryField8b::new(byte).into())
+		.collect()
+}
+
+impl<C: DomainSeparator> DomainSeparator for &mut C {
+	fn observe_label(&mut self, label: &[u8]) {
+		(**self).observe_label(label);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		challenger::{HashChallenger, Vision32bChallenger},
+		oracle::{CommittedBatchSpec, CommittedId, MultilinearOracleSet, ShiftVariant},
+	};
+	use binius_field::BinaryField128b;
+	use binius_hash::GroestlHasher;
+
+	type Challenger = HashChallenger<BinaryField8b, GroestlHasher<BinaryField8b>>;
+
+	#[test]
+	fn test_labels_separate_challenges() {
+		let values = [BinaryField128b::new(1), BinaryField128b::new(2)];
+
+		let mut challenger_a = Challenger::new();
+		challenger_a.observe_slice_labeled("zerocheck.round", &values);
+		let challenge_a: BinaryField128b = challenger_a.sample();
+
+		let mut challenger_b = Challenger::new();
+		challenger_b.observe_slice_labeled("sumcheck.round", &values);
+		let challenge_b: BinaryField128b = challenger_b.sample();
+
+		let mut challenger_c = Challenger::new();
+		challenger_c.observe_slice(&values);
+		let challenge_c: BinaryField128b = challenger_c.sample();
+
+		assert_ne!(challenge_a, challenge_b);
+		assert_ne!(challenge_a, challenge_c);
+		assert_ne!(challenge_b, challenge_c);
+
+		// Labels are length-prefixed, so splitting a label differently changes the challenges
+		let mut challenger_d = Challenger::new();
+		challenger_d.observe_label(b"ab");
+		challenger_d.observe_label(b"c");
+		let mut challenger_e = Challenger::new();
+		challenger_e.observe_label(b"a");
+		challenger_e.observe_label(b"bc");
+		assert_ne!(
+			CanSample::<BinaryField128b>::sample(&mut challenger_d),
+			CanSample::<BinaryField128b>::sample(&mut challenger_e)
+		);
+	}
+
+	#[test]
+	fn test_domain_tag_binds_statement() {
+		let mut challenger_a = Vision32bChallenger::new();
+		challenger_a.observe_domain_tag("example", &(10usize, 3usize));
+		let mut challenger_b = Vision32bChallenger::new();
+		challenger_b.observe_domain_tag("example", &(11usize, 3usize));
+		let mut challenger_c = Vision32bChallenger::new();
+		challenger_c.observe_domain_tag("example2", &(10usize, 3usize));
+
+		let challenge_a: BinaryField128b = challenger_a.sample_labeled("challenge");
+		let challenge_b: BinaryField128b = challenger_b.sample_labeled("challenge");
+		let challenge_c: BinaryField128b = challenger_c.sample_labeled("challenge");
+		assert_ne!(challenge_a, challenge_b);
+		assert_ne!(challenge_a, challenge_c);
+	}
+
+	#[test]
+	fn test_domain_tag_binds_oracle_set() {
+		let mut oracles = MultilinearOracleSet::<BinaryField128b>::new();
+		let batch_id = oracles.add_committed_batch(CommittedBatchSpec {
+			n_vars: 8,
+			n_polys: 1,
+			tower_level: 0,
+		});
+		let committed_id = oracles.committed_oracle_id(CommittedId { batch_id, index: 0 });
+
+		let mut shifted_left = oracles.clone();
+		shifted_left
+			.add_shifted(committed_id, 1, 8, ShiftVariant::LogicalLeft)
+			.unwrap();
+		let mut shifted_right = oracles;
+		shifted_right
+			.add_shifted(committed_id, 1, 8, ShiftVariant::LogicalRight)
+			.unwrap();
+
+		let mut challenger_left = Challenger::new();
+		challenger_left.observe_domain_tag("example", &shifted_left);
+		let mut challenger_right = Challenger::new();
+		challenger_right.observe_domain_tag("example", &shifted_right);
+		assert_ne!(
+			CanSample::<BinaryField128b>::sample(&mut challenger_left),
+			CanSample::<BinaryField128b>::sample(&mut challenger_right)
+		);
+	}
+}
@@ -0,0 +1,9 @@
+// Copyright 2024 Ulvetanna Inc.
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("cannot grind {bits} bits of proof of work, the maximum is {max}")]
+	InvalidGrindingBits { bits: usize, max: usize },
+	#[error("no proof-of-work nonce with {bits} zero bits found in {attempts} attempts")]
+	NonceNotFound { bits: usize, attempts: u64 },
+}
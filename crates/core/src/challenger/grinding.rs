@@ -0,0 +1,196 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Proof-of-work grinding for Fiat-Shamir challengers.
+//!
+//! Before sampling a challenge, the prover can be required to find a nonce such that after the
+//! challenger observes it, the next `bits` sampled bits are all zero. Finding the nonce takes
+//! $2^{bits}$ attempts in expectation, and every attempt to steer the following challenge costs
+//! the prover the same amount of work, so the grinding phase adds `bits` bits of security to the
+//! challenges sampled after it. This lets protocols with many repeated queries, such as the column
+//! tests of [`TensorPCS`](crate::poly_commit::tensor_pcs::TensorPCS), trade query count for prover
+//! time.
+//!
+//! The prover grinds with [`CanGrind::grind`], which observes the nonce it finds and consumes the
+//! `bits` sampled bits. The verifier observes the nonce from the proof and then calls
+//! [`check_grinding`], which only requires [`CanSampleBits`], so both challengers stay in sync.
+
+use super::{CanObserve, CanSampleBits, DuplexChallenger, Error, HashChallenger};
+use binius_field::{BinaryField, BinaryField1b, ExtensionField, Field, PackedField};
+use binius_hash::Hasher;
+use p3_symmetric::CryptographicPermutation;
+
+/// The largest number of proof-of-work bits a challenger grinds.
+///
+/// Grinding takes $2^{bits}$ attempts in expectation, so larger values are impractical. The bound
+/// also keeps the sampled bits within a `usize` on 32-bit targets.
+pub const MAX_GRINDING_BITS: usize = 30;
+
+/// A challenger that can find proof-of-work nonces of type `N`.
+pub trait CanGrind<N> {
+	/// Find a nonce whose observation makes the next `bits` sampled bits zero.
+	///
+	/// The nonce is observed and the `bits` bits are sampled, leaving the challenger in the same
+	/// state as a verifier that observes the nonce and calls [`check_grinding`].
+	///
+	/// Throws if `bits` exceeds [`MAX_GRINDING_BITS`], or if no nonce is found, which happens
+	/// with negligible probability unless `N` has too few elements to reach `bits` zero bits.
+	fn grind(&mut self, bits: usize) -> Result<N, Error>;
+}
+
+/// Sample `bits` bits after the proof-of-work nonce has been observed and check they are all zero.
+pub fn check_grinding<C: CanSampleBits<usize> + ?Sized>(challenger: &mut C, bits: usize) -> bool {
+	challenger.sample_bits(bits) == 0
+}
+
+/// Try nonces counting up from zero on copies of the challenger until one passes the grinding
+/// check.
+///
+/// The search is deterministic, so the prover produces the same proof on every run. It gives up
+/// after $2^{bits + 8}$ attempts, which fails with probability about $e^{-256}$, or earlier once
+/// the nonces of `N` are exhausted, so that a nonce type too small to pass the check does not
+/// make the prover loop forever.
+pub(super) fn find_nonce<C, N>(
+	challenger: &C,
+	bits: usize,
+	observe: impl Fn(&mut C, N),
+) -> Result<N, Error>
+where
+	C: CanSampleBits<usize> + Clone,
+	N: BinaryField,
+{
+	if bits > MAX_GRINDING_BITS {
+		return Err(Error::InvalidGrindingBits {
+			bits,
+			max: MAX_GRINDING_BITS,
+		});
+	}
+
+	let attempts = 1u64 << (bits + 8).min(N::N_BITS);
+	for counter in 0..attempts {
+		let nonce = nonce_from_counter::<N>(counter);
+		let mut trial = challenger.clone();
+		observe(&mut trial, nonce);
+		if check_grinding(&mut trial, bits) {
+			return Ok(nonce);
+		}
+	}
+	Err(Error::NonceNotFound { bits, attempts })
+}
+
+/// The field element whose coordinates in the $\mathbb{F}_2$-basis of `N` are the bits of
+/// `counter`.
+fn nonce_from_counter<N: BinaryField>(counter: u64) -> N {
+	(0..N::N_BITS.min(u64::BITS as usize))
+		.filter(|&i| (counter >> i) & 1 == 1)
+		.map(|i| {
+			<N as ExtensionField<BinaryField1b>>::basis(i).expect("i is less than the degree of N")
+		})
+		.sum()
+}
+
+impl<F, H, N> CanGrind<N> for HashChallenger<F, H>
+where
+	F: Field,
+	H: Hasher<F> + Clone,
+	H::Digest: PackedField<Scalar = F>,
+	N: BinaryField,
+	Self: CanObserve<N> + CanSampleBits<usize>,
+{
+	fn grind(&mut self, bits: usize) -> Result<N, Error> {
+		let nonce =
+			find_nonce(self, bits, |challenger: &mut Self, nonce: N| challenger.observe(nonce))?;
+		self.observe(nonce);
+		let passed = check_grinding(self, bits);
+		debug_assert!(passed);
+		Ok(nonce)
+	}
+}
+
+impl<F, H, N, const RATE: usize, const STATE_SIZE: usize> CanGrind<N>
+	for DuplexChallenger<F, H, RATE, STATE_SIZE>
+where
+	F: Field,
+	H: CryptographicPermutation<[F; STATE_SIZE]> + Clone,
+	N: BinaryField,
+	Self: CanObserve<N> + CanSampleBits<usize>,
+{
+	fn grind(&mut self, bits: usize) -> Result<N, Error> {
+		let nonce =
+			find_nonce(self, bits, |challenger: &mut Self, nonce: N| challenger.observe(nonce))?;
+		self.observe(nonce);
+		let passed = check_grinding(self, bits);
+		debug_assert!(passed);
+		Ok(nonce)
+	}
+}
+
+impl<C: CanGrind<N>, N> CanGrind<N> for &mut C {
+	fn grind(&mut self, bits: usize) -> Result<N, Error> {
+		(**self).grind(bits)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::challenger::{CanSample, TranscriptReader, TranscriptWriter, Vision32bChallenger};
+	use assert_matches::assert_matches;
+	use binius_field::{BinaryField128b, BinaryField8b};
+	use binius_hash::GroestlHasher;
+
+	type Challenger = HashChallenger<BinaryField8b, GroestlHasher<BinaryField8b>>;
+
+	#[test]
+	fn test_grinding() {
+		let bits = 8;
+
+		let mut prover = Challenger::new();
+		let nonce: BinaryField128b = prover.grind(bits).unwrap();
+
+		let mut verifier = Challenger::new();
+		verifier.observe(nonce);
+		assert!(check_grinding(&mut verifier, bits));
+
+		// Both ends are in the same state after grinding
+		let prover_challenge: BinaryField128b = prover.sample();
+		let verifier_challenge: BinaryField128b = verifier.sample();
+		assert_eq!(prover_challenge, verifier_challenge);
+
+		let mut prover = Vision32bChallenger::new();
+		let nonce: BinaryField128b = prover.grind(bits).unwrap();
+		let mut verifier = Vision32bChallenger::new();
+		verifier.observe(nonce);
+		assert!(check_grinding(&mut verifier, bits));
+	}
+
+	#[test]
+	fn test_grinding_deterministic() {
+		let bits = 8;
+		let first: BinaryField128b = Challenger::new().grind(bits).unwrap();
+		let second: BinaryField128b = Challenger::new().grind(bits).unwrap();
+		assert_eq!(first, second);
+	}
+
+	#[test]
+	fn test_grinding_too_many_bits() {
+		let mut prover = Challenger::new();
+		assert_matches!(
+			CanGrind::<BinaryField128b>::grind(&mut prover, MAX_GRINDING_BITS + 1),
+			Err(Error::InvalidGrindingBits { .. })
+		);
+	}
+
+	#[test]
+	fn test_grinding_with_transcript() {
+		let bits = 8;
+
+		let mut writer = TranscriptWriter::new(Challenger::new());
+		let nonce: BinaryField128b = writer.grind(bits).unwrap();
+		let proof = writer.finalize();
+
+		let mut reader = TranscriptReader::new(Challenger::new(), &proof);
+		assert_eq!(reader.read::<BinaryField128b>().unwrap(), nonce);
+		assert!(check_grinding(&mut reader, bits));
+		reader.finalize().unwrap();
+	}
+}
@@ -0,0 +1,14 @@
+// Copyright 2024 Ulvetanna Inc.
+
+#[allow(clippy::module_inception)]
+mod challenger;
+mod domain_separation;
+mod error;
+mod grinding;
+mod transcript;
+
+pub use challenger::*;
+pub use domain_separation::*;
+pub use error::*;
+pub use grinding::*;
+pub use transcript::*;
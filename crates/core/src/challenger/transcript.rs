@@ -0,0 +1,245 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Fiat-Shamir transcripts over a byte stream.
+//!
+//! A non-interactive proof is the sequence of prover messages in an interactive protocol, with the
+//! verifier challenges derived from a challenger that absorbs every message as it is sent. The
+//! [`TranscriptWriter`] is the prover's end of this stream: each message is serialized with its
+//! canonical byte encoding, appended to the proof, and the same bytes are observed by the
+//! challenger. The [`TranscriptReader`] is the verifier's end: each message is decoded from the
+//! front of the proof and its bytes are observed in the same way. Because observation is done by the
+//! transcript itself, the challengers on both ends absorb exactly the proof bytes, and a message
+//! cannot be sent without being bound into all later challenges.
+//!
+//! Both ends implement [`CanSample`] and [`CanSampleBits`], and the writer implements
+//! [`CanObserve`] by sending the observed value, so that provers written against a challenger can
+//! run against a transcript directly. Observed values are written without any framing, so the
+//! verifier is expected to know the number of values in each message, as it does when checking
+//! the shape of a proof object.
+
+use super::{
+	check_grinding, grinding::find_nonce, CanGrind, CanObserve, CanSample, CanSampleBits,
+	DomainSeparator, Error,
+};
+use crate::serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes};
+use binius_field::{BinaryField, BinaryField8b};
+use bytemuck::must_cast_slice;
+
+/// The prover's end of a Fiat-Shamir transcript.
+#[derive(Debug, Clone)]
+pub struct TranscriptWriter<C> {
+	challenger: C,
+	buffer: Vec<u8>,
+}
+
+impl<C> TranscriptWriter<C>
+where
+	C: CanObserve<BinaryField8b>,
+{
+	pub fn new(challenger: C) -> Self {
+		Self {
+			challenger,
+			buffer: Vec::new(),
+		}
+	}
+
+	/// Send a message to the verifier.
+	pub fn write<T: SerializeBytes + ?Sized>(&mut self, message: &T) {
+		let start = self.buffer.len();
+		message.serialize(&mut self.buffer);
+		self.challenger
+			.observe_slice(must_cast_slice(&self.buffer[start..]));
+	}
+
+	/// Returns the proof bytes written so far.
+	pub fn finalize(self) -> Vec<u8> {
+		self.buffer
+	}
+}
+
+/// Observing a value with the writer sends it to the verifier.
+impl<C, T> CanObserve<T> for TranscriptWriter<C>
+where
+	C: CanObserve<BinaryField8b>,
+	T: SerializeBytes,
+{
+	fn observe(&mut self, value: T) {
+		self.write(&value);
+	}
+}
+
+impl<C, F> CanSample<F> for TranscriptWriter<C>
+where
+	C: CanSample<F>,
+{
+	fn sample(&mut self) -> F {
+		self.challenger.sample()
+	}
+}
+
+impl<C> CanSampleBits<usize> for TranscriptWriter<C>
+where
+	C: CanSampleBits<usize>,
+{
+	fn sample_bits(&mut self, bits: usize) -> usize {
+		self.challenger.sample_bits(bits)
+	}
+}
+
+/// The writer grinds on its challenger alone, without copying the proof written so far, and then
+/// sends the nonce.
+impl<C, N> CanGrind<N> for TranscriptWriter<C>
+where
+	C: CanObserve<BinaryField8b> + CanSampleBits<usize> + Clone,
+	N: BinaryField + SerializeBytes,
+{
+	fn grind(&mut self, bits: usize) -> Result<N, Error> {
+		let nonce = find_nonce(&self.challenger, bits, |challenger: &mut C, nonce: N| {
+			let mut bytes = Vec::new();
+			nonce.serialize(&mut bytes);
+			challenger.observe_slice(must_cast_slice(&bytes));
+		})?;
+		self.write(&nonce);
+		let passed = check_grinding(self, bits);
+		debug_assert!(passed);
+		Ok(nonce)
+	}
+}
+
+/// Labels are absorbed by the challenger and are not written to the proof.
+impl<C: DomainSeparator> DomainSeparator for TranscriptWriter<C> {
+	fn observe_label(&mut self, label: &[u8]) {
+		self.challenger.observe_label(label);
+	}
+}
+
+/// The verifier's end of a Fiat-Shamir transcript.
+#[derive(Debug, Clone)]
+pub struct TranscriptReader<'a, C> {
+	challenger: C,
+	read_buf: &'a [u8],
+}
+
+impl<'a, C> TranscriptReader<'a, C>
+where
+	C: CanObserve<BinaryField8b>,
+{
+	pub fn new(challenger: C, proof: &'a [u8]) -> Self {
+		Self {
+			challenger,
+			read_buf: proof,
+		}
+	}
+
+	/// Receive the next message from the prover.
+	pub fn read<T: DeserializeBytes>(&mut self) -> Result<T, SerializationError> {
+		let start = self.read_buf;
+		let message = T::deserialize(&mut self.read_buf)?;
+		self.observe_read(start);
+		Ok(message)
+	}
+
+	/// Receive `n` values observed by the prover, which are sent without a length prefix.
+	pub fn read_vec<T: DeserializeBytes>(
+		&mut self,
+		n: usize,
+	) -> Result<Vec<T>, SerializationError> {
+		let start = self.read_buf;
+		let values = (0..n)
+			.map(|_| T::deserialize(&mut self.read_buf))
+			.collect::<Result<_, _>>()?;
+		self.observe_read(start);
+		Ok(values)
+	}
+
+	fn observe_read(&mut self, start: &[u8]) {
+		let n_read = start.len() - self.read_buf.len();
+		self.challenger
+			.observe_slice(must_cast_slice(&start[..n_read]));
+	}
+
+	/// Checks that the entire proof has been read.
+	pub fn finalize(self) -> Result<(), SerializationError> {
+		if !self.read_buf.is_empty() {
+			return Err(SerializationError::TrailingBytes {
+				n_bytes: self.read_buf.len(),
+			});
+		}
+		Ok(())
+	}
+}
+
+impl<C, F> CanSample<F> for TranscriptReader<'_, C>
+where
+	C: CanSample<F>,
+{
+	fn sample(&mut self) -> F {
+		self.challenger.sample()
+	}
+}
+
+impl<C> CanSampleBits<usize> for TranscriptReader<'_, C>
+where
+	C: CanSampleBits<usize>,
+{
+	fn sample_bits(&mut self, bits: usize) -> usize {
+		self.challenger.sample_bits(bits)
+	}
+}
+
+impl<C: DomainSeparator> DomainSeparator for TranscriptReader<'_, C> {
+	fn observe_label(&mut self, label: &[u8]) {
+		self.challenger.observe_label(label);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::challenger::HashChallenger;
+	use binius_field::BinaryField128b;
+	use binius_hash::GroestlHasher;
+
+	type Challenger = HashChallenger<BinaryField8b, GroestlHasher<BinaryField8b>>;
+
+	#[test]
+	fn test_transcript_round_trip() {
+		let mut writer = TranscriptWriter::new(Challenger::new());
+		writer.write(&BinaryField128b::new(5));
+		let prover_challenge: BinaryField128b = writer.sample();
+		writer.observe_slice(&[BinaryField128b::new(7), prover_challenge]);
+		let prover_bits = writer.sample_bits(10);
+		let proof = writer.finalize();
+
+		let mut reader = TranscriptReader::new(Challenger::new(), &proof);
+		assert_eq!(reader.read::<BinaryField128b>().unwrap(), BinaryField128b::new(5));
+		let verifier_challenge: BinaryField128b = reader.sample();
+		assert_eq!(verifier_challenge, prover_challenge);
+		assert_eq!(
+			reader.read_vec::<BinaryField128b>(2).unwrap(),
+			vec![BinaryField128b::new(7), prover_challenge]
+		);
+		assert_eq!(reader.sample_bits(10), prover_bits);
+		reader.finalize().unwrap();
+	}
+
+	#[test]
+	fn test_transcript_binds_messages() {
+		let mut writer = TranscriptWriter::new(Challenger::new());
+		writer.write(&3u64);
+		let prover_challenge: BinaryField128b = writer.sample();
+		let mut proof = writer.finalize();
+		proof.push(0);
+
+		let mut reader = TranscriptReader::new(Challenger::new(), &proof[..8]);
+		assert!(reader.read::<u64>().is_ok());
+		assert_eq!(CanSample::<BinaryField128b>::sample(&mut reader), prover_challenge);
+
+		// Tampering with a message changes the challenges derived after it
+		proof[0] ^= 1;
+		let mut reader = TranscriptReader::new(Challenger::new(), &proof);
+		assert_eq!(reader.read::<u64>().unwrap(), 2);
+		assert_ne!(CanSample::<BinaryField128b>::sample(&mut reader), prover_challenge);
+		assert!(matches!(reader.finalize(), Err(SerializationError::TrailingBytes { n_bytes: 1 })));
+	}
+}
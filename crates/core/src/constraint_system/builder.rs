@@ -0,0 +1,135 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	constraint_system::{Constraint, ConstraintSystem},
+	error::Error,
+};
+use crate::{
+	oracle::{MultilinearOracleSet, OracleId, ShiftVariant},
+	polynomial::{composition::ArithExpr, MultivariatePoly},
+};
+use binius_field::TowerField;
+use std::collections::BTreeSet;
+
+/// A builder for a [`ConstraintSystem`].
+///
+/// Columns are declared as oracles, and constraints are arithmetic expressions whose variables are
+/// oracle IDs. The shortcuts cover the common oracle kinds; the underlying oracle set is available
+/// through [`Self::oracles_mut`] for the others.
+#[derive(Debug)]
+pub struct ConstraintSystemBuilder<F: TowerField> {
+	oracles: MultilinearOracleSet<F>,
+	constraints: Vec<Constraint<F>>,
+}
+
+impl<F: TowerField> ConstraintSystemBuilder<F> {
+	#[allow(clippy::new_without_default)]
+	pub fn new() -> Self {
+		Self {
+			oracles: MultilinearOracleSet::new(),
+			constraints: Vec::new(),
+		}
+	}
+
+	pub fn oracles(&self) -> &MultilinearOracleSet<F> {
+		&self.oracles
+	}
+
+	pub fn oracles_mut(&mut self) -> &mut MultilinearOracleSet<F> {
+		&mut self.oracles
+	}
+
+	/// Add a batch of `N` committed columns.
+	pub fn add_committed_multiple<const N: usize>(
+		&mut self,
+		n_vars: usize,
+		tower_level: usize,
+	) -> [OracleId; N] {
+		let mut batch_scope = self.oracles.build_committed_batch(n_vars, tower_level);
+		let ids = batch_scope.add_multiple();
+		batch_scope.build();
+		ids
+	}
+
+	pub fn add_transparent(
+		&mut self,
+		poly: impl MultivariatePoly<F> + 'static,
+	) -> Result<OracleId, Error> {
+		Ok(self.oracles.add_transparent(poly)?)
+	}
+
+	pub fn add_shifted(
+		&mut self,
+		id: OracleId,
+		offset: usize,
+		block_bits: usize,
+		variant: ShiftVariant,
+	) -> Result<OracleId, Error> {
+		Ok(self.oracles.add_shifted(id, offset, block_bits, variant)?)
+	}
+
+	pub fn add_packed(&mut self, id: OracleId, log_degree: usize) -> Result<OracleId, Error> {
+		Ok(self.oracles.add_packed(id, log_degree)?)
+	}
+
+	pub fn add_linear_combination(
+		&mut self,
+		n_vars: usize,
+		inner: impl IntoIterator<Item = (OracleId, F)>,
+	) -> Result<OracleId, Error> {
+		Ok(self.oracles.add_linear_combination(n_vars, inner)?)
+	}
+
+	/// Constrain `expr` to vanish on every row. The variables of `expr` are oracle IDs.
+	pub fn assert_zero(&mut self, name: impl ToString, expr: ArithExpr<F>) {
+		self.constraints.push(Constraint {
+			name: name.to_string(),
+			expr,
+		});
+	}
+
+	pub fn build(self) -> Result<ConstraintSystem<F>, Error> {
+		let Self {
+			oracles,
+			constraints,
+		} = self;
+
+		if constraints.is_empty() {
+			return Err(Error::NoConstraints);
+		}
+
+		let mut columns = BTreeSet::new();
+		for constraint in constraints.iter() {
+			let vars = constraint.expr.vars();
+			if vars.is_empty() {
+				return Err(Error::ConstantConstraint {
+					name: constraint.name.clone(),
+				});
+			}
+			if let Some(&id) = vars.iter().find(|&&id| id >= oracles.size()) {
+				return Err(Error::UnknownOracle {
+					name: constraint.name.clone(),
+					id,
+				});
+			}
+			columns.extend(vars);
+		}
+		let columns = columns.into_iter().collect::<Vec<_>>();
+
+		let n_vars = oracles.n_vars(columns[0]);
+		if let Some(&id) = columns.iter().find(|&&id| oracles.n_vars(id) != n_vars) {
+			return Err(Error::ColumnNumberOfVariablesMismatch {
+				id,
+				expected: n_vars,
+				n_vars: oracles.n_vars(id),
+			});
+		}
+
+		Ok(ConstraintSystem {
+			oracles,
+			constraints,
+			columns,
+			n_vars,
+		})
+	}
+}
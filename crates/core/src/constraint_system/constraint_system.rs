@@ -0,0 +1,241 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::error::Error;
+use crate::{
+	oracle::{CompositePolyOracle, MultilinearOracleSet, OracleId},
+	polynomial::{
+		composition::{empty_mix_composition, ArithExpr, ExprComposition},
+		CompositionPoly, MultilinearExtension,
+	},
+	protocols::{greedy_evalcheck::GreedyEvalcheckProof, zerocheck::ZerocheckProof},
+	serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes},
+	witness::{
+		fill_derived_witnesses, find_constraint_violations, ConstraintViolation,
+		MultilinearWitness, MultilinearWitnessIndex,
+	},
+};
+use binius_field::{ExtensionField, Field, PackedField, TowerField};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A named polynomial constraint that must vanish on every row of its columns.
+///
+/// The variables of the expression are oracle IDs.
+#[derive(Debug, Clone)]
+pub struct Constraint<F> {
+	pub name: String,
+	pub expr: ArithExpr<F>,
+}
+
+/// A set of oracles together with polynomial constraints on them.
+///
+/// Constraint systems are created with a [`super::ConstraintSystemBuilder`]. All constraints are
+/// checked by a single zerocheck over the columns referenced by any constraint, so these columns
+/// must all have the same number of variables.
+#[derive(Debug, Clone)]
+pub struct ConstraintSystem<F: TowerField> {
+	pub(super) oracles: MultilinearOracleSet<F>,
+	pub(super) constraints: Vec<Constraint<F>>,
+	pub(super) columns: Vec<OracleId>,
+	pub(super) n_vars: usize,
+}
+
+impl<F: TowerField> ConstraintSystem<F> {
+	pub fn oracles(&self) -> &MultilinearOracleSet<F> {
+		&self.oracles
+	}
+
+	pub fn constraints(&self) -> &[Constraint<F>] {
+		&self.constraints
+	}
+
+	/// The oracles referenced by the constraints, in increasing order of ID.
+	pub fn columns(&self) -> &[OracleId] {
+		&self.columns
+	}
+
+	/// The number of variables of the constraint columns.
+	pub fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	/// The constraints as compositions over [`Self::columns`], with constants mapped into `FW`.
+	pub fn compositions<FW>(&self) -> Vec<ExprComposition<FW>>
+	where
+		FW: TowerField + From<F>,
+	{
+		self.constraints
+			.iter()
+			.map(|constraint| {
+				let expr = constraint.expr.convert_field::<FW>().remap_vars(&|id| {
+					self.columns
+						.binary_search(&id)
+						.expect("columns contain all oracles referenced by constraints")
+				});
+				ExprComposition::new(self.columns.len(), expr)
+					.expect("variables were remapped to column indices")
+			})
+			.collect()
+	}
+
+	/// The constraints batched into a single composition with powers of `challenge`.
+	pub fn mixed_composition<FW>(
+		&self,
+		challenge: FW,
+	) -> Result<impl CompositionPoly<FW> + Clone + 'static, Error>
+	where
+		FW: TowerField + From<F>,
+	{
+		let mix = empty_mix_composition(self.columns.len(), challenge);
+		Ok(mix.include(self.compositions())?)
+	}
+
+	/// Evaluate the constraints on every row of a witness and report the rows where they fail.
+	///
+	/// This is a debugging aid to run before [`super::prove`], which does not check the witness and
+	/// produces a proof that fails verification if a constraint is violated. Witnesses of derived
+	/// oracles that are not given are computed as in the prover. Returns up to `max_violations`
+	/// violations in row order, each with the values of the constraint's columns on the row.
+	pub fn check_witness<P, PW>(
+		&self,
+		witness: &ConstraintSystemWitness<P, PW>,
+		max_violations: usize,
+	) -> Result<Vec<ConstraintViolation<PW>>, Error>
+	where
+		P: PackedField,
+		PW: TowerField + ExtensionField<P::Scalar> + From<F>,
+	{
+		let mut witness_index = witness.index.clone();
+		for (&id, poly) in witness.committed.iter() {
+			witness_index.set(id, poly.to_ref().specialize_arc_dyn());
+		}
+		fill_derived_witnesses(&self.oracles, &mut witness_index)?;
+
+		// The mixing challenge is irrelevant, the oracle only determines the columns
+		let oracle = CompositePolyOracle::new(
+			self.n_vars,
+			self.columns
+				.iter()
+				.map(|&id| self.oracles.oracle(id))
+				.collect(),
+			self.mixed_composition(F::ONE)?,
+		)?;
+		let names = self.constraints.iter().map(|constraint| &constraint.name);
+		let mut violations = find_constraint_violations(
+			&oracle,
+			names.zip(self.compositions::<PW>()),
+			&witness_index,
+			max_violations,
+		)?;
+
+		// Only report the columns referenced by the constraints with the violated name
+		for violation in violations.iter_mut() {
+			let vars = self
+				.constraints
+				.iter()
+				.filter(|constraint| constraint.name == violation.name)
+				.flat_map(|constraint| constraint.expr.vars())
+				.collect::<BTreeSet<_>>();
+			violation.values.retain(|(id, _)| vars.contains(id));
+		}
+
+		Ok(violations)
+	}
+}
+
+/// The canonical encoding of a constraint system, for use in the Fiat-Shamir domain tag.
+///
+/// Constraint names are not part of the statement and are omitted.
+impl<F: TowerField + SerializeBytes> SerializeBytes for ConstraintSystem<F> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.oracles.serialize(write_buf);
+		self.constraints.len().serialize(write_buf);
+		for constraint in self.constraints.iter() {
+			constraint.expr.serialize(write_buf);
+		}
+	}
+}
+
+/// The witness of a constraint system.
+///
+/// The witness holds the committed polynomials, which are needed in packed form to compute the
+/// commitments, and optionally witnesses of derived oracles. The prover computes the witnesses of
+/// derived oracles that are not given.
+#[derive(Debug)]
+pub struct ConstraintSystemWitness<'a, P: PackedField, PW: PackedField> {
+	pub(super) committed: BTreeMap<OracleId, MultilinearExtension<'a, P>>,
+	pub(super) index: MultilinearWitnessIndex<'a, PW>,
+}
+
+impl<'a, P: PackedField, PW: PackedField> Default for ConstraintSystemWitness<'a, P, PW> {
+	fn default() -> Self {
+		Self {
+			committed: BTreeMap::new(),
+			index: MultilinearWitnessIndex::new(),
+		}
+	}
+}
+
+impl<'a, P: PackedField, PW: PackedField> ConstraintSystemWitness<'a, P, PW> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Set the witness of a committed oracle.
+	pub fn set_committed(&mut self, id: OracleId, poly: MultilinearExtension<'a, P>) {
+		self.committed.insert(id, poly);
+	}
+
+	/// Set the witness of a derived oracle.
+	pub fn set(&mut self, id: OracleId, witness: MultilinearWitness<'a, PW>) {
+		self.index.set(id, witness);
+	}
+
+	pub fn committed(&self, id: OracleId) -> Option<&MultilinearExtension<'a, P>> {
+		self.committed.get(&id)
+	}
+
+	pub fn index(&self) -> &MultilinearWitnessIndex<'a, PW> {
+		&self.index
+	}
+}
+
+/// A proof that a witness satisfies a constraint system.
+#[derive(Debug)]
+pub struct Proof<F: Field, PCSComm, PCSProof> {
+	/// One commitment per committed batch, in order of batch ID
+	pub commitments: Vec<PCSComm>,
+	pub zerocheck_proof: ZerocheckProof<F>,
+	pub evalcheck_proof: GreedyEvalcheckProof<F>,
+	/// One opening proof per committed batch, in order of batch ID
+	pub opening_proofs: Vec<PCSProof>,
+}
+
+impl<F, PCSComm, PCSProof> SerializeBytes for Proof<F, PCSComm, PCSProof>
+where
+	F: Field + SerializeBytes,
+	PCSComm: SerializeBytes,
+	PCSProof: SerializeBytes,
+{
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.commitments.serialize(write_buf);
+		self.zerocheck_proof.serialize(write_buf);
+		self.evalcheck_proof.serialize(write_buf);
+		self.opening_proofs.serialize(write_buf);
+	}
+}
+
+impl<F, PCSComm, PCSProof> DeserializeBytes for Proof<F, PCSComm, PCSProof>
+where
+	F: Field + DeserializeBytes,
+	PCSComm: DeserializeBytes,
+	PCSProof: DeserializeBytes,
+{
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		Ok(Self {
+			commitments: Vec::deserialize(read_buf)?,
+			zerocheck_proof: ZerocheckProof::deserialize(read_buf)?,
+			evalcheck_proof: GreedyEvalcheckProof::deserialize(read_buf)?,
+			opening_proofs: Vec::deserialize(read_buf)?,
+		})
+	}
+}
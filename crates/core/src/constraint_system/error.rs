@@ -0,0 +1,56 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::{
+	oracle::{BatchId, OracleId},
+	polynomial,
+	protocols::{greedy_evalcheck, zerocheck},
+	witness,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("the constraint system has no constraints")]
+	NoConstraints,
+	#[error("constraint {name} does not reference any column")]
+	ConstantConstraint { name: String },
+	#[error("constraint {name} references oracle {id}, which does not exist")]
+	UnknownOracle { name: String, id: OracleId },
+	#[error("constraint columns must have {expected} variables, but oracle {id} has {n_vars}")]
+	ColumnNumberOfVariablesMismatch {
+		id: OracleId,
+		expected: usize,
+		n_vars: usize,
+	},
+	#[error("committed batch {batch_id} has {n_vars} variables, but the commitment scheme expects {expected}")]
+	BatchNumberOfVariablesMismatch {
+		batch_id: BatchId,
+		expected: usize,
+		n_vars: usize,
+	},
+	#[error("committed batch {batch_id} has tower level {tower_level}, but the committed witness has tower level {expected}")]
+	BatchTowerLevelMismatch {
+		batch_id: BatchId,
+		expected: usize,
+		tower_level: usize,
+	},
+	#[error("missing witness for oracle {id}")]
+	MissingWitness { id: OracleId },
+	#[error("oracle {id} is not committed")]
+	NotCommitted { id: OracleId },
+	#[error("the proof has {actual} commitments, but the constraint system has {expected} committed batches")]
+	IncorrectNumberOfCommitments { expected: usize, actual: usize },
+	#[error("the proof has {actual} opening proofs, but the constraint system has {expected} committed batches")]
+	IncorrectNumberOfOpeningProofs { expected: usize, actual: usize },
+	#[error("oracle error: {0}")]
+	Oracle(#[from] crate::oracle::Error),
+	#[error("polynomial error: {0}")]
+	Polynomial(#[from] polynomial::Error),
+	#[error("witness error: {0}")]
+	Witness(#[from] witness::Error),
+	#[error("zerocheck error: {0}")]
+	Zerocheck(#[from] zerocheck::Error),
+	#[error("greedy evalcheck error: {0}")]
+	GreedyEvalcheck(#[from] greedy_evalcheck::Error),
+	#[error("polynomial commitment error: {0}")]
+	PolyCommit(#[source] Box<dyn std::error::Error + Send + Sync>),
+}
@@ -0,0 +1,11 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Reusable constraint gadgets for the [`super::ConstraintSystemBuilder`].
+//!
+//! Gadgets over 32-bit words lay out each word in 32 consecutive rows of a 1-bit column, least
+//! significant bit first, so that bit shifts and rotations are shifted oracles with 5-bit blocks.
+
+pub mod u32add;
+
+/// Base-2 logarithm of the number of rows occupied by a 32-bit word.
+pub const LOG_U32_BITS: usize = 5;
@@ -0,0 +1,67 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Addition of 32-bit words modulo 2^32.
+//!
+//! The sum is checked bitwise with a committed carry-out column. The carry-in column is the
+//! carry-out shifted up by one bit within each word, so the carry constraint is the majority
+//! function of the two operand bits and the carry-in bit.
+
+use super::LOG_U32_BITS;
+use crate::{
+	constraint_system::{ConstraintSystemBuilder, Error},
+	oracle::{OracleId, ShiftVariant},
+	polynomial::composition::ArithExpr,
+};
+use binius_field::TowerField;
+
+/// Oracles of the 32-bit addition `z = x + y`.
+#[derive(Debug, Clone, Copy)]
+pub struct U32AddOracle {
+	/// The first operand
+	pub x: OracleId,
+	/// The second operand
+	pub y: OracleId,
+	/// The sum, expected to be committed
+	pub z: OracleId,
+	/// The carry-out bits, expected to be committed
+	pub c_out: OracleId,
+	/// The carry-in bits, a shift of `c_out`
+	pub c_in: OracleId,
+}
+
+impl U32AddOracle {
+	/// Add the carry-in column and constrain `x + y + c_in = z` and `Maj(x, y, c_in) = c_out`.
+	pub fn new<F: TowerField>(
+		builder: &mut ConstraintSystemBuilder<F>,
+		x: OracleId,
+		y: OracleId,
+		z: OracleId,
+		c_out: OracleId,
+	) -> Result<Self, Error> {
+		let c_in = builder.add_shifted(c_out, 1, LOG_U32_BITS, ShiftVariant::LogicalLeft)?;
+
+		let var = ArithExpr::Var;
+		builder.assert_zero("u32add.sum", var(x) + var(y) + var(c_in) - var(z));
+		builder.assert_zero(
+			"u32add.carry",
+			(var(x) + var(c_in)) * (var(y) + var(c_in)) + var(c_in) - var(c_out),
+		);
+
+		Ok(Self {
+			x,
+			y,
+			z,
+			c_out,
+			c_in,
+		})
+	}
+
+	/// Compute `x + y` and append the witness words of the sum and carry columns to `assignment`.
+	pub fn assign(&self, x: u32, y: u32, assignment: &mut Vec<(OracleId, u32)>) -> u32 {
+		let (z, carry) = x.overflowing_add(y);
+		let c_in = x ^ y ^ z;
+		let c_out = (c_in >> 1) | ((carry as u32) << 31);
+		assignment.extend([(self.z, z), (self.c_out, c_out), (self.c_in, c_in)]);
+		z
+	}
+}
@@ -0,0 +1,28 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! A front-end for declaring constraint systems and proving them end to end.
+//!
+//! A [`ConstraintSystemBuilder`] declares columns as multilinear oracles and polynomial
+//! constraints as arithmetic expressions over them. The resulting [`ConstraintSystem`] is proven
+//! with [`prove`] and checked with [`verify`], which run the full protocol: commitment of every
+//! committed batch, a zerocheck over the batched constraints, greedy evalcheck, and one
+//! polynomial commitment opening per batch.
+
+mod builder;
+#[allow(clippy::module_inception)]
+mod constraint_system;
+mod error;
+pub mod gadgets;
+mod prove;
+#[cfg(test)]
+mod tests;
+mod verify;
+
+pub use builder::ConstraintSystemBuilder;
+pub use constraint_system::{Constraint, ConstraintSystem, ConstraintSystemWitness, Proof};
+pub use error::*;
+pub use prove::prove;
+pub use verify::verify;
+
+/// Protocol name of the constraint system prover in the Fiat-Shamir domain tag.
+const DOMAIN_TAG: &str = "binius.constraint_system";
@@ -0,0 +1,190 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	constraint_system::{ConstraintSystem, ConstraintSystemWitness, Proof},
+	error::Error,
+	DOMAIN_TAG,
+};
+use crate::{
+	challenger::{CanGrind, CanObserve, CanSample, CanSampleBits, DomainSeparator},
+	oracle::{CommittedId, CompositePolyOracle},
+	poly_commit::PolyCommitScheme,
+	polynomial::{EvaluationDomain, MultilinearComposite, MultilinearExtension},
+	protocols::{
+		greedy_evalcheck::{self, GreedyEvalcheckProveOutput},
+		zerocheck::{self, ZerocheckClaim, ZerocheckProveOutput},
+	},
+	serialization::SerializeBytes,
+	witness::fill_derived_witnesses,
+};
+use binius_field::{ExtensionField, PackedField, TowerField};
+use std::iter::Step;
+use tracing::instrument;
+
+/// Prove that `witness` satisfies `constraint_system`.
+///
+/// The prover commits to every committed batch with `pcs`, reduces the constraints to evaluation
+/// claims with a zerocheck, reduces those to one opening per batch with greedy evalcheck, and
+/// proves the openings. All committed batches must match the number of variables of `pcs` and the
+/// tower level of `P`. The zerocheck prover works over `PW` with its evaluation domain in `FS`.
+///
+/// The witness must contain every committed column. Witnesses of derived oracles that are not
+/// given are computed with [`fill_derived_witnesses`].
+#[instrument(skip_all, name = "constraint_system::prove")]
+pub fn prove<P, F, PW, FSStep, FS, PCS, CH>(
+	constraint_system: &ConstraintSystem<F>,
+	witness: ConstraintSystemWitness<P, PW>,
+	pcs: &PCS,
+	switchover_fn: impl Fn(usize) -> usize + Clone,
+	mut challenger: CH,
+) -> Result<Proof<F, PCS::Commitment, PCS::Proof>, Error>
+where
+	P: PackedField<Scalar: TowerField>,
+	F: TowerField + ExtensionField<P::Scalar> + SerializeBytes + From<PW> + Step,
+	PW: TowerField + ExtensionField<P::Scalar> + ExtensionField<FS> + From<F>,
+	FSStep: TowerField + Step,
+	FS: TowerField + From<FSStep>,
+	PCS: PolyCommitScheme<P, F>,
+	CH: CanObserve<F>
+		+ CanObserve<PCS::Commitment>
+		+ CanSample<F>
+		+ CanSampleBits<usize>
+		+ CanGrind<F>
+		+ DomainSeparator,
+{
+	let ConstraintSystemWitness {
+		committed: committed_witnesses,
+		index: mut witness_index,
+	} = witness;
+
+	challenger.observe_domain_tag(DOMAIN_TAG, constraint_system);
+
+	// Commit to all batches
+	let mut oracles = constraint_system.oracles.clone();
+	let batches = oracles.committed_batches();
+	let mut commit_polys = Vec::with_capacity(batches.len());
+	for batch in batches.iter() {
+		if batch.n_vars != pcs.n_vars() {
+			return Err(Error::BatchNumberOfVariablesMismatch {
+				batch_id: batch.id,
+				expected: pcs.n_vars(),
+				n_vars: batch.n_vars,
+			});
+		}
+		if batch.tower_level != P::Scalar::TOWER_LEVEL {
+			return Err(Error::BatchTowerLevelMismatch {
+				batch_id: batch.id,
+				expected: P::Scalar::TOWER_LEVEL,
+				tower_level: batch.tower_level,
+			});
+		}
+
+		let polys = (0..batch.n_polys)
+			.map(|index| {
+				let id = oracles.committed_oracle_id(CommittedId {
+					batch_id: batch.id,
+					index,
+				});
+				let poly = committed_witnesses
+					.get(&id)
+					.ok_or(Error::MissingWitness { id })?;
+				witness_index.set(id, poly.to_ref().specialize_arc_dyn());
+				Ok(poly.to_ref())
+			})
+			.collect::<Result<Vec<MultilinearExtension<P>>, Error>>()?;
+		commit_polys.push(polys);
+	}
+	fill_derived_witnesses(&oracles, &mut witness_index)?;
+
+	let mut commitments = Vec::with_capacity(batches.len());
+	let mut committeds = Vec::with_capacity(batches.len());
+	for polys in commit_polys.iter() {
+		let (commitment, committed) = pcs
+			.commit(polys)
+			.map_err(|err| Error::PolyCommit(Box::new(err)))?;
+		challenger.observe(commitment.clone());
+		commitments.push(commitment);
+		committeds.push(committed);
+	}
+
+	// Zerocheck mixing
+	let mixing_challenge: F = challenger.sample();
+	let mix_composition_verifier = constraint_system.mixed_composition(mixing_challenge)?;
+	let mix_composition_prover = constraint_system.mixed_composition(PW::from(mixing_challenge))?;
+
+	let n_vars = constraint_system.n_vars;
+	let zerocheck_claim = ZerocheckClaim {
+		poly: CompositePolyOracle::new(
+			n_vars,
+			constraint_system
+				.columns
+				.iter()
+				.map(|&id| oracles.oracle(id))
+				.collect(),
+			mix_composition_verifier,
+		)?,
+	};
+
+	let zerocheck_witness = MultilinearComposite::new(
+		n_vars,
+		mix_composition_prover,
+		constraint_system
+			.columns
+			.iter()
+			.map(|&id| {
+				witness_index
+					.get(id)
+					.cloned()
+					.ok_or(Error::MissingWitness { id })
+			})
+			.collect::<Result<_, _>>()?,
+	)?;
+
+	let zerocheck_domain = EvaluationDomain::<FS>::new_isomorphic::<FSStep>(
+		zerocheck_claim.poly.max_individual_degree() + 1,
+	)?;
+
+	let ZerocheckProveOutput {
+		evalcheck_claim,
+		zerocheck_proof,
+	} = zerocheck::prove::<F, PW, FS, _, _>(
+		&zerocheck_claim,
+		zerocheck_witness,
+		&zerocheck_domain,
+		&mut challenger,
+		switchover_fn.clone(),
+	)?;
+
+	// Evalcheck
+	let GreedyEvalcheckProveOutput {
+		same_query_claims,
+		proof: evalcheck_proof,
+	} = greedy_evalcheck::prove(
+		&mut oracles,
+		&mut witness_index,
+		[evalcheck_claim],
+		switchover_fn,
+		&mut challenger,
+	)?;
+
+	// Prove the openings of all batches
+	let opening_proofs = same_query_claims
+		.into_iter()
+		.map(|(batch_id, same_query_claim)| {
+			pcs.prove_evaluation(
+				&mut challenger,
+				&committeds[batch_id],
+				&commit_polys[batch_id],
+				&same_query_claim.eval_point,
+			)
+			.map_err(|err| Error::PolyCommit(Box::new(err)))
+		})
+		.collect::<Result<_, _>>()?;
+
+	Ok(Proof {
+		commitments,
+		zerocheck_proof,
+		evalcheck_proof,
+		opening_proofs,
+	})
+}
@@ -0,0 +1,163 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	gadgets::u32add::U32AddOracle, prove, verify, ConstraintSystem, ConstraintSystemBuilder,
+	ConstraintSystemWitness, Error,
+};
+use crate::{
+	challenger::HashChallenger,
+	poly_commit::{
+		tensor_pcs::calculate_n_test_queries_reed_solomon, BlockTensorPCS, PolyCommitScheme,
+	},
+	polynomial::{composition::ArithExpr, MultilinearExtension},
+	reed_solomon::reed_solomon::ReedSolomonCode,
+};
+use assert_matches::assert_matches;
+use binius_field::{
+	BinaryField128b, BinaryField1b, PackedBinaryField128x1b, PackedBinaryField16x8b,
+	PackedBinaryField1x128b, PackedField, TowerField,
+};
+use binius_hash::GroestlHasher;
+use bytemuck::must_cast_slice_mut;
+use itertools::izip;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+type F = BinaryField128b;
+type P = PackedBinaryField128x1b;
+
+fn u32_add_constraint_system(n_vars: usize) -> (ConstraintSystem<F>, U32AddOracle) {
+	let mut builder = ConstraintSystemBuilder::new();
+	let [x, y, z, c_out] = builder.add_committed_multiple(n_vars, BinaryField1b::TOWER_LEVEL);
+	let oracle = U32AddOracle::new(&mut builder, x, y, z, c_out).unwrap();
+	(builder.build().unwrap(), oracle)
+}
+
+// The committed columns; the carry-in column is derived from the carry-out by the witness filler
+fn u32_add_columns(n_vars: usize, rng: &mut StdRng) -> [Vec<P>; 4] {
+	let mut columns = [(); 4].map(|_| vec![P::default(); 1 << (n_vars - P::LOG_WIDTH)]);
+	let [x, y, z, c_out] = columns
+		.each_mut()
+		.map(|column| must_cast_slice_mut::<_, u32>(column.as_mut_slice()));
+	for (x, y, z, c_out) in izip!(x, y, z, c_out) {
+		*x = rng.gen();
+		*y = rng.gen();
+		let carry;
+		(*z, carry) = x.overflowing_add(*y);
+		let c_in = *x ^ *y ^ *z;
+		*c_out = (c_in >> 1) | ((carry as u32) << 31);
+	}
+	columns
+}
+
+fn u32_add_witness<'a>(
+	oracle: &U32AddOracle,
+	columns: &'a [Vec<P>; 4],
+) -> ConstraintSystemWitness<'a, P, F> {
+	let mut witness = ConstraintSystemWitness::new();
+	let [x, y, z, c_out] = columns.each_ref();
+	for (id, values) in [
+		(oracle.x, x),
+		(oracle.y, y),
+		(oracle.z, z),
+		(oracle.c_out, c_out),
+	] {
+		witness.set_committed(id, MultilinearExtension::from_values_slice(values).unwrap());
+	}
+	witness
+}
+
+#[test]
+fn test_u32_add_prove_verify() {
+	let rs_code = ReedSolomonCode::new(5, 2).unwrap();
+	let n_test_queries =
+		calculate_n_test_queries_reed_solomon::<_, BinaryField128b, _>(100, 8, &rs_code).unwrap();
+	let pcs = <BlockTensorPCS<P, PackedBinaryField16x8b, PackedBinaryField1x128b, _, _, _>>::new_using_groestl_merkle_tree(8, rs_code, n_test_queries).unwrap();
+	let n_vars = pcs.n_vars();
+
+	let (constraint_system, oracle) = u32_add_constraint_system(n_vars);
+	assert_eq!(constraint_system.columns().len(), 5);
+	assert_eq!(constraint_system.n_vars(), n_vars);
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let columns = u32_add_columns(n_vars, &mut rng);
+
+	let challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+	let switchover_fn = |_| 1;
+
+	let proof = prove::<_, F, F, F, F, _, _>(
+		&constraint_system,
+		u32_add_witness(&oracle, &columns),
+		&pcs,
+		switchover_fn,
+		challenger.clone(),
+	)
+	.unwrap();
+	verify(&constraint_system, &pcs, proof, challenger.clone()).unwrap();
+
+	// The prover does not check the constraints, so a wrong sum bit yields a proof that the
+	// verifier must reject
+	let mut bad_columns = columns.clone();
+	must_cast_slice_mut::<_, u32>(bad_columns[2].as_mut_slice())[3] ^= 1 << 7;
+	let proof = prove::<_, F, F, F, F, _, _>(
+		&constraint_system,
+		u32_add_witness(&oracle, &bad_columns),
+		&pcs,
+		switchover_fn,
+		challenger.clone(),
+	)
+	.unwrap();
+	assert!(verify(&constraint_system, &pcs, proof, challenger).is_err());
+}
+
+#[test]
+fn test_u32_add_check_witness() {
+	let n_vars = 8;
+	let (constraint_system, oracle) = u32_add_constraint_system(n_vars);
+	let mut rng = StdRng::seed_from_u64(0);
+	let mut columns = u32_add_columns(n_vars, &mut rng);
+
+	let violations = constraint_system
+		.check_witness(&u32_add_witness(&oracle, &columns), 10)
+		.unwrap();
+	assert!(violations.is_empty());
+
+	// Flipping bit 7 of the fourth sum breaks only the sum constraint on that row
+	must_cast_slice_mut::<_, u32>(columns[2].as_mut_slice())[3] ^= 1 << 7;
+	let violations = constraint_system
+		.check_witness(&u32_add_witness(&oracle, &columns), 10)
+		.unwrap();
+	assert_eq!(violations.len(), 1);
+	assert_eq!(violations[0].name, "u32add.sum");
+	assert_eq!(violations[0].row, 3 * 32 + 7);
+	let ids = violations[0]
+		.values
+		.iter()
+		.map(|&(id, _)| id)
+		.collect::<Vec<_>>();
+	assert_eq!(ids, [oracle.x, oracle.y, oracle.z, oracle.c_in]);
+
+	assert!(constraint_system
+		.check_witness(&u32_add_witness(&oracle, &columns), 0)
+		.unwrap()
+		.is_empty());
+}
+
+#[test]
+fn test_build_errors() {
+	let builder = ConstraintSystemBuilder::<F>::new();
+	assert_matches!(builder.build(), Err(Error::NoConstraints));
+
+	let mut builder = ConstraintSystemBuilder::<F>::new();
+	let [x] = builder.add_committed_multiple(4, 0);
+	let [y] = builder.add_committed_multiple(5, 0);
+	builder.assert_zero("mismatch", ArithExpr::Var(x) * ArithExpr::Var(y));
+	assert_matches!(
+		builder.build(),
+		Err(Error::ColumnNumberOfVariablesMismatch { id, .. }) if id == y
+	);
+
+	let mut builder = ConstraintSystemBuilder::<F>::new();
+	let [x] = builder.add_committed_multiple(4, 0);
+	builder.assert_zero("unknown", ArithExpr::Var(x) - ArithExpr::Var(x + 1));
+	assert_matches!(builder.build(), Err(Error::UnknownOracle { id, .. }) if id == x + 1);
+}
@@ -0,0 +1,116 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	constraint_system::{ConstraintSystem, Proof},
+	error::Error,
+	DOMAIN_TAG,
+};
+use crate::{
+	challenger::{CanObserve, CanSample, CanSampleBits, DomainSeparator},
+	oracle::CompositePolyOracle,
+	poly_commit::PolyCommitScheme,
+	protocols::{greedy_evalcheck, zerocheck, zerocheck::ZerocheckClaim},
+	serialization::SerializeBytes,
+};
+use binius_field::{ExtensionField, PackedField, TowerField};
+use std::iter;
+use tracing::instrument;
+
+/// Verify a proof that the prover knows a witness satisfying `constraint_system`.
+#[instrument(skip_all, name = "constraint_system::verify")]
+pub fn verify<P, F, PCS, CH>(
+	constraint_system: &ConstraintSystem<F>,
+	pcs: &PCS,
+	proof: Proof<F, PCS::Commitment, PCS::Proof>,
+	mut challenger: CH,
+) -> Result<(), Error>
+where
+	P: PackedField<Scalar: TowerField>,
+	F: TowerField + ExtensionField<P::Scalar> + SerializeBytes,
+	PCS: PolyCommitScheme<P, F>,
+	CH: CanObserve<F>
+		+ CanObserve<PCS::Commitment>
+		+ CanSample<F>
+		+ CanSampleBits<usize>
+		+ DomainSeparator,
+{
+	let Proof {
+		commitments,
+		zerocheck_proof,
+		evalcheck_proof,
+		opening_proofs,
+	} = proof;
+
+	challenger.observe_domain_tag(DOMAIN_TAG, constraint_system);
+
+	let mut oracles = constraint_system.oracles.clone();
+	let batches = oracles.committed_batches();
+	for batch in batches.iter() {
+		if batch.n_vars != pcs.n_vars() {
+			return Err(Error::BatchNumberOfVariablesMismatch {
+				batch_id: batch.id,
+				expected: pcs.n_vars(),
+				n_vars: batch.n_vars,
+			});
+		}
+	}
+
+	if commitments.len() != batches.len() {
+		return Err(Error::IncorrectNumberOfCommitments {
+			expected: batches.len(),
+			actual: commitments.len(),
+		});
+	}
+	if opening_proofs.len() != batches.len() {
+		return Err(Error::IncorrectNumberOfOpeningProofs {
+			expected: batches.len(),
+			actual: opening_proofs.len(),
+		});
+	}
+
+	for commitment in commitments.iter() {
+		challenger.observe(commitment.clone());
+	}
+
+	// Zerocheck
+	let mixing_challenge: F = challenger.sample();
+	let mix_composition = constraint_system.mixed_composition(mixing_challenge)?;
+
+	let zerocheck_claim = ZerocheckClaim {
+		poly: CompositePolyOracle::new(
+			constraint_system.n_vars,
+			constraint_system
+				.columns
+				.iter()
+				.map(|&id| oracles.oracle(id))
+				.collect(),
+			mix_composition,
+		)?,
+	};
+
+	let evalcheck_claim = zerocheck::verify(&zerocheck_claim, zerocheck_proof, &mut challenger)?;
+
+	// Evalcheck
+	let same_query_claims = greedy_evalcheck::verify(
+		&mut oracles,
+		[evalcheck_claim],
+		evalcheck_proof,
+		&mut challenger,
+	)?;
+
+	// Verify the openings of all batches
+	for ((batch_id, same_query_claim), opening_proof) in
+		iter::zip(same_query_claims, opening_proofs)
+	{
+		pcs.verify_evaluation(
+			&mut challenger,
+			&commitments[batch_id],
+			&same_query_claim.eval_point,
+			opening_proof,
+			&same_query_claim.evals,
+		)
+		.map_err(|err| Error::PolyCommit(Box::new(err)))?;
+	}
+
+	Ok(())
+}
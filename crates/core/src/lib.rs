@@ -7,6 +7,7 @@
 #![allow(clippy::suspicious_op_assign_impl)]
 
 pub mod challenger;
+pub mod constraint_system;
 pub mod linalg;
 pub mod linear_code;
 pub mod merkle_tree;
@@ -16,6 +17,7 @@ pub mod polynomial;
 pub mod protocols;
 #[allow(clippy::module_inception)]
 pub mod reed_solomon;
+pub mod serialization;
 pub mod witness;
 
 pub use core::iter::Step;
@@ -6,6 +6,20 @@ pub enum Error {
 	IncorrectVectorLen { expected: usize },
 	#[error("Index exceeds Merkle tree base size: {max}")]
 	IndexOutOfRange { max: usize },
+	#[error("Merkle tree arity must be a power of two greater than one, got {arity}")]
+	InvalidArity { arity: usize },
+	#[error(
+		"Cap height {cap_height} is not reachable from 2^{log_len} leaves with the tree arity"
+	)]
+	InvalidCapHeight { cap_height: usize, log_len: usize },
+	#[error("Chunks of 2^{log_chunk_len} leaves do not form whole subtrees of the Merkle tree")]
+	InvalidChunkLength { log_chunk_len: usize },
+	#[error("Number of leaf chunks is expected to be {expected}")]
+	IncorrectNumberOfChunks { expected: usize },
+	#[error("All leaf chunks must contain the same number of vectors")]
+	InconsistentBatchSize,
+	#[error("I/O error: {0}")]
+	Io(#[from] std::io::Error),
 	#[error("Verification error: {0}")]
 	Verification(#[from] VerificationError),
 }
@@ -16,4 +30,14 @@ pub enum VerificationError {
 	IncorrectBranchLength { expected: usize },
 	#[error("Computed Merkle root does not match commitment")]
 	MerkleRootMismatch,
+	#[error("Merkle cap is expected to have {expected} nodes")]
+	IncorrectCapLength { expected: usize },
+	#[error("Merkle multi-proof is expected to have {expected} nodes")]
+	IncorrectMultiProofLength { expected: usize },
+	#[error("Number of opened leaves is expected to be {expected}")]
+	IncorrectNumberOfLeaves { expected: usize },
+	#[error("Opened leaves are expected to contain {expected} values")]
+	IncorrectBatchSize { expected: usize },
+	#[error("Leaves opened at the same index {index} are inconsistent")]
+	InconsistentLeaves { index: usize },
 }
@@ -9,20 +9,28 @@ use super::{
 	error::{Error, VerificationError},
 	vcs::VectorCommitScheme,
 };
+use crate::serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes};
 use binius_field::PackedField;
 use binius_hash::Hasher;
+use p3_util::log2_strict_usize;
 
-/// A binary Merkle tree that commits batches of vectors.
+/// A Merkle tree that commits batches of vectors.
 ///
 /// The vector entries at each index in a batch are hashed together into leaf digests. Then a
-/// Merkle tree is constructed over the  implementation requires that the number of
+/// Merkle tree is constructed over the leaf digests, where each inner node compresses `arity`
+/// child nodes. Instead of a single root, the tree may be truncated at a cap of $2^k$ nodes, where
+/// $k$ is the cap height, which shortens every branch by $k / \log_2(\text{arity})$ layers.
 #[derive(Debug, Clone)]
 pub struct MerkleTree<D> {
 	/// Base-2 logarithm of the number of leaves
 	pub log_len: usize,
+	/// Base-2 logarithm of the number of children of each inner node
+	pub log_arity: usize,
+	/// Base-2 logarithm of the number of nodes in the cap
+	pub cap_height: usize,
 	/// Number of vectors that are committed in this batch
 	pub batch_size: usize,
-	/// The inner nodes, arranged as a flattened array of layers with the root at the end
+	/// The inner nodes, arranged as a flattened array of layers with the cap at the end
 	pub inner_nodes: Vec<D>,
 }
 
@@ -30,47 +38,110 @@ impl<D> MerkleTree<D>
 where
 	D: Copy + Default + Send + Sync,
 {
-	pub fn build<P, H, C>(
+	/// Build a Merkle tree of arity `N` over the leaves, truncated at a cap of $2^k$ nodes, where
+	/// $k$ is `cap_height`.
+	///
+	/// Throws if the arity is not a power of two greater than one, or if the cap height is not
+	/// reached from the leaves by a whole number of layers.
+	pub fn build<P, H, C, const N: usize>(
 		compression: &C,
 		log_len: usize,
+		cap_height: usize,
 		leaves: impl Iterator<Item = impl AsRef<[P]>>,
 	) -> Result<Self, Error>
 	where
 		P: PackedField + Sync,
 		H: Hasher<P, Digest = D> + Send,
-		C: PseudoCompressionFunction<D, 2> + Sync,
+		C: PseudoCompressionFunction<D, N> + Sync,
 	{
-		let len = 1 << log_len;
+		let log_arity = check_tree_shape::<N>(log_len, cap_height)?;
+		let mut inner_nodes =
+			vec![H::Digest::default(); Self::n_nodes(log_len, log_arity, cap_height)];
+		let batch_size = Self::hash_leaves::<_, H>(leaves, &mut inner_nodes[..1 << log_len])?;
+		Self::compress_layers::<_, N>(compression, log_len, cap_height, &mut inner_nodes);
 
-		let mut inner_nodes = vec![H::Digest::default(); 2 * len - 1];
-		let batch_size = Self::hash_leaves::<_, H>(leaves, &mut inner_nodes[..len])?;
+		Ok(Self {
+			log_len,
+			log_arity,
+			cap_height,
+			batch_size,
+			inner_nodes,
+		})
+	}
 
-		{
-			let (mut prev_layer, mut remaining) = inner_nodes.split_at_mut(len);
-			for i in 1..log_len + 1 {
-				let (next_layer, next_remaining) = remaining.split_at_mut(1 << (log_len - i));
-				Self::compress_layer(compression, prev_layer, next_layer);
-				(prev_layer, remaining) = (next_layer, next_remaining);
-			}
+	/// Build a Merkle tree of arity `N` over precomputed leaf digests.
+	///
+	/// This is used to build the upper layers of a tree whose lower layers are built separately.
+	pub(super) fn from_leaf_digests<C, const N: usize>(
+		compression: &C,
+		log_len: usize,
+		cap_height: usize,
+		batch_size: usize,
+		leaf_digests: &[D],
+	) -> Result<Self, Error>
+	where
+		C: PseudoCompressionFunction<D, N> + Sync,
+	{
+		let log_arity = check_tree_shape::<N>(log_len, cap_height)?;
+		if leaf_digests.len() != 1 << log_len {
+			return Err(Error::IncorrectVectorLen {
+				expected: 1 << log_len,
+			});
 		}
 
+		let mut inner_nodes = vec![D::default(); Self::n_nodes(log_len, log_arity, cap_height)];
+		inner_nodes[..1 << log_len].copy_from_slice(leaf_digests);
+		Self::compress_layers::<_, N>(compression, log_len, cap_height, &mut inner_nodes);
+
 		Ok(Self {
 			log_len,
+			log_arity,
+			cap_height,
 			batch_size,
 			inner_nodes,
 		})
 	}
 
-	/// Get the Merkle root
-	pub fn root(&self) -> D {
-		*self
-			.inner_nodes
-			.last()
-			.expect("Merkle tree length is at least 1")
+	pub(super) fn n_nodes(log_len: usize, log_arity: usize, cap_height: usize) -> usize {
+		(0..=(log_len - cap_height) / log_arity)
+			.map(|layer| 1 << (log_len - layer * log_arity))
+			.sum()
+	}
+
+	/// Compute all layers above the leaf digests, which are stored in the first layer.
+	fn compress_layers<C, const N: usize>(
+		compression: &C,
+		log_len: usize,
+		cap_height: usize,
+		inner_nodes: &mut [D],
+	) where
+		C: PseudoCompressionFunction<D, N> + Sync,
+	{
+		let log_arity = log2_strict_usize(N);
+		let (mut prev_layer, mut remaining) = inner_nodes.split_at_mut(1 << log_len);
+		for layer in 1..=(log_len - cap_height) / log_arity {
+			let (next_layer, next_remaining) =
+				remaining.split_at_mut(1 << (log_len - layer * log_arity));
+			Self::compress_layer(compression, prev_layer, next_layer);
+			(prev_layer, remaining) = (next_layer, next_remaining);
+		}
+	}
+
+	/// Get the Merkle cap
+	pub fn cap(&self) -> &[D] {
+		&self.inner_nodes[self.inner_nodes.len() - (1 << self.cap_height)..]
+	}
+
+	/// The number of layers of inner nodes below the cap
+	pub fn depth(&self) -> usize {
+		(self.log_len - self.cap_height) / self.log_arity
 	}
 
 	/// Get a Merkle branch for the given index
 	///
+	/// The branch contains, for every layer from the leaves up to the layer below the cap, the
+	/// `arity - 1` siblings of the node on the path in order of their position.
+	///
 	/// Throws if the index is out of range
 	pub fn branch(&self, index: usize) -> Result<Vec<D>, Error> {
 		if index >= 1 << self.log_len {
@@ -79,16 +150,50 @@ where
 			});
 		}
 
-		let branch = (0..self.log_len)
-			.map(|j| {
-				let node_index = (((1 << j) - 1) << (self.log_len + 1 - j)) | (index >> j) ^ 1;
-				self.inner_nodes[node_index]
+		let arity = 1 << self.log_arity;
+		let branch = (0..self.depth())
+			.flat_map(|layer| {
+				let node_index = index >> (layer * self.log_arity);
+				let group_start = node_index & !(arity - 1);
+				(group_start..group_start + arity)
+					.filter(move |&sibling_index| sibling_index != node_index)
+					.map(move |sibling_index| self.node(layer, sibling_index))
 			})
 			.collect();
 
 		Ok(branch)
 	}
 
+	/// Get the nodes of a Merkle multi-proof for the given indices
+	///
+	/// The multi-proof contains the sibling nodes of the paths from all indexed leaves to the
+	/// cap, omitting the nodes that lie on one of these paths, in the order given by
+	/// [`multi_proof_node_indices`].
+	///
+	/// Throws if any index is out of range
+	pub fn multi_branch(&self, indices: &[usize]) -> Result<Vec<D>, Error> {
+		if indices.iter().any(|&index| index >= 1 << self.log_len) {
+			return Err(Error::IndexOutOfRange {
+				max: 1 << self.log_len,
+			});
+		}
+
+		let multi_branch =
+			multi_proof_node_indices(self.log_len, self.log_arity, self.cap_height, indices)
+				.into_iter()
+				.map(|(layer, index)| self.node(layer, index))
+				.collect();
+
+		Ok(multi_branch)
+	}
+
+	fn node(&self, layer: usize, index: usize) -> D {
+		let layer_offset = (0..layer)
+			.map(|layer| 1 << (self.log_len - layer * self.log_arity))
+			.sum::<usize>();
+		self.inner_nodes[layer_offset + index]
+	}
+
 	fn hash_leaves<P, H>(
 		leaves: impl Iterator<Item = impl AsRef<[P]>>,
 		digests: &mut [D],
@@ -125,27 +230,118 @@ where
 		Ok(batch_size)
 	}
 
-	fn compress_layer<C>(compression: &C, prev_layer: &[D], next_layer: &mut [D])
+	fn compress_layer<C, const N: usize>(compression: &C, prev_layer: &[D], next_layer: &mut [D])
 	where
-		C: PseudoCompressionFunction<D, 2> + Sync,
+		C: PseudoCompressionFunction<D, N> + Sync,
 	{
 		prev_layer
-			.par_chunks_exact(2)
+			.par_chunks_exact(N)
 			.zip(next_layer.par_iter_mut())
-			.for_each(|(prev_pair, next_digest)| {
+			.for_each(|(prev_group, next_digest)| {
 				*next_digest = compression.compress(
-					prev_pair
+					prev_group
 						.try_into()
-						.expect("prev_pair is an chunk of exactly 2 elements"),
+						.expect("prev_group is an chunk of exactly N elements"),
 				);
 			})
 	}
 }
 
-/// [`VectorCommitScheme`] implementation using a binary Merkle tree.
+/// Check the arity and cap height of a Merkle tree, returning the base-2 logarithm of the arity.
+pub(super) fn check_tree_shape<const N: usize>(
+	log_len: usize,
+	cap_height: usize,
+) -> Result<usize, Error> {
+	if N < 2 || !N.is_power_of_two() {
+		return Err(Error::InvalidArity { arity: N });
+	}
+	let log_arity = log2_strict_usize(N);
+	if cap_height > log_len || (log_len - cap_height) % log_arity != 0 {
+		return Err(Error::InvalidCapHeight {
+			cap_height,
+			log_len,
+		});
+	}
+	Ok(log_arity)
+}
+
+/// Returns the positions of the nodes in a Merkle multi-proof for the given leaf indices.
+///
+/// The positions are pairs of a layer, where the leaves are layer 0, and an index within the
+/// layer. The multi-proof includes the siblings of every node on a path from an indexed leaf to
+/// the cap, unless a sibling is itself on such a path. Nodes are ordered by layer and then by
+/// index within the layer.
+pub fn multi_proof_node_indices(
+	log_len: usize,
+	log_arity: usize,
+	cap_height: usize,
+	indices: &[usize],
+) -> Vec<(usize, usize)> {
+	let mut layer_indices = indices.to_vec();
+	layer_indices.sort_unstable();
+	layer_indices.dedup();
+
+	let arity = 1 << log_arity;
+	let mut node_indices = Vec::new();
+	for layer in 0..(log_len - cap_height) / log_arity {
+		let mut known = layer_indices.iter().peekable();
+		while let Some(&index) = known.next() {
+			let group_start = index & !(arity - 1);
+			let mut group_known = vec![index];
+			while let Some(&&next_index) = known.peek() {
+				if next_index >= group_start + arity {
+					break;
+				}
+				group_known.push(next_index);
+				known.next();
+			}
+			node_indices.extend(
+				(group_start..group_start + arity)
+					.filter(|sibling_index| !group_known.contains(sibling_index))
+					.map(|sibling_index| (layer, sibling_index)),
+			);
+		}
+
+		layer_indices = layer_indices
+			.into_iter()
+			.map(|index| index >> log_arity)
+			.collect();
+		layer_indices.dedup();
+	}
+	node_indices
+}
+
+/// The commitment of a [`MerkleTreeVCS`], consisting of the nodes of the Merkle cap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleCap<D>(pub Vec<D>);
+
+impl<D: Copy> MerkleCap<D> {
+	/// Returns the Merkle root if the cap consists of a single node.
+	pub fn root(&self) -> Option<D> {
+		match self.0.as_slice() {
+			[root] => Some(*root),
+			_ => None,
+		}
+	}
+}
+
+impl<D: SerializeBytes> SerializeBytes for MerkleCap<D> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.0.serialize(write_buf);
+	}
+}
+
+impl<D: DeserializeBytes> DeserializeBytes for MerkleCap<D> {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		Ok(Self(Vec::deserialize(read_buf)?))
+	}
+}
+
+/// [`VectorCommitScheme`] implementation using a Merkle tree of arity `N`.
 #[derive(Copy, Clone)]
-pub struct MerkleTreeVCS<P, D, H, C> {
+pub struct MerkleTreeVCS<P, D, H, C, const N: usize = 2> {
 	log_len: usize,
+	cap_height: usize,
 	compression: C,
 	_p_marker: PhantomData<P>,
 	_d_marker: PhantomData<D>,
@@ -153,9 +349,11 @@ pub struct MerkleTreeVCS<P, D, H, C> {
 }
 
 impl<P, D, H, C> MerkleTreeVCS<P, D, H, C> {
+	/// Construct a binary Merkle tree VCS committing to a single root.
 	pub fn new(log_len: usize, compression: C) -> Self {
 		Self {
 			log_len,
+			cap_height: 0,
 			compression,
 			_p_marker: PhantomData,
 			_d_marker: PhantomData,
@@ -164,16 +362,71 @@ impl<P, D, H, C> MerkleTreeVCS<P, D, H, C> {
 	}
 }
 
-impl<P, D, H, C> VectorCommitScheme<P> for MerkleTreeVCS<P, D, H, C>
+impl<P, D, H, C, const N: usize> MerkleTreeVCS<P, D, H, C, N> {
+	/// Construct a Merkle tree VCS of arity `N` committing to a cap of $2^k$ nodes, where $k$ is
+	/// `cap_height`.
+	///
+	/// Throws if the arity is not a power of two greater than one, or if the cap height is not
+	/// reached from the leaves by a whole number of layers.
+	pub fn new_with_cap(log_len: usize, cap_height: usize, compression: C) -> Result<Self, Error> {
+		check_tree_shape::<N>(log_len, cap_height)?;
+		Ok(Self {
+			log_len,
+			cap_height,
+			compression,
+			_p_marker: PhantomData,
+			_d_marker: PhantomData,
+			_h_marker: PhantomData,
+		})
+	}
+
+	/// Base-2 logarithm of the number of nodes in the committed cap.
+	pub fn cap_height(&self) -> usize {
+		self.cap_height
+	}
+
+	fn log_arity(&self) -> usize {
+		log2_strict_usize(N)
+	}
+
+	fn depth(&self) -> usize {
+		(self.log_len - self.cap_height) / self.log_arity()
+	}
+
+	fn check_committed(&self, committed: &MerkleTree<D>) -> Result<(), Error> {
+		if committed.log_len != self.log_len
+			|| committed.log_arity != self.log_arity()
+			|| committed.cap_height != self.cap_height
+		{
+			return Err(Error::IncorrectVectorLen {
+				expected: 1 << self.log_len,
+			});
+		}
+		Ok(())
+	}
+
+	fn check_cap(&self, commitment: &MerkleCap<D>) -> Result<(), Error> {
+		if commitment.0.len() != 1 << self.cap_height {
+			return Err(VerificationError::IncorrectCapLength {
+				expected: 1 << self.cap_height,
+			}
+			.into());
+		}
+		Ok(())
+	}
+}
+
+impl<P, D, H, C, const N: usize> VectorCommitScheme<P> for MerkleTreeVCS<P, D, H, C, N>
 where
 	P: PackedField + Sync,
 	D: PackedField + Send + Sync,
 	H: Hasher<P, Digest = D> + Send,
-	C: PseudoCompressionFunction<D, 2> + Sync,
+	C: PseudoCompressionFunction<D, N> + Sync,
 {
-	type Commitment = D;
+	type Commitment = MerkleCap<D>;
 	type Committed = MerkleTree<D>;
 	type Proof = Vec<D>;
+	type MultiProof = Vec<D>;
 	type Error = Error;
 
 	fn vector_len(&self) -> usize {
@@ -184,8 +437,13 @@ where
 		&self,
 		vecs: impl Iterator<Item = impl AsRef<[P]>>,
 	) -> Result<(Self::Commitment, Self::Committed), Self::Error> {
-		let tree = MerkleTree::build::<_, H, _>(&self.compression, self.log_len, vecs)?;
-		Ok((tree.root(), tree))
+		let tree = MerkleTree::build::<_, H, _, N>(
+			&self.compression,
+			self.log_len,
+			self.cap_height,
+			vecs,
+		)?;
+		Ok((MerkleCap(tree.cap().to_vec()), tree))
 	}
 
 	fn prove_batch_opening(
@@ -193,11 +451,7 @@ where
 		committed: &Self::Committed,
 		index: usize,
 	) -> Result<Self::Proof, Self::Error> {
-		if committed.log_len != self.log_len {
-			return Err(Error::IncorrectVectorLen {
-				expected: 1 << self.log_len,
-			});
-		}
+		self.check_committed(committed)?;
 		committed.branch(index)
 	}
 
@@ -208,9 +462,10 @@ where
 		proof: Self::Proof,
 		values: impl Iterator<Item = P>,
 	) -> Result<(), Self::Error> {
-		if proof.len() != self.log_len {
+		let depth = self.depth();
+		if proof.len() != (N - 1) * depth {
 			return Err(VerificationError::IncorrectBranchLength {
-				expected: self.log_len,
+				expected: (N - 1) * depth,
 			}
 			.into());
 		}
@@ -219,22 +474,27 @@ where
 				max: 1 << self.log_len,
 			});
 		}
+		self.check_cap(commitment)?;
 
 		let leaf_digest = values
 			.fold(H::new(), |hasher, value| hasher.chain_update(slice::from_ref(&value)))
 			.finalize();
 
-		let root = proof.into_iter().fold(leaf_digest, |node, branch_node| {
-			let next_node = if index & 1 == 0 {
-				self.compression.compress([node, branch_node])
-			} else {
-				self.compression.compress([branch_node, node])
-			};
-			index >>= 1;
-			next_node
+		let mut siblings = proof.into_iter();
+		let node = (0..depth).fold(leaf_digest, |node, _| {
+			let position = index % N;
+			let group = std::array::from_fn(|j| {
+				if j == position {
+					node
+				} else {
+					siblings.next().expect("proof length is checked above")
+				}
+			});
+			index /= N;
+			self.compression.compress(group)
 		});
 
-		if root == *commitment {
+		if node == commitment.0[index] {
 			Ok(())
 		} else {
 			Err(VerificationError::MerkleRootMismatch.into())
@@ -242,7 +502,124 @@ where
 	}
 
 	fn proof_size(&self, _n_vecs: usize) -> usize {
-		self.log_len * mem::size_of::<D>()
+		(N - 1) * self.depth() * mem::size_of::<D>()
+	}
+
+	fn prove_multi_opening(
+		&self,
+		committed: &Self::Committed,
+		indices: &[usize],
+	) -> Result<Self::MultiProof, Self::Error> {
+		self.check_committed(committed)?;
+		committed.multi_branch(indices)
+	}
+
+	fn verify_multi_opening(
+		&self,
+		commitment: &Self::Commitment,
+		indices: &[usize],
+		n_vecs: usize,
+		proof: Self::MultiProof,
+		values: impl Iterator<Item = impl Iterator<Item = P>>,
+	) -> Result<(), Self::Error> {
+		if indices.iter().any(|&index| index >= 1 << self.log_len) {
+			return Err(Error::IndexOutOfRange {
+				max: 1 << self.log_len,
+			});
+		}
+		let expected_len =
+			multi_proof_node_indices(self.log_len, self.log_arity(), self.cap_height, indices)
+				.len();
+		if proof.len() != expected_len {
+			return Err(VerificationError::IncorrectMultiProofLength {
+				expected: expected_len,
+			}
+			.into());
+		}
+		self.check_cap(commitment)?;
+
+		let leaves = values
+			.map(|values| {
+				let (n_values, hasher) = values.fold((0, H::new()), |(n_values, hasher), value| {
+					(n_values + 1, hasher.chain_update(slice::from_ref(&value)))
+				});
+				(n_values, hasher.finalize())
+			})
+			.collect::<Vec<_>>();
+		if leaves.len() != indices.len() {
+			return Err(VerificationError::IncorrectNumberOfLeaves {
+				expected: indices.len(),
+			}
+			.into());
+		}
+		if leaves.iter().any(|&(n_values, _)| n_values != n_vecs) {
+			return Err(VerificationError::IncorrectBatchSize { expected: n_vecs }.into());
+		}
+		let mut layer = indices
+			.iter()
+			.copied()
+			.zip(leaves.into_iter().map(|(_, leaf)| leaf))
+			.collect::<Vec<_>>();
+
+		// Duplicate indices must open to the same leaf
+		layer.sort_by_key(|&(index, _)| index);
+		for pair in layer.windows(2) {
+			if pair[0].0 == pair[1].0 && pair[0].1 != pair[1].1 {
+				return Err(VerificationError::InconsistentLeaves { index: pair[0].0 }.into());
+			}
+		}
+		layer.dedup_by_key(|&mut (index, _)| index);
+
+		// The nodes are consumed in the order given by multi_proof_node_indices
+		let mut proof_nodes = proof.into_iter();
+		for _ in 0..self.depth() {
+			let mut next_layer = Vec::with_capacity(layer.len());
+			let mut known = layer.into_iter().peekable();
+			while let Some((index, node)) = known.next() {
+				let parent_index = index / N;
+				let mut group = [None; N];
+				group[index % N] = Some(node);
+				while let Some(&(next_index, next_node)) = known.peek() {
+					if next_index / N != parent_index {
+						break;
+					}
+					group[next_index % N] = Some(next_node);
+					known.next();
+				}
+				let group = group.map(|node| {
+					node.unwrap_or_else(|| {
+						proof_nodes.next().expect("proof length is checked above")
+					})
+				});
+				next_layer.push((parent_index, self.compression.compress(group)));
+			}
+			layer = next_layer;
+		}
+
+		if layer
+			.into_iter()
+			.all(|(index, node)| node == commitment.0[index])
+		{
+			Ok(())
+		} else {
+			Err(VerificationError::MerkleRootMismatch.into())
+		}
+	}
+
+	fn multi_proof_size(&self, _n_vecs: usize, n_indices: usize) -> usize {
+		// Every group of siblings in a layer with 2^k groups contributes at most N - 1 nodes to the
+		// multi-proof, and at most one group per distinct index is opened.
+		let n_nodes = (0..self.depth())
+			.map(|layer| {
+				let log_n_groups = self.log_len - (layer + 1) * self.log_arity();
+				(N - 1) * n_indices.min(1 << log_n_groups)
+			})
+			.sum::<usize>();
+		n_nodes * mem::size_of::<D>()
+	}
+
+	fn commitment_size(&self) -> usize {
+		(1 << self.cap_height) * mem::size_of::<D>()
 	}
 }
 
@@ -251,7 +628,7 @@ mod tests {
 	use super::*;
 	use assert_matches::assert_matches;
 	use binius_field::{BinaryField16b, Field};
-	use binius_hash::{GroestlDigestCompression, GroestlHasher};
+	use binius_hash::{GroestlDigest, GroestlDigestCompression, GroestlHasher};
 	use rand::{rngs::StdRng, SeedableRng};
 
 	#[test]
@@ -266,9 +643,10 @@ mod tests {
 		.take(7);
 
 		let tree =
-			MerkleTree::build::<_, GroestlHasher<_>, _>(&GroestlDigestCompression, 8, leaves)
+			MerkleTree::build::<_, GroestlHasher<_>, _, 2>(&GroestlDigestCompression, 8, 0, leaves)
 				.unwrap();
 		assert_eq!(tree.log_len, 8);
+		assert_eq!(tree.batch_size, 7);
 	}
 
 	#[test]
@@ -286,7 +664,7 @@ mod tests {
 		.collect::<Vec<_>>();
 
 		let (commitment, tree) = vcs.commit_batch(vecs.iter()).unwrap();
-		assert_eq!(commitment, tree.root());
+		assert_eq!(commitment, MerkleCap(tree.cap().to_vec()));
 
 		for i in 0..16 {
 			let proof = vcs.prove_batch_opening(&tree, i).unwrap();
@@ -311,7 +689,7 @@ mod tests {
 		.collect::<Vec<_>>();
 
 		let (commitment, tree) = vcs.commit_batch(vecs.iter()).unwrap();
-		assert_eq!(commitment, tree.root());
+		assert_eq!(commitment, MerkleCap(tree.cap().to_vec()));
 
 		let proof = vcs.prove_batch_opening(&tree, 6).unwrap();
 		let values = vecs.iter().map(|vec| vec[6]);
@@ -364,5 +742,246 @@ mod tests {
 		);
 		assert_eq!(vcs.proof_size(1), 4 * 32);
 		assert_eq!(vcs.proof_size(2), 4 * 32);
+		assert_eq!(vcs.multi_proof_size(1, 1), 4 * 32);
+		assert_eq!(vcs.multi_proof_size(1, 4), (4 + 4 + 2 + 1) * 32);
+		assert_eq!(vcs.multi_proof_size(1, 16), (8 + 4 + 2 + 1) * 32);
+	}
+
+	#[test]
+	fn test_multi_proof_node_indices() {
+		// Sibling leaves 4 and 5 need no nodes at the leaf layer
+		assert_eq!(multi_proof_node_indices(3, 1, 0, &[5, 1, 4, 5]), vec![(0, 0), (1, 1), (1, 3)]);
+		assert_eq!(multi_proof_node_indices(3, 1, 0, &[6]), vec![(0, 7), (1, 2), (2, 0)]);
+		assert_eq!(multi_proof_node_indices(2, 1, 0, &[0, 1, 2, 3]), vec![]);
+		assert_eq!(multi_proof_node_indices(2, 1, 0, &[]), vec![]);
+
+		// The cap ends the multi-proof early
+		assert_eq!(multi_proof_node_indices(3, 1, 1, &[6]), vec![(0, 7), (1, 2)]);
+
+		// Siblings are included per group of four nodes
+		assert_eq!(
+			multi_proof_node_indices(4, 2, 0, &[1, 3, 9]),
+			vec![(0, 0), (0, 2), (0, 8), (0, 10), (0, 11), (1, 1), (1, 3)]
+		);
+	}
+
+	#[test]
+	fn test_merkle_vcs_multi_opening() {
+		let mut rng = StdRng::seed_from_u64(0);
+
+		let vcs = <MerkleTreeVCS<_, _, GroestlHasher<_>, _>>::new(6, GroestlDigestCompression);
+
+		let vecs = repeat_with(|| {
+			repeat_with(|| Field::random(&mut rng))
+				.take(64)
+				.collect::<Vec<BinaryField16b>>()
+		})
+		.take(3)
+		.collect::<Vec<_>>();
+
+		let (commitment, tree) = vcs.commit_batch(vecs.iter()).unwrap();
+
+		let indices = [17, 3, 40, 16, 63, 3, 0];
+		let proof = vcs.prove_multi_opening(&tree, &indices).unwrap();
+		assert!(proof.len() * 32 <= vcs.multi_proof_size(3, indices.len()));
+		assert!(proof.len() < 6 * indices.len());
+
+		let values = |indices: &[usize]| {
+			indices
+				.iter()
+				.map(|&i| vecs.iter().map(move |vec| vec[i]))
+				.collect::<Vec<_>>()
+				.into_iter()
+		};
+		vcs.verify_multi_opening(&commitment, &indices, 3, proof.clone(), values(&indices))
+			.unwrap();
+
+		// Case: prove-verify indices mismatch
+		let other_indices = [17, 3, 40, 16, 62, 3, 0];
+		assert_matches!(
+			vcs.verify_multi_opening(
+				&commitment,
+				&other_indices,
+				3,
+				proof.clone(),
+				values(&indices)
+			),
+			Err(Error::Verification(VerificationError::MerkleRootMismatch))
+		);
+
+		// Case: corrupted proof
+		let mut corrupted_proof = proof.clone();
+		corrupted_proof[2] = corrupted_proof[0];
+		assert_matches!(
+			vcs.verify_multi_opening(&commitment, &indices, 3, corrupted_proof, values(&indices)),
+			Err(Error::Verification(VerificationError::MerkleRootMismatch))
+		);
+
+		// Case: inconsistent values at a duplicated index
+		let mut corrupted_values = values(&indices)
+			.map(Iterator::collect)
+			.collect::<Vec<Vec<_>>>();
+		corrupted_values[5][1] += BinaryField16b::ONE;
+		assert_matches!(
+			vcs.verify_multi_opening(
+				&commitment,
+				&indices,
+				3,
+				proof.clone(),
+				corrupted_values.into_iter().map(Vec::into_iter)
+			),
+			Err(Error::Verification(VerificationError::InconsistentLeaves { index: 3 }))
+		);
+
+		// Case: incorrect proof length
+		let mut corrupted_proof = proof.clone();
+		corrupted_proof.pop();
+		assert_matches!(
+			vcs.verify_multi_opening(&commitment, &indices, 3, corrupted_proof, values(&indices)),
+			Err(Error::Verification(VerificationError::IncorrectMultiProofLength { .. }))
+		);
+
+		// Case: missing leaf values
+		assert_matches!(
+			vcs.verify_multi_opening(
+				&commitment,
+				&indices,
+				3,
+				proof.clone(),
+				values(&indices[1..])
+			),
+			Err(Error::Verification(VerificationError::IncorrectNumberOfLeaves { .. }))
+		);
+
+		// Case: extra leaf values
+		let extra_indices = [indices.as_slice(), &[0]].concat();
+		assert_matches!(
+			vcs.verify_multi_opening(
+				&commitment,
+				&indices,
+				3,
+				proof.clone(),
+				values(&extra_indices)
+			),
+			Err(Error::Verification(VerificationError::IncorrectNumberOfLeaves { .. }))
+		);
+
+		// Case: truncated and extended leaves
+		let truncated = values(&indices).map(|values| values.take(2));
+		assert_matches!(
+			vcs.verify_multi_opening(&commitment, &indices, 3, proof.clone(), truncated),
+			Err(Error::Verification(VerificationError::IncorrectBatchSize { expected: 3 }))
+		);
+		let extended = values(&indices).map(|values| values.chain([BinaryField16b::ZERO]));
+		assert_matches!(
+			vcs.verify_multi_opening(&commitment, &indices, 3, proof, extended),
+			Err(Error::Verification(VerificationError::IncorrectBatchSize { expected: 3 }))
+		);
+	}
+
+	fn check_vcs_with_cap<const N: usize>(log_len: usize, cap_height: usize)
+	where
+		GroestlDigestCompression: PseudoCompressionFunction<GroestlDigest, N>,
+	{
+		let mut rng = StdRng::seed_from_u64(0);
+
+		let vcs = <MerkleTreeVCS<_, _, GroestlHasher<_>, _, N>>::new_with_cap(
+			log_len,
+			cap_height,
+			GroestlDigestCompression,
+		)
+		.unwrap();
+		assert_eq!(vcs.commitment_size(), (1 << cap_height) * 32);
+
+		let vecs = repeat_with(|| {
+			repeat_with(|| Field::random(&mut rng))
+				.take(1 << log_len)
+				.collect::<Vec<BinaryField16b>>()
+		})
+		.take(2)
+		.collect::<Vec<_>>();
+
+		let (commitment, tree) = vcs.commit_batch(vecs.iter()).unwrap();
+		assert_eq!(commitment.0.len(), 1 << cap_height);
+
+		for i in [0, 5, (1 << log_len) - 1] {
+			let proof = vcs.prove_batch_opening(&tree, i).unwrap();
+			assert_eq!(proof.len() * 32, vcs.proof_size(2));
+			let values = vecs.iter().map(|vec| vec[i]);
+			vcs.verify_batch_opening(&commitment, i, proof.clone(), values.clone())
+				.unwrap();
+
+			// Case: opening against a different cap node
+			let mut corrupted_commitment = commitment.clone();
+			corrupted_commitment.0[i >> (log_len - cap_height)] = Default::default();
+			assert_matches!(
+				vcs.verify_batch_opening(&corrupted_commitment, i, proof, values),
+				Err(Error::Verification(VerificationError::MerkleRootMismatch))
+			);
+		}
+
+		let indices = [1, 5, 6, (1 << log_len) - 1, 5];
+		let proof = vcs.prove_multi_opening(&tree, &indices).unwrap();
+		assert!(proof.len() * 32 <= vcs.multi_proof_size(2, indices.len()));
+		let values = indices.iter().map(|&i| vecs.iter().map(move |vec| vec[i]));
+		vcs.verify_multi_opening(&commitment, &indices, 2, proof, values)
+			.unwrap();
+
+		// Case: cap of the wrong size
+		let short_commitment = MerkleCap(commitment.0[1..].to_vec());
+		let proof = vcs.prove_batch_opening(&tree, 0).unwrap();
+		assert_matches!(
+			vcs.verify_batch_opening(&short_commitment, 0, proof, vecs.iter().map(|vec| vec[0])),
+			Err(Error::Verification(VerificationError::IncorrectCapLength { .. }))
+		);
+	}
+
+	#[test]
+	fn test_merkle_vcs_cap_and_arity() {
+		check_vcs_with_cap::<2>(6, 0);
+		check_vcs_with_cap::<2>(6, 3);
+		check_vcs_with_cap::<4>(6, 2);
+		check_vcs_with_cap::<8>(6, 0);
+		check_vcs_with_cap::<8>(6, 3);
+	}
+
+	#[test]
+	fn test_merkle_cap_shortens_branches() {
+		let vcs = <MerkleTreeVCS<BinaryField16b, _, GroestlHasher<_>, _, 4>>::new_with_cap(
+			8,
+			2,
+			GroestlDigestCompression,
+		)
+		.unwrap();
+		assert_eq!(vcs.proof_size(1), 3 * 3 * 32);
+		assert_eq!(vcs.multi_proof_size(1, 1), 3 * 3 * 32);
+
+		assert_matches!(
+			<MerkleTreeVCS<BinaryField16b, GroestlDigest, GroestlHasher<BinaryField16b>, _, 4>>::new_with_cap(
+				7,
+				0,
+				GroestlDigestCompression,
+			)
+			.err(),
+			Some(Error::InvalidCapHeight { .. })
+		);
+		assert_matches!(
+			<MerkleTreeVCS<BinaryField16b, GroestlDigest, GroestlHasher<BinaryField16b>, _, 2>>::new_with_cap(
+				4,
+				5,
+				GroestlDigestCompression,
+			)
+			.err(),
+			Some(Error::InvalidCapHeight { .. })
+		);
+		assert_matches!(
+			<MerkleTreeVCS<BinaryField16b, GroestlDigest, GroestlHasher<BinaryField16b>, _, 3>>::new_with_cap(
+				4,
+				0,
+				GroestlDigestCompression,
+			)
+			.err(),
+			Some(Error::InvalidArity { arity: 3 })
+		);
 	}
 }
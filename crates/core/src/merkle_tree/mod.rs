@@ -3,8 +3,10 @@
 mod error;
 #[allow(clippy::module_inception)]
 mod merkle_tree;
+mod streaming;
 mod vcs;
 
 pub use error::*;
 pub use merkle_tree::*;
+pub use streaming::*;
 pub use vcs::*;
@@ -0,0 +1,376 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Construction of Merkle trees that are too large to be held in memory.
+//!
+//! The leaves are split into chunks of $2^c$ consecutive indices, which are produced and hashed one
+//! at a time. Each chunk determines a subtree of $c / \log_2(\text{arity})$ layers, whose root is a
+//! node of the full tree. Only the chunk roots and the layers above them stay resident, while the
+//! lower layers of the subtrees are either kept in memory or spilled to a file, from which the
+//! subtree of a chunk is read back when a branch is requested. The resulting cap and branches are
+//! identical to those of a [`MerkleTree`] built over the same leaves.
+
+use std::{
+	fs::{File, OpenOptions},
+	io::{Read, Seek, SeekFrom, Write},
+	mem,
+	path::PathBuf,
+	sync::Mutex,
+};
+
+use bytemuck::{must_cast_slice, must_cast_slice_mut, Pod};
+use p3_symmetric::PseudoCompressionFunction;
+
+use super::{
+	error::Error,
+	merkle_tree::{check_tree_shape, MerkleTree},
+};
+use binius_field::PackedField;
+use binius_hash::Hasher;
+
+/// Builder for a [`StreamingMerkleTree`].
+#[derive(Debug, Clone)]
+pub struct StreamingMerkleTreeBuilder<'a, C> {
+	compression: &'a C,
+	log_len: usize,
+	log_chunk_len: usize,
+	cap_height: usize,
+	spill_path: Option<PathBuf>,
+}
+
+impl<'a, C> StreamingMerkleTreeBuilder<'a, C> {
+	/// Create a builder for a tree with $2^n$ leaves, where $n$ is `log_len`, whose leaves are
+	/// produced in chunks of $2^c$ leaves, where $c$ is `log_chunk_len`.
+	pub fn new(compression: &'a C, log_len: usize, log_chunk_len: usize) -> Self {
+		Self {
+			compression,
+			log_len,
+			log_chunk_len,
+			cap_height: 0,
+			spill_path: None,
+		}
+	}
+
+	/// Truncate the tree at a cap of $2^k$ nodes, where $k$ is `cap_height`.
+	pub fn cap_height(mut self, cap_height: usize) -> Self {
+		self.cap_height = cap_height;
+		self
+	}
+
+	/// Spill the layers below the chunk roots to a file at the given path.
+	///
+	/// The file is created, or truncated if it exists, when the tree is built.
+	pub fn spill_to_file(mut self, path: impl Into<PathBuf>) -> Self {
+		self.spill_path = Some(path.into());
+		self
+	}
+
+	/// Build a Merkle tree of arity `N` by hashing the leaf chunks in order.
+	///
+	/// Each chunk yields, for every vector in the committed batch, the $2^c$ vector entries at the
+	/// chunk's indices. All chunks must contain the same number of vectors.
+	pub fn build<P, H, D, const N: usize>(
+		self,
+		chunks: impl Iterator<Item = impl IntoIterator<Item = impl AsRef<[P]>>>,
+	) -> Result<StreamingMerkleTree<D>, Error>
+	where
+		P: PackedField + Sync,
+		H: Hasher<P, Digest = D> + Send,
+		D: Pod + Default + Send + Sync,
+		C: PseudoCompressionFunction<D, N> + Sync,
+	{
+		let log_n_chunks =
+			self.log_len
+				.checked_sub(self.log_chunk_len)
+				.ok_or(Error::InvalidChunkLength {
+					log_chunk_len: self.log_chunk_len,
+				})?;
+		let log_arity = check_tree_shape::<N>(self.log_chunk_len, 0).map_err(|_| {
+			Error::InvalidChunkLength {
+				log_chunk_len: self.log_chunk_len,
+			}
+		})?;
+		check_tree_shape::<N>(log_n_chunks, self.cap_height).map_err(|_| {
+			Error::InvalidCapHeight {
+				cap_height: self.cap_height,
+				log_len: self.log_len,
+			}
+		})?;
+
+		let mut lower = match self.spill_path {
+			Some(path) => LowerLayers::File(Mutex::new(
+				OpenOptions::new()
+					.read(true)
+					.write(true)
+					.create(true)
+					.truncate(true)
+					.open(path)?,
+			)),
+			None => LowerLayers::Memory(Vec::new()),
+		};
+
+		let n_chunks = 1 << log_n_chunks;
+		let mut chunk_roots = Vec::with_capacity(n_chunks);
+		let mut batch_size = None;
+		for chunk in chunks {
+			if chunk_roots.len() == n_chunks {
+				return Err(Error::IncorrectNumberOfChunks { expected: n_chunks });
+			}
+
+			let subtree = MerkleTree::build::<P, H, C, N>(
+				self.compression,
+				self.log_chunk_len,
+				0,
+				chunk.into_iter(),
+			)?;
+			if *batch_size.get_or_insert(subtree.batch_size) != subtree.batch_size {
+				return Err(Error::InconsistentBatchSize);
+			}
+
+			// The chunk root is resident as a leaf of the upper tree, so only the nodes below it
+			// are stored.
+			let (&chunk_root, lower_nodes) = subtree
+				.inner_nodes
+				.split_last()
+				.expect("Merkle tree has at least one node");
+			chunk_roots.push(chunk_root);
+			match &mut lower {
+				LowerLayers::Memory(nodes) => nodes.extend_from_slice(lower_nodes),
+				LowerLayers::File(file) => file
+					.get_mut()
+					.expect("mutex is not shared during construction")
+					.write_all(must_cast_slice(lower_nodes))?,
+			}
+		}
+		if chunk_roots.len() != n_chunks {
+			return Err(Error::IncorrectNumberOfChunks { expected: n_chunks });
+		}
+
+		let upper = MerkleTree::from_leaf_digests::<C, N>(
+			self.compression,
+			log_n_chunks,
+			self.cap_height,
+			batch_size.unwrap_or_default(),
+			&chunk_roots,
+		)?;
+
+		Ok(StreamingMerkleTree {
+			log_len: self.log_len,
+			log_arity,
+			log_chunk_len: self.log_chunk_len,
+			upper,
+			lower,
+		})
+	}
+}
+
+/// Storage for the nodes of the chunk subtrees below the chunk roots.
+#[derive(Debug)]
+enum LowerLayers<D> {
+	Memory(Vec<D>),
+	File(Mutex<File>),
+}
+
+/// A Merkle tree built from leaf chunks by a [`StreamingMerkleTreeBuilder`].
+#[derive(Debug)]
+pub struct StreamingMerkleTree<D> {
+	log_len: usize,
+	log_arity: usize,
+	log_chunk_len: usize,
+	/// The tree whose leaves are the chunk roots
+	upper: MerkleTree<D>,
+	lower: LowerLayers<D>,
+}
+
+impl<D> StreamingMerkleTree<D>
+where
+	D: Pod + Default + Send + Sync,
+{
+	/// Base-2 logarithm of the number of leaves
+	pub fn log_len(&self) -> usize {
+		self.log_len
+	}
+
+	/// Number of vectors that are committed in this batch
+	pub fn batch_size(&self) -> usize {
+		self.upper.batch_size
+	}
+
+	/// Get the Merkle cap
+	pub fn cap(&self) -> &[D] {
+		self.upper.cap()
+	}
+
+	/// Read back the subtree over the leaves of the given chunk.
+	///
+	/// Throws if the chunk index is out of range or the spill file cannot be read
+	pub fn chunk_tree(&self, chunk_index: usize) -> Result<MerkleTree<D>, Error> {
+		let n_chunks = 1 << (self.log_len - self.log_chunk_len);
+		if chunk_index >= n_chunks {
+			return Err(Error::IndexOutOfRange { max: n_chunks });
+		}
+
+		let n_lower_nodes = MerkleTree::<D>::n_nodes(self.log_chunk_len, self.log_arity, 0) - 1;
+		let mut inner_nodes = match &self.lower {
+			LowerLayers::Memory(nodes) => {
+				nodes[chunk_index * n_lower_nodes..(chunk_index + 1) * n_lower_nodes].to_vec()
+			}
+			LowerLayers::File(file) => {
+				let mut nodes = vec![D::default(); n_lower_nodes];
+				let mut file = file.lock().expect("spill file mutex is not poisoned");
+				file.seek(SeekFrom::Start(
+					(chunk_index * n_lower_nodes * mem::size_of::<D>()) as u64,
+				))?;
+				file.read_exact(must_cast_slice_mut(&mut nodes))?;
+				nodes
+			}
+		};
+		inner_nodes.push(self.upper.inner_nodes[chunk_index]);
+
+		Ok(MerkleTree {
+			log_len: self.log_chunk_len,
+			log_arity: self.log_arity,
+			cap_height: 0,
+			batch_size: self.upper.batch_size,
+			inner_nodes,
+		})
+	}
+
+	/// Get a Merkle branch for the given index
+	///
+	/// The branch is identical to the branch of a [`MerkleTree`] built over the same leaves.
+	///
+	/// Throws if the index is out of range or the spill file cannot be read
+	pub fn branch(&self, index: usize) -> Result<Vec<D>, Error> {
+		if index >= 1 << self.log_len {
+			return Err(Error::IndexOutOfRange {
+				max: 1 << self.log_len,
+			});
+		}
+
+		let chunk_index = index >> self.log_chunk_len;
+		let mut branch = self
+			.chunk_tree(chunk_index)?
+			.branch(index % (1 << self.log_chunk_len))?;
+		branch.extend(self.upper.branch(chunk_index)?);
+		Ok(branch)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use assert_matches::assert_matches;
+	use binius_field::{BinaryField16b, Field};
+	use binius_hash::{GroestlDigest, GroestlDigestCompression, GroestlHasher};
+	use rand::{rngs::StdRng, SeedableRng};
+	use std::{fs, iter::repeat_with};
+
+	fn random_vecs(log_len: usize, batch_size: usize) -> Vec<Vec<BinaryField16b>> {
+		let mut rng = StdRng::seed_from_u64(0);
+		repeat_with(|| {
+			repeat_with(|| Field::random(&mut rng))
+				.take(1 << log_len)
+				.collect()
+		})
+		.take(batch_size)
+		.collect()
+	}
+
+	fn chunks(
+		vecs: &[Vec<BinaryField16b>],
+		log_chunk_len: usize,
+	) -> impl Iterator<Item = Vec<&[BinaryField16b]>> {
+		let chunk_len = 1 << log_chunk_len;
+		(0..vecs[0].len() / chunk_len).map(move |i| {
+			vecs.iter()
+				.map(|vec| &vec[i * chunk_len..(i + 1) * chunk_len])
+				.collect()
+		})
+	}
+
+	fn check_matches_in_memory_tree<const N: usize>(
+		log_len: usize,
+		log_chunk_len: usize,
+		cap_height: usize,
+		spill_path: Option<PathBuf>,
+	) where
+		GroestlDigestCompression: PseudoCompressionFunction<GroestlDigest, N>,
+	{
+		let vecs = random_vecs(log_len, 3);
+		let tree = MerkleTree::build::<_, GroestlHasher<_>, _, N>(
+			&GroestlDigestCompression,
+			log_len,
+			cap_height,
+			vecs.iter(),
+		)
+		.unwrap();
+
+		let mut builder =
+			StreamingMerkleTreeBuilder::new(&GroestlDigestCompression, log_len, log_chunk_len)
+				.cap_height(cap_height);
+		if let Some(path) = &spill_path {
+			builder = builder.spill_to_file(path);
+		}
+		let streaming_tree = builder
+			.build::<_, GroestlHasher<_>, _, N>(chunks(&vecs, log_chunk_len))
+			.unwrap();
+
+		assert_eq!(streaming_tree.batch_size(), 3);
+		assert_eq!(streaming_tree.cap(), tree.cap());
+		for index in 0..1 << log_len {
+			assert_eq!(streaming_tree.branch(index).unwrap(), tree.branch(index).unwrap());
+		}
+
+		if let Some(path) = spill_path {
+			fs::remove_file(path).unwrap();
+		}
+	}
+
+	fn spill_path(name: &str) -> PathBuf {
+		std::env::temp_dir().join(format!(
+			"binius_streaming_merkle_{}_{}",
+			std::process::id(),
+			name
+		))
+	}
+
+	#[test]
+	fn test_streaming_tree_matches_in_memory_tree() {
+		check_matches_in_memory_tree::<2>(6, 2, 0, None);
+		check_matches_in_memory_tree::<2>(6, 0, 0, None);
+		check_matches_in_memory_tree::<2>(6, 6, 0, None);
+		check_matches_in_memory_tree::<2>(6, 3, 1, Some(spill_path("binary")));
+		check_matches_in_memory_tree::<4>(6, 2, 2, Some(spill_path("quaternary")));
+	}
+
+	#[test]
+	fn test_streaming_tree_rejects_invalid_chunks() {
+		let vecs = random_vecs(4, 2);
+		let builder = StreamingMerkleTreeBuilder::new(&GroestlDigestCompression, 4, 2);
+
+		// Case: missing chunk
+		assert_matches!(
+			builder
+				.clone()
+				.build::<_, GroestlHasher<_>, GroestlDigest, 2>(chunks(&vecs, 2).skip(1)),
+			Err(Error::IncorrectNumberOfChunks { expected: 4 })
+		);
+
+		// Case: chunks with different numbers of vectors
+		let mut uneven_chunks = chunks(&vecs, 2).collect::<Vec<_>>();
+		uneven_chunks[1].pop();
+		assert_matches!(
+			builder
+				.clone()
+				.build::<_, GroestlHasher<_>, GroestlDigest, 2>(uneven_chunks.into_iter()),
+			Err(Error::InconsistentBatchSize)
+		);
+
+		// Case: chunks that are not whole subtrees of a 4-ary tree
+		assert_matches!(
+			StreamingMerkleTreeBuilder::new(&GroestlDigestCompression, 4, 1)
+				.build::<_, GroestlHasher<_>, GroestlDigest, 4>(chunks(&vecs, 1)),
+			Err(Error::InvalidChunkLength { log_chunk_len: 1 })
+		);
+	}
+}
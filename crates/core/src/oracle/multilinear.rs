@@ -5,6 +5,7 @@ use crate::{
 		BatchId, CommittedBatch, CommittedBatchSpec, CommittedId, CompositePolyOracle, Error,
 	},
 	polynomial::{Error as PolynomialError, IdentityCompositionPoly, MultivariatePoly},
+	serialization::SerializeBytes,
 };
 use binius_field::{Field, TowerField};
 use getset::{CopyGetters, Getters};
@@ -413,6 +414,11 @@ impl<F: TowerField> MultilinearOracleSet<F> {
 		}
 	}
 
+	/// The number of oracles in the set. Oracle IDs range from zero up to the size.
+	pub fn size(&self) -> usize {
+		self.oracles.len()
+	}
+
 	pub fn n_vars(&self, id: OracleId) -> usize {
 		use MultilinearOracleMeta::*;
 		match &self.oracles[id] {
@@ -462,6 +468,110 @@ impl<F: TowerField> MultilinearOracleSet<F> {
 	}
 }
 
+/// The canonical encoding of an oracle set describes the structure of every oracle, so that it can
+/// be included in the Fiat-Shamir domain tag of a protocol instance.
+///
+/// Transparent polynomials are opaque to the oracle set and are described only by their number of
+/// variables, degree, and tower level. Protocols with transparent oracles that are not determined
+/// by the rest of the statement must bind them separately.
+impl<F: TowerField + SerializeBytes> SerializeBytes for MultilinearOracleSet<F> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		use MultilinearOracleMeta::*;
+
+		self.batches.len().serialize(write_buf);
+		for batch in self.batches.iter() {
+			batch.first_oracle_id.serialize(write_buf);
+			batch.spec.n_vars.serialize(write_buf);
+			batch.spec.n_polys.serialize(write_buf);
+			batch.spec.tower_level.serialize(write_buf);
+		}
+
+		self.oracles.len().serialize(write_buf);
+		for oracle in self.oracles.iter() {
+			match oracle {
+				Transparent(poly) => {
+					0u8.serialize(write_buf);
+					poly.n_vars().serialize(write_buf);
+					poly.degree().serialize(write_buf);
+					poly.binary_tower_level().serialize(write_buf);
+				}
+				Committed(CommittedId { batch_id, index }) => {
+					1u8.serialize(write_buf);
+					batch_id.serialize(write_buf);
+					index.serialize(write_buf);
+				}
+				Repeating {
+					inner_id,
+					log_count,
+				} => {
+					2u8.serialize(write_buf);
+					inner_id.serialize(write_buf);
+					log_count.serialize(write_buf);
+				}
+				Interleaved(inner_id_0, inner_id_1) => {
+					3u8.serialize(write_buf);
+					inner_id_0.serialize(write_buf);
+					inner_id_1.serialize(write_buf);
+				}
+				Merged(inner_id_0, inner_id_1) => {
+					4u8.serialize(write_buf);
+					inner_id_0.serialize(write_buf);
+					inner_id_1.serialize(write_buf);
+				}
+				Shifted {
+					inner_id,
+					offset,
+					block_bits,
+					variant,
+				} => {
+					5u8.serialize(write_buf);
+					inner_id.serialize(write_buf);
+					offset.serialize(write_buf);
+					block_bits.serialize(write_buf);
+					let variant: u8 = match variant {
+						ShiftVariant::CircularLeft => 0,
+						ShiftVariant::LogicalLeft => 1,
+						ShiftVariant::LogicalRight => 2,
+					};
+					variant.serialize(write_buf);
+				}
+				Packed {
+					inner_id,
+					log_degree,
+				} => {
+					6u8.serialize(write_buf);
+					inner_id.serialize(write_buf);
+					log_degree.serialize(write_buf);
+				}
+				Projected {
+					inner_id,
+					values,
+					variant,
+				} => {
+					7u8.serialize(write_buf);
+					inner_id.serialize(write_buf);
+					values.serialize(write_buf);
+					let variant: u8 = match variant {
+						ProjectionVariant::FirstVars => 0,
+						ProjectionVariant::LastVars => 1,
+					};
+					variant.serialize(write_buf);
+				}
+				LinearCombination {
+					n_vars,
+					offset,
+					inner,
+				} => {
+					8u8.serialize(write_buf);
+					n_vars.serialize(write_buf);
+					offset.serialize(write_buf);
+					inner.serialize(write_buf);
+				}
+			}
+		}
+	}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MultilinearPolyOracle<F: Field> {
 	Transparent(OracleId, TransparentPolyOracle<F>),
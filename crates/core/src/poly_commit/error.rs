@@ -1,6 +1,6 @@
 // Copyright 2023 Ulvetanna Inc.
 
-use crate::polynomial;
+use crate::{challenger, polynomial, serialization};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -14,14 +14,27 @@ pub enum Error {
 	ExtensionDegreePowerOfTwoRequired,
 	#[error("cannot commit unaligned message")]
 	UnalignedMessage,
+	#[error("batches committed under a single vector commitment must have the same code length")]
+	CodeLengthMismatch,
 	#[error("packing width must divide code dimension")]
 	PackingWidthMustDivideCodeDimension,
 	#[error("packing width must divide the number of rows")]
 	PackingWidthMustDivideNumberOfRows,
 	#[error("error in batching: {err_str}")]
 	NumBatchedMismatchError { err_str: String },
+	#[error("no polynomial {poly_index} in committed batch {batch_index}")]
+	UnknownClaimedPolynomial {
+		batch_index: usize,
+		poly_index: usize,
+	},
 	#[error("cannot calculate parameters satisfying the security target")]
 	ParameterError,
+	#[error("invalid parameters for hiding mode: {0}")]
+	InvalidHidingParameters(&'static str),
+	#[error("batches committed under a single vector commitment cannot grind proof of work")]
+	GrindingNotSupported,
+	#[error("cannot grind {bits} bits of proof of work, the maximum is {max}")]
+	InvalidGrindingBits { bits: usize, max: usize },
 	#[error("field error: {0}")]
 	Field(#[from] binius_field::Error),
 	#[error("polynomial error: {0}")]
@@ -32,6 +45,10 @@ pub enum Error {
 	Transpose(#[from] binius_field::transpose::Error),
 	#[error("verification failure: {0}")]
 	Verification(#[from] VerificationError),
+	#[error("transcript error: {0}")]
+	Transcript(#[from] serialization::Error),
+	#[error("challenger error: {0}")]
+	Challenger(#[from] challenger::Error),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -51,4 +68,12 @@ pub enum VerificationError {
 	IncorrectPartialEvaluation,
 	#[error("partial evaluation (t') is the wrong size")]
 	PartialEvaluationSize,
+	#[error("the proof does not have the expected shape")]
+	ProofShape,
+	#[error("query opening proof does not have the expected shape")]
+	QueryProofShape,
+	#[error("query openings are inconsistent with the folded codewords")]
+	IncorrectFolding,
+	#[error("the proof-of-work nonce does not have the required number of zero bits")]
+	InvalidProofOfWork,
 }
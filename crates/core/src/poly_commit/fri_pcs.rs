@@ -0,0 +1,1337 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! The FRI-Binius multilinear polynomial commitment scheme from [DP24].
+//!
+//! Each committed polynomial $t$ over a small field $F$ is packed into the multilinear $t'$ over
+//! the extension field $F_E$, whose $2^\kappa$ coordinates over $F$ are the evaluations of $t$ on
+//! the $\kappa$ low variables. The commitment is a Merkle tree over the Reed–Solomon encodings of
+//! the $t'$, computed with the additive NTT so that the evaluations of $t'$ on the hypercube are
+//! its coefficients in the novel polynomial basis.
+//!
+//! An evaluation claim on $t$ is reduced by ring-switching to a sumcheck claim on $t'$. The
+//! sumcheck rounds are interleaved with FRI folding rounds that use the same challenges, so that
+//! the final FRI value is the evaluation of $t'$ at the sumcheck challenge point. Proof sizes grow
+//! polylogarithmically with the size of the committed polynomials.
+//!
+//! [DP24]: <https://eprint.iacr.org/2024/504>
+
+use super::{
+	error::{Error, VerificationError},
+	product_sumcheck::{fold_halves, sumcheck_round_poly},
+};
+use crate::{
+	challenger::{
+		CanObserve, CanSample, CanSampleBits, DomainSeparator, TranscriptReader, TranscriptWriter,
+	},
+	merkle_tree::{MerkleCap, MerkleTree, MerkleTreeVCS, VectorCommitScheme},
+	poly_commit::PolyCommitScheme,
+	polynomial::{
+		multilinear_query::MultilinearQuery, Error as PolynomialError, MultilinearExtension,
+	},
+	serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes},
+};
+use binius_field::{
+	BinaryField, BinaryField8b, ExtensionField, Field, PackedExtensionField, PackedField,
+	PackedFieldIndexable,
+};
+use binius_hash::{GroestlDigestCompression, GroestlHasher, Hasher};
+use binius_ntt::{AdditiveNTT, AdditiveNTTWithOTFCompute};
+use p3_symmetric::PseudoCompressionFunction;
+use p3_util::log2_ceil_usize;
+use rand::RngCore;
+use rayon::prelude::*;
+use std::{iter, iter::repeat_with, marker::PhantomData, mem};
+use tracing::instrument;
+
+/// Challenger label for the partial evaluations sent for ring-switching.
+const PARTIAL_EVALS_LABEL: &str = "fri_pcs.partial_evals";
+/// Challenger label for the ring-switching challenges.
+const RING_SWITCH_LABEL: &str = "fri_pcs.ring_switch";
+/// Challenger label for the coefficients mixing the committed polynomials.
+const MIXING_LABEL: &str = "fri_pcs.mixing";
+/// Challenger label for the sumcheck round messages.
+const ROUND_LABEL: &str = "fri_pcs.round";
+/// Challenger label for the commitments to the folded codewords.
+const COMMITMENT_LABEL: &str = "fri_pcs.commitment";
+/// Challenger label for the final folded value.
+const FINAL_VALUE_LABEL: &str = "fri_pcs.final_value";
+/// Challenger label for the sampled query indices.
+const QUERIES_LABEL: &str = "fri_pcs.queries";
+
+/// Openings for a single FRI query.
+///
+/// There is one opening for each oracle, starting with the committed codewords. Each opening is a
+/// coset of the oracle's codeword together with its Merkle branch. The first opening contains the
+/// cosets of all committed codewords, concatenated.
+pub type QueryProof<F, D> = Vec<(Vec<F>, Vec<D>)>;
+
+/// Evaluation proof data for the `FRIPCS` polynomial commitment scheme.
+#[derive(Debug, Clone)]
+pub struct Proof<F, D> {
+	/// Number of distinct multilinear polynomials in the batch opening proof
+	pub n_polys: usize,
+	/// The evaluations of each polynomial with the high variables fixed to the query
+	///
+	/// The $j$-th entry for a polynomial $t$ is $t(j, q_{\kappa}, \ldots, q_{\mu - 1})$, where
+	/// $j$ ranges over the $2^\kappa$ assignments to the packed variables.
+	pub partial_evals: Vec<Vec<F>>,
+	/// The sumcheck round polynomials, given by their constant and quadratic coefficients
+	pub round_polys: Vec<[F; 2]>,
+	/// Commitments to the folded codewords
+	pub round_commitments: Vec<D>,
+	/// The value of the codeword after all folding rounds
+	pub final_value: F,
+	/// Openings for the sampled queries
+	pub queries: Vec<QueryProof<F, D>>,
+}
+
+impl<F, D> SerializeBytes for Proof<F, D>
+where
+	F: SerializeBytes,
+	D: SerializeBytes,
+{
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.n_polys.serialize(write_buf);
+		self.partial_evals.serialize(write_buf);
+		self.round_polys.serialize(write_buf);
+		self.round_commitments.serialize(write_buf);
+		self.final_value.serialize(write_buf);
+		self.queries.serialize(write_buf);
+	}
+}
+
+impl<F, D> DeserializeBytes for Proof<F, D>
+where
+	F: DeserializeBytes,
+	D: DeserializeBytes,
+{
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		let n_polys = usize::deserialize(read_buf)?;
+		let partial_evals = Vec::<Vec<F>>::deserialize(read_buf)?;
+		let round_polys = Vec::deserialize(read_buf)?;
+		let round_commitments = Vec::deserialize(read_buf)?;
+		let final_value = F::deserialize(read_buf)?;
+		let queries = Vec::deserialize(read_buf)?;
+
+		// The number of partial evaluation vectors is redundant with n_polys, so it must agree for
+		// the encoding to be canonical.
+		if partial_evals.len() != n_polys {
+			return Err(SerializationError::InvalidConstruction {
+				name: "fri_pcs::Proof",
+			});
+		}
+
+		Ok(Self {
+			n_polys,
+			partial_evals,
+			round_polys,
+			round_commitments,
+			final_value,
+			queries,
+		})
+	}
+}
+
+/// The FRI-Binius multilinear polynomial commitment scheme from [DP24].
+///
+/// The codewords are folded $2^\eta$-to-one between consecutive oracles, where $\eta$ is the
+/// fold arity, so each query opens a coset of $2^\eta$ entries from every oracle.
+///
+/// # Type Parameters
+///
+/// * `P`: The base field type of committed elements.
+/// * `PE`: The extension field type used for cryptographic challenges.
+/// * `FD`: The field of the Reed–Solomon evaluation domain.
+/// * `H`: The hasher for the Merkle tree leaves.
+/// * `C`: The compression function for the Merkle tree inner nodes.
+///
+/// [DP24]: <https://eprint.iacr.org/2024/504>
+#[derive(Debug)]
+pub struct FRIPCS<P, PE, FD, H, C>
+where
+	FD: BinaryField,
+{
+	n_vars: usize,
+	log_inv_rate: usize,
+	log_fold_arity: usize,
+	n_test_queries: usize,
+	ntt: AdditiveNTTWithOTFCompute<FD>,
+	compression: C,
+	_p_marker: PhantomData<P>,
+	_pe_marker: PhantomData<PE>,
+	_h_marker: PhantomData<H>,
+}
+
+impl<F, P, FE, PE, FD, H, C> FRIPCS<P, PE, FD, H, C>
+where
+	F: Field,
+	P: PackedField<Scalar = F>,
+	FE: BinaryField + ExtensionField<F>,
+	PE: PackedField<Scalar = FE>,
+	FD: BinaryField,
+{
+	/// Construct a [`FRIPCS`].
+	///
+	/// Throws if the extension degree is not a power of two, if the polynomials have too few
+	/// variables to be packed into extension field elements, or if the evaluation domain field is
+	/// too small for the code length.
+	pub fn new(
+		n_vars: usize,
+		log_inv_rate: usize,
+		log_fold_arity: usize,
+		n_test_queries: usize,
+		compression: C,
+	) -> Result<Self, Error> {
+		let degree = <FE as ExtensionField<F>>::DEGREE;
+		if !degree.is_power_of_two() {
+			return Err(Error::ExtensionDegreePowerOfTwoRequired);
+		}
+		let log_degree = degree.trailing_zeros() as usize;
+		if n_vars <= log_degree {
+			return Err(Error::IncorrectPolynomialSize {
+				expected: log_degree + 1,
+			});
+		}
+		if PE::LOG_WIDTH > log_degree || PE::LOG_WIDTH > n_vars - log_degree {
+			return Err(Error::PackingWidthMustDivideCodeDimension);
+		}
+		if log_inv_rate == 0 || log_fold_arity == 0 {
+			return Err(Error::ParameterError);
+		}
+
+		let ntt = AdditiveNTTWithOTFCompute::new(n_vars - log_degree + log_inv_rate)
+			.map_err(|err| Error::EncodeError(Box::new(err)))?;
+
+		Ok(Self {
+			n_vars,
+			log_inv_rate,
+			log_fold_arity,
+			n_test_queries,
+			ntt,
+			compression,
+			_p_marker: PhantomData,
+			_pe_marker: PhantomData,
+			_h_marker: PhantomData,
+		})
+	}
+
+	/// The base-2 logarithm of the inverse rate of the Reed–Solomon code.
+	pub fn log_inv_rate(&self) -> usize {
+		self.log_inv_rate
+	}
+
+	/// The base-2 logarithm of the number of codeword entries folded between oracles.
+	pub fn log_fold_arity(&self) -> usize {
+		self.log_fold_arity
+	}
+
+	/// The number of FRI queries.
+	pub fn n_test_queries(&self) -> usize {
+		self.n_test_queries
+	}
+
+	/// The base-2 logarithm of the number of base field elements packed into an extension field
+	/// element.
+	fn log_degree(&self) -> usize {
+		<FE as ExtensionField<F>>::DEGREE.trailing_zeros() as usize
+	}
+
+	/// The number of variables of the packed polynomials, which is also the number of folding
+	/// rounds.
+	fn log_dim(&self) -> usize {
+		self.n_vars - self.log_degree()
+	}
+
+	/// The base-2 logarithm of the Reed–Solomon code length.
+	fn log_code_len(&self) -> usize {
+		self.log_dim() + self.log_inv_rate
+	}
+
+	/// The number of folding rounds covered by each oracle, starting with the committed codewords.
+	fn oracle_arities(&self) -> Vec<usize> {
+		let log_dim = self.log_dim();
+		(0..log_dim)
+			.step_by(self.log_fold_arity)
+			.map(|start_round| self.log_fold_arity.min(log_dim - start_round))
+			.collect()
+	}
+}
+
+impl<F, P, FE, PE, FD> FRIPCS<P, PE, FD, GroestlHasher<FE>, GroestlDigestCompression>
+where
+	F: Field,
+	P: PackedField<Scalar = F>,
+	FE: BinaryField + ExtensionField<F>,
+	PE: PackedField<Scalar = FE>,
+	FD: BinaryField,
+{
+	pub fn new_using_groestl_merkle_tree(
+		n_vars: usize,
+		log_inv_rate: usize,
+		log_fold_arity: usize,
+		n_test_queries: usize,
+	) -> Result<Self, Error> {
+		Self::new(n_vars, log_inv_rate, log_fold_arity, n_test_queries, GroestlDigestCompression)
+	}
+}
+
+impl<F, P, FE, PE, FD, H, C> PolyCommitScheme<P, FE> for FRIPCS<P, PE, FD, H, C>
+where
+	F: Field,
+	P: PackedField<Scalar = F>,
+	FE: BinaryField + ExtensionField<F> + ExtensionField<FD>,
+	PE: PackedFieldIndexable<Scalar = FE> + PackedExtensionField<P> + PackedExtensionField<FD>,
+	FD: BinaryField,
+	H: Hasher<FE> + Send + Sync,
+	H::Digest: PackedField + Send + Sync + SerializeBytes + DeserializeBytes,
+	C: PseudoCompressionFunction<H::Digest, 2> + Clone + Sync,
+{
+	type Commitment = H::Digest;
+	type Committed = (Vec<Vec<FE>>, MerkleTree<H::Digest>);
+	type Proof = Proof<FE, H::Digest>;
+	type Error = Error;
+
+	fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	#[instrument(skip_all, name = "fri_pcs::commit")]
+	fn commit_with_rng(
+		&self,
+		polys: &[MultilinearExtension<P>],
+		_rng: impl RngCore,
+	) -> Result<(Self::Commitment, Self::Committed), Error> {
+		let log_dim = self.log_dim();
+		let codewords = polys
+			.iter()
+			.map(|poly| {
+				if poly.n_vars() != self.n_vars {
+					return Err(Error::IncorrectPolynomialSize {
+						expected: self.n_vars,
+					});
+				}
+				let message = PE::try_cast_to_ext(poly.evals()).ok_or(Error::UnalignedMessage)?;
+
+				let mut codeword = vec![PE::default(); (1 << self.log_code_len()) / PE::WIDTH];
+				codeword
+					.par_chunks_exact_mut((1 << log_dim) / PE::WIDTH)
+					.enumerate()
+					.try_for_each(|(coset, chunk)| {
+						chunk.copy_from_slice(message);
+						AdditiveNTT::<FD>::forward_transform_ext(&self.ntt, chunk, coset as u32)
+					})
+					.map_err(|err| Error::EncodeError(Box::new(err)))?;
+				let codeword = PE::unpack_scalars(&codeword).to_vec();
+				Ok(codeword)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let log_coset_size = self.oracle_arities()[0];
+		let (commitment, tree) = self
+			.oracle_vcs(self.log_code_len() - log_coset_size)
+			.commit_batch(
+				codewords
+					.iter()
+					.flat_map(|codeword| coset_columns(codeword, log_coset_size)),
+			)
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+		Ok((oracle_root(&commitment), (codewords, tree)))
+	}
+
+	/// Generate an evaluation proof at a *random* challenge point.
+	///
+	/// Precondition: The queried point must already be observed by the challenger.
+	#[instrument(skip_all, name = "fri_pcs::prove_evaluation")]
+	fn prove_evaluation<CH>(
+		&self,
+		challenger: &mut CH,
+		committed: &Self::Committed,
+		polys: &[MultilinearExtension<P>],
+		query: &[FE],
+	) -> Result<Self::Proof, Error>
+	where
+		CH: CanObserve<FE>
+			+ CanObserve<Self::Commitment>
+			+ CanSample<FE>
+			+ CanSampleBits<usize>
+			+ DomainSeparator,
+	{
+		let (codewords, tree) = committed;
+		let n_polys = polys.len();
+		if codewords.len() != n_polys {
+			return Err(Error::NumBatchedMismatchError {
+				err_str: format!("In prove_evaluation: number of polynomials {} must match number of committed codewords {}", n_polys, codewords.len()),
+			});
+		}
+		if query.len() != self.n_vars {
+			return Err(PolynomialError::IncorrectQuerySize {
+				expected: self.n_vars,
+			}
+			.into());
+		}
+
+		let log_dim = self.log_dim();
+		let query_high = &query[self.log_degree()..];
+
+		// Ring-switching reduces the claims on the polynomials to a sumcheck claim on the mixed
+		// packed polynomial t' times the ring-switching equality indicator.
+		let high_query = MultilinearQuery::<PE>::with_full_query(query_high)?;
+		let partial_evals = polys
+			.iter()
+			.map(|poly| {
+				let partial_eval = poly.evaluate_partial_high(&high_query)?;
+				Ok(PE::unpack_scalars(partial_eval.evals()).to_vec())
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+		challenger.observe_slice_labeled(PARTIAL_EVALS_LABEL, &partial_evals.concat());
+
+		let ring_switch_challenges =
+			challenger.sample_vec_labeled(RING_SWITCH_LABEL, self.log_degree());
+		let mixing_challenges =
+			challenger.sample_vec_labeled(MIXING_LABEL, log2_ceil_usize(n_polys));
+		let row_coeffs =
+			MultilinearQuery::<FE>::with_full_query(&ring_switch_challenges)?.into_expansion();
+		let mixing_coeffs = &MultilinearQuery::<FE>::with_full_query(&mixing_challenges)?
+			.into_expansion()[..n_polys];
+
+		let packed_polys = polys
+			.iter()
+			.map(|poly| {
+				PE::try_cast_to_ext(poly.evals())
+					.map(PE::unpack_scalars)
+					.ok_or(Error::UnalignedMessage)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		let mut t_prime = mix_vectors(&packed_polys, mixing_coeffs);
+		let mut ring_switch_eq =
+			MultilinearQuery::<FE>::with_full_query(query_high)?.into_expansion();
+		ring_switch_eq
+			.par_iter_mut()
+			.for_each(|eq| *eq = inner_product_bases::<F, _>(*eq, &row_coeffs));
+
+		// Run the sumcheck rounds, folding the mixed codeword with the same challenges.
+		let arities = self.oracle_arities();
+		let mut codeword = mix_vectors(codewords, mixing_coeffs);
+		let mut round_polys = Vec::with_capacity(log_dim);
+		let mut round_commitments = Vec::with_capacity(arities.len() - 1);
+		let mut round_committed = Vec::with_capacity(arities.len() - 1);
+		for round in 0..log_dim {
+			let round_poly = sumcheck_round_poly(&t_prime, &ring_switch_eq);
+			challenger.observe_slice_labeled(ROUND_LABEL, &round_poly);
+			let challenge = challenger.sample();
+			round_polys.push(round_poly);
+
+			t_prime = fold_halves(&t_prime, challenge);
+			ring_switch_eq = fold_halves(&ring_switch_eq, challenge);
+			codeword = self.fold_codeword(round, &codeword, challenge);
+
+			if (round + 1) % self.log_fold_arity == 0 && round + 1 < log_dim {
+				let log_coset_size = arities[(round + 1) / self.log_fold_arity];
+				let log_len = self.log_code_len() - (round + 1) - log_coset_size;
+				let (commitment, tree) = self
+					.oracle_vcs(log_len)
+					.commit_batch(coset_columns(&codeword, log_coset_size).into_iter())
+					.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+				let commitment = oracle_root(&commitment);
+				challenger.observe_labeled(COMMITMENT_LABEL, commitment);
+				round_commitments.push(commitment);
+				round_committed.push((codeword.clone(), tree));
+			}
+		}
+
+		let final_value = t_prime[0];
+		debug_assert!(codeword.iter().all(|&value| value == final_value));
+		challenger.observe_labeled(FINAL_VALUE_LABEL, final_value);
+
+		challenger.observe_label(QUERIES_LABEL.as_bytes());
+		let queries = repeat_with(|| challenger.sample_bits(self.log_code_len() - arities[0]))
+			.take(self.n_test_queries)
+			.map(|index| self.prove_query(codewords, tree, &round_committed, index))
+			.collect::<Result<_, _>>()?;
+
+		Ok(Proof {
+			n_polys,
+			partial_evals,
+			round_polys,
+			round_commitments,
+			final_value,
+			queries,
+		})
+	}
+
+	/// Verify an evaluation proof at a *random* challenge point.
+	///
+	/// Precondition: The queried point must already be observed by the challenger.
+	#[instrument(skip_all, name = "fri_pcs::verify_evaluation")]
+	fn verify_evaluation<CH>(
+		&self,
+		challenger: &mut CH,
+		commitment: &Self::Commitment,
+		query: &[FE],
+		proof: Self::Proof,
+		values: &[FE],
+	) -> Result<(), Error>
+	where
+		CH: CanObserve<FE>
+			+ CanObserve<Self::Commitment>
+			+ CanSample<FE>
+			+ CanSampleBits<usize>
+			+ DomainSeparator,
+	{
+		self.check_proof_shape(&proof, values.len())?;
+
+		let Proof {
+			partial_evals,
+			round_polys,
+			round_commitments,
+			final_value,
+			queries,
+			..
+		} = proof;
+		let mut scalars = partial_evals
+			.into_iter()
+			.flatten()
+			.chain(round_polys.into_iter().flatten())
+			.chain(iter::once(final_value));
+		let mut round_commitments = round_commitments.into_iter();
+
+		self.verify_evaluation_with(
+			challenger,
+			commitment,
+			query,
+			values,
+			|challenger, n| {
+				let scalars = scalars.by_ref().take(n).collect::<Vec<_>>();
+				challenger.observe_slice(&scalars);
+				Ok(scalars)
+			},
+			|challenger| {
+				let commitment = round_commitments
+					.next()
+					.expect("number of commitments is checked by check_proof_shape");
+				challenger.observe(commitment);
+				Ok(commitment)
+			},
+			|_| Ok(queries),
+		)
+	}
+
+	/// Generate an evaluation proof at a *random* challenge point, writing it to a transcript.
+	///
+	/// The query openings are written after the query indices are sampled.
+	fn prove_evaluation_with_transcript<C2>(
+		&self,
+		transcript: &mut TranscriptWriter<C2>,
+		committed: &Self::Committed,
+		polys: &[MultilinearExtension<P>],
+		query: &[FE],
+	) -> Result<(), Error>
+	where
+		C2: CanObserve<BinaryField8b>
+			+ CanSample<FE>
+			+ CanSampleBits<usize>
+			+ DomainSeparator
+			+ Clone,
+		FE: SerializeBytes,
+	{
+		let Proof { queries, .. } = self.prove_evaluation(transcript, committed, polys, query)?;
+		transcript.write(&queries);
+		Ok(())
+	}
+
+	/// Verify an evaluation proof at a *random* challenge point, reading it from a transcript.
+	#[instrument(skip_all, name = "fri_pcs::verify_evaluation_with_transcript")]
+	fn verify_evaluation_with_transcript<C2>(
+		&self,
+		transcript: &mut TranscriptReader<C2>,
+		commitment: &Self::Commitment,
+		query: &[FE],
+		values: &[FE],
+	) -> Result<(), Error>
+	where
+		C2: CanObserve<BinaryField8b> + CanSample<FE> + CanSampleBits<usize> + DomainSeparator,
+		FE: DeserializeBytes,
+	{
+		self.verify_evaluation_with(
+			transcript,
+			commitment,
+			query,
+			values,
+			|transcript, n| Ok(transcript.read_vec(n)?),
+			|transcript| Ok(transcript.read()?),
+			|transcript| Ok(transcript.read()?),
+		)
+	}
+
+	fn proof_size(&self, n_polys: usize) -> usize {
+		let fe_size = mem::size_of::<FE>();
+		let digest_size = mem::size_of::<H::Digest>();
+		let log_dim = self.log_dim();
+		let arities = self.oracle_arities();
+
+		let messages_size = fe_size * ((n_polys << self.log_degree()) + 2 * log_dim + 1)
+			+ digest_size * (arities.len() - 1);
+		let query_size = arities
+			.iter()
+			.enumerate()
+			.map(|(oracle, &log_coset_size)| {
+				let start_round = oracle * self.log_fold_arity;
+				let n_values = if oracle == 0 { n_polys } else { 1 } << log_coset_size;
+				let branch_len = self.log_code_len() - start_round - log_coset_size;
+				fe_size * n_values + digest_size * branch_len
+			})
+			.sum::<usize>();
+		messages_size + query_size * self.n_test_queries
+	}
+}
+
+impl<F, P, FE, PE, FD, H, C> FRIPCS<P, PE, FD, H, C>
+where
+	F: Field,
+	P: PackedField<Scalar = F>,
+	FE: BinaryField + ExtensionField<F> + ExtensionField<FD>,
+	PE: PackedFieldIndexable<Scalar = FE> + PackedExtensionField<P> + PackedExtensionField<FD>,
+	FD: BinaryField,
+	H: Hasher<FE> + Send + Sync,
+	H::Digest: PackedField + Send + Sync,
+	C: PseudoCompressionFunction<H::Digest, 2> + Clone + Sync,
+{
+	/// Shared implementation of the evaluation proof verifier.
+	///
+	/// The prover messages are obtained through `read_scalars`, `read_commitment` and
+	/// `read_queries`, which are responsible for observing them with the challenger after the
+	/// message label has been absorbed.
+	#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+	fn verify_evaluation_with<CH>(
+		&self,
+		challenger: &mut CH,
+		commitment: &H::Digest,
+		query: &[FE],
+		values: &[FE],
+		mut read_scalars: impl FnMut(&mut CH, usize) -> Result<Vec<FE>, Error>,
+		mut read_commitment: impl FnMut(&mut CH) -> Result<H::Digest, Error>,
+		read_queries: impl FnOnce(&mut CH) -> Result<Vec<QueryProof<FE, H::Digest>>, Error>,
+	) -> Result<(), Error>
+	where
+		CH: CanSample<FE> + CanSampleBits<usize> + DomainSeparator,
+	{
+		if query.len() != self.n_vars {
+			return Err(PolynomialError::IncorrectQuerySize {
+				expected: self.n_vars,
+			}
+			.into());
+		}
+
+		let n_polys = values.len();
+		let log_degree = self.log_degree();
+		let log_dim = self.log_dim();
+		let (query_low, query_high) = query.split_at(log_degree);
+
+		// Check the partial evaluations against the claimed evaluations.
+		challenger.observe_label(PARTIAL_EVALS_LABEL.as_bytes());
+		let partial_evals = read_scalars(challenger, n_polys << log_degree)?;
+		let low_query = MultilinearQuery::<FE>::with_full_query(query_low)?;
+		let incorrect_partial_eval = partial_evals.chunks_exact(1 << log_degree).zip(values).any(
+			|(partial_evals, &value)| {
+				let computed_value = partial_evals
+					.iter()
+					.zip(low_query.expansion())
+					.map(|(&partial_eval, &coeff)| partial_eval * coeff)
+					.sum::<FE>();
+				computed_value != value
+			},
+		);
+		if incorrect_partial_eval {
+			return Err(VerificationError::IncorrectPartialEvaluation.into());
+		}
+
+		let ring_switch_challenges = challenger.sample_vec_labeled(RING_SWITCH_LABEL, log_degree);
+		let mixing_challenges =
+			challenger.sample_vec_labeled(MIXING_LABEL, log2_ceil_usize(n_polys));
+		let row_coeffs =
+			MultilinearQuery::<FE>::with_full_query(&ring_switch_challenges)?.into_expansion();
+		let mixing_coeffs = &MultilinearQuery::<FE>::with_full_query(&mixing_challenges)?
+			.into_expansion()[..n_polys];
+
+		// Combine the rows of the partial evaluations into the sumcheck claim.
+		let bases = (0..1 << log_degree)
+			.map(|j| {
+				<FE as ExtensionField<F>>::basis(j).expect("j is less than the extension degree")
+			})
+			.collect::<Vec<_>>();
+		let mut claim = partial_evals
+			.chunks_exact(1 << log_degree)
+			.zip(mixing_coeffs)
+			.map(|(partial_evals, &mixing_coeff)| {
+				let row_combination = partial_evals
+					.iter()
+					.zip(&bases)
+					.map(|(&partial_eval, &basis)| {
+						basis * inner_product_bases::<F, _>(partial_eval, &row_coeffs)
+					})
+					.sum::<FE>();
+				row_combination * mixing_coeff
+			})
+			.sum::<FE>();
+
+		// Verify the sumcheck rounds and read the commitments to the folded codewords.
+		let arities = self.oracle_arities();
+		let mut challenges = Vec::with_capacity(log_dim);
+		let mut round_commitments = Vec::with_capacity(arities.len() - 1);
+		for round in 0..log_dim {
+			challenger.observe_label(ROUND_LABEL.as_bytes());
+			let round_poly = read_scalars(challenger, 2)?;
+			let (constant, quadratic) = (round_poly[0], round_poly[1]);
+			let challenge = challenger.sample();
+
+			// The round polynomial h satisfies h(0) + h(1) = claim, which determines its linear
+			// coefficient in characteristic 2.
+			claim = constant + (claim + quadratic) * challenge + quadratic * challenge.square();
+			challenges.push(challenge);
+
+			if (round + 1) % self.log_fold_arity == 0 && round + 1 < log_dim {
+				challenger.observe_label(COMMITMENT_LABEL.as_bytes());
+				round_commitments.push(read_commitment(challenger)?);
+			}
+		}
+
+		challenger.observe_label(FINAL_VALUE_LABEL.as_bytes());
+		let final_value = read_scalars(challenger, 1)?[0];
+		let ring_switch_eq_eval = eval_ring_switch_eq::<F, _>(query_high, &challenges, &row_coeffs);
+		if claim != final_value * ring_switch_eq_eval {
+			return Err(VerificationError::IncorrectEvaluation.into());
+		}
+
+		challenger.observe_label(QUERIES_LABEL.as_bytes());
+		let indices = repeat_with(|| challenger.sample_bits(self.log_code_len() - arities[0]))
+			.take(self.n_test_queries)
+			.collect::<Vec<_>>();
+		let queries = read_queries(challenger)?;
+		if queries.len() != self.n_test_queries {
+			return Err(VerificationError::NumberOfOpeningProofs {
+				expected: self.n_test_queries,
+			}
+			.into());
+		}
+
+		indices
+			.into_iter()
+			.zip(queries)
+			.try_for_each(|(index, query_proof)| {
+				self.verify_query(
+					commitment,
+					&round_commitments,
+					&challenges,
+					mixing_coeffs,
+					final_value,
+					index,
+					query_proof,
+				)
+			})
+	}
+
+	/// Verify the openings of a single query and check that they fold to the final value.
+	#[allow(clippy::too_many_arguments)]
+	fn verify_query(
+		&self,
+		commitment: &H::Digest,
+		round_commitments: &[H::Digest],
+		challenges: &[FE],
+		mixing_coeffs: &[FE],
+		final_value: FE,
+		index: usize,
+		query_proof: QueryProof<FE, H::Digest>,
+	) -> Result<(), Error> {
+		let arities = self.oracle_arities();
+		let mut index = index << arities[0];
+		if query_proof.len() != arities.len() {
+			return Err(VerificationError::QueryProofShape.into());
+		}
+
+		let mut value = None;
+		let commitments = iter::once(commitment).chain(round_commitments);
+		for (oracle, (((values, branch), commitment), &log_coset_size)) in query_proof
+			.into_iter()
+			.zip(commitments)
+			.zip(&arities)
+			.enumerate()
+		{
+			let start_round = oracle * self.log_fold_arity;
+			let n_vecs = if oracle == 0 { mixing_coeffs.len() } else { 1 };
+			if values.len() != n_vecs << log_coset_size {
+				return Err(VerificationError::QueryProofShape.into());
+			}
+
+			let coset_index = index >> log_coset_size;
+			let log_len = self.log_code_len() - start_round - log_coset_size;
+			self.oracle_vcs(log_len)
+				.verify_batch_opening(
+					&MerkleCap(vec![*commitment]),
+					coset_index,
+					branch,
+					values.iter().copied(),
+				)
+				.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+
+			let coset = if oracle == 0 {
+				let columns = values.chunks_exact(1 << log_coset_size).collect::<Vec<_>>();
+				mix_vectors(&columns, mixing_coeffs)
+			} else {
+				values
+			};
+
+			// The value folded from the previous oracle must match the opened coset.
+			if let Some(value) = value {
+				if coset[index % (1 << log_coset_size)] != value {
+					return Err(VerificationError::IncorrectFolding.into());
+				}
+			}
+
+			let folded = (0..log_coset_size).fold(coset, |coset, i| {
+				let round = start_round + i;
+				let n_pairs = coset.len() / 2;
+				coset
+					.chunks_exact(2)
+					.enumerate()
+					.map(|(j, pair)| {
+						self.fold_pair(round, coset_index * n_pairs + j, pair, challenges[round])
+					})
+					.collect()
+			});
+			value = Some(folded[0]);
+			index = coset_index;
+		}
+
+		if value != Some(final_value) {
+			return Err(VerificationError::IncorrectFolding.into());
+		}
+		Ok(())
+	}
+
+	fn prove_query(
+		&self,
+		codewords: &[Vec<FE>],
+		tree: &MerkleTree<H::Digest>,
+		round_committed: &[(Vec<FE>, MerkleTree<H::Digest>)],
+		index: usize,
+	) -> Result<QueryProof<FE, H::Digest>, Error> {
+		let arities = self.oracle_arities();
+		let trees = iter::once((codewords, tree)).chain(
+			round_committed
+				.iter()
+				.map(|(codeword, tree)| (std::slice::from_ref(codeword), tree)),
+		);
+
+		let mut index = index << arities[0];
+		trees
+			.zip(&arities)
+			.enumerate()
+			.map(|(oracle, ((codewords, tree), &log_coset_size))| {
+				let start_round = oracle * self.log_fold_arity;
+				let coset_index = index >> log_coset_size;
+				index = coset_index;
+
+				let coset_range =
+					coset_index << log_coset_size..(coset_index + 1) << log_coset_size;
+				let values = codewords
+					.iter()
+					.flat_map(|codeword| codeword[coset_range.clone()].iter().copied())
+					.collect();
+				let log_len = self.log_code_len() - start_round - log_coset_size;
+				let branch = self
+					.oracle_vcs(log_len)
+					.prove_batch_opening(tree, coset_index)
+					.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+				Ok((values, branch))
+			})
+			.collect()
+	}
+
+	fn check_proof_shape(&self, proof: &Proof<FE, H::Digest>, n_polys: usize) -> Result<(), Error> {
+		if proof.n_polys != n_polys {
+			return Err(Error::NumBatchedMismatchError {
+				err_str: format!(
+					"In verify_evaluation: proof number of polynomials {} must match number of opened values {}",
+					proof.n_polys, n_polys
+				),
+			});
+		}
+
+		let n_partial_evals = 1 << self.log_degree();
+		if proof
+			.partial_evals
+			.iter()
+			.any(|partial_evals| partial_evals.len() != n_partial_evals)
+		{
+			return Err(VerificationError::PartialEvaluationSize.into());
+		}
+		if proof.round_polys.len() != self.log_dim()
+			|| proof.round_commitments.len() != self.oracle_arities().len() - 1
+		{
+			return Err(VerificationError::ProofShape.into());
+		}
+		Ok(())
+	}
+
+	/// Fold a codeword in half using the folding challenge for the given round.
+	fn fold_codeword(&self, round: usize, codeword: &[FE], challenge: FE) -> Vec<FE> {
+		codeword
+			.par_chunks_exact(2)
+			.enumerate()
+			.map(|(j, pair)| self.fold_pair(round, j, pair, challenge))
+			.collect()
+	}
+
+	/// Fold a pair of adjacent codeword entries.
+	///
+	/// This applies the inverse additive NTT butterfly for the round, which splits the pair into
+	/// the evaluations of the even and odd parts of the codeword's message, and then interpolates
+	/// between them at the challenge.
+	fn fold_pair(&self, round: usize, pair_index: usize, pair: &[FE], challenge: FE) -> FE {
+		let twiddle = self.ntt.get_subspace_eval(round, pair_index);
+		let odd = pair[0] + pair[1];
+		let even = pair[0] + odd * twiddle;
+		even + (even + odd) * challenge
+	}
+
+	fn oracle_vcs(&self, log_len: usize) -> MerkleTreeVCS<FE, H::Digest, H, C> {
+		MerkleTreeVCS::new(log_len, self.compression.clone())
+	}
+}
+
+/// Returns the root of an oracle commitment, which is a Merkle tree with a single root.
+fn oracle_root<D: Copy>(commitment: &MerkleCap<D>) -> D {
+	commitment
+		.root()
+		.expect("oracle Merkle trees are constructed with cap height 0")
+}
+
+/// Returns the column vectors whose entries at each index form a coset of the codeword.
+fn coset_columns<F: Copy>(codeword: &[F], log_coset_size: usize) -> Vec<Vec<F>> {
+	(0..1 << log_coset_size)
+		.map(|offset| {
+			codeword
+				.iter()
+				.skip(offset)
+				.step_by(1 << log_coset_size)
+				.copied()
+				.collect()
+		})
+		.collect()
+}
+
+/// Computes the linear combination of equal-length vectors with the given coefficients.
+fn mix_vectors<F: Field>(vectors: &[impl AsRef<[F]> + Sync], coeffs: &[F]) -> Vec<F> {
+	let len = vectors.first().map_or(0, |vector| vector.as_ref().len());
+	(0..len)
+		.into_par_iter()
+		.map(|i| {
+			vectors
+				.iter()
+				.zip(coeffs)
+				.map(|(vector, &coeff)| vector.as_ref()[i] * coeff)
+				.sum()
+		})
+		.collect()
+}
+
+/// Computes $\sum_k x_k c_k$, where $x_k$ are the coordinates of $x$ over the subfield.
+fn inner_product_bases<F: Field, FE: ExtensionField<F>>(x: FE, coeffs: &[FE]) -> FE {
+	x.iter_bases()
+		.zip(coeffs)
+		.map(|(coord, &coeff)| coeff * coord)
+		.sum()
+}
+
+/// Evaluates the multilinear extension of the ring-switching equality indicator at `point`.
+///
+/// The indicator maps $v$ to $\sum_k e_{v,k} c_k$, where $e_{v,k}$ are the coordinates of
+/// $\widetilde{eq}(q, v)$ over the subfield and $c_k$ are the row coefficients. Its multilinear
+/// extension at $r$ is read off the element
+/// $\sum_v \widetilde{eq}(r, v) \otimes \widetilde{eq}(q, v) = \prod_i ((1 - r_i) \otimes (1 - q_i) + r_i \otimes q_i)$
+/// of the tensor algebra $F_E \otimes_F F_E$, which is computed in $O(\kappa^2)$ operations per
+/// variable. Tensor algebra elements are represented by their coordinates $z_k$ in
+/// $\sum_k z_k \otimes \beta_k$.
+fn eval_ring_switch_eq<F: Field, FE: ExtensionField<F>>(
+	query: &[FE],
+	point: &[FE],
+	row_coeffs: &[FE],
+) -> FE {
+	let degree = FE::DEGREE;
+	let mut tensor = vec![FE::ZERO; degree];
+	tensor[0] = FE::ONE;
+
+	for (&q_i, &r_i) in query.iter().zip(point) {
+		// Multiply the tensor by 1 ⊗ q_i
+		let mut scaled = vec![FE::ZERO; degree];
+		for (j, &z_j) in tensor.iter().enumerate() {
+			let basis_times_q = FE::basis(j).expect("j is less than the extension degree") * q_i;
+			for (scaled_k, coord) in scaled.iter_mut().zip(basis_times_q.iter_bases()) {
+				*scaled_k += z_j * coord;
+			}
+		}
+
+		for (z_k, scaled_k) in tensor.iter_mut().zip(scaled) {
+			*z_k = (FE::ONE - r_i) * (*z_k - scaled_k) + r_i * scaled_k;
+		}
+	}
+
+	tensor
+		.into_iter()
+		.zip(row_coeffs)
+		.map(|(z_k, &coeff)| z_k * coeff)
+		.sum()
+}
+
+/// Calculates the number of FRI queries required for the given security level.
+///
+/// The per-query soundness error is bounded by $(1 + \rho) / 2$ in the unique decoding regime,
+/// where $\rho$ is the code rate. Throws if the extension field is too small for the errors of the
+/// folding and sumcheck rounds to be within the security level.
+pub fn calculate_n_test_queries<FE: BinaryField>(
+	security_bits: usize,
+	log_code_len: usize,
+	log_inv_rate: usize,
+) -> Result<usize, Error> {
+	let field_err_bits = FE::N_BITS as f64 - log_code_len as f64 - (log_code_len as f64).log2();
+	if field_err_bits < security_bits as f64 {
+		return Err(Error::ParameterError);
+	}
+
+	let rate = 2.0_f64.powi(-(log_inv_rate as i32));
+	let per_query_err = (1.0 + rate) / 2.0;
+	Ok((-(security_bits as f64) / per_query_err.log2()).ceil() as usize)
+}
+
+/// Find the [`FRIPCS`] parameterization that optimizes proof size.
+///
+/// This constructs a [`FRIPCS`] using a Merkle tree using Groestl, searching over the code rate and
+/// the fold arity. The proof size is comparable with
+/// [`tensor_pcs::find_proof_size_optimal_pcs`](super::tensor_pcs::find_proof_size_optimal_pcs)
+/// through [`PolyCommitScheme::proof_size`].
+#[allow(clippy::type_complexity)]
+pub fn find_proof_size_optimal_pcs<F, P, FE, PE, FD>(
+	security_bits: usize,
+	n_vars: usize,
+	n_polys: usize,
+) -> Option<FRIPCS<P, PE, FD, GroestlHasher<FE>, GroestlDigestCompression>>
+where
+	F: Field,
+	P: PackedField<Scalar = F>,
+	FE: BinaryField + ExtensionField<F> + ExtensionField<FD> + PackedExtensionField<BinaryField8b>,
+	<FE as PackedField>::Scalar: ExtensionField<BinaryField8b>,
+	PE: PackedFieldIndexable<Scalar = FE> + PackedExtensionField<P> + PackedExtensionField<FD>,
+	FD: BinaryField,
+{
+	let log_degree = <FE as ExtensionField<F>>::DEGREE.trailing_zeros() as usize;
+	let log_dim = n_vars.checked_sub(log_degree)?;
+
+	let mut best: Option<(usize, FRIPCS<_, _, _, _, _>)> = None;
+	for log_inv_rate in 1..=4 {
+		let n_test_queries = match calculate_n_test_queries::<FE>(
+			security_bits,
+			log_dim + log_inv_rate,
+			log_inv_rate,
+		) {
+			Ok(n_test_queries) => n_test_queries,
+			Err(_) => continue,
+		};
+		for log_fold_arity in 1..=log_dim.min(6) {
+			let pcs = match FRIPCS::new_using_groestl_merkle_tree(
+				n_vars,
+				log_inv_rate,
+				log_fold_arity,
+				n_test_queries,
+			) {
+				Ok(pcs) => pcs,
+				Err(_) => continue,
+			};
+
+			let proof_size = PolyCommitScheme::<P, FE>::proof_size(&pcs, n_polys);
+			if best
+				.as_ref()
+				.map_or(true, |(best_proof_size, _)| proof_size < *best_proof_size)
+			{
+				best = Some((proof_size, pcs));
+			}
+		}
+	}
+
+	best.map(|(_, pcs)| pcs)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		challenger::HashChallenger,
+		poly_commit::tensor_pcs,
+		serialization::{from_versioned_bytes, to_versioned_bytes},
+	};
+	use assert_matches::assert_matches;
+	use binius_field::{
+		BinaryField128b, BinaryField32b, PackedBinaryField128x1b, PackedBinaryField16x8b,
+		PackedBinaryField1x128b, PackedBinaryField8x16b,
+	};
+	use binius_hash::GroestlDigest;
+	use rand::{rngs::StdRng, SeedableRng};
+
+	type TestPCS<P> = FRIPCS<
+		P,
+		PackedBinaryField1x128b,
+		BinaryField32b,
+		GroestlHasher<BinaryField128b>,
+		GroestlDigestCompression,
+	>;
+
+	fn random_polys<P: PackedField>(
+		n_vars: usize,
+		n_polys: usize,
+	) -> Vec<MultilinearExtension<'static, P>> {
+		let mut rng = StdRng::seed_from_u64(0);
+		repeat_with(|| {
+			let evals = repeat_with(|| P::random(&mut rng))
+				.take((1 << n_vars) / P::WIDTH)
+				.collect::<Vec<_>>();
+			MultilinearExtension::from_values(evals).unwrap()
+		})
+		.take(n_polys)
+		.collect()
+	}
+
+	fn evaluate_polys<P: PackedField>(
+		polys: &[MultilinearExtension<P>],
+		query: &[BinaryField128b],
+	) -> Vec<BinaryField128b>
+	where
+		BinaryField128b: ExtensionField<P::Scalar>,
+	{
+		let multilin_query =
+			MultilinearQuery::<PackedBinaryField1x128b>::with_full_query(query).unwrap();
+		polys
+			.iter()
+			.map(|poly| poly.evaluate(&multilin_query).unwrap())
+			.collect()
+	}
+
+	fn check_commit_prove_verify<P>(pcs: &TestPCS<P>, n_polys: usize)
+	where
+		P: PackedField,
+		BinaryField128b: ExtensionField<P::Scalar>,
+		PackedBinaryField1x128b: PackedExtensionField<P>,
+	{
+		let polys = random_polys::<P>(pcs.n_vars(), n_polys);
+		let (commitment, committed) = pcs.commit(&polys).unwrap();
+
+		let mut challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+		challenger.observe(commitment);
+		let query = repeat_with(|| challenger.sample())
+			.take(pcs.n_vars())
+			.collect::<Vec<_>>();
+		let values = evaluate_polys(&polys, &query);
+
+		let mut prove_challenger = challenger.clone();
+		let proof = pcs
+			.prove_evaluation(&mut prove_challenger, &committed, &polys, &query)
+			.unwrap();
+
+		let mut verify_challenger = challenger.clone();
+		pcs.verify_evaluation(&mut verify_challenger, &commitment, &query, proof.clone(), &values)
+			.unwrap();
+
+		// An incorrect evaluation must be rejected
+		let mut wrong_values = values.clone();
+		wrong_values[0] += BinaryField128b::ONE;
+		let mut verify_challenger = challenger.clone();
+		assert!(pcs
+			.verify_evaluation(
+				&mut verify_challenger,
+				&commitment,
+				&query,
+				proof.clone(),
+				&wrong_values
+			)
+			.is_err());
+
+		// A tampered opening must be rejected
+		let mut tampered_proof = proof;
+		tampered_proof.queries[0][0].0[0] += BinaryField128b::ONE;
+		let mut verify_challenger = challenger;
+		assert!(pcs
+			.verify_evaluation(&mut verify_challenger, &commitment, &query, tampered_proof, &values)
+			.is_err());
+	}
+
+	#[test]
+	fn test_packed_1b_commit_prove_verify() {
+		let pcs = TestPCS::<PackedBinaryField128x1b>::new_using_groestl_merkle_tree(12, 1, 2, 32)
+			.unwrap();
+		check_commit_prove_verify(&pcs, 1);
+		check_commit_prove_verify(&pcs, 3);
+	}
+
+	#[test]
+	fn test_packed_8b_commit_prove_verify() {
+		let pcs =
+			TestPCS::<PackedBinaryField16x8b>::new_using_groestl_merkle_tree(11, 2, 3, 32).unwrap();
+		check_commit_prove_verify(&pcs, 1);
+		check_commit_prove_verify(&pcs, 4);
+	}
+
+	#[test]
+	fn test_unpacked_commit_prove_verify() {
+		let pcs =
+			TestPCS::<PackedBinaryField1x128b>::new_using_groestl_merkle_tree(6, 1, 4, 16).unwrap();
+		check_commit_prove_verify(&pcs, 2);
+	}
+
+	#[test]
+	fn test_ring_switch_eq_eval() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let query = repeat_with(|| <BinaryField128b as Field>::random(&mut rng))
+			.take(3)
+			.collect::<Vec<_>>();
+		let point = repeat_with(|| <BinaryField128b as Field>::random(&mut rng))
+			.take(3)
+			.collect::<Vec<_>>();
+		let row_coeffs = repeat_with(|| <BinaryField128b as Field>::random(&mut rng))
+			.take(16)
+			.collect::<Vec<_>>();
+
+		let evals = MultilinearQuery::<BinaryField128b>::with_full_query(&query)
+			.unwrap()
+			.into_expansion()
+			.into_iter()
+			.map(|eq| inner_product_bases::<BinaryField8b, _>(eq, &row_coeffs))
+			.collect::<Vec<_>>();
+		let expected = MultilinearExtension::from_values(evals)
+			.unwrap()
+			.evaluate(&MultilinearQuery::<BinaryField128b>::with_full_query(&point).unwrap())
+			.unwrap();
+		assert_eq!(eval_ring_switch_eq::<BinaryField8b, _>(&query, &point, &row_coeffs), expected);
+	}
+
+	#[test]
+	fn test_commit_prove_verify_with_transcript() {
+		let pcs = TestPCS::<PackedBinaryField128x1b>::new_using_groestl_merkle_tree(12, 1, 2, 32)
+			.unwrap();
+		let polys = random_polys::<PackedBinaryField128x1b>(pcs.n_vars(), 2);
+		let (commitment, committed) = pcs.commit(&polys).unwrap();
+
+		let challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+
+		let mut prover_transcript = TranscriptWriter::new(challenger.clone());
+		prover_transcript.write(&commitment);
+		let query: Vec<BinaryField128b> = repeat_with(|| prover_transcript.sample())
+			.take(pcs.n_vars())
+			.collect();
+		pcs.prove_evaluation_with_transcript(&mut prover_transcript, &committed, &polys, &query)
+			.unwrap();
+		let proof = prover_transcript.finalize();
+		let values = evaluate_polys(&polys, &query);
+
+		let mut verifier_transcript = TranscriptReader::new(challenger.clone(), &proof);
+		let commitment = verifier_transcript.read().unwrap();
+		let verifier_query: Vec<BinaryField128b> = repeat_with(|| verifier_transcript.sample())
+			.take(pcs.n_vars())
+			.collect();
+		assert_eq!(verifier_query, query);
+		pcs.verify_evaluation_with_transcript(
+			&mut verifier_transcript,
+			&commitment,
+			&query,
+			&values,
+		)
+		.unwrap();
+		verifier_transcript.finalize().unwrap();
+
+		// Any change to the query openings must be rejected
+		let mut tampered_proof = proof.clone();
+		*tampered_proof.last_mut().unwrap() ^= 1;
+		let mut verifier_transcript = TranscriptReader::new(challenger, &tampered_proof);
+		let commitment = verifier_transcript.read().unwrap();
+		for _ in 0..pcs.n_vars() {
+			let _: BinaryField128b = verifier_transcript.sample();
+		}
+		assert!(pcs
+			.verify_evaluation_with_transcript(
+				&mut verifier_transcript,
+				&commitment,
+				&query,
+				&values,
+			)
+			.is_err());
+	}
+
+	#[test]
+	fn test_proof_serialization_round_trip() {
+		let pcs =
+			TestPCS::<PackedBinaryField16x8b>::new_using_groestl_merkle_tree(11, 2, 3, 32).unwrap();
+		let n_polys = 2;
+		let polys = random_polys::<PackedBinaryField16x8b>(pcs.n_vars(), n_polys);
+		let (commitment, committed) = pcs.commit(&polys).unwrap();
+
+		let mut challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+		let query = repeat_with(|| challenger.sample())
+			.take(pcs.n_vars())
+			.collect::<Vec<_>>();
+		let values = evaluate_polys(&polys, &query);
+
+		let mut prove_challenger = challenger.clone();
+		let proof = pcs
+			.prove_evaluation(&mut prove_challenger, &committed, &polys, &query)
+			.unwrap();
+
+		// The encoding only adds the version, the length prefixes and n_polys on top of the
+		// estimated proof size.
+		let n_oracles = pcs.oracle_arities().len();
+		let framing_size =
+			2 + 8 + 8 * (1 + n_polys) + 8 + 8 + 8 + pcs.n_test_queries() * (8 + 16 * n_oracles);
+		let proof_bytes = to_versioned_bytes(&proof);
+		assert_eq!(proof_bytes.len(), pcs.proof_size(n_polys) + framing_size);
+
+		let decoded_proof = from_versioned_bytes(&proof_bytes).unwrap();
+		assert_eq!(to_versioned_bytes(&decoded_proof), proof_bytes);
+
+		let mut verify_challenger = challenger.clone();
+		pcs.verify_evaluation(&mut verify_challenger, &commitment, &query, decoded_proof, &values)
+			.unwrap();
+
+		type TestProof = Proof<BinaryField128b, GroestlDigest>;
+		assert_matches!(
+			from_versioned_bytes::<TestProof>(&proof_bytes[..proof_bytes.len() - 1]),
+			Err(SerializationError::NotEnoughBytes { .. })
+		);
+	}
+
+	#[test]
+	fn test_proof_size_compared_to_tensor_pcs() {
+		let fri_pcs = find_proof_size_optimal_pcs::<
+			_,
+			PackedBinaryField128x1b,
+			BinaryField128b,
+			PackedBinaryField1x128b,
+			BinaryField32b,
+		>(96, 30, 1)
+		.unwrap();
+		assert_eq!(fri_pcs.n_vars(), 30);
+
+		let tensor_pcs = tensor_pcs::find_proof_size_optimal_pcs::<
+			_,
+			PackedBinaryField128x1b,
+			_,
+			PackedBinaryField8x16b,
+			_,
+			PackedBinaryField8x16b,
+			_,
+			PackedBinaryField1x128b,
+		>(96, 30, 1, 2, false, 0)
+		.unwrap();
+
+		// FRI proofs grow polylogarithmically, so they are smaller for large polynomials.
+		assert!(
+			PolyCommitScheme::<_, BinaryField128b>::proof_size(&fri_pcs, 1)
+				< PolyCommitScheme::<_, BinaryField128b>::proof_size(&tensor_pcs, 1)
+		);
+	}
+}
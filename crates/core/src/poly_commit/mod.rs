@@ -1,9 +1,16 @@
 // Copyright 2023 Ulvetanna Inc.
 
 mod error;
+pub mod fri_pcs;
+pub mod multi_batch;
+mod multi_point;
 mod pcs;
+mod product_sumcheck;
 pub mod tensor_pcs;
 
 pub use error::*;
+pub use fri_pcs::FRIPCS;
+pub use multi_batch::{MultiBatchProof, MultiBatchTensorPCS, TensorPCSBatch};
+pub use multi_point::{MultiPointEvalClaim, MultiPointProof};
 pub use pcs::*;
 pub use tensor_pcs::{BasicTensorPCS, BlockTensorPCS, TensorPCS};
@@ -0,0 +1,763 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Commitments to several batches of polynomials under a single vector commitment.
+//!
+//! Each batch is committed with its own [`TensorPCS`] instance, possibly over a different field,
+//! as a matrix of intermediate field elements whose rows are encoded with a linear code. When all
+//! instances share the intermediate field, the extension field and the code length, the encoded
+//! columns of all batches at the same index can be committed as one leaf of a single vector
+//! commitment. An evaluation proof then sends the mixed partial evaluation $t'$ of every batch
+//! and opens the columns of all batches at column indices that are sampled once and shared across
+//! the batches.
+//!
+//! [`TensorPCS`]: super::TensorPCS
+
+use super::{
+	error::{Error, VerificationError},
+	tensor_pcs::column_digests,
+};
+use crate::{
+	challenger::{CanObserve, CanSample, CanSampleBits, DomainSeparator},
+	merkle_tree::{MerkleTreeVCS, VectorCommitScheme},
+	polynomial::{
+		multilinear_query::MultilinearQuery, Error as PolynomialError, MultilinearExtension,
+	},
+	serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes},
+};
+use binius_field::{
+	util::inner_product_unchecked, BinaryField8b, ExtensionField, Field, PackedExtensionField,
+	PackedField, PackedFieldIndexable,
+};
+use binius_hash::{
+	GroestlDigest, GroestlDigestCompression, GroestlHasher, HashDigest, HasherDigest,
+};
+use p3_matrix::{dense::RowMajorMatrix, MatrixRowSlices};
+use p3_util::{log2_ceil_usize, log2_strict_usize};
+use std::{iter::repeat_with, marker::PhantomData, mem};
+use tracing::instrument;
+
+/// Challenger label for the coefficients mixing the polynomials of a batch.
+const MIXING_LABEL: &str = "pcs.multi_batch.mixing";
+/// Challenger label for the mixed partial evaluation $t'$ of a batch.
+const T_PRIME_LABEL: &str = "pcs.multi_batch.t_prime";
+/// Challenger label for the column indices shared by all batches.
+const QUERIES_LABEL: &str = "pcs.multi_batch.queries";
+
+/// A tensor PCS instance whose encoded columns can be committed together with those of other
+/// instances.
+///
+/// The trait is object safe and does not depend on the field of the committed polynomials, so
+/// that instances over different fields can be combined in a [`MultiBatchTensorPCS`]. The
+/// polynomials are passed as their evaluations packed into the intermediate field.
+pub trait TensorPCSBatch<PI, PE>
+where
+	PE: PackedField,
+{
+	/// The base-2 logarithm of the number of rows in the committed matrix.
+	fn log_rows(&self) -> usize;
+
+	/// The base-2 logarithm of the number of columns in the pre-encoded matrix.
+	fn log_cols(&self) -> usize;
+
+	/// The length of the encoded rows.
+	fn code_len(&self) -> usize;
+
+	/// The number of columns opened by an evaluation proof.
+	fn n_test_queries(&self) -> usize;
+
+	/// Whether the instance runs in hiding mode.
+	fn is_hiding(&self) -> bool;
+
+	/// The number of proof-of-work bits the prover grinds before the column indices are sampled.
+	fn grinding_bits(&self) -> usize;
+
+	/// Encode the rows of a polynomial's matrix, returning the encoded matrix in column-major
+	/// order.
+	fn encode_packed(&self, poly: &[PI]) -> Result<RowMajorMatrix<PI>, Error>;
+
+	/// Compute the mixed partial evaluation $t'$ of a batch of polynomials.
+	fn mixed_t_prime_packed(
+		&self,
+		polys: &[&[PI]],
+		query: &[PE::Scalar],
+		mixing_coefficients: &[PE::Scalar],
+	) -> Result<MultilinearExtension<'static, PE>, Error>;
+
+	/// Check the opened columns of a batch against the mixed partial evaluation $t'$.
+	///
+	/// `value` is the claimed evaluation mixed with the mixing coefficients. The columns must
+	/// already be verified against the vector commitment.
+	fn check_column_openings(
+		&self,
+		query: &[PE::Scalar],
+		value: PE::Scalar,
+		mixing_coefficients: &[PE::Scalar],
+		mixed_t_prime: &MultilinearExtension<PE>,
+		columns: Vec<(usize, Vec<Vec<PI>>)>,
+	) -> Result<(), Error>;
+}
+
+/// Prover state for the batches committed by a [`MultiBatchTensorPCS`].
+///
+/// Contains the encoded matrices of every polynomial in each batch and the committed vector
+/// commitment state.
+pub type MultiBatchCommitted<PI, VCSCommitted> = (Vec<Vec<RowMajorMatrix<PI>>>, VCSCommitted);
+
+/// Evaluation proof data for the [`MultiBatchTensorPCS`] commitment scheme.
+#[derive(Debug)]
+pub struct MultiBatchProof<PI, PE, VCSProof>
+where
+	PE: PackedField,
+{
+	/// The mixed partial evaluation $t'$ of each batch
+	pub mixed_t_primes: Vec<MultilinearExtension<'static, PE>>,
+	/// For each sampled column index, the opened columns of every polynomial in each batch
+	pub opened_cols: Vec<Vec<Vec<Vec<PI>>>>,
+	/// A single proof that the columns at all opened indices are consistent with the vector
+	/// commitment
+	pub vcs_proof: VCSProof,
+}
+
+impl<PI, PE, VCSProof> SerializeBytes for MultiBatchProof<PI, PE, VCSProof>
+where
+	PI: SerializeBytes,
+	PE: PackedField + SerializeBytes,
+	VCSProof: SerializeBytes,
+{
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.mixed_t_primes.serialize(write_buf);
+		self.opened_cols.serialize(write_buf);
+		self.vcs_proof.serialize(write_buf);
+	}
+}
+
+impl<PI, PE, VCSProof> DeserializeBytes for MultiBatchProof<PI, PE, VCSProof>
+where
+	PI: DeserializeBytes,
+	PE: PackedField + DeserializeBytes,
+	VCSProof: DeserializeBytes,
+{
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		let mixed_t_primes = Vec::<MultilinearExtension<PE>>::deserialize(read_buf)?;
+		let opened_cols = Vec::<Vec<Vec<Vec<PI>>>>::deserialize(read_buf)?;
+		let vcs_proof = VCSProof::deserialize(read_buf)?;
+
+		// Every query opens the columns of all batches, so the number of batches must agree for
+		// the encoding to be canonical.
+		if opened_cols
+			.iter()
+			.any(|cols| cols.len() != mixed_t_primes.len())
+		{
+			return Err(SerializationError::InvalidConstruction {
+				name: "MultiBatchProof",
+			});
+		}
+
+		Ok(Self {
+			mixed_t_primes,
+			opened_cols,
+			vcs_proof,
+		})
+	}
+}
+
+/// A commitment scheme for several batches of polynomials over possibly different fields under a
+/// single vector commitment.
+///
+/// The batches are described by [`TensorPCSBatch`] instances, which must agree on the code
+/// length. The column indices are sampled once for all batches, and the number of test queries is
+/// the largest number required by any batch.
+pub struct MultiBatchTensorPCS<'a, PI, PE, H, VCS>
+where
+	PE: PackedField,
+{
+	batches: Vec<&'a dyn TensorPCSBatch<PI, PE>>,
+	vcs: VCS,
+	n_test_queries: usize,
+	_h_marker: PhantomData<H>,
+}
+
+impl<'a, PI, PE>
+	MultiBatchTensorPCS<
+		'a,
+		PI,
+		PE,
+		HasherDigest<PI, GroestlHasher<PI>>,
+		MerkleTreeVCS<
+			GroestlDigest,
+			GroestlDigest,
+			GroestlHasher<GroestlDigest>,
+			GroestlDigestCompression,
+		>,
+	> where
+	PI: PackedField + PackedExtensionField<BinaryField8b> + Sync,
+	PI::Scalar: ExtensionField<BinaryField8b>,
+	PE: PackedFieldIndexable,
+{
+	pub fn new_using_groestl_merkle_tree(
+		batches: Vec<&'a dyn TensorPCSBatch<PI, PE>>,
+	) -> Result<Self, Error> {
+		let code_len = batches.first().map_or(0, |batch| batch.code_len());
+		// Check power of two length because MerkleTreeVCS requires it
+		if !code_len.is_power_of_two() {
+			return Err(Error::CodeLengthPowerOfTwoRequired);
+		}
+		let log_len = log2_strict_usize(code_len);
+		Self::new(batches, MerkleTreeVCS::new(log_len, GroestlDigestCompression))
+	}
+}
+
+impl<'a, PI, FE, PE, H, VCS> MultiBatchTensorPCS<'a, PI, PE, H, VCS>
+where
+	PI: PackedField,
+	FE: Field,
+	PE: PackedFieldIndexable<Scalar = FE>,
+	H: HashDigest<PI>,
+	H::Digest: Copy + Default + Send,
+	VCS: VectorCommitScheme<H::Digest>,
+{
+	/// Construct a commitment scheme for the given batches.
+	///
+	/// Throws if there are no batches, if the batches have different code lengths, if any batch
+	/// is in hiding mode or grinds proof of work, or if the vector commitment does not commit
+	/// vectors of the code length.
+	pub fn new(batches: Vec<&'a dyn TensorPCSBatch<PI, PE>>, vcs: VCS) -> Result<Self, Error> {
+		let code_len = batches
+			.first()
+			.ok_or_else(|| Error::NumBatchedMismatchError {
+				err_str: "at least one batch is required".to_string(),
+			})?
+			.code_len();
+		if batches.iter().any(|batch| batch.code_len() != code_len) {
+			return Err(Error::CodeLengthMismatch);
+		}
+		if vcs.vector_len() != code_len {
+			return Err(Error::CodeLengthMismatch);
+		}
+		if batches.iter().any(|batch| batch.is_hiding()) {
+			return Err(Error::InvalidHidingParameters(
+				"batches in hiding mode cannot share a vector commitment",
+			));
+		}
+		// The shared column indices are sampled without grinding, so the test queries of every
+		// batch must achieve its security level on their own.
+		if batches.iter().any(|batch| batch.grinding_bits() > 0) {
+			return Err(Error::GrindingNotSupported);
+		}
+
+		let n_test_queries = batches
+			.iter()
+			.map(|batch| batch.n_test_queries())
+			.max()
+			.expect("batches is not empty");
+
+		Ok(Self {
+			batches,
+			vcs,
+			n_test_queries,
+			_h_marker: PhantomData,
+		})
+	}
+
+	/// The number of batches.
+	pub fn n_batches(&self) -> usize {
+		self.batches.len()
+	}
+
+	/// The number of variables of the polynomials in the given batch.
+	pub fn n_vars(&self, batch_index: usize) -> usize {
+		let batch = self.batches[batch_index];
+		batch.log_rows() + batch.log_cols()
+	}
+
+	/// The number of columns opened by an evaluation proof.
+	pub fn n_test_queries(&self) -> usize {
+		self.n_test_queries
+	}
+
+	/// Commit to all batches of polynomials.
+	///
+	/// The polynomials of each batch are given by their evaluations packed into the intermediate
+	/// field, see [`packed_evals`].
+	#[allow(clippy::type_complexity)]
+	#[instrument(skip_all, name = "multi_batch::commit")]
+	pub fn commit(
+		&self,
+		polys: &[Vec<&[PI]>],
+	) -> Result<(VCS::Commitment, MultiBatchCommitted<PI, VCS::Committed>), Error> {
+		self.check_n_batches(polys.len())?;
+
+		let encoded_mats = self
+			.batches
+			.iter()
+			.zip(polys)
+			.map(|(batch, polys)| {
+				polys
+					.iter()
+					.map(|poly| batch.encode_packed(poly))
+					.collect::<Result<Vec<_>, _>>()
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let (commitment, vcs_committed) = self
+			.vcs
+			.commit_batch(encoded_mats.iter().flatten().map(column_digests::<_, H>))
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+		Ok((commitment, (encoded_mats, vcs_committed)))
+	}
+
+	/// Generate an evaluation proof for every batch at its own *random* challenge point.
+	#[instrument(skip_all, name = "multi_batch::prove_evaluation")]
+	pub fn prove_evaluation<CH>(
+		&self,
+		challenger: &mut CH,
+		committed: &MultiBatchCommitted<PI, VCS::Committed>,
+		polys: &[Vec<&[PI]>],
+		queries: &[&[FE]],
+	) -> Result<MultiBatchProof<PI, PE, VCS::MultiProof>, Error>
+	where
+		CH: CanObserve<FE> + CanSample<FE> + CanSampleBits<usize> + DomainSeparator,
+	{
+		let (encoded_mats, vcs_committed) = committed;
+		self.check_n_batches(polys.len())?;
+		self.check_queries(queries)?;
+		if encoded_mats
+			.iter()
+			.zip(polys)
+			.any(|(mats, polys)| mats.len() != polys.len())
+		{
+			return Err(Error::NumBatchedMismatchError {
+				err_str: "the number of polynomials in each batch must match the number of \
+					committed matrices"
+					.to_string(),
+			});
+		}
+
+		let mixed_t_primes = self
+			.batches
+			.iter()
+			.zip(polys)
+			.zip(queries)
+			.map(|((batch, polys), query)| {
+				let mixing_coefficients = sample_mixing_coefficients(challenger, polys.len())?;
+				let t_prime = batch.mixed_t_prime_packed(polys, query, &mixing_coefficients)?;
+				challenger
+					.observe_slice_labeled(T_PRIME_LABEL, PE::unpack_scalars(t_prime.evals()));
+				Ok(t_prime)
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		challenger.observe_label(QUERIES_LABEL.as_bytes());
+		let code_len_bits = log2_strict_usize(self.vcs.vector_len());
+		let indices = repeat_with(|| challenger.sample_bits(code_len_bits))
+			.take(self.n_test_queries)
+			.collect::<Vec<_>>();
+		let vcs_proof = self
+			.vcs
+			.prove_multi_opening(vcs_committed, &indices)
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+		let opened_cols = indices
+			.iter()
+			.map(|&index| {
+				encoded_mats
+					.iter()
+					.map(|mats| {
+						mats.iter()
+							.map(|mat| mat.row_slice(index).to_vec())
+							.collect()
+					})
+					.collect()
+			})
+			.collect();
+
+		Ok(MultiBatchProof {
+			mixed_t_primes,
+			opened_cols,
+			vcs_proof,
+		})
+	}
+
+	/// Verify an evaluation proof for every batch at its own *random* challenge point.
+	#[instrument(skip_all, name = "multi_batch::verify_evaluation")]
+	pub fn verify_evaluation<CH>(
+		&self,
+		challenger: &mut CH,
+		commitment: &VCS::Commitment,
+		queries: &[&[FE]],
+		proof: MultiBatchProof<PI, PE, VCS::MultiProof>,
+		values: &[&[FE]],
+	) -> Result<(), Error>
+	where
+		CH: CanObserve<FE> + CanSample<FE> + CanSampleBits<usize> + DomainSeparator,
+	{
+		let MultiBatchProof {
+			mixed_t_primes,
+			opened_cols,
+			vcs_proof,
+		} = proof;
+		self.check_n_batches(values.len())?;
+		self.check_queries(queries)?;
+		if mixed_t_primes.len() != self.batches.len() {
+			return Err(VerificationError::ProofShape.into());
+		}
+
+		let mixing_coefficients = values
+			.iter()
+			.zip(&mixed_t_primes)
+			.map(|(values, t_prime)| {
+				let mixing_coefficients = sample_mixing_coefficients(challenger, values.len())?;
+				challenger
+					.observe_slice_labeled(T_PRIME_LABEL, PE::unpack_scalars(t_prime.evals()));
+				Ok(mixing_coefficients)
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		challenger.observe_label(QUERIES_LABEL.as_bytes());
+		let code_len_bits = log2_strict_usize(self.vcs.vector_len());
+		let indices = repeat_with(|| challenger.sample_bits(code_len_bits))
+			.take(self.n_test_queries)
+			.collect::<Vec<_>>();
+		if opened_cols.len() != self.n_test_queries {
+			return Err(VerificationError::NumberOfOpeningProofs {
+				expected: self.n_test_queries,
+			}
+			.into());
+		}
+
+		if opened_cols.iter().any(|cols| {
+			cols.len() != self.batches.len()
+				|| cols
+					.iter()
+					.zip(values)
+					.any(|(cols, values)| cols.len() != values.len())
+		}) {
+			return Err(VerificationError::ProofShape.into());
+		}
+
+		// Check the vector commitment openings and split the opened columns by batch.
+		self.vcs
+			.verify_multi_opening(
+				commitment,
+				&indices,
+				values.iter().map(|values| values.len()).sum(),
+				vcs_proof,
+				opened_cols
+					.iter()
+					.map(|cols| cols.iter().flatten().map(H::hash)),
+			)
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+
+		let mut columns = vec![Vec::with_capacity(self.n_test_queries); self.batches.len()];
+		for (cols, index) in opened_cols.into_iter().zip(indices) {
+			for (batch_columns, cols) in columns.iter_mut().zip(cols) {
+				batch_columns.push((index, cols));
+			}
+		}
+
+		for (((((batch, query), values), mixing_coefficients), t_prime), columns) in self
+			.batches
+			.iter()
+			.zip(queries)
+			.zip(values)
+			.zip(&mixing_coefficients)
+			.zip(&mixed_t_primes)
+			.zip(columns)
+		{
+			let value = inner_product_unchecked(
+				values.iter().copied(),
+				mixing_coefficients.iter().copied(),
+			);
+			batch.check_column_openings(query, value, mixing_coefficients, t_prime, columns)?;
+		}
+
+		Ok(())
+	}
+
+	/// Return the byte-size of a serialized proof for batches with the given numbers of
+	/// polynomials.
+	///
+	/// The size includes the length prefixes of the encoding, but not the version prefix added by
+	/// [`to_versioned_bytes`](crate::serialization::to_versioned_bytes). It is exact, except that
+	/// the vector commitment multi-proof may be smaller than its estimate when the opened
+	/// authentication paths share nodes.
+	pub fn proof_size(&self, n_polys: &[usize]) -> usize {
+		// Vectors are prefixed with their length, encoded as a u64
+		const LEN_PREFIX_SIZE: usize = mem::size_of::<u64>();
+
+		let t_primes_size = self
+			.batches
+			.iter()
+			.map(|batch| LEN_PREFIX_SIZE + (mem::size_of::<PE>() << batch.log_cols()) / PE::WIDTH)
+			.sum::<usize>();
+		let columns_size = self
+			.batches
+			.iter()
+			.zip(n_polys)
+			.map(|(batch, &n_polys)| {
+				LEN_PREFIX_SIZE
+					+ n_polys
+						* (LEN_PREFIX_SIZE + (mem::size_of::<PI>() << batch.log_rows()) / PI::WIDTH)
+			})
+			.sum::<usize>();
+		let total_n_polys = n_polys.iter().sum();
+		LEN_PREFIX_SIZE
+			+ t_primes_size
+			+ LEN_PREFIX_SIZE
+			+ (LEN_PREFIX_SIZE + columns_size) * self.n_test_queries
+			+ LEN_PREFIX_SIZE
+			+ self
+				.vcs
+				.multi_proof_size(total_n_polys, self.n_test_queries)
+	}
+
+	fn check_n_batches(&self, n_batches: usize) -> Result<(), Error> {
+		if n_batches != self.batches.len() {
+			return Err(Error::NumBatchedMismatchError {
+				err_str: format!("expected {} batches, got {}", self.batches.len(), n_batches),
+			});
+		}
+		Ok(())
+	}
+
+	fn check_queries(&self, queries: &[&[FE]]) -> Result<(), Error> {
+		self.check_n_batches(queries.len())?;
+		for (batch_index, query) in queries.iter().enumerate() {
+			let n_vars = self.n_vars(batch_index);
+			if query.len() != n_vars {
+				return Err(PolynomialError::IncorrectQuerySize { expected: n_vars }.into());
+			}
+		}
+		Ok(())
+	}
+}
+
+/// View the evaluations of a batch of polynomials as packed intermediate field elements.
+pub fn packed_evals<'b, P, PI>(polys: &'b [MultilinearExtension<P>]) -> Result<Vec<&'b [PI]>, Error>
+where
+	P: PackedField,
+	PI: PackedExtensionField<P>,
+	PI::Scalar: ExtensionField<P::Scalar>,
+{
+	polys
+		.iter()
+		.map(|poly| PI::try_cast_to_ext(poly.evals()).ok_or(Error::UnalignedMessage))
+		.collect()
+}
+
+fn sample_mixing_coefficients<F, CH>(challenger: &mut CH, n_polys: usize) -> Result<Vec<F>, Error>
+where
+	F: Field,
+	CH: CanSample<F> + DomainSeparator,
+{
+	let mixing_challenges = challenger.sample_vec_labeled(MIXING_LABEL, log2_ceil_usize(n_polys));
+	let mut mixing_coefficients =
+		MultilinearQuery::<F>::with_full_query(&mixing_challenges)?.into_expansion();
+	mixing_coefficients.truncate(n_polys);
+	Ok(mixing_coefficients)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		challenger::HashChallenger,
+		poly_commit::{tensor_pcs::TensorPCS, PolyCommitScheme},
+		reed_solomon::reed_solomon::ReedSolomonCode,
+		serialization::{from_versioned_bytes, to_versioned_bytes},
+	};
+	use assert_matches::assert_matches;
+	use binius_field::{
+		BinaryField128b, BinaryField32b, PackedBinaryField128x1b, PackedBinaryField16x8b,
+		PackedBinaryField1x128b, PackedBinaryField4x32b,
+	};
+	use rand::{rngs::StdRng, SeedableRng};
+
+	type TestPCS<P> = TensorPCS<
+		P,
+		PackedBinaryField16x8b,
+		PackedBinaryField4x32b,
+		PackedBinaryField1x128b,
+		ReedSolomonCode<PackedBinaryField16x8b>,
+		HasherDigest<PackedBinaryField4x32b, GroestlHasher<PackedBinaryField4x32b>>,
+		MerkleTreeVCS<
+			GroestlDigest,
+			GroestlDigest,
+			GroestlHasher<GroestlDigest>,
+			GroestlDigestCompression,
+		>,
+	>;
+
+	fn test_pcs<P: PackedField>(log_rows: usize) -> TestPCS<P>
+	where
+		BinaryField32b: ExtensionField<P::Scalar>,
+		BinaryField128b: ExtensionField<P::Scalar>,
+	{
+		let rs_code = ReedSolomonCode::new(5, 2).unwrap();
+		TestPCS::<P>::new_using_groestl_merkle_tree(log_rows, rs_code, 16).unwrap()
+	}
+
+	fn random_polys<P: PackedField>(
+		rng: &mut StdRng,
+		n_vars: usize,
+		n_polys: usize,
+	) -> Vec<MultilinearExtension<'static, P>> {
+		repeat_with(|| {
+			let evals = repeat_with(|| P::random(&mut *rng))
+				.take((1 << n_vars) / P::WIDTH)
+				.collect::<Vec<_>>();
+			MultilinearExtension::from_values(evals).unwrap()
+		})
+		.take(n_polys)
+		.collect()
+	}
+
+	fn random_query(rng: &mut StdRng, n_vars: usize) -> Vec<BinaryField128b> {
+		repeat_with(|| <BinaryField128b as Field>::random(&mut *rng))
+			.take(n_vars)
+			.collect()
+	}
+
+	fn evaluate_polys<P: PackedField>(
+		polys: &[MultilinearExtension<P>],
+		query: &[BinaryField128b],
+	) -> Vec<BinaryField128b>
+	where
+		BinaryField128b: ExtensionField<P::Scalar>,
+	{
+		let query = MultilinearQuery::<BinaryField128b>::with_full_query(query).unwrap();
+		polys
+			.iter()
+			.map(|poly| poly.evaluate(&query).unwrap())
+			.collect()
+	}
+
+	#[test]
+	fn test_commit_prove_verify_heterogeneous_batches() {
+		let pcs_1b = test_pcs::<PackedBinaryField128x1b>(7);
+		let pcs_8b = test_pcs::<PackedBinaryField16x8b>(6);
+		let pcs_32b = test_pcs::<PackedBinaryField4x32b>(4);
+		let multi_pcs = MultiBatchTensorPCS::new_using_groestl_merkle_tree(vec![
+			&pcs_1b as &dyn TensorPCSBatch<_, _>,
+			&pcs_8b,
+			&pcs_32b,
+		])
+		.unwrap();
+		assert_eq!(multi_pcs.n_batches(), 3);
+		assert_eq!(multi_pcs.n_vars(0), PolyCommitScheme::n_vars(&pcs_1b));
+		assert_eq!(multi_pcs.n_vars(2), PolyCommitScheme::n_vars(&pcs_32b));
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let polys_1b = random_polys::<PackedBinaryField128x1b>(&mut rng, multi_pcs.n_vars(0), 3);
+		let polys_8b = random_polys::<PackedBinaryField16x8b>(&mut rng, multi_pcs.n_vars(1), 1);
+		let polys_32b = random_polys::<PackedBinaryField4x32b>(&mut rng, multi_pcs.n_vars(2), 2);
+		let polys = vec![
+			packed_evals(&polys_1b).unwrap(),
+			packed_evals(&polys_8b).unwrap(),
+			packed_evals(&polys_32b).unwrap(),
+		];
+		let (commitment, committed) = multi_pcs.commit(&polys).unwrap();
+
+		let queries = (0..3)
+			.map(|batch_index| random_query(&mut rng, multi_pcs.n_vars(batch_index)))
+			.collect::<Vec<_>>();
+		let queries = queries.iter().map(Vec::as_slice).collect::<Vec<_>>();
+		let values = [
+			evaluate_polys(&polys_1b, queries[0]),
+			evaluate_polys(&polys_8b, queries[1]),
+			evaluate_polys(&polys_32b, queries[2]),
+		];
+		let values = values.iter().map(Vec::as_slice).collect::<Vec<_>>();
+
+		let challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+		let proof = multi_pcs
+			.prove_evaluation(&mut challenger.clone(), &committed, &polys, &queries)
+			.unwrap();
+		assert_eq!(proof.opened_cols.len(), 16);
+
+		// The proof is smaller than three separate proofs, which open three Merkle trees
+		let proof_bytes = to_versioned_bytes(&proof);
+		let separate_proofs_size =
+			pcs_1b.proof_size(3) + pcs_8b.proof_size(1) + pcs_32b.proof_size(2);
+		assert!(multi_pcs.proof_size(&[3, 1, 2]) < separate_proofs_size);
+
+		// The size estimate is exact up to the nodes shared by the opened Merkle paths, and the
+		// versioned encoding adds a two-byte version prefix
+		let merkle_proof_size = proof.vcs_proof.len() * mem::size_of::<GroestlDigest>();
+		let merkle_proof_estimate = multi_pcs.vcs.multi_proof_size(6, 16);
+		assert!(merkle_proof_size <= merkle_proof_estimate);
+		assert_eq!(
+			proof_bytes.len(),
+			2 + multi_pcs.proof_size(&[3, 1, 2]) - (merkle_proof_estimate - merkle_proof_size)
+		);
+
+		multi_pcs
+			.verify_evaluation(
+				&mut challenger.clone(),
+				&commitment,
+				&queries,
+				from_versioned_bytes(&proof_bytes).unwrap(),
+				&values,
+			)
+			.unwrap();
+
+		// An incorrect evaluation in any batch must be rejected
+		let mut wrong_values = values
+			.iter()
+			.map(|values| values.to_vec())
+			.collect::<Vec<_>>();
+		wrong_values[1][0] += BinaryField128b::ONE;
+		let wrong_values = wrong_values.iter().map(Vec::as_slice).collect::<Vec<_>>();
+		assert_matches!(
+			multi_pcs.verify_evaluation(
+				&mut challenger.clone(),
+				&commitment,
+				&queries,
+				from_versioned_bytes(&proof_bytes).unwrap(),
+				&wrong_values,
+			),
+			Err(Error::Verification(VerificationError::IncorrectEvaluation))
+		);
+
+		// Tampering with an opened column of one batch must be rejected
+		let mut tampered_proof: MultiBatchProof<_, _, _> =
+			from_versioned_bytes(&proof_bytes).unwrap();
+		tampered_proof.opened_cols[0][2][1][0] += PackedBinaryField4x32b::one();
+		assert_matches!(
+			multi_pcs.verify_evaluation(
+				&mut challenger.clone(),
+				&commitment,
+				&queries,
+				tampered_proof,
+				&values,
+			),
+			Err(Error::VectorCommit(_))
+		);
+	}
+
+	#[test]
+	fn test_batches_must_share_code_length() {
+		let pcs_1b = test_pcs::<PackedBinaryField128x1b>(7);
+		let rs_code = ReedSolomonCode::new(4, 2).unwrap();
+		let pcs_32b =
+			TestPCS::<PackedBinaryField4x32b>::new_using_groestl_merkle_tree(4, rs_code, 16)
+				.unwrap();
+		assert_matches!(
+			MultiBatchTensorPCS::new_using_groestl_merkle_tree(vec![
+				&pcs_1b as &dyn TensorPCSBatch<_, _>,
+				&pcs_32b,
+			])
+			.err(),
+			Some(Error::CodeLengthMismatch)
+		);
+
+		let pcs_hiding = test_pcs::<PackedBinaryField4x32b>(4).with_hiding().unwrap();
+		assert_matches!(
+			MultiBatchTensorPCS::new_using_groestl_merkle_tree(vec![
+				&pcs_1b as &dyn TensorPCSBatch<_, _>,
+				&pcs_hiding,
+			])
+			.err(),
+			Some(Error::InvalidHidingParameters(_))
+		);
+	}
+}
@@ -0,0 +1,615 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Batched opening of committed polynomials at multiple evaluation points.
+//!
+//! A set of claims $t_{b_j, i_j}(z_j) = v_j$ on polynomials from one or more committed batches is
+//! reduced to claims at a single random point with a sumcheck. After observing the commitments and
+//! the claims and sampling batching coefficients $\lambda_j$, the prover and verifier run a
+//! sumcheck on
+//!
+//! $$\sum_j \lambda_j v_j = \sum_{x \in \{0, 1\}^\mu} \sum_{(b, i)} t_{b, i}(x) \cdot g_{b, i}(x),$$
+//!
+//! where $g_{b, i}(x) = \sum_{j : (b_j, i_j) = (b, i)} \lambda_j \widetilde{eq}(z_j, x)$. At the
+//! end of the sumcheck, the prover sends the evaluations of all polynomials in every batch at the
+//! sumcheck challenge point $r$, the verifier evaluates the $g_{b, i}(r)$ itself, and each batch is
+//! opened at $r$ with the scheme's same-query evaluation proof. Since $r$ is sampled by the
+//! verifier, the original evaluation points need not be random.
+
+use super::{
+	error::{Error, VerificationError},
+	product_sumcheck::{fold_halves, sumcheck_round_poly},
+	PolyCommitScheme,
+};
+use crate::{
+	challenger::{CanGrind, CanObserve, CanSample, CanSampleBits, DomainSeparator},
+	polynomial::{
+		multilinear_query::MultilinearQuery, Error as PolynomialError, MultilinearExtension,
+	},
+	serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes},
+};
+use binius_field::{packed::iter_packed_slice, BinaryField, ExtensionField, Field, PackedField};
+use tracing::instrument;
+
+/// Challenger label for the commitments to the claimed batches.
+const COMMITMENTS_LABEL: &str = "pcs.multi_point.commitments";
+/// Challenger label for the evaluation point and claimed value of each claim.
+const CLAIM_LABEL: &str = "pcs.multi_point.claim";
+/// Challenger label for the coefficients batching the evaluation claims.
+const BATCHING_LABEL: &str = "pcs.multi_point.batching";
+/// Challenger label for the sumcheck round messages.
+const ROUND_LABEL: &str = "pcs.multi_point.round";
+/// Challenger label for the evaluations of the batches at the sumcheck challenge point.
+const EVALS_LABEL: &str = "pcs.multi_point.evals";
+
+/// An evaluation claim on a single polynomial in one of several committed batches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiPointEvalClaim<F> {
+	/// Index of the committed batch containing the polynomial
+	pub batch_index: usize,
+	/// Index of the polynomial within its batch
+	pub poly_index: usize,
+	/// Evaluation point
+	pub eval_point: Vec<F>,
+	/// Claimed evaluation
+	pub eval: F,
+}
+
+/// Combined proof for evaluation claims at multiple points across several committed batches.
+#[derive(Debug, Clone)]
+pub struct MultiPointProof<F, Proof> {
+	/// The sumcheck round polynomials, given by their constant and quadratic coefficients
+	pub round_polys: Vec<[F; 2]>,
+	/// The evaluations of all polynomials in each batch at the sumcheck challenge point
+	pub batch_evals: Vec<Vec<F>>,
+	/// The same-query evaluation proofs for each batch at the sumcheck challenge point
+	pub batch_proofs: Vec<Proof>,
+}
+
+impl<F, Proof> SerializeBytes for MultiPointProof<F, Proof>
+where
+	F: SerializeBytes,
+	Proof: SerializeBytes,
+{
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.round_polys.serialize(write_buf);
+		self.batch_evals.serialize(write_buf);
+		self.batch_proofs.serialize(write_buf);
+	}
+}
+
+impl<F, Proof> DeserializeBytes for MultiPointProof<F, Proof>
+where
+	F: DeserializeBytes,
+	Proof: DeserializeBytes,
+{
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		let round_polys = Vec::deserialize(read_buf)?;
+		let batch_evals = Vec::<Vec<F>>::deserialize(read_buf)?;
+		let batch_proofs = Vec::<Proof>::deserialize(read_buf)?;
+
+		// There is exactly one evaluation proof per batch, so the lengths must agree for the
+		// encoding to be canonical.
+		if batch_proofs.len() != batch_evals.len() {
+			return Err(SerializationError::InvalidConstruction {
+				name: "MultiPointProof",
+			});
+		}
+
+		Ok(Self {
+			round_polys,
+			batch_evals,
+			batch_proofs,
+		})
+	}
+}
+
+/// Prove a set of evaluation claims on polynomials in several committed batches.
+///
+/// Every batch is opened at the sumcheck challenge point, including batches without claims.
+#[instrument(skip_all, name = "pcs::prove_multi_point_evaluation")]
+pub(super) fn prove<P, FE, PCS, CH>(
+	pcs: &PCS,
+	challenger: &mut CH,
+	commitments: &[PCS::Commitment],
+	committed: &[&PCS::Committed],
+	polys: &[&[MultilinearExtension<P>]],
+	claims: &[MultiPointEvalClaim<FE>],
+) -> Result<MultiPointProof<FE, PCS::Proof>, PCS::Error>
+where
+	P: PackedField,
+	FE: BinaryField + ExtensionField<P::Scalar>,
+	PCS: PolyCommitScheme<P, FE> + ?Sized,
+	CH: CanObserve<FE>
+		+ CanObserve<PCS::Commitment>
+		+ CanSample<FE>
+		+ CanSampleBits<usize>
+		+ CanGrind<FE>
+		+ DomainSeparator,
+{
+	let n_vars = pcs.n_vars();
+	if commitments.len() != polys.len() || committed.len() != polys.len() {
+		return Err(Error::NumBatchedMismatchError {
+			err_str: format!(
+				"got {} commitments, {} committed batches and {} polynomial batches",
+				commitments.len(),
+				committed.len(),
+				polys.len()
+			),
+		}
+		.into());
+	}
+	let batch_lengths = polys.iter().map(|batch| batch.len()).collect::<Vec<_>>();
+	check_claims(n_vars, &batch_lengths, claims)?;
+
+	observe_statement(challenger, commitments, claims);
+	let batching_coeffs = challenger.sample_vec_labeled(BATCHING_LABEL, claims.len());
+
+	// Pair each claimed polynomial with the combination of equality indicators for its claims.
+	let mut claimed_polys = Vec::<((usize, usize), Vec<FE>)>::new();
+	for (claim, &coeff) in claims.iter().zip(&batching_coeffs) {
+		let key = (claim.batch_index, claim.poly_index);
+		let eq_ind = MultilinearQuery::<FE>::with_full_query(&claim.eval_point)
+			.map_err(Error::from)?
+			.into_expansion();
+		match claimed_polys
+			.iter_mut()
+			.find(|(other_key, _)| *other_key == key)
+		{
+			Some((_, multiplier)) => {
+				for (multiplier_i, eq_ind_i) in multiplier.iter_mut().zip(eq_ind) {
+					*multiplier_i += coeff * eq_ind_i;
+				}
+			}
+			None => {
+				let multiplier = eq_ind
+					.into_iter()
+					.map(|eq_ind_i| coeff * eq_ind_i)
+					.collect();
+				claimed_polys.push((key, multiplier));
+			}
+		}
+	}
+	let (mut evals, mut multipliers): (Vec<_>, Vec<_>) = claimed_polys
+		.into_iter()
+		.map(|((batch_index, poly_index), multiplier)| {
+			let evals = iter_packed_slice(polys[batch_index][poly_index].evals())
+				.take(1 << n_vars)
+				.map(FE::from)
+				.collect::<Vec<_>>();
+			(evals, multiplier)
+		})
+		.unzip();
+
+	let mut round_polys = Vec::with_capacity(n_vars);
+	let mut challenges = Vec::with_capacity(n_vars);
+	for _ in 0..n_vars {
+		let round_poly = evals.iter().zip(&multipliers).fold(
+			[FE::ZERO; 2],
+			|[constant, quadratic], (evals, multiplier)| {
+				let [constant_i, quadratic_i] = sumcheck_round_poly(evals, multiplier);
+				[constant + constant_i, quadratic + quadratic_i]
+			},
+		);
+		challenger.observe_slice_labeled(ROUND_LABEL, &round_poly);
+		let challenge = challenger.sample();
+		round_polys.push(round_poly);
+		challenges.push(challenge);
+
+		for (evals, multiplier) in evals.iter_mut().zip(multipliers.iter_mut()) {
+			*evals = fold_halves(evals, challenge);
+			*multiplier = fold_halves(multiplier, challenge);
+		}
+	}
+
+	let query = MultilinearQuery::<FE>::with_full_query(&challenges).map_err(Error::from)?;
+	let batch_evals = polys
+		.iter()
+		.map(|batch| {
+			batch
+				.iter()
+				.map(|poly| poly.evaluate(&query))
+				.collect::<Result<Vec<_>, _>>()
+		})
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(Error::from)?;
+	for evals in &batch_evals {
+		challenger.observe_slice_labeled(EVALS_LABEL, evals);
+	}
+
+	let batch_proofs = committed
+		.iter()
+		.zip(polys)
+		.map(|(committed, polys)| pcs.prove_evaluation(challenger, committed, polys, &challenges))
+		.collect::<Result<_, _>>()?;
+
+	Ok(MultiPointProof {
+		round_polys,
+		batch_evals,
+		batch_proofs,
+	})
+}
+
+/// Verify a combined proof for evaluation claims on polynomials in several committed batches.
+#[instrument(skip_all, name = "pcs::verify_multi_point_evaluation")]
+pub(super) fn verify<P, FE, PCS, CH>(
+	pcs: &PCS,
+	challenger: &mut CH,
+	commitments: &[PCS::Commitment],
+	claims: &[MultiPointEvalClaim<FE>],
+	proof: MultiPointProof<FE, PCS::Proof>,
+) -> Result<(), PCS::Error>
+where
+	P: PackedField,
+	FE: BinaryField + ExtensionField<P::Scalar>,
+	PCS: PolyCommitScheme<P, FE> + ?Sized,
+	CH: CanObserve<FE>
+		+ CanObserve<PCS::Commitment>
+		+ CanSample<FE>
+		+ CanSampleBits<usize>
+		+ DomainSeparator,
+{
+	let n_vars = pcs.n_vars();
+	let MultiPointProof {
+		round_polys,
+		batch_evals,
+		batch_proofs,
+	} = proof;
+	if round_polys.len() != n_vars
+		|| batch_evals.len() != commitments.len()
+		|| batch_proofs.len() != commitments.len()
+	{
+		return Err(Error::from(VerificationError::ProofShape).into());
+	}
+	let batch_lengths = batch_evals.iter().map(Vec::len).collect::<Vec<_>>();
+	check_claims(n_vars, &batch_lengths, claims)?;
+
+	observe_statement(challenger, commitments, claims);
+	let batching_coeffs = challenger.sample_vec_labeled::<FE>(BATCHING_LABEL, claims.len());
+	let mut sum = claims
+		.iter()
+		.zip(&batching_coeffs)
+		.map(|(claim, &coeff)| claim.eval * coeff)
+		.sum::<FE>();
+
+	let mut challenges = Vec::with_capacity(n_vars);
+	for round_poly in round_polys {
+		challenger.observe_slice_labeled(ROUND_LABEL, &round_poly);
+		let challenge = challenger.sample();
+		let [constant, quadratic] = round_poly;
+
+		// The round polynomial h satisfies h(0) + h(1) = sum, which determines its linear
+		// coefficient in characteristic 2.
+		sum = constant + (sum + quadratic) * challenge + quadratic * challenge.square();
+		challenges.push(challenge);
+	}
+
+	let expected_sum = claims
+		.iter()
+		.zip(&batching_coeffs)
+		.map(|(claim, &coeff)| {
+			coeff
+				* eq_ind_eval(&claim.eval_point, &challenges)
+				* batch_evals[claim.batch_index][claim.poly_index]
+		})
+		.sum::<FE>();
+	if sum != expected_sum {
+		return Err(Error::from(VerificationError::IncorrectEvaluation).into());
+	}
+
+	for evals in &batch_evals {
+		challenger.observe_slice_labeled(EVALS_LABEL, evals);
+	}
+
+	for ((commitment, proof), evals) in commitments.iter().zip(batch_proofs).zip(&batch_evals) {
+		pcs.verify_evaluation(challenger, commitment, &challenges, proof, evals)?;
+	}
+
+	Ok(())
+}
+
+/// Observe the commitments and every claim's evaluation point and value.
+///
+/// The batching coefficients are sampled afterwards, so that the claims cannot be chosen depending
+/// on them.
+fn observe_statement<FE, Commitment, CH>(
+	challenger: &mut CH,
+	commitments: &[Commitment],
+	claims: &[MultiPointEvalClaim<FE>],
+) where
+	FE: Field,
+	Commitment: Clone,
+	CH: CanObserve<FE> + CanObserve<Commitment> + DomainSeparator,
+{
+	challenger.observe_slice_labeled(COMMITMENTS_LABEL, commitments);
+	for claim in claims {
+		challenger.observe_slice_labeled(CLAIM_LABEL, &claim.eval_point);
+		challenger.observe(claim.eval);
+	}
+}
+
+/// Check that every claim refers to an existing polynomial and has a point of the right size.
+fn check_claims<F>(
+	n_vars: usize,
+	batch_lengths: &[usize],
+	claims: &[MultiPointEvalClaim<F>],
+) -> Result<(), Error> {
+	for claim in claims {
+		if batch_lengths
+			.get(claim.batch_index)
+			.map_or(true, |&batch_len| claim.poly_index >= batch_len)
+		{
+			return Err(Error::UnknownClaimedPolynomial {
+				batch_index: claim.batch_index,
+				poly_index: claim.poly_index,
+			});
+		}
+		if claim.eval_point.len() != n_vars {
+			return Err(PolynomialError::IncorrectQuerySize { expected: n_vars }.into());
+		}
+	}
+	Ok(())
+}
+
+/// Evaluates the equality indicator $\widetilde{eq}(z, r)$.
+fn eq_ind_eval<F: BinaryField>(z: &[F], r: &[F]) -> F {
+	z.iter()
+		.zip(r)
+		.map(|(&z_i, &r_i)| z_i * r_i + (F::ONE + z_i) * (F::ONE + r_i))
+		.product()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		challenger::HashChallenger,
+		poly_commit::{tensor_pcs::BasicTensorPCS, FRIPCS},
+		reed_solomon::reed_solomon::ReedSolomonCode,
+		serialization::{from_versioned_bytes, to_versioned_bytes},
+	};
+	use assert_matches::assert_matches;
+	use binius_field::{
+		BinaryField128b, BinaryField32b, BinaryField8b, Field, PackedBinaryField16x8b,
+		PackedBinaryField1x128b, PackedBinaryField4x32b,
+	};
+	use binius_hash::{GroestlDigestCompression, GroestlHasher};
+	use rand::{rngs::StdRng, SeedableRng};
+	use std::iter::repeat_with;
+
+	type TestChallenger = HashChallenger<BinaryField8b, GroestlHasher<BinaryField8b>>;
+
+	fn random_polys<P: PackedField>(
+		rng: &mut StdRng,
+		n_vars: usize,
+		n_polys: usize,
+	) -> Vec<MultilinearExtension<'static, P>> {
+		repeat_with(|| {
+			let evals = repeat_with(|| P::random(&mut *rng))
+				.take((1 << n_vars) / P::WIDTH)
+				.collect::<Vec<_>>();
+			MultilinearExtension::from_values(evals).unwrap()
+		})
+		.take(n_polys)
+		.collect()
+	}
+
+	/// Build claims at several points, some of them shared, on two batches of polynomials.
+	fn make_claims<P: PackedField>(
+		rng: &mut StdRng,
+		n_vars: usize,
+		batches: &[&[MultilinearExtension<P>]],
+	) -> Vec<MultiPointEvalClaim<BinaryField128b>>
+	where
+		BinaryField128b: ExtensionField<P::Scalar>,
+	{
+		let points = repeat_with(|| {
+			repeat_with(|| <BinaryField128b as PackedField>::random(&mut *rng))
+				.take(n_vars)
+				.collect::<Vec<_>>()
+		})
+		.take(3)
+		.collect::<Vec<_>>();
+
+		[
+			(0, 0, 0),
+			(0, 1, 1),
+			(0, 0, 2),
+			(1, 2, 0),
+			(1, 0, 0),
+			(1, 2, 1),
+		]
+		.into_iter()
+		.map(|(batch_index, poly_index, point_index)| {
+			let eval_point = points[point_index].clone();
+			let query = MultilinearQuery::<BinaryField128b>::with_full_query(&eval_point).unwrap();
+			let eval = batches[batch_index][poly_index].evaluate(&query).unwrap();
+			MultiPointEvalClaim {
+				batch_index,
+				poly_index,
+				eval_point,
+				eval,
+			}
+		})
+		.collect()
+	}
+
+	fn check_multi_point_opening<P, PCS>(pcs: &PCS)
+	where
+		P: PackedField,
+		BinaryField128b: ExtensionField<P::Scalar>,
+		PCS: PolyCommitScheme<P, BinaryField128b>,
+		PCS::Proof: SerializeBytes + DeserializeBytes,
+		PCS::Error: std::fmt::Debug,
+		TestChallenger: CanObserve<PCS::Commitment>,
+	{
+		let mut rng = StdRng::seed_from_u64(0);
+		let n_vars = pcs.n_vars();
+		let batch_0 = random_polys::<P>(&mut rng, n_vars, 2);
+		let batch_1 = random_polys::<P>(&mut rng, n_vars, 3);
+		let (commitment_0, committed_0) = pcs.commit_with_rng(&batch_0, &mut rng).unwrap();
+		let (commitment_1, committed_1) = pcs.commit_with_rng(&batch_1, &mut rng).unwrap();
+		let commitments = [commitment_0, commitment_1];
+		let claims = make_claims(&mut rng, n_vars, &[&batch_0, &batch_1]);
+
+		let challenger = TestChallenger::new();
+		let proof = pcs
+			.prove_multi_point_evaluation(
+				&mut challenger.clone(),
+				&commitments,
+				&[&committed_0, &committed_1],
+				&[&batch_0, &batch_1],
+				&claims,
+			)
+			.unwrap();
+		assert_eq!(proof.round_polys.len(), n_vars);
+		assert_eq!(proof.batch_evals[0].len(), 2);
+		assert_eq!(proof.batch_evals[1].len(), 3);
+
+		let proof_bytes = to_versioned_bytes(&proof);
+		pcs.verify_multi_point_evaluation(
+			&mut challenger.clone(),
+			&commitments,
+			&claims,
+			from_versioned_bytes(&proof_bytes).unwrap(),
+		)
+		.unwrap();
+
+		// An incorrect evaluation must be rejected
+		let mut wrong_claims = claims.clone();
+		wrong_claims[3].eval += BinaryField128b::ONE;
+		assert!(pcs
+			.verify_multi_point_evaluation(
+				&mut challenger.clone(),
+				&commitments,
+				&wrong_claims,
+				from_versioned_bytes(&proof_bytes).unwrap(),
+			)
+			.is_err());
+
+		// Claims changed after the batching coefficients are known must be rejected. Shifting two
+		// claims by each other's coefficient leaves the batched sum unchanged, so this forgery
+		// would pass if the coefficients did not depend on the claims.
+		let mut batching_challenger = challenger.clone();
+		observe_statement(&mut batching_challenger, &commitments, &claims);
+		let batching_coeffs =
+			batching_challenger.sample_vec_labeled::<BinaryField128b>(BATCHING_LABEL, claims.len());
+		let mut forged_claims = claims.clone();
+		forged_claims[0].eval += batching_coeffs[1];
+		forged_claims[1].eval += batching_coeffs[0];
+		assert_eq!(
+			forged_claims
+				.iter()
+				.zip(&batching_coeffs)
+				.map(|(claim, &coeff)| claim.eval * coeff)
+				.sum::<BinaryField128b>(),
+			claims
+				.iter()
+				.zip(&batching_coeffs)
+				.map(|(claim, &coeff)| claim.eval * coeff)
+				.sum::<BinaryField128b>()
+		);
+		assert!(pcs
+			.verify_multi_point_evaluation(
+				&mut challenger.clone(),
+				&commitments,
+				&forged_claims,
+				from_versioned_bytes(&proof_bytes).unwrap(),
+			)
+			.is_err());
+
+		// The claims must be checked against the right batches
+		let swapped_commitments = [commitments[1].clone(), commitments[0].clone()];
+		assert!(pcs
+			.verify_multi_point_evaluation(
+				&mut challenger.clone(),
+				&swapped_commitments,
+				&claims,
+				from_versioned_bytes(&proof_bytes).unwrap(),
+			)
+			.is_err());
+	}
+
+	#[test]
+	fn test_tensor_pcs_multi_point_opening() {
+		let rs_code = ReedSolomonCode::new(5, 2).unwrap();
+		let pcs = <BasicTensorPCS<
+			PackedBinaryField16x8b,
+			PackedBinaryField16x8b,
+			PackedBinaryField1x128b,
+			_,
+			_,
+			_,
+		>>::new_using_groestl_merkle_tree(4, rs_code, 16)
+		.unwrap();
+		check_multi_point_opening(&pcs);
+	}
+
+	#[test]
+	fn test_tensor_pcs_hiding_multi_point_opening() {
+		let rs_code = ReedSolomonCode::new(5, 2).unwrap();
+		let pcs = <BasicTensorPCS<
+			PackedBinaryField4x32b,
+			PackedBinaryField16x8b,
+			PackedBinaryField1x128b,
+			_,
+			_,
+			_,
+		>>::new_using_groestl_merkle_tree(8, rs_code, 16)
+		.unwrap()
+		.with_hiding()
+		.unwrap();
+		check_multi_point_opening(&pcs);
+	}
+
+	#[test]
+	fn test_fri_pcs_multi_point_opening() {
+		let pcs = FRIPCS::<
+			PackedBinaryField4x32b,
+			PackedBinaryField1x128b,
+			BinaryField32b,
+			GroestlHasher<BinaryField128b>,
+			GroestlDigestCompression,
+		>::new_using_groestl_merkle_tree(10, 2, 2, 16)
+		.unwrap();
+		check_multi_point_opening(&pcs);
+	}
+
+	#[test]
+	fn test_multi_point_claim_checks() {
+		let rs_code = ReedSolomonCode::new(5, 2).unwrap();
+		let pcs = <BasicTensorPCS<
+			PackedBinaryField16x8b,
+			PackedBinaryField16x8b,
+			PackedBinaryField1x128b,
+			_,
+			_,
+			_,
+		>>::new_using_groestl_merkle_tree(4, rs_code, 16)
+		.unwrap();
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let polys = random_polys::<PackedBinaryField16x8b>(&mut rng, pcs.n_vars(), 2);
+		let (commitment, committed) = pcs.commit(&polys).unwrap();
+		let claim = MultiPointEvalClaim {
+			batch_index: 0,
+			poly_index: 2,
+			eval_point: vec![BinaryField128b::ZERO; pcs.n_vars()],
+			eval: BinaryField128b::ZERO,
+		};
+
+		let mut challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+		assert_matches!(
+			pcs.prove_multi_point_evaluation(
+				&mut challenger,
+				&[commitment],
+				&[&committed],
+				&[&polys],
+				&[claim]
+			),
+			Err(Error::UnknownClaimedPolynomial {
+				batch_index: 0,
+				poly_index: 2
+			})
+		);
+	}
+}
@@ -1,10 +1,19 @@
 // Copyright 2023 Ulvetanna Inc.
 
+use super::{
+	error::Error,
+	multi_point::{self, MultiPointEvalClaim, MultiPointProof},
+};
 use crate::{
-	challenger::{CanObserve, CanSample, CanSampleBits},
+	challenger::{
+		CanGrind, CanObserve, CanSample, CanSampleBits, DomainSeparator, TranscriptReader,
+		TranscriptWriter,
+	},
 	polynomial::MultilinearExtension,
+	serialization::{DeserializeBytes, SerializeBytes},
 };
-use binius_field::{ExtensionField, PackedField};
+use binius_field::{BinaryField, BinaryField8b, ExtensionField, PackedField};
+use rand::{thread_rng, RngCore};
 
 pub trait PolyCommitScheme<P, FE>
 where
@@ -14,17 +23,32 @@ where
 	type Commitment: Clone;
 	type Committed;
 	type Proof;
-	type Error: std::error::Error + Send + Sync + 'static;
+	type Error: std::error::Error + Send + Sync + 'static + From<Error>;
 
 	fn n_vars(&self) -> usize;
 
 	/// Commit to a batch of polynomials
+	///
+	/// Schemes with randomized commitments sample their randomness from the thread-local RNG.
 	fn commit(
 		&self,
 		polys: &[MultilinearExtension<P>],
+	) -> Result<(Self::Commitment, Self::Committed), Self::Error> {
+		self.commit_with_rng(polys, thread_rng())
+	}
+
+	/// Commit to a batch of polynomials, sampling any randomness of the commitment from `rng`.
+	///
+	/// Schemes with deterministic commitments ignore `rng`.
+	fn commit_with_rng(
+		&self,
+		polys: &[MultilinearExtension<P>],
+		rng: impl RngCore,
 	) -> Result<(Self::Commitment, Self::Committed), Self::Error>;
 
 	/// Generate an evaluation proof at a *random* challenge point.
+	///
+	/// Schemes with a proof-of-work phase grind nonces in `FE` with the challenger.
 	fn prove_evaluation<CH>(
 		&self,
 		challenger: &mut CH,
@@ -33,7 +57,12 @@ where
 		query: &[FE],
 	) -> Result<Self::Proof, Self::Error>
 	where
-		CH: CanObserve<FE> + CanSample<FE> + CanSampleBits<usize>;
+		CH: CanObserve<FE>
+			+ CanObserve<Self::Commitment>
+			+ CanSample<FE>
+			+ CanSampleBits<usize>
+			+ CanGrind<FE>
+			+ DomainSeparator;
 
 	/// Verify an evaluation proof at a *random* challenge point.
 	fn verify_evaluation<CH>(
@@ -45,8 +74,87 @@ where
 		values: &[FE],
 	) -> Result<(), Self::Error>
 	where
-		CH: CanObserve<FE> + CanSample<FE> + CanSampleBits<usize>;
+		CH: CanObserve<FE>
+			+ CanObserve<Self::Commitment>
+			+ CanSample<FE>
+			+ CanSampleBits<usize>
+			+ DomainSeparator;
+
+	/// Generate an evaluation proof at a *random* challenge point, writing it to a transcript.
+	fn prove_evaluation_with_transcript<C>(
+		&self,
+		transcript: &mut TranscriptWriter<C>,
+		committed: &Self::Committed,
+		polys: &[MultilinearExtension<P>],
+		query: &[FE],
+	) -> Result<(), Self::Error>
+	where
+		C: CanObserve<BinaryField8b>
+			+ CanSample<FE>
+			+ CanSampleBits<usize>
+			+ DomainSeparator
+			+ Clone,
+		FE: SerializeBytes;
+
+	/// Verify an evaluation proof at a *random* challenge point, reading it from a transcript.
+	fn verify_evaluation_with_transcript<C>(
+		&self,
+		transcript: &mut TranscriptReader<C>,
+		commitment: &Self::Commitment,
+		query: &[FE],
+		values: &[FE],
+	) -> Result<(), Self::Error>
+	where
+		C: CanObserve<BinaryField8b> + CanSample<FE> + CanSampleBits<usize> + DomainSeparator,
+		FE: DeserializeBytes;
 
 	/// Return the byte-size of a proof.
 	fn proof_size(&self, n_polys: usize) -> usize;
+
+	/// Generate a combined proof for evaluation claims at arbitrary points on polynomials in one or
+	/// more committed batches.
+	///
+	/// The claims are reduced with a sumcheck to evaluations at a single random point, at which
+	/// every batch is opened. The claim batch indices refer to positions in `commitments`,
+	/// `committed` and `polys`, and the claimed evaluation points need not be random. The
+	/// commitments and claims are observed before the claims are batched.
+	fn prove_multi_point_evaluation<CH>(
+		&self,
+		challenger: &mut CH,
+		commitments: &[Self::Commitment],
+		committed: &[&Self::Committed],
+		polys: &[&[MultilinearExtension<P>]],
+		claims: &[MultiPointEvalClaim<FE>],
+	) -> Result<MultiPointProof<FE, Self::Proof>, Self::Error>
+	where
+		FE: BinaryField,
+		CH: CanObserve<FE>
+			+ CanObserve<Self::Commitment>
+			+ CanSample<FE>
+			+ CanSampleBits<usize>
+			+ CanGrind<FE>
+			+ DomainSeparator,
+	{
+		multi_point::prove(self, challenger, commitments, committed, polys, claims)
+	}
+
+	/// Verify a combined proof for evaluation claims at arbitrary points on polynomials in one or
+	/// more committed batches.
+	fn verify_multi_point_evaluation<CH>(
+		&self,
+		challenger: &mut CH,
+		commitments: &[Self::Commitment],
+		claims: &[MultiPointEvalClaim<FE>],
+		proof: MultiPointProof<FE, Self::Proof>,
+	) -> Result<(), Self::Error>
+	where
+		FE: BinaryField,
+		CH: CanObserve<FE>
+			+ CanObserve<Self::Commitment>
+			+ CanSample<FE>
+			+ CanSampleBits<usize>
+			+ DomainSeparator,
+	{
+		multi_point::verify(self, challenger, commitments, claims, proof)
+	}
 }
@@ -0,0 +1,26 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Helpers for sumchecks over the product of two multilinears given by their evaluations.
+//!
+//! These are shared by the FRI-Binius evaluation proof and the multi-point batched opening, which
+//! both reduce evaluation claims with such a sumcheck, binding the lowest variable first.
+
+use binius_field::Field;
+use rayon::prelude::*;
+
+/// Computes the constant and quadratic coefficients of a sumcheck round polynomial for the product
+/// of two multilinears, binding the lowest variable.
+pub(super) fn sumcheck_round_poly<F: Field>(a: &[F], b: &[F]) -> [F; 2] {
+	a.par_chunks_exact(2)
+		.zip(b.par_chunks_exact(2))
+		.map(|(a, b)| [a[0] * b[0], (a[0] + a[1]) * (b[0] + b[1])])
+		.reduce(|| [F::ZERO; 2], |x, y| [x[0] + y[0], x[1] + y[1]])
+}
+
+/// Partially evaluates a multilinear, given by its evaluations, at the lowest variable.
+pub(super) fn fold_halves<F: Field>(evals: &[F], challenge: F) -> Vec<F> {
+	evals
+		.par_chunks_exact(2)
+		.map(|pair| pair[0] + (pair[0] + pair[1]) * challenge)
+		.collect()
+}
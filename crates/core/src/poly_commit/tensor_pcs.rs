@@ -1,8 +1,14 @@
 // Copyright 2023 Ulvetanna Inc.
 
-use super::error::{Error, VerificationError};
+use super::{
+	error::{Error, VerificationError},
+	multi_batch::TensorPCSBatch,
+};
 use crate::{
-	challenger::{CanObserve, CanSample, CanSampleBits},
+	challenger::{
+		check_grinding, CanGrind, CanObserve, CanSample, CanSampleBits, DomainSeparator,
+		TranscriptReader, TranscriptWriter, MAX_GRINDING_BITS,
+	},
 	linear_code::LinearCode,
 	merkle_tree::{MerkleTreeVCS, VectorCommitScheme},
 	poly_commit::PolyCommitScheme,
@@ -10,9 +16,10 @@ use crate::{
 		multilinear_query::MultilinearQuery, Error as PolynomialError, MultilinearExtension,
 	},
 	reed_solomon::reed_solomon::ReedSolomonCode,
+	serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes},
 };
 use binius_field::{
-	packed::{get_packed_slice, iter_packed_slice},
+	packed::{get_packed_slice, iter_packed_slice, set_packed_slice},
 	square_transpose, transpose_scalars,
 	util::inner_product_unchecked,
 	BinaryField, BinaryField8b, ExtensionField, Field, PackedExtensionField, PackedField,
@@ -23,10 +30,22 @@ use binius_hash::{
 };
 use p3_matrix::{dense::RowMajorMatrix, MatrixRowSlices};
 use p3_util::{log2_ceil_usize, log2_strict_usize};
+use rand::RngCore;
 use rayon::prelude::*;
-use std::{iter::repeat_with, marker::PhantomData, mem};
+use std::{borrow::Cow, iter::repeat_with, marker::PhantomData, mem};
 use tracing::instrument;
 
+/// Challenger label for the coefficients mixing the committed polynomials.
+const MIXING_LABEL: &str = "pcs.mixing";
+/// Challenger label for the mixed partial evaluation $t'$.
+const T_PRIME_LABEL: &str = "pcs.t_prime";
+/// Challenger label for the sampled column indices.
+const QUERIES_LABEL: &str = "pcs.queries";
+/// Challenger label for the evaluation of the mask polynomial in hiding mode.
+const MASK_EVAL_LABEL: &str = "pcs.mask_eval";
+/// Challenger label for the proof-of-work nonce sent before the column indices are sampled.
+const GRINDING_LABEL: &str = "pcs.grinding";
+
 /// Creates a new multilinear from a batch of multilinears and a mixing challenge
 ///
 /// REQUIRES:
@@ -67,6 +86,20 @@ where
 	Ok(mixed_t_prime)
 }
 
+/// Hash the columns of an encoded matrix, which is stored in column-major order.
+pub(super) fn column_digests<PI, H>(encoded_mat: &RowMajorMatrix<PI>) -> Vec<H::Digest>
+where
+	PI: PackedField,
+	H: HashDigest<PI>,
+	H::Digest: Send,
+{
+	encoded_mat
+		.values
+		.par_chunks_exact(encoded_mat.width)
+		.map(H::hash)
+		.collect()
+}
+
 /// Evaluation proof data for the `TensorPCS` polynomial commitment scheme.
 ///
 /// # Type Parameters
@@ -81,6 +114,11 @@ where
 {
 	/// Number of distinct multilinear polynomials in the batch opening proof
 	pub n_polys: usize,
+	/// Evaluation of the random mask polynomial at the query, present only in hiding mode
+	///
+	/// In hiding mode, the mask polynomial is committed and opened as the last polynomial of the
+	/// batch, and it is included in n_polys.
+	pub mask_eval: Option<PE::Scalar>,
 	/// Represents a mixing of individual polynomial t_primes
 	///
 	/// Let $n$ denote n_polys. Define $l = \lceil\log_2(n)\rceil$.
@@ -91,17 +129,88 @@ where
 	/// This value represents the multilinear polynomial such that $\forall v \in \{0, 1\}^{\mu}$,
 	/// $v \rightarrow \sum_{i=0}^{n-1} c_i * t'_i(v)$
 	pub mixed_t_prime: MultilinearExtension<'a, PE>,
-	/// Opening proofs for chosen columns of the encoded matrices
+	/// Proof-of-work nonce found before sampling the column indices, present only when grinding
+	/// is enabled
+	pub pow_nonce: Option<PE::Scalar>,
+	/// Chosen columns of the encoded matrices
 	///
 	/// Let $j_1, \ldots, j_k$ be the indices of the columns that are opened.
-	/// The ith element is a tuple of:
-	/// * A vector (size=n_polys) of the $j_i$th columns (one from each polynomial's encoded matrix)
-	/// * A proof that these columns are consistent with the vector commitment
-	pub vcs_proofs: Vec<(Vec<Vec<PI>>, VCSProof)>,
+	/// The ith element is a vector (size=n_polys) of the $j_i$th columns, one from each
+	/// polynomial's encoded matrix.
+	pub opened_cols: Vec<Vec<Vec<PI>>>,
+	/// A single proof that the columns at all opened indices are consistent with the vector
+	/// commitment
+	pub vcs_proof: VCSProof,
+}
+
+impl<PI, PE, VCSProof> SerializeBytes for Proof<'_, PI, PE, VCSProof>
+where
+	PI: SerializeBytes,
+	PE: PackedField + SerializeBytes,
+	PE::Scalar: SerializeBytes,
+	VCSProof: SerializeBytes,
+{
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.n_polys.serialize(write_buf);
+		self.mask_eval.serialize(write_buf);
+		self.mixed_t_prime.serialize(write_buf);
+		self.pow_nonce.serialize(write_buf);
+		self.opened_cols.serialize(write_buf);
+		self.vcs_proof.serialize(write_buf);
+	}
+}
+
+impl<PI, PE, VCSProof> DeserializeBytes for Proof<'static, PI, PE, VCSProof>
+where
+	PI: DeserializeBytes,
+	PE: PackedField + DeserializeBytes,
+	PE::Scalar: DeserializeBytes,
+	VCSProof: DeserializeBytes,
+{
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		let n_polys = usize::deserialize(read_buf)?;
+		let mask_eval = Option::deserialize(read_buf)?;
+		let mixed_t_prime = MultilinearExtension::deserialize(read_buf)?;
+		let pow_nonce = Option::deserialize(read_buf)?;
+		let opened_cols = Vec::<Vec<Vec<PI>>>::deserialize(read_buf)?;
+		let vcs_proof = VCSProof::deserialize(read_buf)?;
+
+		// The number of opened columns per query is redundant with n_polys, so it must agree for
+		// the encoding to be canonical.
+		if opened_cols.iter().any(|cols| cols.len() != n_polys) {
+			return Err(SerializationError::InvalidConstruction {
+				name: "tensor_pcs::Proof",
+			});
+		}
+
+		Ok(Self {
+			n_polys,
+			mask_eval,
+			mixed_t_prime,
+			pow_nonce,
+			opened_cols,
+			vcs_proof,
+		})
+	}
 }
 
 /// The multilinear polynomial commitment scheme specified in [DP23].
 ///
+/// The scheme optionally runs in hiding mode, enabled with [`TensorPCS::with_hiding`], in which
+/// commitments and evaluation proofs reveal nothing about the committed polynomials beyond the
+/// claimed evaluations. In hiding mode, every row of the committed matrix consists of a uniformly
+/// random block of message symbols followed by the polynomial's values, so any `n_test_queries`
+/// opened symbols of an encoded row are uniformly distributed when the code, like
+/// [`ReedSolomonCode`], has the property that the low half of the message symbols determines a
+/// subcode of dimension `code.dim() / 2` that is `n_test_queries`-wise independent. The evaluation
+/// proof additionally opens a uniformly random mask polynomial, mixed with the batch, which
+/// masks $t'$. Blinding the rows halves the number of columns available to the polynomials, so
+/// the hiding scheme commits polynomials with one variable fewer.
+///
+/// With [`TensorPCS::with_grinding`], the prover must find a proof-of-work nonce before the
+/// column indices are sampled, which allows fewer test queries for the same security level, see
+/// [`calculate_n_test_queries_with_grinding`].
+///
 /// # Type Parameters
 ///
 /// * `P`: The base field type of committed elements.
@@ -123,8 +232,10 @@ where
 {
 	log_rows: usize,
 	n_test_queries: usize,
+	grinding_bits: usize,
 	code: LC,
 	vcs: VCS,
+	hiding: bool,
 	_p_marker: PhantomData<P>,
 	_pi_marker: PhantomData<PI>,
 	_h_marker: PhantomData<H>,
@@ -153,18 +264,26 @@ where
 		log_rows: usize,
 		code: LC,
 		n_test_queries: usize,
+	) -> Result<Self, Error> {
+		Self::new_using_groestl_merkle_tree_with_cap(log_rows, code, n_test_queries, 0)
+	}
+
+	/// Construct a PCS whose commitment is the cap of $2^k$ nodes of a binary Merkle tree, where
+	/// $k$ is `cap_height`.
+	pub fn new_using_groestl_merkle_tree_with_cap(
+		log_rows: usize,
+		code: LC,
+		n_test_queries: usize,
+		cap_height: usize,
 	) -> Result<Self, Error> {
 		// Check power of two length because MerkleTreeVCS requires it
 		if !code.len().is_power_of_two() {
 			return Err(Error::CodeLengthPowerOfTwoRequired);
 		}
 		let log_len = log2_strict_usize(code.len());
-		Self::new(
-			log_rows,
-			code,
-			n_test_queries,
-			MerkleTreeVCS::new(log_len, GroestlDigestCompression),
-		)
+		let vcs = MerkleTreeVCS::new_with_cap(log_len, cap_height, GroestlDigestCompression)
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+		Self::new(log_rows, code, n_test_queries, vcs)
 	}
 }
 
@@ -183,73 +302,41 @@ where
 	H: HashDigest<PI>,
 	H::Digest: Copy + Default + Send,
 	VCS: VectorCommitScheme<H::Digest>,
+	PI: SerializeBytes + DeserializeBytes,
+	VCS::Commitment: SerializeBytes,
+	VCS::MultiProof: SerializeBytes + DeserializeBytes,
 {
 	type Commitment = VCS::Commitment;
-	type Committed = (Vec<RowMajorMatrix<PI>>, VCS::Committed);
-	type Proof = Proof<'static, PI, PE, VCS::Proof>;
+	/// The encoded matrices, the vector commitment prover data, and in hiding mode, the blinded
+	/// polynomials followed by the mask polynomial.
+	type Committed =
+		(Vec<RowMajorMatrix<PI>>, VCS::Committed, Vec<MultilinearExtension<'static, P>>);
+	type Proof = Proof<'static, PI, PE, VCS::MultiProof>;
 	type Error = Error;
 
 	fn n_vars(&self) -> usize {
-		self.log_rows() + self.log_cols()
+		self.log_rows() + self.log_cols() - usize::from(self.hiding)
 	}
 
+	/// Commit to a batch of polynomials.
+	///
+	/// In hiding mode, the blinding values of the rows are sampled from `rng`. Otherwise the
+	/// commitment is deterministic and `rng` is not used.
 	#[instrument(skip_all, name = "tensor_pcs::commit")]
-	fn commit(
+	fn commit_with_rng(
 		&self,
 		polys: &[MultilinearExtension<P>],
+		rng: impl RngCore,
 	) -> Result<(Self::Commitment, Self::Committed), Error> {
-		for poly in polys {
-			if poly.n_vars() != self.n_vars() {
-				return Err(Error::IncorrectPolynomialSize {
-					expected: self.n_vars(),
-				});
-			}
-		}
-
-		// These conditions are checked by the constructor, so are safe to assert defensively
-		debug_assert_eq!(self.code.dim() % PI::WIDTH, 0);
-
-		// Dimensions as an intermediate field matrix.
-		let n_rows = 1 << self.log_rows;
-		let n_cols_enc = self.code.len();
-
-		let mut encoded_mats = Vec::with_capacity(polys.len());
-		let mut all_digests = Vec::with_capacity(polys.len());
-		for poly in polys {
-			let mut encoded = vec![PI::default(); n_rows * n_cols_enc / PI::WIDTH];
-			let poly_vals_packed =
-				PI::try_cast_to_ext(poly.evals()).ok_or_else(|| Error::UnalignedMessage)?;
-
-			transpose::transpose(
-				PI::unpack_scalars(poly_vals_packed),
-				PI::unpack_scalars_mut(&mut encoded[..n_rows * self.code.dim() / PI::WIDTH]),
-				1 << self.code.dim_bits(),
-				1 << self.log_rows,
-			);
-
-			self.code
-				.encode_batch_inplace(
-					<PI as PackedExtensionField<PA>>::cast_to_bases_mut(&mut encoded),
-					self.log_rows + log2_strict_usize(<FI as ExtensionField<FA>>::DEGREE),
-				)
-				.map_err(|err| Error::EncodeError(Box::new(err)))?;
-
-			let mut digests = vec![H::Digest::default(); n_cols_enc];
-			encoded
-				.par_chunks_exact(n_rows / PI::WIDTH)
-				.map(H::hash)
-				.collect_into_vec(&mut digests);
-			all_digests.push(digests);
-
-			let encoded_mat = RowMajorMatrix::new(encoded, n_rows / PI::WIDTH);
-			encoded_mats.push(encoded_mat);
+		self.check_committed_n_vars(polys)?;
+		if self.hiding {
+			let blinded = self.blind_polys(polys, rng)?;
+			let (commitment, encoded_mats, vcs_committed) = self.commit_encoded(&blinded)?;
+			Ok((commitment, (encoded_mats, vcs_committed, blinded)))
+		} else {
+			let (commitment, encoded_mats, vcs_committed) = self.commit_encoded(polys)?;
+			Ok((commitment, (encoded_mats, vcs_committed, Vec::new())))
 		}
-
-		let (commitment, vcs_committed) = self
-			.vcs
-			.commit_batch(all_digests.into_iter())
-			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
-		Ok((commitment, (encoded_mats, vcs_committed)))
 	}
 
 	/// Generate an evaluation proof at a *random* challenge point.
@@ -258,6 +345,9 @@ where
 	///
 	/// Precondition: The queried point must already be observed by the challenger.
 	///
+	/// In hiding mode, the proof opens the blinded polynomials and the mask polynomial stored in
+	/// `committed`, in place of `polys`.
+	///
 	/// [DP23]: https://eprint.iacr.org/2023/630
 	#[instrument(skip_all, name = "tensor_pcs::prove_evaluation")]
 	fn prove_evaluation<CH>(
@@ -268,62 +358,83 @@ where
 		query: &[FE],
 	) -> Result<Self::Proof, Error>
 	where
-		CH: CanObserve<FE> + CanSample<FE> + CanSampleBits<usize>,
+		CH: CanObserve<FE>
+			+ CanObserve<Self::Commitment>
+			+ CanSample<FE>
+			+ CanSampleBits<usize>
+			+ CanGrind<FE>
+			+ DomainSeparator,
 	{
+		if query.len() != self.n_vars() {
+			return Err(PolynomialError::IncorrectQuerySize {
+				expected: self.n_vars(),
+			}
+			.into());
+		}
+
+		let (col_major_mats, ref vcs_committed, blinded_polys) = committed;
+		let query = self.blinded_query(query);
+		let (polys, mask_eval) = if self.hiding {
+			if blinded_polys.len() != polys.len() + 1 {
+				return Err(Error::NumBatchedMismatchError {
+					err_str: format!("In prove_evaluation: number of polynomials {} plus the mask polynomial must match number of committed blinded polynomials {}", polys.len(), blinded_polys.len()),
+				});
+			}
+			let mask = blinded_polys.last().expect("blinded_polys is not empty");
+			let mask_eval = mask.evaluate(&MultilinearQuery::<PE>::with_full_query(&query)?)?;
+			challenger.observe_labeled(MASK_EVAL_LABEL, mask_eval);
+			(blinded_polys.as_slice(), Some(mask_eval))
+		} else {
+			(polys, None)
+		};
+
 		let n_polys = polys.len();
 		let n_challenges = log2_ceil_usize(n_polys);
-		let mixing_challenges = challenger.sample_vec(n_challenges);
+		let mixing_challenges = challenger.sample_vec_labeled(MIXING_LABEL, n_challenges);
 		let mixing_coefficients =
 			&MultilinearQuery::with_full_query(&mixing_challenges)?.into_expansion()[..n_polys];
 
-		let (col_major_mats, ref vcs_committed) = committed;
 		if col_major_mats.len() != n_polys {
 			return Err(Error::NumBatchedMismatchError {
 				err_str: format!("In prove_evaluation: number of polynomials {} must match number of committed matrices {}", n_polys, col_major_mats.len()),
 			});
 		}
 
-		if query.len() != self.n_vars() {
-			return Err(PolynomialError::IncorrectQuerySize {
-				expected: self.n_vars(),
-			}
-			.into());
-		}
-
 		let code_len_bits = log2_strict_usize(self.code.len());
-		let log_block_size = log2_strict_usize(<FI as ExtensionField<F>>::DEGREE);
-		let log_n_cols = self.code.dim_bits() + log_block_size;
+		let t_prime = self.mixed_t_prime(polys, &query, mixing_coefficients)?;
 
-		let partial_query = &MultilinearQuery::with_full_query(&query[log_n_cols..])?;
-		let ts = polys;
-		let t_primes = ts
-			.iter()
-			.map(|t| t.evaluate_partial_high(partial_query))
-			.collect::<Result<Vec<_>, _>>()?;
-		let t_prime = mix_t_primes(log_n_cols, &t_primes, mixing_coefficients)?;
-
-		challenger.observe_slice(PE::unpack_scalars(t_prime.evals()));
-		let merkle_proofs = repeat_with(|| challenger.sample_bits(code_len_bits))
+		challenger.observe_slice_labeled(T_PRIME_LABEL, PE::unpack_scalars(t_prime.evals()));
+		let pow_nonce = (self.grinding_bits > 0)
+			.then(|| {
+				challenger.observe_label(GRINDING_LABEL.as_bytes());
+				challenger.grind(self.grinding_bits)
+			})
+			.transpose()?;
+		challenger.observe_label(QUERIES_LABEL.as_bytes());
+		let indices = repeat_with(|| challenger.sample_bits(code_len_bits))
 			.take(self.n_test_queries)
-			.map(|index| {
-				let vcs_proof = self
-					.vcs
-					.prove_batch_opening(vcs_committed, index)
-					.map_err(|err| Error::VectorCommit(Box::new(err)))?;
-
-				let cols: Vec<_> = col_major_mats
+			.collect::<Vec<_>>();
+		let vcs_proof = self
+			.vcs
+			.prove_multi_opening(vcs_committed, &indices)
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+		let opened_cols = indices
+			.iter()
+			.map(|&index| {
+				col_major_mats
 					.iter()
 					.map(|col_major_mat| col_major_mat.row_slice(index).to_vec())
-					.collect();
-
-				Ok((cols, vcs_proof))
+					.collect()
 			})
-			.collect::<Result<_, Error>>()?;
+			.collect();
 
 		Ok(Proof {
 			n_polys,
+			mask_eval,
 			mixed_t_prime: t_prime,
-			vcs_proofs: merkle_proofs,
+			pow_nonce,
+			opened_cols,
+			vcs_proof,
 		})
 	}
 
@@ -344,7 +455,171 @@ where
 		values: &[FE],
 	) -> Result<(), Error>
 	where
-		CH: CanObserve<FE> + CanSample<FE> + CanSampleBits<usize>,
+		CH: CanObserve<FE>
+			+ CanObserve<Self::Commitment>
+			+ CanSample<FE>
+			+ CanSampleBits<usize>
+			+ DomainSeparator,
+	{
+		let Proof {
+			n_polys,
+			mask_eval,
+			mixed_t_prime,
+			pow_nonce,
+			opened_cols,
+			vcs_proof,
+		} = proof;
+
+		if mask_eval.is_some() != self.hiding || pow_nonce.is_some() != (self.grinding_bits > 0) {
+			return Err(VerificationError::ProofShape.into());
+		}
+		if values.len() + usize::from(self.hiding) != n_polys {
+			return Err(Error::NumBatchedMismatchError {
+				err_str:
+					format!("In verify_evaluation: proof number of polynomials {} must match number of opened values {}", n_polys, values.len()),
+			});
+		}
+
+		self.verify_evaluation_with(
+			challenger,
+			commitment,
+			query,
+			values,
+			|challenger| {
+				let mask_eval = mask_eval.expect("mask_eval is present in hiding mode");
+				challenger.observe(mask_eval);
+				Ok(mask_eval)
+			},
+			|challenger| {
+				challenger.observe_slice(PE::unpack_scalars(mixed_t_prime.evals()));
+				Ok(mixed_t_prime)
+			},
+			|challenger| {
+				let pow_nonce = pow_nonce.expect("pow_nonce is present when grinding is enabled");
+				challenger.observe(pow_nonce);
+				Ok(pow_nonce)
+			},
+			|_| Ok((opened_cols, vcs_proof)),
+		)
+	}
+
+	/// Generate an evaluation proof at a *random* challenge point, writing it to a transcript.
+	///
+	/// The mixed $t'$ and the proof-of-work nonce are written before the column indices are
+	/// sampled, and the column openings are written after.
+	fn prove_evaluation_with_transcript<C>(
+		&self,
+		transcript: &mut TranscriptWriter<C>,
+		committed: &Self::Committed,
+		polys: &[MultilinearExtension<P>],
+		query: &[FE],
+	) -> Result<(), Error>
+	where
+		C: CanObserve<BinaryField8b>
+			+ CanSample<FE>
+			+ CanSampleBits<usize>
+			+ DomainSeparator
+			+ Clone,
+		FE: SerializeBytes,
+	{
+		let Proof {
+			opened_cols,
+			vcs_proof,
+			..
+		} = self.prove_evaluation(transcript, committed, polys, query)?;
+		transcript.write(&(opened_cols, vcs_proof));
+		Ok(())
+	}
+
+	/// Verify an evaluation proof at a *random* challenge point, reading it from a transcript.
+	#[instrument(skip_all, name = "tensor_pcs::verify_evaluation_with_transcript")]
+	fn verify_evaluation_with_transcript<C>(
+		&self,
+		transcript: &mut TranscriptReader<C>,
+		commitment: &Self::Commitment,
+		query: &[FE],
+		values: &[FE],
+	) -> Result<(), Error>
+	where
+		C: CanObserve<BinaryField8b> + CanSample<FE> + CanSampleBits<usize> + DomainSeparator,
+		FE: DeserializeBytes,
+	{
+		let log_block_size = log2_strict_usize(<FI as ExtensionField<F>>::DEGREE);
+		self.verify_evaluation_with(
+			transcript,
+			commitment,
+			query,
+			values,
+			|transcript| Ok(transcript.read()?),
+			|transcript| {
+				let n_cols = 1 << (self.code.dim_bits() + log_block_size);
+				let scalars = transcript.read_vec::<FE>(n_cols)?;
+				let mut evals = vec![PE::default(); n_cols / PE::WIDTH];
+				PE::unpack_scalars_mut(&mut evals).copy_from_slice(&scalars);
+				Ok(MultilinearExtension::from_values(evals)?)
+			},
+			|transcript| Ok(transcript.read()?),
+			|transcript| Ok(transcript.read()?),
+		)
+	}
+
+	fn proof_size(&self, n_polys: usize) -> usize {
+		// In hiding mode, the proof additionally opens the mask polynomial and its evaluation
+		let (n_polys, mask_eval_size) = if self.hiding {
+			(n_polys + 1, mem::size_of::<FE>())
+		} else {
+			(n_polys, 0)
+		};
+		let pow_nonce_size = if self.grinding_bits > 0 {
+			mem::size_of::<FE>()
+		} else {
+			0
+		};
+		let t_prime_size = (mem::size_of::<PE>() << self.log_cols()) / PE::WIDTH;
+		let column_size = (mem::size_of::<PI>() << self.log_rows()) / PI::WIDTH;
+		mask_eval_size
+			+ pow_nonce_size
+			+ t_prime_size
+			+ n_polys * column_size * self.n_test_queries
+			+ self.vcs.multi_proof_size(n_polys, self.n_test_queries)
+	}
+}
+
+impl<F, P, FA, PA, FI, PI, FE, PE, LC, H, VCS> TensorPCS<P, PA, PI, PE, LC, H, VCS>
+where
+	F: Field,
+	P: PackedField<Scalar = F>,
+	FA: Field,
+	PA: PackedField<Scalar = FA>,
+	FI: ExtensionField<F> + ExtensionField<FA>,
+	PI: PackedFieldIndexable<Scalar = FI> + PackedExtensionField<P> + PackedExtensionField<PA>,
+	FE: ExtensionField<F> + ExtensionField<FI>,
+	PE: PackedFieldIndexable<Scalar = FE> + PackedExtensionField<PI>,
+	LC: LinearCode<P = PA>,
+	H: HashDigest<PI>,
+	H::Digest: Copy + Default + Send,
+	VCS: VectorCommitScheme<H::Digest>,
+{
+	/// Shared implementation of the evaluation proof verifier.
+	///
+	/// The prover messages are obtained through `read_mask_eval`, `read_t_prime`, `read_pow_nonce`
+	/// and `read_openings`, which are responsible for observing them with the challenger after the
+	/// message label has been absorbed. `read_mask_eval` is only called in hiding mode and
+	/// `read_pow_nonce` only when grinding is enabled.
+	#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+	fn verify_evaluation_with<CH>(
+		&self,
+		challenger: &mut CH,
+		commitment: &VCS::Commitment,
+		query: &[FE],
+		values: &[FE],
+		read_mask_eval: impl FnOnce(&mut CH) -> Result<FE, Error>,
+		read_t_prime: impl FnOnce(&mut CH) -> Result<MultilinearExtension<'static, PE>, Error>,
+		read_pow_nonce: impl FnOnce(&mut CH) -> Result<FE, Error>,
+		read_openings: impl FnOnce(&mut CH) -> Result<(Vec<Vec<Vec<PI>>>, VCS::MultiProof), Error>,
+	) -> Result<(), Error>
+	where
+		CH: CanSample<FE> + CanSampleBits<usize> + DomainSeparator,
 	{
 		// These are all checked during construction, so it is safe to assert as a defensive
 		// measure.
@@ -354,43 +629,97 @@ where
 		debug_assert_eq!(self.code.dim() % PI::WIDTH, 0);
 		debug_assert_eq!(self.code.dim() % PE::WIDTH, 0);
 
-		if values.len() != proof.n_polys {
-			return Err(Error::NumBatchedMismatchError {
-				err_str:
-					format!("In verify_evaluation: proof number of polynomials {} must match number of opened values {}", proof.n_polys, values.len()),
-			});
+		let n_vars = self.log_rows + self.log_cols() - usize::from(self.hiding);
+		if query.len() != n_vars {
+			return Err(PolynomialError::IncorrectQuerySize { expected: n_vars }.into());
 		}
 
-		let n_challenges = log2_ceil_usize(proof.n_polys);
-		let mixing_challenges = challenger.sample_vec(n_challenges);
-		let mixing_coefficients = &MultilinearQuery::<PE>::with_full_query(&mixing_challenges)?
-			.into_expansion()[..proof.n_polys];
+		// In hiding mode, the mask polynomial is opened as the last polynomial of the batch
+		let query = self.blinded_query(query);
+		let mut values = Cow::Borrowed(values);
+		if self.hiding {
+			challenger.observe_label(MASK_EVAL_LABEL.as_bytes());
+			values.to_mut().push(read_mask_eval(challenger)?);
+		}
+
+		let n_polys = values.len();
+		let n_challenges = log2_ceil_usize(n_polys);
+		let mixing_challenges = challenger.sample_vec_labeled(MIXING_LABEL, n_challenges);
+		let mixing_coefficients = &MultilinearQuery::<FE>::with_full_query(&mixing_challenges)?
+			.into_expansion()[..n_polys];
 		let value =
-			inner_product_unchecked(values.iter().copied(), iter_packed_slice(mixing_coefficients));
+			inner_product_unchecked(values.iter().copied(), mixing_coefficients.iter().copied());
 
-		if query.len() != self.n_vars() {
-			return Err(PolynomialError::IncorrectQuerySize {
-				expected: self.n_vars(),
-			}
-			.into());
-		}
+		// Code length is checked to be a power of two in the constructor
+		let code_len_bits = log2_strict_usize(self.code.len());
 
+		challenger.observe_label(T_PRIME_LABEL.as_bytes());
+		let mixed_t_prime = read_t_prime(challenger)?;
+		let pow_nonce = if self.grinding_bits > 0 {
+			challenger.observe_label(GRINDING_LABEL.as_bytes());
+			let pow_nonce = read_pow_nonce(challenger)?;
+			if !check_grinding(challenger, self.grinding_bits) {
+				return Err(VerificationError::InvalidProofOfWork.into());
+			}
+			Some(pow_nonce)
+		} else {
+			None
+		};
+		challenger.observe_label(QUERIES_LABEL.as_bytes());
+		let indices = repeat_with(|| challenger.sample_bits(code_len_bits))
+			.take(self.n_test_queries)
+			.collect::<Vec<_>>();
+		let (opened_cols, vcs_proof) = read_openings(challenger)?;
+		let proof = Proof {
+			n_polys,
+			mask_eval: None,
+			mixed_t_prime,
+			pow_nonce,
+			opened_cols,
+			vcs_proof,
+		};
 		self.check_proof_shape(&proof)?;
 
-		// Code length is checked to be a power of two in the constructor
+		// Check vector commitment openings.
+		self.vcs
+			.verify_multi_opening(
+				commitment,
+				&indices,
+				proof.n_polys,
+				proof.vcs_proof,
+				proof
+					.opened_cols
+					.iter()
+					.map(|cols| cols.iter().map(H::hash)),
+			)
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+		let columns = indices.into_iter().zip(proof.opened_cols).collect();
+
+		self.check_openings(&query, value, mixing_coefficients, &proof.mixed_t_prime, columns)
+	}
+
+	/// Check the opened columns of a batch against the mixed partial evaluation $t'$.
+	///
+	/// The query is the full query to the committed polynomials and `value` is the mixed claimed
+	/// evaluation. The columns must already be verified against the vector commitment.
+	fn check_openings(
+		&self,
+		query: &[FE],
+		value: FE,
+		mixing_coefficients: &[FE],
+		mixed_t_prime: &MultilinearExtension<PE>,
+		columns: Vec<(usize, Vec<Vec<PI>>)>,
+	) -> Result<(), Error> {
 		let code_len_bits = log2_strict_usize(self.code.len());
 		let block_size = <FI as ExtensionField<F>>::DEGREE;
 		let log_block_size = log2_strict_usize(block_size);
 		let log_n_cols = self.code.dim_bits() + log_block_size;
-
 		let n_rows = 1 << self.log_rows;
-
-		challenger.observe_slice(PE::unpack_scalars(proof.mixed_t_prime.evals()));
+		let n_polys = mixing_coefficients.len();
 
 		// Check evaluation of t' matches the claimed value
 		let multilin_query = MultilinearQuery::<PE>::with_full_query(&query[..log_n_cols])?;
-		let computed_value = proof
-			.mixed_t_prime
+		let computed_value = mixed_t_prime
 			.evaluate(&multilin_query)
 			.expect("query is the correct size by check_proof_shape checks");
 		if computed_value != value {
@@ -399,24 +728,7 @@ where
 
 		// Encode t' into u'
 		let mut u_prime = vec![PE::default(); (1 << (code_len_bits + log_block_size)) / PE::WIDTH];
-		self.encode_ext(proof.mixed_t_prime.evals(), &mut u_prime)?;
-
-		// Check vector commitment openings.
-		let columns = proof
-			.vcs_proofs
-			.into_iter()
-			.map(|(cols, vcs_proof)| {
-				let index = challenger.sample_bits(code_len_bits);
-
-				let leaf_digests = cols.iter().map(H::hash);
-
-				self.vcs
-					.verify_batch_opening(commitment, index, vcs_proof, leaf_digests)
-					.map_err(|err| Error::VectorCommit(Box::new(err)))?;
-
-				Ok((index, cols))
-			})
-			.collect::<Result<Vec<_>, Error>>()?;
+		self.encode_ext(mixed_t_prime.evals(), &mut u_prime)?;
 
 		// Get the sequence of column tests.
 		let column_tests = columns
@@ -425,7 +737,7 @@ where
 				let mut batched_column_test = (0..block_size)
 					.map(|j| {
 						let u_prime_i = get_packed_slice(&u_prime, index << log_block_size | j);
-						let base_cols = Vec::with_capacity(proof.n_polys);
+						let base_cols = Vec::with_capacity(n_polys);
 						(u_prime_i, base_cols)
 					})
 					.collect::<Vec<_>>();
@@ -479,7 +791,7 @@ where
 				// Check that opened column evaluations match u'
 				let actual_result = inner_product_unchecked(
 					unmixed_actual_results.into_iter(),
-					iter_packed_slice(mixing_coefficients),
+					mixing_coefficients.iter().copied(),
 				);
 				actual_result != *expected_result
 			});
@@ -490,12 +802,6 @@ where
 			Ok(())
 		}
 	}
-
-	fn proof_size(&self, n_polys: usize) -> usize {
-		let t_prime_size = (mem::size_of::<PE>() << self.log_cols()) / PE::WIDTH;
-		let column_size = (mem::size_of::<PI>() << self.log_rows()) / PI::WIDTH;
-		t_prime_size + (n_polys * column_size + self.vcs.proof_size(n_polys)) * self.n_test_queries
-	}
 }
 
 impl<F, P, FA, PA, FI, PI, FE, PE, LC, H, VCS> TensorPCS<P, PA, PI, PE, LC, H, VCS>
@@ -521,6 +827,16 @@ where
 	pub fn log_cols(&self) -> usize {
 		self.code.dim_bits() + log2_strict_usize(FI::DEGREE)
 	}
+
+	/// The number of columns opened by an evaluation proof.
+	pub fn n_test_queries(&self) -> usize {
+		self.n_test_queries
+	}
+
+	/// The number of proof-of-work bits the prover grinds before the column indices are sampled.
+	pub fn grinding_bits(&self) -> usize {
+		self.grinding_bits
+	}
 }
 
 impl<F, P, FA, PA, FI, PI, FE, PE, LC, H, VCS> TensorPCS<P, PA, PI, PE, LC, H, VCS>
@@ -573,14 +889,67 @@ where
 		Ok(Self {
 			log_rows,
 			n_test_queries,
+			grinding_bits: 0,
 			code,
 			vcs,
+			hiding: false,
 			_p_marker: PhantomData,
 			_pi_marker: PhantomData,
 			_h_marker: PhantomData,
 			_ext_marker: PhantomData,
 		})
 	}
+
+	/// Enable hiding mode.
+	///
+	/// In hiding mode, half of the message symbols of every row are blinding values, so the scheme
+	/// commits to polynomials with one variable fewer.
+	///
+	/// Throws if the code dimension is less than 2, if more test queries are made than half of the
+	/// code dimension, or if there are fewer rows than the degree of the extension field, because
+	/// the blinding values would not hide the opened columns and $t'$ in these cases.
+	pub fn with_hiding(mut self) -> Result<Self, Error> {
+		if self.code.dim_bits() == 0 {
+			return Err(Error::InvalidHidingParameters("the code dimension must be at least 2"));
+		}
+		if self.n_test_queries > self.code.dim() / 2 {
+			return Err(Error::InvalidHidingParameters(
+				"the number of test queries must not exceed half the code dimension",
+			));
+		}
+		if (1 << self.log_rows) < <FE as ExtensionField<F>>::DEGREE {
+			return Err(Error::InvalidHidingParameters(
+				"the number of rows must be at least the extension degree",
+			));
+		}
+
+		self.hiding = true;
+		Ok(self)
+	}
+
+	/// Whether the scheme runs in hiding mode.
+	pub fn is_hiding(&self) -> bool {
+		self.hiding
+	}
+
+	/// Require the prover to grind a proof-of-work nonce with `bits` zero bits before the column
+	/// indices are sampled.
+	///
+	/// The number of test queries is not changed. Use [`calculate_n_test_queries_with_grinding`]
+	/// to find the number of queries that, together with grinding, achieves a security level.
+	///
+	/// Throws if `bits` exceeds [`MAX_GRINDING_BITS`].
+	pub fn with_grinding(mut self, bits: usize) -> Result<Self, Error> {
+		if bits > MAX_GRINDING_BITS {
+			return Err(Error::InvalidGrindingBits {
+				bits,
+				max: MAX_GRINDING_BITS,
+			});
+		}
+
+		self.grinding_bits = bits;
+		Ok(self)
+	}
 }
 
 // Helper functions for PolyCommitScheme implementation.
@@ -602,36 +971,175 @@ where
 	H::Digest: Copy + Default + Send,
 	VCS: VectorCommitScheme<H::Digest>,
 {
-	fn check_proof_shape(&self, proof: &Proof<PI, PE, VCS::Proof>) -> Result<(), Error> {
-		let n_rows = 1 << self.log_rows;
-		let log_block_size = log2_strict_usize(<FI as ExtensionField<F>>::DEGREE);
-		let log_n_cols = self.code.dim_bits() + log_block_size;
-		let n_queries = self.n_test_queries;
-
-		if proof.vcs_proofs.len() != n_queries {
-			return Err(VerificationError::NumberOfOpeningProofs {
-				expected: n_queries,
+	fn check_committed_n_vars(&self, polys: &[MultilinearExtension<P>]) -> Result<(), Error> {
+		let n_vars = self.log_rows + self.log_cols() - usize::from(self.hiding);
+		for poly in polys {
+			if poly.n_vars() != n_vars {
+				return Err(Error::IncorrectPolynomialSize { expected: n_vars });
 			}
-			.into());
 		}
-		for (col_idx, (polys_col, _)) in proof.vcs_proofs.iter().enumerate() {
-			if polys_col.len() != proof.n_polys {
-				return Err(Error::NumBatchedMismatchError {
-					err_str: format!(
-						"Expected {} polynomials, but VCS proof at col_idx {} found {} polynomials instead",
-						proof.n_polys,
-						col_idx,
-						polys_col.len()
-					),
-				});
-			}
+		Ok(())
+	}
 
-			for (poly_idx, poly_col) in polys_col.iter().enumerate() {
-				if poly_col.len() * PI::WIDTH != n_rows {
-					return Err(VerificationError::OpenedColumnSize {
-						col_index: col_idx,
-						poly_index: poly_idx,
-						expected: n_rows,
+	/// Blind the rows of the polynomials and append a uniformly random mask polynomial.
+	///
+	/// The blinded polynomials have one more variable than the input polynomials. The top column
+	/// variable selects between the uniformly random low half of the row and the polynomial's
+	/// values in the high half.
+	fn blind_polys(
+		&self,
+		polys: &[MultilinearExtension<P>],
+		mut rng: impl RngCore,
+	) -> Result<Vec<MultilinearExtension<'static, P>>, Error> {
+		let log_cols = self.log_cols();
+		let half_cols = 1 << (log_cols - 1);
+		let n_packed = (1 << (self.log_rows + log_cols)) / P::WIDTH;
+
+		let mut blinded = polys
+			.iter()
+			.map(|poly| {
+				let mut evals = repeat_with(|| P::random(&mut rng))
+					.take(n_packed)
+					.collect::<Vec<_>>();
+				for (i, value) in iter_packed_slice(poly.evals()).enumerate() {
+					let (row, col) = (i >> (log_cols - 1), i & (half_cols - 1));
+					set_packed_slice(&mut evals, row << log_cols | half_cols | col, value);
+				}
+				MultilinearExtension::from_values(evals)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let mask = repeat_with(|| P::random(&mut rng))
+			.take(n_packed)
+			.collect::<Vec<_>>();
+		blinded.push(MultilinearExtension::from_values(mask)?);
+		Ok(blinded)
+	}
+
+	/// Encode the polynomials into matrices and commit to their columns.
+	#[allow(clippy::type_complexity)]
+	fn commit_encoded(
+		&self,
+		polys: &[MultilinearExtension<P>],
+	) -> Result<(VCS::Commitment, Vec<RowMajorMatrix<PI>>, VCS::Committed), Error> {
+		// These conditions are checked by the constructor, so are safe to assert defensively
+		debug_assert_eq!(self.code.dim() % PI::WIDTH, 0);
+
+		let encoded_mats = polys
+			.iter()
+			.map(|poly| {
+				let poly_vals_packed =
+					PI::try_cast_to_ext(poly.evals()).ok_or_else(|| Error::UnalignedMessage)?;
+				self.encode_poly(poly_vals_packed)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let (commitment, vcs_committed) = self
+			.vcs
+			.commit_batch(encoded_mats.iter().map(column_digests::<_, H>))
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+		Ok((commitment, encoded_mats, vcs_committed))
+	}
+
+	/// Encode the rows of a polynomial's matrix, returning the encoded matrix in column-major
+	/// order.
+	fn encode_poly(&self, poly_vals_packed: &[PI]) -> Result<RowMajorMatrix<PI>, Error> {
+		let n_rows = 1 << self.log_rows;
+		let n_cols_enc = self.code.len();
+
+		let mut encoded = vec![PI::default(); n_rows * n_cols_enc / PI::WIDTH];
+		transpose::transpose(
+			PI::unpack_scalars(poly_vals_packed),
+			PI::unpack_scalars_mut(&mut encoded[..n_rows * self.code.dim() / PI::WIDTH]),
+			1 << self.code.dim_bits(),
+			1 << self.log_rows,
+		);
+
+		self.code
+			.encode_batch_inplace(
+				<PI as PackedExtensionField<PA>>::cast_to_bases_mut(&mut encoded),
+				self.log_rows + log2_strict_usize(<FI as ExtensionField<FA>>::DEGREE),
+			)
+			.map_err(|err| Error::EncodeError(Box::new(err)))?;
+
+		Ok(RowMajorMatrix::new(encoded, n_rows / PI::WIDTH))
+	}
+
+	/// Compute the mixed partial evaluation $t'$ of a batch of polynomials at the high variables
+	/// of the query.
+	fn mixed_t_prime(
+		&self,
+		polys: &[MultilinearExtension<P>],
+		query: &[FE],
+		mixing_coefficients: &[FE],
+	) -> Result<MultilinearExtension<'static, PE>, Error> {
+		let log_n_cols = self.log_cols();
+		let partial_query = &MultilinearQuery::with_full_query(&query[log_n_cols..])?;
+		let t_primes = polys
+			.iter()
+			.map(|t| t.evaluate_partial_high(partial_query))
+			.collect::<Result<Vec<_>, _>>()?;
+		mix_t_primes(log_n_cols, &t_primes, mixing_coefficients)
+	}
+
+	/// Expand a query to the blinded polynomials by fixing the top column variable to one.
+	fn blinded_query<'a>(&self, query: &'a [FE]) -> Cow<'a, [FE]> {
+		if self.hiding {
+			let (col_query, row_query) = query.split_at(self.log_cols() - 1);
+			let mut blinded_query = Vec::with_capacity(query.len() + 1);
+			blinded_query.extend_from_slice(col_query);
+			blinded_query.push(FE::ONE);
+			blinded_query.extend_from_slice(row_query);
+			Cow::Owned(blinded_query)
+		} else {
+			Cow::Borrowed(query)
+		}
+	}
+
+	fn check_proof_shape(&self, proof: &Proof<PI, PE, VCS::MultiProof>) -> Result<(), Error> {
+		let n_queries = self.n_test_queries;
+
+		if proof.opened_cols.len() != n_queries {
+			return Err(VerificationError::NumberOfOpeningProofs {
+				expected: n_queries,
+			}
+			.into());
+		}
+		self.check_opening_shape(proof.n_polys, &proof.mixed_t_prime, proof.opened_cols.iter())
+	}
+
+	/// Check the shape of $t'$ and of the columns opened at every query.
+	fn check_opening_shape<'b>(
+		&self,
+		n_polys: usize,
+		mixed_t_prime: &MultilinearExtension<PE>,
+		opened_cols: impl Iterator<Item = &'b Vec<Vec<PI>>>,
+	) -> Result<(), Error>
+	where
+		PI: 'b,
+	{
+		let n_rows = 1 << self.log_rows;
+		let log_block_size = log2_strict_usize(<FI as ExtensionField<F>>::DEGREE);
+		let log_n_cols = self.code.dim_bits() + log_block_size;
+
+		for (col_idx, polys_col) in opened_cols.enumerate() {
+			if polys_col.len() != n_polys {
+				return Err(Error::NumBatchedMismatchError {
+					err_str: format!(
+						"Expected {} polynomials, but VCS proof at col_idx {} found {} polynomials instead",
+						n_polys,
+						col_idx,
+						polys_col.len()
+					),
+				});
+			}
+
+			for (poly_idx, poly_col) in polys_col.iter().enumerate() {
+				if poly_col.len() * PI::WIDTH != n_rows {
+					return Err(VerificationError::OpenedColumnSize {
+						col_index: col_idx,
+						poly_index: poly_idx,
+						expected: n_rows,
 						actual: poly_col.len() * PI::WIDTH,
 					}
 					.into());
@@ -639,7 +1147,7 @@ where
 			}
 		}
 
-		if proof.mixed_t_prime.n_vars() != log_n_cols {
+		if mixed_t_prime.n_vars() != log_n_cols {
 			return Err(VerificationError::PartialEvaluationSize.into());
 		}
 
@@ -705,6 +1213,95 @@ where
 	}
 }
 
+impl<F, P, FA, PA, FI, PI, FE, PE, LC, H, VCS> TensorPCSBatch<PI, PE>
+	for TensorPCS<P, PA, PI, PE, LC, H, VCS>
+where
+	F: Field,
+	P: PackedField<Scalar = F> + Send,
+	FA: Field,
+	PA: PackedField<Scalar = FA>,
+	FI: ExtensionField<F> + ExtensionField<FA>,
+	PI: PackedFieldIndexable<Scalar = FI>
+		+ PackedExtensionField<P>
+		+ PackedExtensionField<PA>
+		+ Sync,
+	FE: ExtensionField<F> + ExtensionField<FI>,
+	PE: PackedFieldIndexable<Scalar = FE> + PackedExtensionField<PI>,
+	LC: LinearCode<P = PA>,
+	H: HashDigest<PI>,
+	H::Digest: Copy + Default + Send,
+	VCS: VectorCommitScheme<H::Digest>,
+{
+	fn log_rows(&self) -> usize {
+		self.log_rows
+	}
+
+	fn log_cols(&self) -> usize {
+		TensorPCS::log_cols(self)
+	}
+
+	fn code_len(&self) -> usize {
+		self.code.len()
+	}
+
+	fn n_test_queries(&self) -> usize {
+		self.n_test_queries
+	}
+
+	fn is_hiding(&self) -> bool {
+		self.hiding
+	}
+
+	fn grinding_bits(&self) -> usize {
+		self.grinding_bits
+	}
+
+	fn encode_packed(&self, poly: &[PI]) -> Result<RowMajorMatrix<PI>, Error> {
+		let n_vars = self.log_rows + TensorPCS::log_cols(self);
+		if poly.len() * PI::WIDTH * <FI as ExtensionField<F>>::DEGREE != 1 << n_vars {
+			return Err(Error::IncorrectPolynomialSize { expected: n_vars });
+		}
+		self.encode_poly(poly)
+	}
+
+	fn mixed_t_prime_packed(
+		&self,
+		polys: &[&[PI]],
+		query: &[FE],
+		mixing_coefficients: &[FE],
+	) -> Result<MultilinearExtension<'static, PE>, Error> {
+		let n_vars = self.log_rows + TensorPCS::log_cols(self);
+		if query.len() != n_vars {
+			return Err(PolynomialError::IncorrectQuerySize { expected: n_vars }.into());
+		}
+		let polys = polys
+			.iter()
+			.map(|poly| {
+				MultilinearExtension::from_values_slice(PackedExtensionField::<P>::cast_to_bases(
+					poly,
+				))
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		self.mixed_t_prime(&polys, query, mixing_coefficients)
+	}
+
+	fn check_column_openings(
+		&self,
+		query: &[FE],
+		value: FE,
+		mixing_coefficients: &[FE],
+		mixed_t_prime: &MultilinearExtension<PE>,
+		columns: Vec<(usize, Vec<Vec<PI>>)>,
+	) -> Result<(), Error> {
+		self.check_opening_shape(
+			mixing_coefficients.len(),
+			mixed_t_prime,
+			columns.iter().map(|(_, cols)| cols),
+		)?;
+		self.check_openings(query, value, mixing_coefficients, mixed_t_prime, columns)
+	}
+}
+
 /// The basic multilinear polynomial commitment scheme from [DP23].
 ///
 /// The basic scheme follows Construction 3.7. In this case, the encoding alphabet is a subfield of
@@ -725,14 +1322,31 @@ pub fn calculate_n_test_queries<F: BinaryField, LC: LinearCode>(
 	security_bits: usize,
 	log_rows: usize,
 	code: &LC,
+) -> Result<usize, Error> {
+	calculate_n_test_queries_with_grinding::<F, _>(security_bits, log_rows, code, 0)
+}
+
+/// Calculates the number of test queries when the prover grinds `grinding_bits` bits of proof of
+/// work before the column indices are sampled, see [`TensorPCS::with_grinding`].
+///
+/// Grinding is credited against the error terms of the column tests. It is not credited against
+/// the tensor batching error, since those challenges are sampled before the grinding phase.
+pub fn calculate_n_test_queries_with_grinding<F: BinaryField, LC: LinearCode>(
+	security_bits: usize,
+	log_rows: usize,
+	code: &LC,
+	grinding_bits: usize,
 ) -> Result<usize, Error> {
 	// Assume we are limited by the non-proximal error term
 	let relative_dist = code.min_dist() as f64 / code.len() as f64;
 	let non_proximal_per_query_err = 1.0 - (relative_dist / 3.0);
-	let mut n_queries =
-		(-(security_bits as f64) / non_proximal_per_query_err.log2()).ceil() as usize;
+	let mut n_queries = (-(security_bits.saturating_sub(grinding_bits) as f64)
+		/ non_proximal_per_query_err.log2())
+	.ceil()
+	.max(1.0) as usize;
 	for _ in 0..10 {
-		if calculate_error_bound::<F, _>(log_rows, code, n_queries) >= security_bits {
+		if calculate_error_bound::<F, _>(log_rows, code, n_queries, grinding_bits) >= security_bits
+		{
 			return Ok(n_queries);
 		}
 		n_queries += 1;
@@ -743,19 +1357,22 @@ pub fn calculate_n_test_queries<F: BinaryField, LC: LinearCode>(
 /// Calculates the base-2 log soundness error bound when using general linear codes.
 ///
 /// Returns the number of bits of security achieved with the given parameters. This is computed
-/// using the formulae in Section 3.5 of [DP23].
+/// using the formulae in Section 3.5 of [DP23], with the column test error terms reduced by
+/// `grinding_bits`.
 ///
 /// [DP23]: https://eprint.iacr.org/2023/1784
 fn calculate_error_bound<F: BinaryField, LC: LinearCode>(
 	log_rows: usize,
 	code: &LC,
 	n_queries: usize,
+	grinding_bits: usize,
 ) -> usize {
 	let e = (code.min_dist() - 1) / 3;
 	let relative_dist = code.min_dist() as f64 / code.len() as f64;
+	let grinding_factor = 2.0_f64.powi(-(grinding_bits as i32));
 	let tensor_batching_err = (2 * log_rows * (e + 1)) as f64 / 2.0_f64.powi(F::N_BITS as i32);
-	let non_proximal_err = (1.0 - relative_dist / 3.0).powi(n_queries as i32);
-	let proximal_err = (1.0 - 2.0 * relative_dist / 3.0).powi(n_queries as i32);
+	let non_proximal_err = (1.0 - relative_dist / 3.0).powi(n_queries as i32) * grinding_factor;
+	let proximal_err = (1.0 - 2.0 * relative_dist / 3.0).powi(n_queries as i32) * grinding_factor;
 	let total_err = (tensor_batching_err + non_proximal_err).max(proximal_err);
 	-total_err.log2() as usize
 }
@@ -765,6 +1382,28 @@ pub fn calculate_n_test_queries_reed_solomon<F, FE, P>(
 	log_rows: usize,
 	code: &ReedSolomonCode<P>,
 ) -> Result<usize, Error>
+where
+	F: BinaryField,
+	FE: BinaryField + ExtensionField<F>,
+	P: PackedField<Scalar = F> + PackedExtensionField<F>,
+	P::Scalar: BinaryField,
+{
+	calculate_n_test_queries_reed_solomon_with_grinding::<_, FE, _>(
+		security_bits,
+		log_rows,
+		code,
+		0,
+	)
+}
+
+/// Calculates the number of test queries for Reed–Solomon codes when the prover grinds
+/// `grinding_bits` bits of proof of work, as in [`calculate_n_test_queries_with_grinding`].
+pub fn calculate_n_test_queries_reed_solomon_with_grinding<F, FE, P>(
+	security_bits: usize,
+	log_rows: usize,
+	code: &ReedSolomonCode<P>,
+	grinding_bits: usize,
+) -> Result<usize, Error>
 where
 	F: BinaryField,
 	FE: BinaryField + ExtensionField<F>,
@@ -774,10 +1413,12 @@ where
 	// Assume we are limited by the non-proximal error term
 	let relative_dist = code.min_dist() as f64 / code.len() as f64;
 	let non_proximal_per_query_err = 1.0 - (relative_dist / 2.0);
-	let mut n_queries =
-		(-(security_bits as f64) / non_proximal_per_query_err.log2()).ceil() as usize;
+	let mut n_queries = (-(security_bits.saturating_sub(grinding_bits) as f64)
+		/ non_proximal_per_query_err.log2())
+	.ceil()
+	.max(1.0) as usize;
 	for _ in 0..10 {
-		if calculate_error_bound_reed_solomon::<_, FE, _>(log_rows, code, n_queries)
+		if calculate_error_bound_reed_solomon::<_, FE, _>(log_rows, code, n_queries, grinding_bits)
 			>= security_bits
 		{
 			return Ok(n_queries);
@@ -790,14 +1431,16 @@ where
 /// Calculates the base-2 log soundness error bound when using Reed–Solomon codes.
 ///
 /// Returns the number of bits of security achieved with the given parameters. This is computed
-/// using the formulae in Section 3.5 of [DP23]. We use the improved proximity gap result for
-/// Reed–Solomon codes, following Remark 3.18 in [DP23].
+/// using the formulae in Section 3.5 of [DP23], with the column test error terms reduced by
+/// `grinding_bits`. We use the improved proximity gap result for Reed–Solomon codes, following
+/// Remark 3.18 in [DP23].
 ///
 /// [DP23]: https://eprint.iacr.org/2023/1784
 fn calculate_error_bound_reed_solomon<F, FE, P>(
 	log_rows: usize,
 	code: &ReedSolomonCode<P>,
 	n_queries: usize,
+	grinding_bits: usize,
 ) -> usize
 where
 	F: BinaryField,
@@ -807,16 +1450,23 @@ where
 {
 	let e = (code.min_dist() - 1) / 2;
 	let relative_dist = code.min_dist() as f64 / code.len() as f64;
+	let grinding_factor = 2.0_f64.powi(-(grinding_bits as i32));
 	let tensor_batching_err = (2 * log_rows * (e + 1)) as f64 / 2.0_f64.powi(FE::N_BITS as i32);
-	let non_proximal_err = (1.0 - (relative_dist / 2.0)).powi(n_queries as i32);
-	let proximal_err = (1.0 - relative_dist / 2.0).powi(n_queries as i32);
+	let non_proximal_err = (1.0 - (relative_dist / 2.0)).powi(n_queries as i32) * grinding_factor;
+	let proximal_err = (1.0 - relative_dist / 2.0).powi(n_queries as i32) * grinding_factor;
 	let total_err = (tensor_batching_err + non_proximal_err).max(proximal_err);
 	-total_err.log2() as usize
 }
 
 /// Find the TensorPCS parameterization that optimizes proof size.
 ///
-/// This constructs a TensorPCS using a Reed-Solomon code and a Merkle tree using Groestl.
+/// This constructs a TensorPCS using a Reed-Solomon code and a Merkle tree using Groestl. The
+/// Merkle cap height is part of the search, and since the cap is sent as the commitment, the size
+/// minimized is that of the proof together with the commitment.
+///
+/// The prover grinds `grinding_bits` bits of proof of work before the column tests, which lowers
+/// the number of test queries, see [`TensorPCS::with_grinding`]. Returns `None` if
+/// `grinding_bits` exceeds [`MAX_GRINDING_BITS`].
 #[allow(clippy::type_complexity)]
 pub fn find_proof_size_optimal_pcs<F, P, FA, PA, FI, PI, FE, PE>(
 	security_bits: usize,
@@ -824,6 +1474,7 @@ pub fn find_proof_size_optimal_pcs<F, P, FA, PA, FI, PI, FE, PE>(
 	n_polys: usize,
 	log_inv_rate: usize,
 	conservative_testing: bool,
+	grinding_bits: usize,
 ) -> Option<
 	TensorPCS<
 		P,
@@ -845,7 +1496,9 @@ where
 		+ PackedExtensionField<BinaryField8b>
 		+ PackedExtensionField<FI>
 		+ PackedExtensionField<P>
-		+ PackedExtensionField<PA>,
+		+ PackedExtensionField<PA>
+		+ SerializeBytes
+		+ DeserializeBytes,
 	FE: BinaryField + ExtensionField<F> + ExtensionField<FA> + ExtensionField<FI>,
 	PE: PackedField<Scalar = FE> + PackedExtensionField<PI> + PackedExtensionField<FE>,
 {
@@ -861,36 +1514,56 @@ where
 		};
 
 		let n_test_queries_result = if conservative_testing {
-			calculate_n_test_queries::<FE, _>(security_bits, log_rows, &rs_code)
+			calculate_n_test_queries_with_grinding::<FE, _>(
+				security_bits,
+				log_rows,
+				&rs_code,
+				grinding_bits,
+			)
 		} else {
-			calculate_n_test_queries_reed_solomon::<_, FE, _>(security_bits, log_rows, &rs_code)
+			calculate_n_test_queries_reed_solomon_with_grinding::<_, FE, _>(
+				security_bits,
+				log_rows,
+				&rs_code,
+				grinding_bits,
+			)
 		};
 		let n_test_queries = match n_test_queries_result {
 			Ok(n_test_queries) => n_test_queries,
 			Err(_) => continue,
 		};
 
-		let pcs = match TensorPCS::<P, PA, PI, PE, _, _, _>::new_using_groestl_merkle_tree(
+		// The cap height only affects the size of the Merkle multi-proof and the commitment
+		let log_len = log2_strict_usize(rs_code.len());
+		let cap_height = (0..=log_len)
+			.min_by_key(|&cap_height| {
+				GroestlMerkleTreeVCS::new_with_cap(log_len, cap_height, GroestlDigestCompression)
+					.map(|vcs| {
+						vcs.multi_proof_size(n_polys, n_test_queries) + vcs.commitment_size()
+					})
+					.unwrap_or(usize::MAX)
+			})
+			.expect("range of cap heights is not empty");
+
+		let pcs = match TensorPCS::<P, PA, PI, PE, _, _, _>::new_using_groestl_merkle_tree_with_cap(
 			log_rows,
 			rs_code,
 			n_test_queries,
-		) {
+			cap_height,
+		)
+		.and_then(|pcs| pcs.with_grinding(grinding_bits))
+		{
 			Ok(pcs) => pcs,
 			Err(_) => continue,
 		};
 
+		let proof_size = pcs.proof_size(n_polys) + pcs.vcs.commitment_size();
 		match best_proof_size {
-			None => {
-				best_proof_size = Some(pcs.proof_size(n_polys));
+			Some(current_best) if proof_size >= current_best => {}
+			_ => {
+				best_proof_size = Some(proof_size);
 				best_pcs = Some(pcs);
 			}
-			Some(current_best) => {
-				let proof_size = pcs.proof_size(n_polys);
-				if proof_size < current_best {
-					best_proof_size = Some(proof_size);
-					best_pcs = Some(pcs);
-				}
-			}
 		}
 	}
 
@@ -901,6 +1574,7 @@ where
 mod tests {
 	use super::*;
 	use crate::challenger::HashChallenger;
+	use assert_matches::assert_matches;
 	use binius_field::{
 		BinaryField128b, PackedBinaryField128x1b, PackedBinaryField16x8b, PackedBinaryField1x128b,
 		PackedBinaryField4x32b, PackedBinaryField8x16b,
@@ -947,6 +1621,46 @@ mod tests {
 			.unwrap();
 	}
 
+	#[test]
+	fn test_merkle_cap_commit_prove_verify() {
+		type Packed = PackedBinaryField16x8b;
+
+		let rs_code = ReedSolomonCode::new(5, 2).unwrap();
+		let pcs = <BasicTensorPCS<Packed, Packed, PackedBinaryField1x128b, _, _, _>>::new_using_groestl_merkle_tree_with_cap(4, rs_code, 16, 3).unwrap();
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let evals = repeat_with(|| Packed::random(&mut rng))
+			.take((1 << pcs.n_vars()) / Packed::WIDTH)
+			.collect::<Vec<_>>();
+		let poly = MultilinearExtension::from_values(evals).unwrap();
+		let polys = [poly.to_ref()];
+
+		let (commitment, committed) = pcs.commit(&polys).unwrap();
+		assert_eq!(commitment.0.len(), 8);
+
+		let mut challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+		challenger.observe(commitment.clone());
+		let query = repeat_with(|| challenger.sample())
+			.take(pcs.n_vars())
+			.collect::<Vec<_>>();
+
+		let multilin_query =
+			MultilinearQuery::<PackedBinaryField1x128b>::with_full_query(&query).unwrap();
+		let values = vec![poly.evaluate(&multilin_query).unwrap()];
+
+		let proof = pcs
+			.prove_evaluation(&mut challenger.clone(), &committed, &polys, &query)
+			.unwrap();
+		// The branches stop 3 layers below the root of the 128-leaf Merkle tree
+		assert!(proof.vcs_proof.len() <= 16 * 4);
+		pcs.verify_evaluation(&mut challenger.clone(), &commitment, &query, proof, &values)
+			.unwrap();
+
+		// Cap heights above the tree height are rejected
+		let rs_code = ReedSolomonCode::new(5, 2).unwrap();
+		assert!(<BasicTensorPCS<Packed, Packed, PackedBinaryField1x128b, _, _, _>>::new_using_groestl_merkle_tree_with_cap(4, rs_code, 16, 8).is_err());
+	}
+
 	#[test]
 	fn test_simple_commit_prove_verify_batch_without_error() {
 		type Packed = PackedBinaryField16x8b;
@@ -1183,6 +1897,471 @@ mod tests {
 			.unwrap();
 	}
 
+	#[test]
+	fn test_commit_prove_verify_with_transcript() {
+		let rs_code = ReedSolomonCode::new(5, 2).unwrap();
+		let n_test_queries =
+			calculate_n_test_queries_reed_solomon::<_, BinaryField128b, _>(100, 8, &rs_code)
+				.unwrap();
+		let pcs = <BasicTensorPCS<
+			PackedBinaryField4x32b,
+			PackedBinaryField16x8b,
+			PackedBinaryField1x128b,
+			_,
+			_,
+			_,
+		>>::new_using_groestl_merkle_tree(8, rs_code, n_test_queries)
+		.unwrap();
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let polys = repeat_with(|| {
+			let evals = repeat_with(|| PackedBinaryField4x32b::random(&mut rng))
+				.take((1 << pcs.n_vars()) / PackedBinaryField4x32b::WIDTH)
+				.collect::<Vec<_>>();
+			MultilinearExtension::from_values(evals).unwrap()
+		})
+		.take(3)
+		.collect::<Vec<_>>();
+		let (commitment, committed) = pcs.commit(&polys).unwrap();
+
+		let challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+
+		let mut prover_transcript = TranscriptWriter::new(challenger.clone());
+		prover_transcript.write(&commitment);
+		let query: Vec<BinaryField128b> = repeat_with(|| prover_transcript.sample())
+			.take(pcs.n_vars())
+			.collect();
+		pcs.prove_evaluation_with_transcript(&mut prover_transcript, &committed, &polys, &query)
+			.unwrap();
+		let proof = prover_transcript.finalize();
+
+		let multilin_query =
+			MultilinearQuery::<PackedBinaryField1x128b>::with_full_query(&query).unwrap();
+		let values = polys
+			.iter()
+			.map(|poly| poly.evaluate(&multilin_query).unwrap())
+			.collect::<Vec<_>>();
+
+		let mut verifier_transcript = TranscriptReader::new(challenger.clone(), &proof);
+		let commitment = verifier_transcript.read().unwrap();
+		let verifier_query: Vec<BinaryField128b> = repeat_with(|| verifier_transcript.sample())
+			.take(pcs.n_vars())
+			.collect();
+		assert_eq!(verifier_query, query);
+		pcs.verify_evaluation_with_transcript(
+			&mut verifier_transcript,
+			&commitment,
+			&query,
+			&values,
+		)
+		.unwrap();
+		verifier_transcript.finalize().unwrap();
+
+		// Any change to the opened columns must be rejected
+		let mut tampered_proof = proof.clone();
+		*tampered_proof.last_mut().unwrap() ^= 1;
+		let mut verifier_transcript = TranscriptReader::new(challenger, &tampered_proof);
+		let commitment = verifier_transcript.read().unwrap();
+		for _ in 0..pcs.n_vars() {
+			let _: BinaryField128b = verifier_transcript.sample();
+		}
+		assert!(pcs
+			.verify_evaluation_with_transcript(
+				&mut verifier_transcript,
+				&commitment,
+				&query,
+				&values,
+			)
+			.is_err());
+	}
+
+	#[test]
+	fn test_grinding_bits_out_of_range() {
+		let rs_code = ReedSolomonCode::new(5, 2).unwrap();
+		let pcs = <BasicTensorPCS<
+			PackedBinaryField4x32b,
+			PackedBinaryField16x8b,
+			PackedBinaryField1x128b,
+			_,
+			_,
+			_,
+		>>::new_using_groestl_merkle_tree(8, rs_code, 10)
+		.unwrap();
+		assert_matches!(
+			pcs.with_grinding(MAX_GRINDING_BITS + 1),
+			Err(Error::InvalidGrindingBits { .. })
+		);
+	}
+
+	#[test]
+	fn test_grinding_commit_prove_verify() {
+		let grinding_bits = 8;
+		let rs_code = ReedSolomonCode::new(5, 2).unwrap();
+		let n_test_queries = calculate_n_test_queries_reed_solomon_with_grinding::<
+			_,
+			BinaryField128b,
+			_,
+		>(100, 8, &rs_code, grinding_bits)
+		.unwrap();
+		assert!(
+			n_test_queries
+				< calculate_n_test_queries_reed_solomon::<_, BinaryField128b, _>(100, 8, &rs_code)
+					.unwrap()
+		);
+		let pcs = <BasicTensorPCS<
+			PackedBinaryField4x32b,
+			PackedBinaryField16x8b,
+			PackedBinaryField1x128b,
+			_,
+			_,
+			_,
+		>>::new_using_groestl_merkle_tree(8, rs_code, n_test_queries)
+		.unwrap()
+		.with_grinding(grinding_bits)
+		.unwrap();
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let evals = repeat_with(|| PackedBinaryField4x32b::random(&mut rng))
+			.take((1 << pcs.n_vars()) / PackedBinaryField4x32b::WIDTH)
+			.collect::<Vec<_>>();
+		let poly = MultilinearExtension::from_values(evals).unwrap();
+		let polys = [poly.to_ref()];
+		let (commitment, committed) = pcs.commit(&polys).unwrap();
+
+		let mut challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+		let query = repeat_with(|| challenger.sample())
+			.take(pcs.n_vars())
+			.collect::<Vec<_>>();
+		let multilin_query =
+			MultilinearQuery::<PackedBinaryField1x128b>::with_full_query(&query).unwrap();
+		let values = vec![poly.evaluate(&multilin_query).unwrap()];
+
+		let prove = || {
+			pcs.prove_evaluation(&mut challenger.clone(), &committed, &polys, &query)
+				.unwrap()
+		};
+		let proof = prove();
+		assert!(proof.pow_nonce.is_some());
+
+		// A different nonce either fails the proof-of-work check or changes the column indices
+		let mut tampered_proof = prove();
+		tampered_proof.pow_nonce = tampered_proof.pow_nonce.map(|nonce| nonce + Field::ONE);
+		assert!(pcs
+			.verify_evaluation(
+				&mut challenger.clone(),
+				&commitment,
+				&query,
+				tampered_proof,
+				&values
+			)
+			.is_err());
+
+		// Proofs without a nonce are rejected
+		let mut tampered_proof = prove();
+		tampered_proof.pow_nonce = None;
+		assert_matches!(
+			pcs.verify_evaluation(
+				&mut challenger.clone(),
+				&commitment,
+				&query,
+				tampered_proof,
+				&values
+			),
+			Err(Error::Verification(VerificationError::ProofShape))
+		);
+
+		pcs.verify_evaluation(&mut challenger, &commitment, &query, proof, &values)
+			.unwrap();
+	}
+
+	#[test]
+	fn test_proof_serialization_round_trip() {
+		use crate::serialization::{from_versioned_bytes, to_versioned_bytes};
+
+		let rs_code = ReedSolomonCode::new(5, 2).unwrap();
+		let n_test_queries =
+			calculate_n_test_queries_reed_solomon::<_, BinaryField128b, _>(100, 8, &rs_code)
+				.unwrap();
+		let pcs = <BasicTensorPCS<
+			PackedBinaryField4x32b,
+			PackedBinaryField16x8b,
+			PackedBinaryField1x128b,
+			_,
+			_,
+			_,
+		>>::new_using_groestl_merkle_tree(8, rs_code, n_test_queries)
+		.unwrap();
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let n_polys = 3;
+		let polys = repeat_with(|| {
+			let evals = repeat_with(|| PackedBinaryField4x32b::random(&mut rng))
+				.take((1 << pcs.n_vars()) / PackedBinaryField4x32b::WIDTH)
+				.collect::<Vec<_>>();
+			MultilinearExtension::from_values(evals).unwrap()
+		})
+		.take(n_polys)
+		.collect::<Vec<_>>();
+		let (commitment, committed) = pcs.commit(&polys).unwrap();
+
+		let mut challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+		let query = repeat_with(|| challenger.sample())
+			.take(pcs.n_vars())
+			.collect::<Vec<_>>();
+		let multilin_query =
+			MultilinearQuery::<PackedBinaryField1x128b>::with_full_query(&query).unwrap();
+		let values = polys
+			.iter()
+			.map(|poly| poly.evaluate(&multilin_query).unwrap())
+			.collect::<Vec<_>>();
+
+		let mut prove_challenger = challenger.clone();
+		let proof = pcs
+			.prove_evaluation(&mut prove_challenger, &committed, &polys, &query)
+			.unwrap();
+
+		let proof_bytes = to_versioned_bytes(&proof);
+		let commitment_bytes = to_versioned_bytes(&commitment);
+
+		// The encoding only adds the version, the length prefixes, n_polys and the mask_eval and
+		// pow_nonce flags on top of the estimated proof size, which bounds the size of the Merkle
+		// multi-proof.
+		let framing_size = 2 + 8 + 1 + 1 + 8 + 8 + n_test_queries * (8 + 8 * n_polys) + 8;
+		assert!(proof_bytes.len() <= pcs.proof_size(n_polys) + framing_size);
+
+		let decoded_proof = from_versioned_bytes(&proof_bytes).unwrap();
+		let decoded_commitment = from_versioned_bytes(&commitment_bytes).unwrap();
+		assert_eq!(decoded_commitment, commitment);
+		assert_eq!(to_versioned_bytes(&decoded_proof), proof_bytes);
+
+		let mut verify_challenger = challenger.clone();
+		pcs.verify_evaluation(
+			&mut verify_challenger,
+			&decoded_commitment,
+			&query,
+			decoded_proof,
+			&values,
+		)
+		.unwrap();
+
+		type TestProof =
+			Proof<'static, PackedBinaryField4x32b, PackedBinaryField1x128b, Vec<GroestlDigest>>;
+		assert_matches!(
+			from_versioned_bytes::<TestProof>(&proof_bytes[..proof_bytes.len() - 1]),
+			Err(SerializationError::NotEnoughBytes { .. })
+		);
+	}
+
+	type HidingTestPCS = BasicTensorPCS<
+		PackedBinaryField4x32b,
+		PackedBinaryField16x8b,
+		PackedBinaryField1x128b,
+		ReedSolomonCode<PackedBinaryField16x8b>,
+		HasherDigest<PackedBinaryField4x32b, GroestlHasher<PackedBinaryField4x32b>>,
+		GroestlMerkleTreeVCS,
+	>;
+
+	fn hiding_test_pcs(hiding: bool) -> HidingTestPCS {
+		let rs_code = ReedSolomonCode::new(5, 2).unwrap();
+		let pcs = HidingTestPCS::new_using_groestl_merkle_tree(8, rs_code, 16).unwrap();
+		if hiding {
+			pcs.with_hiding().unwrap()
+		} else {
+			pcs
+		}
+	}
+
+	#[test]
+	fn test_hiding_commit_prove_verify() {
+		use crate::serialization::{from_versioned_bytes, to_versioned_bytes};
+
+		let pcs = hiding_test_pcs(true);
+		assert!(pcs.is_hiding());
+		assert_eq!(pcs.n_vars(), pcs.log_rows() + pcs.log_cols() - 1);
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let n_polys = 3;
+		let polys = repeat_with(|| {
+			let evals = repeat_with(|| PackedBinaryField4x32b::random(&mut rng))
+				.take((1 << pcs.n_vars()) / PackedBinaryField4x32b::WIDTH)
+				.collect::<Vec<_>>();
+			MultilinearExtension::from_values(evals).unwrap()
+		})
+		.take(n_polys)
+		.collect::<Vec<_>>();
+		let (commitment, committed) = pcs.commit_with_rng(&polys, &mut rng).unwrap();
+
+		let mut challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+		let query = repeat_with(|| challenger.sample())
+			.take(pcs.n_vars())
+			.collect::<Vec<_>>();
+		let multilin_query =
+			MultilinearQuery::<PackedBinaryField1x128b>::with_full_query(&query).unwrap();
+		let values = polys
+			.iter()
+			.map(|poly| poly.evaluate(&multilin_query).unwrap())
+			.collect::<Vec<_>>();
+
+		let proof = pcs
+			.prove_evaluation(&mut challenger.clone(), &committed, &polys, &query)
+			.unwrap();
+		assert_eq!(proof.n_polys, n_polys + 1);
+		assert!(proof.mask_eval.is_some());
+
+		let proof_bytes = to_versioned_bytes(&proof);
+		let framing_size = 2 + 8 + 1 + 8 + 8 + 16 * (8 + 8 * (n_polys + 1)) + 8;
+		assert!(proof_bytes.len() <= pcs.proof_size(n_polys) + framing_size);
+
+		let decoded_proof = from_versioned_bytes(&proof_bytes).unwrap();
+		pcs.verify_evaluation(&mut challenger.clone(), &commitment, &query, decoded_proof, &values)
+			.unwrap();
+
+		// An incorrect evaluation must be rejected
+		let mut wrong_values = values.clone();
+		wrong_values[1] += BinaryField128b::ONE;
+		let decoded_proof = from_versioned_bytes(&proof_bytes).unwrap();
+		assert_matches!(
+			pcs.verify_evaluation(
+				&mut challenger.clone(),
+				&commitment,
+				&query,
+				decoded_proof,
+				&wrong_values
+			),
+			Err(Error::Verification(VerificationError::IncorrectEvaluation))
+		);
+
+		// A proof without the mask evaluation must be rejected
+		let mut unmasked_proof: Proof<_, _, _> = from_versioned_bytes(&proof_bytes).unwrap();
+		unmasked_proof.mask_eval = None;
+		assert_matches!(
+			pcs.verify_evaluation(
+				&mut challenger.clone(),
+				&commitment,
+				&query,
+				unmasked_proof,
+				&values
+			),
+			Err(Error::Verification(VerificationError::ProofShape))
+		);
+
+		// The same proof through a transcript
+		let mut prover_transcript = TranscriptWriter::new(challenger.clone());
+		pcs.prove_evaluation_with_transcript(&mut prover_transcript, &committed, &polys, &query)
+			.unwrap();
+		let transcript = prover_transcript.finalize();
+		let mut verifier_transcript = TranscriptReader::new(challenger, &transcript);
+		pcs.verify_evaluation_with_transcript(
+			&mut verifier_transcript,
+			&commitment,
+			&query,
+			&values,
+		)
+		.unwrap();
+		verifier_transcript.finalize().unwrap();
+	}
+
+	#[test]
+	fn test_hiding_parameter_checks() {
+		let rs_code = ReedSolomonCode::new(5, 2).unwrap();
+		let pcs = HidingTestPCS::new_using_groestl_merkle_tree(8, rs_code, 17).unwrap();
+		assert_matches!(pcs.with_hiding().err(), Some(Error::InvalidHidingParameters(_)));
+
+		// Up to half the code dimension of opened columns can be hidden
+		let rs_code = ReedSolomonCode::new(5, 2).unwrap();
+		let pcs = HidingTestPCS::new_using_groestl_merkle_tree(8, rs_code, 16).unwrap();
+		assert!(pcs.with_hiding().unwrap().is_hiding());
+	}
+
+	#[test]
+	fn test_hiding_commit_randomness() {
+		use crate::serialization::to_versioned_bytes;
+
+		let pcs = hiding_test_pcs(true);
+		let mut rng = StdRng::seed_from_u64(0);
+		let evals = repeat_with(|| PackedBinaryField4x32b::random(&mut rng))
+			.take((1 << pcs.n_vars()) / PackedBinaryField4x32b::WIDTH)
+			.collect::<Vec<_>>();
+		let polys = [MultilinearExtension::from_values(evals).unwrap()];
+		// Committing through the trait samples fresh blinding values every time
+		let (commitment_a, _) = pcs.commit(&polys).unwrap();
+		let (commitment_b, _) = pcs.commit(&polys).unwrap();
+		assert_ne!(to_versioned_bytes(&commitment_a), to_versioned_bytes(&commitment_b));
+
+		// Hiding commitments are reproducible from the RNG seed
+		let commit = |seed| {
+			let (commitment, _) = pcs
+				.commit_with_rng(&polys, StdRng::seed_from_u64(seed))
+				.unwrap();
+			to_versioned_bytes(&commitment)
+		};
+		assert_eq!(commit(1), commit(1));
+		assert_ne!(commit(1), commit(2));
+	}
+
+	/// Collect the fraction of set bits in $t'$ and in the opened columns of evaluation proofs for
+	/// the given witness, over several commitments and queries.
+	fn hiding_test_bit_frequencies(
+		pcs: &HidingTestPCS,
+		witness: impl Fn(&mut StdRng) -> PackedBinaryField4x32b,
+	) -> (f64, f64) {
+		use crate::serialization::to_versioned_bytes;
+
+		fn bit_frequency(bytes: &[u8]) -> f64 {
+			let n_ones = bytes.iter().map(|byte| byte.count_ones()).sum::<u32>();
+			n_ones as f64 / (8 * bytes.len()) as f64
+		}
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let mut t_prime_bytes = Vec::new();
+		let mut column_bytes = Vec::new();
+		for _ in 0..4 {
+			let evals = repeat_with(|| witness(&mut rng))
+				.take((1 << pcs.n_vars()) / PackedBinaryField4x32b::WIDTH)
+				.collect::<Vec<_>>();
+			let polys = [MultilinearExtension::from_values(evals).unwrap()];
+			let (_, committed) = pcs.commit_with_rng(&polys, &mut rng).unwrap();
+
+			let mut challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+			let query = repeat_with(|| <BinaryField128b as Field>::random(&mut rng))
+				.take(pcs.n_vars())
+				.collect::<Vec<_>>();
+			let proof = pcs
+				.prove_evaluation(&mut challenger, &committed, &polys, &query)
+				.unwrap();
+
+			t_prime_bytes.extend(to_versioned_bytes(&proof.mixed_t_prime));
+			for cols in &proof.opened_cols {
+				column_bytes.extend(to_versioned_bytes(&cols[0]));
+			}
+		}
+		(bit_frequency(&t_prime_bytes), bit_frequency(&column_bytes))
+	}
+
+	#[test]
+	fn test_hiding_transcript_independent_of_witness() {
+		// Without hiding, the proof for the zero polynomial is all zeros except for the framing
+		let (t_prime_freq, column_freq) =
+			hiding_test_bit_frequencies(&hiding_test_pcs(false), |_| {
+				PackedBinaryField4x32b::zero()
+			});
+		assert!(t_prime_freq < 0.1);
+		assert!(column_freq < 0.1);
+
+		// With hiding, the proof for any witness looks uniformly random
+		let pcs = hiding_test_pcs(true);
+		let witnesses: [&dyn Fn(&mut StdRng) -> PackedBinaryField4x32b; 3] = [
+			&|_| PackedBinaryField4x32b::zero(),
+			&|_| PackedBinaryField4x32b::one(),
+			&|rng| PackedBinaryField4x32b::random(rng),
+		];
+		for witness in witnesses {
+			let (t_prime_freq, column_freq) = hiding_test_bit_frequencies(&pcs, witness);
+			assert!((0.45..0.55).contains(&t_prime_freq), "t' bit frequency {t_prime_freq}");
+			assert!((0.45..0.55).contains(&column_freq), "column bit frequency {column_freq}");
+		}
+	}
+
 	#[test]
 	fn test_proof_size() {
 		let rs_code = ReedSolomonCode::new(5, 2).unwrap();
@@ -1199,8 +2378,8 @@ mod tests {
 		>>::new_using_groestl_merkle_tree(8, rs_code, n_test_queries)
 		.unwrap();
 
-		assert_eq!(pcs.proof_size(1), 182720);
-		assert_eq!(pcs.proof_size(2), 332224);
+		assert_eq!(pcs.proof_size(1), 154080);
+		assert_eq!(pcs.proof_size(2), 303584);
 	}
 
 	#[test]
@@ -1214,7 +2393,7 @@ mod tests {
 			PackedBinaryField8x16b,
 			_,
 			PackedBinaryField1x128b,
-		>(100, 28, 1, 2, false)
+		>(100, 28, 1, 2, false, 0)
 		.unwrap();
 		assert_eq!(pcs.n_vars(), 28);
 		assert_eq!(pcs.log_rows(), 12);
@@ -1230,7 +2409,7 @@ mod tests {
 			PackedBinaryField8x16b,
 			_,
 			PackedBinaryField1x128b,
-		>(100, 28, 8, 2, false)
+		>(100, 28, 8, 2, false, 0)
 		.unwrap();
 		assert_eq!(pcs.n_vars(), 28);
 		assert_eq!(pcs.log_rows(), 10);
@@ -1248,7 +2427,7 @@ mod tests {
 			PackedBinaryField4x32b,
 			_,
 			PackedBinaryField1x128b,
-		>(100, 28, 1, 2, false)
+		>(100, 28, 1, 2, false, 0)
 		.unwrap();
 		assert_eq!(pcs.n_vars(), 28);
 		assert_eq!(pcs.log_rows(), 11);
@@ -1264,10 +2443,38 @@ mod tests {
 			PackedBinaryField4x32b,
 			_,
 			PackedBinaryField1x128b,
-		>(100, 28, 8, 2, false)
+		>(100, 28, 8, 2, false, 0)
 		.unwrap();
 		assert_eq!(pcs.n_vars(), 28);
 		assert_eq!(pcs.log_rows(), 10);
 		assert_eq!(pcs.log_cols(), 18);
+
+		// A cap saves fewer nodes in the deduplicated Merkle multi-proof than it adds to the
+		// commitment, so the search commits to a single root.
+		assert_eq!(pcs.vcs.cap_height(), 0);
+	}
+
+	#[test]
+	fn test_proof_size_optimal_pcs_with_grinding() {
+		let find_pcs = |grinding_bits| {
+			find_proof_size_optimal_pcs::<
+				_,
+				PackedBinaryField128x1b,
+				_,
+				PackedBinaryField8x16b,
+				_,
+				PackedBinaryField8x16b,
+				_,
+				PackedBinaryField1x128b,
+			>(100, 28, 1, 2, false, grinding_bits)
+		};
+
+		let pcs = find_pcs(0).unwrap();
+		let grinding_pcs = find_pcs(16).unwrap();
+		assert_eq!(grinding_pcs.n_vars(), 28);
+		assert_eq!(grinding_pcs.grinding_bits(), 16);
+		assert!(grinding_pcs.n_test_queries() < pcs.n_test_queries());
+
+		assert!(find_pcs(MAX_GRINDING_BITS + 1).is_none());
 	}
 }
@@ -1,6 +1,6 @@
 // Copyright 2024 Ulvetanna Inc.
 
-use crate::polynomial::{CompositionPoly, Error};
+use crate::polynomial::{composition::LinearFactor, CompositionPoly, Error};
 use binius_field::{Field, PackedField};
 
 #[derive(Debug, Copy, Clone)]
@@ -25,4 +25,12 @@ impl<F: Field> CompositionPoly<F> for BivariateProduct {
 	fn binary_tower_level(&self) -> usize {
 		0
 	}
+
+	fn vanishes_with_var(&self, var: usize) -> bool {
+		var < 2
+	}
+
+	fn linear_factors(&self) -> Option<Vec<LinearFactor<F>>> {
+		Some(vec![LinearFactor::variable(0), LinearFactor::variable(1)])
+	}
 }
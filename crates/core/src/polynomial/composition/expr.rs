@@ -0,0 +1,376 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::LinearFactor;
+use crate::{
+	polynomial::{CompositionPoly, Error},
+	serialization::SerializeBytes,
+};
+use binius_field::{Field, PackedField, TowerField};
+use std::{
+	collections::BTreeSet,
+	ops::{Add, Mul, Sub},
+};
+
+/// An arithmetic expression over the variables of a multivariate polynomial.
+///
+/// Expressions are built with the `+`, `-` and `*` operators from variables and constants, for
+/// example `ArithExpr::Var(0) * ArithExpr::Var(1) - ArithExpr::Var(2)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArithExpr<F> {
+	Const(F),
+	Var(usize),
+	Add(Box<ArithExpr<F>>, Box<ArithExpr<F>>),
+	Sub(Box<ArithExpr<F>>, Box<ArithExpr<F>>),
+	Mul(Box<ArithExpr<F>>, Box<ArithExpr<F>>),
+}
+
+impl<F: Field> ArithExpr<F> {
+	/// The minimum number of variables of a polynomial defined by the expression.
+	pub fn n_vars(&self) -> usize {
+		self.vars().last().map_or(0, |&var| var + 1)
+	}
+
+	/// The set of variables occurring in the expression.
+	pub fn vars(&self) -> BTreeSet<usize> {
+		let mut vars = BTreeSet::new();
+		self.collect_vars(&mut vars);
+		vars
+	}
+
+	fn collect_vars(&self, vars: &mut BTreeSet<usize>) {
+		match self {
+			Self::Const(_) => {}
+			Self::Var(var) => {
+				vars.insert(*var);
+			}
+			Self::Add(left, right) | Self::Sub(left, right) | Self::Mul(left, right) => {
+				left.collect_vars(vars);
+				right.collect_vars(vars);
+			}
+		}
+	}
+
+	/// An upper bound on the total degree of the polynomial defined by the expression.
+	pub fn degree(&self) -> usize {
+		match self {
+			Self::Const(_) => 0,
+			Self::Var(_) => 1,
+			Self::Add(left, right) | Self::Sub(left, right) => left.degree().max(right.degree()),
+			Self::Mul(left, right) => left.degree() + right.degree(),
+		}
+	}
+
+	/// Substitute every variable with the variable given by `remap`.
+	pub fn remap_vars(&self, remap: &impl Fn(usize) -> usize) -> Self {
+		match self {
+			Self::Const(value) => Self::Const(*value),
+			Self::Var(var) => Self::Var(remap(*var)),
+			Self::Add(left, right) => {
+				Self::Add(Box::new(left.remap_vars(remap)), Box::new(right.remap_vars(remap)))
+			}
+			Self::Sub(left, right) => {
+				Self::Sub(Box::new(left.remap_vars(remap)), Box::new(right.remap_vars(remap)))
+			}
+			Self::Mul(left, right) => {
+				Self::Mul(Box::new(left.remap_vars(remap)), Box::new(right.remap_vars(remap)))
+			}
+		}
+	}
+
+	/// Map the constants of the expression into a field `FTgt` that `F` embeds into.
+	pub fn convert_field<FTgt: Field + From<F>>(&self) -> ArithExpr<FTgt> {
+		match self {
+			Self::Const(value) => ArithExpr::Const(FTgt::from(*value)),
+			Self::Var(var) => ArithExpr::Var(*var),
+			Self::Add(left, right) => {
+				ArithExpr::Add(Box::new(left.convert_field()), Box::new(right.convert_field()))
+			}
+			Self::Sub(left, right) => {
+				ArithExpr::Sub(Box::new(left.convert_field()), Box::new(right.convert_field()))
+			}
+			Self::Mul(left, right) => {
+				ArithExpr::Mul(Box::new(left.convert_field()), Box::new(right.convert_field()))
+			}
+		}
+	}
+
+	/// Evaluate the expression on packed values.
+	///
+	/// Returns an error if the query is shorter than the number of variables.
+	pub fn evaluate<P: PackedField<Scalar = F>>(&self, query: &[P]) -> Result<P, Error> {
+		let value = match self {
+			Self::Const(value) => P::broadcast(*value),
+			Self::Var(var) => *query
+				.get(*var)
+				.ok_or(Error::IncorrectQuerySize { expected: var + 1 })?,
+			Self::Add(left, right) => left.evaluate(query)? + right.evaluate(query)?,
+			Self::Sub(left, right) => left.evaluate(query)? - right.evaluate(query)?,
+			Self::Mul(left, right) => left.evaluate(query)? * right.evaluate(query)?,
+		};
+		Ok(value)
+	}
+
+	/// Whether the expression evaluates to zero whenever variable `var` is zero.
+	///
+	/// This is a syntactic check, so it may return `false` for expressions that do vanish.
+	pub fn vanishes_with_var(&self, var: usize) -> bool {
+		match self {
+			Self::Const(value) => *value == F::ZERO,
+			Self::Var(other) => *other == var,
+			Self::Add(left, right) | Self::Sub(left, right) => {
+				left.vanishes_with_var(var) && right.vanishes_with_var(var)
+			}
+			Self::Mul(left, right) => left.vanishes_with_var(var) || right.vanishes_with_var(var),
+		}
+	}
+
+	/// The affine factors of the expression, if it is a product of affine forms.
+	///
+	/// This is a syntactic check that decomposes the top-level products, so it may return `None`
+	/// for expressions that do factor.
+	pub fn linear_factors(&self) -> Option<Vec<LinearFactor<F>>> {
+		match self {
+			Self::Mul(left, right) => {
+				let mut factors = left.linear_factors()?;
+				factors.extend(right.linear_factors()?);
+				Some(factors)
+			}
+			_ => Some(vec![self.affine_form()?]),
+		}
+	}
+
+	/// The expression as an affine form, if it has degree at most one.
+	fn affine_form(&self) -> Option<LinearFactor<F>> {
+		let combine = |left: LinearFactor<F>, right: LinearFactor<F>, sign: F| LinearFactor {
+			constant: left.constant + sign * right.constant,
+			terms: left
+				.terms
+				.into_iter()
+				.chain(
+					right
+						.terms
+						.into_iter()
+						.map(|(var, coeff)| (var, sign * coeff)),
+				)
+				.collect(),
+		};
+		match self {
+			Self::Const(value) => Some(LinearFactor {
+				constant: *value,
+				terms: Vec::new(),
+			}),
+			Self::Var(var) => Some(LinearFactor::variable(*var)),
+			Self::Add(left, right) => {
+				Some(combine(left.affine_form()?, right.affine_form()?, F::ONE))
+			}
+			Self::Sub(left, right) => {
+				Some(combine(left.affine_form()?, right.affine_form()?, -F::ONE))
+			}
+			Self::Mul(left, right) => {
+				let (left, right) = (left.affine_form()?, right.affine_form()?);
+				let (scalar, form) = match (left.terms.is_empty(), right.terms.is_empty()) {
+					(true, _) => (left.constant, right),
+					(_, true) => (right.constant, left),
+					_ => return None,
+				};
+				Some(LinearFactor {
+					constant: scalar * form.constant,
+					terms: form
+						.terms
+						.into_iter()
+						.map(|(var, coeff)| (var, scalar * coeff))
+						.collect(),
+				})
+			}
+		}
+	}
+
+	fn all_constants_binary(&self) -> bool {
+		match self {
+			Self::Const(value) => *value == F::ZERO || *value == F::ONE,
+			Self::Var(_) => true,
+			Self::Add(left, right) | Self::Sub(left, right) | Self::Mul(left, right) => {
+				left.all_constants_binary() && right.all_constants_binary()
+			}
+		}
+	}
+}
+
+impl<F> Add for ArithExpr<F> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self {
+		Self::Add(Box::new(self), Box::new(rhs))
+	}
+}
+
+impl<F> Sub for ArithExpr<F> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self {
+		Self::Sub(Box::new(self), Box::new(rhs))
+	}
+}
+
+impl<F> Mul for ArithExpr<F> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self {
+		Self::Mul(Box::new(self), Box::new(rhs))
+	}
+}
+
+/// The canonical encoding of an expression, so that constraints can be bound in a domain tag.
+impl<F: SerializeBytes> SerializeBytes for ArithExpr<F> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		match self {
+			Self::Const(value) => {
+				0u8.serialize(write_buf);
+				value.serialize(write_buf);
+			}
+			Self::Var(var) => {
+				1u8.serialize(write_buf);
+				var.serialize(write_buf);
+			}
+			Self::Add(left, right) => {
+				2u8.serialize(write_buf);
+				left.serialize(write_buf);
+				right.serialize(write_buf);
+			}
+			Self::Sub(left, right) => {
+				3u8.serialize(write_buf);
+				left.serialize(write_buf);
+				right.serialize(write_buf);
+			}
+			Self::Mul(left, right) => {
+				4u8.serialize(write_buf);
+				left.serialize(write_buf);
+				right.serialize(write_buf);
+			}
+		}
+	}
+}
+
+/// A composition polynomial defined by an arithmetic expression.
+#[derive(Debug, Clone)]
+pub struct ExprComposition<F> {
+	n_vars: usize,
+	degree: usize,
+	expr: ArithExpr<F>,
+}
+
+impl<F: Field> ExprComposition<F> {
+	pub fn new(n_vars: usize, expr: ArithExpr<F>) -> Result<Self, Error> {
+		if expr.n_vars() > n_vars {
+			return Err(Error::ArgumentRangeError {
+				arg: format!("variable {} of expression", expr.n_vars() - 1),
+				range: 0..n_vars,
+			});
+		}
+		Ok(Self {
+			n_vars,
+			degree: expr.degree(),
+			expr,
+		})
+	}
+
+	pub fn expr(&self) -> &ArithExpr<F> {
+		&self.expr
+	}
+}
+
+impl<F: TowerField> CompositionPoly<F> for ExprComposition<F> {
+	fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	fn degree(&self) -> usize {
+		self.degree
+	}
+
+	fn evaluate<P: PackedField<Scalar = F>>(&self, query: &[P]) -> Result<P, Error> {
+		if query.len() != self.n_vars {
+			return Err(Error::IncorrectQuerySize {
+				expected: self.n_vars,
+			});
+		}
+		self.expr.evaluate(query)
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		if self.expr.all_constants_binary() {
+			0
+		} else {
+			F::TOWER_LEVEL
+		}
+	}
+
+	fn vanishes_with_var(&self, var: usize) -> bool {
+		self.expr.vanishes_with_var(var)
+	}
+
+	fn linear_factors(&self) -> Option<Vec<LinearFactor<F>>> {
+		self.expr.linear_factors()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use binius_field::BinaryField32b;
+
+	#[test]
+	fn test_expr_composition_evaluate() {
+		type F = BinaryField32b;
+		let c = F::new(7);
+		// X_2 * (X_0 + c) - X_0 * X_0
+		let expr = ArithExpr::Var(2) * (ArithExpr::Var(0) + ArithExpr::Const(c))
+			- ArithExpr::Var(0) * ArithExpr::Var(0);
+		assert_eq!(expr.n_vars(), 3);
+		assert_eq!(expr.degree(), 2);
+
+		let composition = ExprComposition::new(4, expr.clone()).unwrap();
+		assert!(!composition.vanishes_with_var(0));
+		assert!(ExprComposition::new(4, ArithExpr::Var(2) * expr.clone())
+			.unwrap()
+			.vanishes_with_var(2));
+		assert_eq!(composition.binary_tower_level(), F::TOWER_LEVEL);
+
+		let query = [F::new(2), F::new(3), F::new(5), F::new(11)];
+		let expected = query[2] * (query[0] + c) - query[0] * query[0];
+		assert_eq!(composition.evaluate(&query).unwrap(), expected);
+		assert!(composition.evaluate(&query[..3]).is_err());
+
+		let remapped = expr.remap_vars(&|var| var + 1);
+		assert_eq!(remapped.vars().into_iter().collect::<Vec<_>>(), vec![1, 3]);
+
+		assert!(ExprComposition::new(2, expr).is_err());
+	}
+
+	#[test]
+	fn test_expr_composition_linear_factors() {
+		type F = BinaryField32b;
+		let c = F::new(7);
+		// (X_0 + c) * (X_1 - X_2) * c
+		let expr = (ArithExpr::Var(0) + ArithExpr::Const(c))
+			* (ArithExpr::Var(1) - ArithExpr::Var(2))
+			* ArithExpr::Const(c);
+		let composition = ExprComposition::new(3, expr).unwrap();
+		let factors = composition.linear_factors().unwrap();
+		assert_eq!(factors.len(), 3);
+
+		let query = [F::new(2), F::new(3), F::new(5)];
+		let product = factors
+			.iter()
+			.map(|factor| factor.evaluate(&query).unwrap())
+			.product::<F>();
+		assert_eq!(product, composition.evaluate(&query).unwrap());
+
+		// A difference of products is not a product of affine forms
+		let expr = ArithExpr::Var(0) * ArithExpr::Var(1) - ArithExpr::Var(2);
+		assert!(ExprComposition::new(3, expr)
+			.unwrap()
+			.linear_factors()
+			.is_none());
+	}
+}
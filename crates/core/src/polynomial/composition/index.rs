@@ -1,6 +1,6 @@
 // Copyright 2024 Ulvetanna Inc.
 
-use crate::polynomial::{CompositionPoly, Error};
+use crate::polynomial::{composition::LinearFactor, CompositionPoly, Error};
 use binius_field::{Field, PackedField};
 use std::fmt::Debug;
 
@@ -41,6 +41,23 @@ impl<F: Field, C: CompositionPoly<F>, const N: usize> CompositionPoly<F>
 	fn binary_tower_level(&self) -> usize {
 		self.composition.binary_tower_level()
 	}
+
+	fn vanishes_with_var(&self, var: usize) -> bool {
+		self.indices
+			.iter()
+			.enumerate()
+			.any(|(i, &index)| index == var && self.composition.vanishes_with_var(i))
+	}
+
+	fn linear_factors(&self) -> Option<Vec<LinearFactor<F>>> {
+		let mut factors = self.composition.linear_factors()?;
+		for factor in factors.iter_mut() {
+			for (var, _) in factor.terms.iter_mut() {
+				*var = self.indices[*var];
+			}
+		}
+		Some(factors)
+	}
 }
 
 /// A factory helper method to create an [`IndexComposition`] by looking at
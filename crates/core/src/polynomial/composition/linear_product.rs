@@ -0,0 +1,147 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::polynomial::{CompositionPoly, Error};
+use binius_field::{Field, PackedField, TowerField};
+
+/// An affine form $c + \sum_j a_j X_j$ in the variables of a composition polynomial.
+///
+/// The terms are given as `(variable, coefficient)` pairs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinearFactor<F> {
+	pub constant: F,
+	pub terms: Vec<(usize, F)>,
+}
+
+impl<F: Field> LinearFactor<F> {
+	/// The affine form consisting of a single variable.
+	pub fn variable(var: usize) -> Self {
+		Self {
+			constant: F::ZERO,
+			terms: vec![(var, F::ONE)],
+		}
+	}
+
+	pub fn evaluate<P: PackedField<Scalar = F>>(&self, query: &[P]) -> Result<P, Error> {
+		self.terms
+			.iter()
+			.try_fold(P::broadcast(self.constant), |acc, &(var, coeff)| {
+				let value = query
+					.get(var)
+					.ok_or(Error::IncorrectQuerySize { expected: var + 1 })?;
+				Ok(acc + *value * coeff)
+			})
+	}
+}
+
+/// A composition polynomial that is a product of affine forms, $\prod_k L_k(X)$.
+///
+/// Constraints like selectors, range checks over small sets, and S-box relations often take this
+/// shape. The composition exposes its factors through [`CompositionPoly::linear_factors`], which
+/// lets provers evaluate it on a line at many points with one multiplication per factor and point.
+#[derive(Debug, Clone)]
+pub struct LinearProductComposition<F> {
+	n_vars: usize,
+	factors: Vec<LinearFactor<F>>,
+}
+
+impl<F: Field> LinearProductComposition<F> {
+	pub fn new(n_vars: usize, factors: Vec<LinearFactor<F>>) -> Result<Self, Error> {
+		for factor in factors.iter() {
+			if let Some(&(var, _)) = factor.terms.iter().find(|&&(var, _)| var >= n_vars) {
+				return Err(Error::ArgumentRangeError {
+					arg: format!("variable {var} of linear factor"),
+					range: 0..n_vars,
+				});
+			}
+		}
+		Ok(Self { n_vars, factors })
+	}
+
+	pub fn factors(&self) -> &[LinearFactor<F>] {
+		&self.factors
+	}
+}
+
+impl<F: TowerField> CompositionPoly<F> for LinearProductComposition<F> {
+	fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	fn degree(&self) -> usize {
+		self.factors
+			.iter()
+			.filter(|factor| factor.terms.iter().any(|&(_, coeff)| coeff != F::ZERO))
+			.count()
+	}
+
+	fn evaluate<P: PackedField<Scalar = F>>(&self, query: &[P]) -> Result<P, Error> {
+		if query.len() != self.n_vars {
+			return Err(Error::IncorrectQuerySize {
+				expected: self.n_vars,
+			});
+		}
+		self.factors
+			.iter()
+			.try_fold(P::one(), |acc, factor| Ok(acc * factor.evaluate(query)?))
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		let is_binary = |value: F| value == F::ZERO || value == F::ONE;
+		let all_binary = self.factors.iter().all(|factor| {
+			is_binary(factor.constant) && factor.terms.iter().all(|&(_, coeff)| is_binary(coeff))
+		});
+		if all_binary {
+			0
+		} else {
+			F::TOWER_LEVEL
+		}
+	}
+
+	fn vanishes_with_var(&self, var: usize) -> bool {
+		self.factors
+			.iter()
+			.any(|factor| factor.constant == F::ZERO && factor.terms == [(var, F::ONE)])
+	}
+
+	fn linear_factors(&self) -> Option<Vec<LinearFactor<F>>> {
+		Some(self.factors.clone())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use binius_field::BinaryField32b;
+
+	#[test]
+	fn test_linear_product_evaluate() {
+		type F = BinaryField32b;
+		let c = F::new(7);
+		// X_0 * (X_1 + c) * (X_0 + X_2)
+		let composition = LinearProductComposition::new(
+			3,
+			vec![
+				LinearFactor::variable(0),
+				LinearFactor {
+					constant: c,
+					terms: vec![(1, F::ONE)],
+				},
+				LinearFactor {
+					constant: F::ZERO,
+					terms: vec![(0, F::ONE), (2, F::ONE)],
+				},
+			],
+		)
+		.unwrap();
+		assert_eq!(composition.degree(), 3);
+		assert!(composition.vanishes_with_var(0));
+		assert!(!composition.vanishes_with_var(1));
+		assert_eq!(composition.binary_tower_level(), F::TOWER_LEVEL);
+
+		let query = [F::new(2), F::new(3), F::new(5)];
+		let expected = query[0] * (query[1] + c) * (query[0] + query[2]);
+		assert_eq!(composition.evaluate(&query).unwrap(), expected);
+
+		assert!(LinearProductComposition::new(2, vec![LinearFactor::<F>::variable(2)]).is_err());
+	}
+}
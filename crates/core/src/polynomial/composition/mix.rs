@@ -1,5 +1,6 @@
 // Copyright 2024 Ulvetanna Inc.
 
+use super::LinearFactor;
 use crate::polynomial::{CompositionPoly, Error};
 use binius_field::{Field, PackedField, TowerField};
 use std::fmt::Debug;
@@ -33,6 +34,15 @@ pub trait HornerCompositions<F: Field> {
 		query: &[P],
 	) -> Result<Option<P>, Error>;
 	fn evaluate_with_inner_evals(&self, challenge: F, inner_evals: &[F]) -> Result<F, Error>;
+
+	/// Whether no compositions are mixed.
+	fn is_empty(&self) -> bool;
+
+	/// The affine factors of the mixed polynomial.
+	///
+	/// A linear combination of products does not factor in general, so these only exist when a
+	/// single composition is mixed and it has linear factors.
+	fn linear_factors(&self) -> Option<Vec<LinearFactor<F>>>;
 }
 
 impl<F: Field> HornerCompositions<F> for () {
@@ -51,6 +61,14 @@ impl<F: Field> HornerCompositions<F> for () {
 
 		Ok(F::ZERO)
 	}
+
+	fn is_empty(&self) -> bool {
+		true
+	}
+
+	fn linear_factors(&self) -> Option<Vec<LinearFactor<F>>> {
+		None
+	}
 }
 
 impl<F: Field, C, IC> HornerCompositions<F> for (Vec<C>, IC)
@@ -89,6 +107,19 @@ where
 
 		Ok(acc)
 	}
+
+	fn is_empty(&self) -> bool {
+		self.0.is_empty() && self.1.is_empty()
+	}
+
+	fn linear_factors(&self) -> Option<Vec<LinearFactor<F>>> {
+		// The tail is scaled by the challenge only once compositions are mixed in after it
+		match self.0.as_slice() {
+			[] => self.1.linear_factors(),
+			[inner] if self.1.is_empty() => inner.linear_factors(),
+			_ => None,
+		}
+	}
 }
 
 impl<F, IC> CompositionPoly<F> for MixComposition<F, IC>
@@ -114,6 +145,10 @@ where
 	fn binary_tower_level(&self) -> usize {
 		F::TOWER_LEVEL
 	}
+
+	fn linear_factors(&self) -> Option<Vec<LinearFactor<F>>> {
+		self.inner_compositions.linear_factors()
+	}
 }
 
 pub fn empty_mix_composition<F: Field>(n_vars: usize, challenge: F) -> MixComposition<F, ()> {
@@ -166,3 +201,38 @@ impl<F: Field, IC: HornerCompositions<F>> MixComposition<F, IC> {
 			.evaluate_with_inner_evals(self.challenge, inner_evals)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::polynomial::composition::LinearProductComposition;
+	use binius_field::BinaryField32b;
+
+	#[test]
+	fn test_mix_composition_linear_factors() {
+		type F = BinaryField32b;
+		let product = LinearProductComposition::new(
+			2,
+			vec![LinearFactor::variable(0), LinearFactor::variable(1)],
+		)
+		.unwrap();
+		let challenge = F::new(7);
+
+		let single = empty_mix_composition(2, challenge)
+			.include([product.clone()])
+			.unwrap();
+		assert_eq!(single.linear_factors(), Some(product.factors().to_vec()));
+
+		let nested = empty_mix_composition(2, challenge)
+			.include([product.clone()])
+			.unwrap()
+			.include(Vec::<LinearProductComposition<F>>::new())
+			.unwrap();
+		assert_eq!(nested.linear_factors(), Some(product.factors().to_vec()));
+
+		let pair = empty_mix_composition(2, challenge)
+			.include([product.clone(), product])
+			.unwrap();
+		assert_eq!(pair.linear_factors(), None);
+	}
+}
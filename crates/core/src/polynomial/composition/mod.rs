@@ -1,9 +1,13 @@
 // Copyright 2024 Ulvetanna Inc.
 
 pub mod bivariate_product;
+pub mod expr;
 pub mod index;
+pub mod linear_product;
 pub mod mix;
 
 pub use bivariate_product::*;
+pub use expr::*;
 pub use index::*;
+pub use linear_product::*;
 pub use mix::*;
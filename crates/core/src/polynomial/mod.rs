@@ -5,6 +5,7 @@ pub mod error;
 pub mod multilinear;
 pub mod multilinear_extension;
 pub mod multilinear_query;
+pub mod multilinear_sparse;
 pub mod multivariate;
 pub mod transparent;
 pub mod univariate;
@@ -14,5 +15,6 @@ pub use error::*;
 pub use multilinear::*;
 pub use multilinear_extension::*;
 pub use multilinear_query::*;
+pub use multilinear_sparse::*;
 pub use multivariate::*;
 pub use univariate::*;
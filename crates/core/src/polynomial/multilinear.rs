@@ -68,6 +68,15 @@ pub trait MultilinearPoly<P: PackedField>: Debug {
 
 	/// Get a subcube of the boolean hypercube of a given size.
 	fn subcube_evals(&self, vars: usize, index: usize, dst: &mut [P]) -> Result<(), Error>;
+
+	/// The hypercube indices where the polynomial may evaluate to a nonzero value, in increasing
+	/// order.
+	///
+	/// Sparse representations return their nonzero indices so that provers can skip the vertices
+	/// where the polynomial vanishes. `None` means that the polynomial is dense.
+	fn sparse_support(&self) -> Option<&[usize]> {
+		None
+	}
 }
 
 impl<T, P: PackedField> MultilinearPoly<P> for T
@@ -128,4 +137,8 @@ where
 	fn subcube_evals(&self, vars: usize, index: usize, dst: &mut [P]) -> Result<(), Error> {
 		(**self).subcube_evals(vars, index, dst)
 	}
+
+	fn sparse_support(&self) -> Option<&[usize]> {
+		(**self).sparse_support()
+	}
 }
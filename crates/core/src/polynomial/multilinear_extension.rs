@@ -1,6 +1,7 @@
 // Copyright 2023 Ulvetanna Inc.
 
 use super::{error::Error, multilinear::MultilinearPoly, multilinear_query::MultilinearQuery};
+use crate::serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes};
 use binius_field::{
 	packed::{get_packed_slice, iter_packed_slice, set_packed_slice},
 	util::{inner_product_par, inner_product_unchecked},
@@ -301,6 +302,21 @@ impl<'a, P: PackedField> MultilinearExtension<'a, P> {
 	}
 }
 
+impl<P: PackedField + SerializeBytes> SerializeBytes for MultilinearExtension<'_, P> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.evals().serialize(write_buf);
+	}
+}
+
+impl<P: PackedField + DeserializeBytes> DeserializeBytes for MultilinearExtension<'static, P> {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		let evals = Vec::<P>::deserialize(read_buf)?;
+		Self::from_values(evals).map_err(|_| SerializationError::InvalidConstruction {
+			name: "MultilinearExtension",
+		})
+	}
+}
+
 /// A wrapper type for [`MultilinearExtension`] that specializes to a packed extension field type.
 ///
 /// This struct implements `MultilinearPoly` for an extension field of the base field that the
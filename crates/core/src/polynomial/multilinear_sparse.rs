@@ -0,0 +1,398 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	error::Error, multilinear::MultilinearPoly, multilinear_extension::MultilinearExtension,
+	multilinear_query::MultilinearQuery, MultilinearExtensionSpecialized,
+};
+use binius_field::{
+	packed::{get_packed_slice, set_packed_slice},
+	ExtensionField, Field, PackedField,
+};
+use std::{marker::PhantomData, ops::Range, sync::Arc};
+
+/// A multilinear polynomial represented by its nonzero evaluations over the boolean hypercube.
+///
+/// The evaluations are stored as `(index, value)` pairs sorted by hypercube index, and every
+/// vertex that is not listed evaluates to zero. Evaluation and partial evaluation run in time
+/// proportional to the number of nonzero evaluations, and the sumcheck provers use
+/// [`MultilinearPoly::sparse_support`] to skip the hypercube vertices where the polynomial
+/// vanishes. This suits multilinears that are mostly zero, like selector columns, lookup
+/// multiplicities, and memory-checking traces.
+///
+/// The polynomial is defined over the field `F`, and implements [`MultilinearPoly`] for any
+/// packed extension field of `F`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseMultilinearExtension<F: Field, PE = F> {
+	// The number of variables
+	mu: usize,
+	// The hypercube indices of the nonzero evaluations, in increasing order
+	indices: Vec<usize>,
+	// The nonzero evaluations, aligned with `indices`
+	values: Vec<F>,
+	_pe_marker: PhantomData<PE>,
+}
+
+impl<F: Field, PE> SparseMultilinearExtension<F, PE> {
+	/// Create a sparse multilinear from `(index, value)` pairs of hypercube evaluations.
+	///
+	/// The pairs may be given in any order, and zero values are dropped. Each index must be in
+	/// range and appear at most once, whether its value is zero or not.
+	pub fn from_entries(
+		n_vars: usize,
+		entries: impl IntoIterator<Item = (usize, F)>,
+	) -> Result<Self, Error> {
+		if n_vars > 31 {
+			return Err(Error::TooManyVariables);
+		}
+
+		let mut entries = entries.into_iter().collect::<Vec<_>>();
+		entries.sort_unstable_by_key(|&(index, _)| index);
+
+		if let Some(&(index, _)) = entries.last() {
+			if index >= 1 << n_vars {
+				return Err(Error::HypercubeIndexOutOfRange { index });
+			}
+		}
+		if let Some(window) = entries.windows(2).find(|w| w[0].0 == w[1].0) {
+			return Err(Error::DuplicateHypercubeIndex { index: window[0].0 });
+		}
+		entries.retain(|&(_, value)| value != F::ZERO);
+
+		let (indices, values) = entries.into_iter().unzip();
+		Ok(Self {
+			mu: n_vars,
+			indices,
+			values,
+			_pe_marker: PhantomData,
+		})
+	}
+
+	/// Create a sparse multilinear from the evaluations of a dense one, dropping the zeros.
+	pub fn from_dense_values(values: &[F]) -> Result<Self, Error> {
+		if !values.len().is_power_of_two() {
+			return Err(Error::PowerOfTwoLengthRequired);
+		}
+		let n_vars = values.len().trailing_zeros() as usize;
+		Self::from_entries(n_vars, values.iter().copied().enumerate())
+	}
+
+	pub fn n_vars(&self) -> usize {
+		self.mu
+	}
+
+	/// The number of nonzero evaluations over the hypercube.
+	pub fn n_nonzero(&self) -> usize {
+		self.indices.len()
+	}
+
+	/// Iterate over the nonzero evaluations as `(index, value)` pairs, in increasing index order.
+	pub fn iter_nonzero(&self) -> impl Iterator<Item = (usize, F)> + '_ {
+		self.indices
+			.iter()
+			.copied()
+			.zip(self.values.iter().copied())
+	}
+
+	pub fn upcast_arc_dyn<'a>(self) -> Arc<dyn MultilinearPoly<PE> + Send + Sync + 'a>
+	where
+		PE: PackedField + 'a,
+		PE::Scalar: ExtensionField<F>,
+	{
+		Arc::new(self)
+	}
+
+	/// The positions in `indices` of the nonzero evaluations within the given index range.
+	fn entries_in_range(&self, range: Range<usize>) -> Range<usize> {
+		let start = self.indices.partition_point(|&index| index < range.start);
+		let end = self.indices.partition_point(|&index| index < range.end);
+		start..end
+	}
+
+	/// Sum the nonzero evaluations into the buckets selected by `bucket_of`, weighted by the query
+	/// coefficients selected by `query_index_of`.
+	fn evaluate_partial<PE2>(
+		&self,
+		query: &MultilinearQuery<PE2>,
+		bucket_of: impl Fn(usize) -> usize,
+		query_index_of: impl Fn(usize) -> usize,
+	) -> Result<MultilinearExtensionSpecialized<'static, PE2, PE2>, Error>
+	where
+		PE2: PackedField,
+		PE2::Scalar: ExtensionField<F>,
+	{
+		if self.mu < query.n_vars() {
+			return Err(Error::IncorrectQuerySize { expected: self.mu });
+		}
+		let new_n_vars = self.mu - query.n_vars();
+		if new_n_vars < PE2::LOG_WIDTH {
+			return Err(Error::IncorrectQuerySize { expected: self.mu });
+		}
+
+		let query_expansion = query.expansion();
+		let mut result_evals = vec![PE2::default(); 1 << (new_n_vars - PE2::LOG_WIDTH)];
+		for (index, value) in self.iter_nonzero() {
+			let bucket = bucket_of(index);
+			let basis_eval = get_packed_slice(query_expansion, query_index_of(index));
+			let result_eval = get_packed_slice(&result_evals, bucket) + basis_eval * value;
+			set_packed_slice(&mut result_evals, bucket, result_eval);
+		}
+
+		MultilinearExtension::from_values(result_evals).map(MultilinearExtensionSpecialized::from)
+	}
+}
+
+impl<F, PE> MultilinearPoly<PE> for SparseMultilinearExtension<F, PE>
+where
+	F: Field,
+	PE: PackedField,
+	PE::Scalar: ExtensionField<F>,
+{
+	fn n_vars(&self) -> usize {
+		self.mu
+	}
+
+	fn extension_degree(&self) -> usize {
+		<PE::Scalar as ExtensionField<F>>::DEGREE
+	}
+
+	fn evaluate_on_hypercube(&self, index: usize) -> Result<PE::Scalar, Error> {
+		if index >= 1 << self.mu {
+			return Err(Error::HypercubeIndexOutOfRange { index });
+		}
+		let eval = match self.indices.binary_search(&index) {
+			Ok(position) => self.values[position].into(),
+			Err(_) => PE::Scalar::ZERO,
+		};
+		Ok(eval)
+	}
+
+	fn evaluate_on_hypercube_and_scale(
+		&self,
+		index: usize,
+		scalar: PE::Scalar,
+	) -> Result<PE::Scalar, Error> {
+		if index >= 1 << self.mu {
+			return Err(Error::HypercubeIndexOutOfRange { index });
+		}
+		let eval = match self.indices.binary_search(&index) {
+			Ok(position) => scalar * self.values[position],
+			Err(_) => PE::Scalar::ZERO,
+		};
+		Ok(eval)
+	}
+
+	fn evaluate(&self, query: &MultilinearQuery<PE>) -> Result<PE::Scalar, Error> {
+		if self.mu != query.n_vars() {
+			return Err(Error::IncorrectQuerySize { expected: self.mu });
+		}
+		let query_expansion = query.expansion();
+		let eval = self
+			.iter_nonzero()
+			.map(|(index, value)| get_packed_slice(query_expansion, index) * value)
+			.sum();
+		Ok(eval)
+	}
+
+	fn evaluate_partial_low(
+		&self,
+		query: &MultilinearQuery<PE>,
+	) -> Result<MultilinearExtensionSpecialized<'static, PE, PE>, Error> {
+		let n_query_vars = query.n_vars();
+		let low_mask = (1 << n_query_vars) - 1;
+		self.evaluate_partial(query, |index| index >> n_query_vars, |index| index & low_mask)
+	}
+
+	fn evaluate_partial_high(
+		&self,
+		query: &MultilinearQuery<PE>,
+	) -> Result<MultilinearExtensionSpecialized<'static, PE, PE>, Error> {
+		let new_n_vars = self.mu.saturating_sub(query.n_vars());
+		let low_mask = (1 << new_n_vars) - 1;
+		self.evaluate_partial(query, |index| index & low_mask, |index| index >> new_n_vars)
+	}
+
+	fn evaluate_subcube(
+		&self,
+		index: usize,
+		query: &MultilinearQuery<PE>,
+	) -> Result<PE::Scalar, Error> {
+		let n_query_vars = query.n_vars();
+		if n_query_vars > self.mu {
+			return Err(Error::ArgumentRangeError {
+				arg: "n_vars".into(),
+				range: 0..self.mu + 1,
+			});
+		}
+		let max_index = 1 << (self.mu - n_query_vars);
+		if index >= max_index {
+			return Err(Error::ArgumentRangeError {
+				arg: "index".into(),
+				range: 0..max_index,
+			});
+		}
+
+		let query_expansion = query.expansion();
+		let low_mask = (1 << n_query_vars) - 1;
+		let positions =
+			self.entries_in_range((index << n_query_vars)..((index + 1) << n_query_vars));
+		let eval = self.indices[positions.clone()]
+			.iter()
+			.zip(&self.values[positions])
+			.map(|(&eval_index, &value)| {
+				get_packed_slice(query_expansion, eval_index & low_mask) * value
+			})
+			.sum();
+		Ok(eval)
+	}
+
+	fn subcube_evals(&self, vars: usize, index: usize, dst: &mut [PE]) -> Result<(), Error> {
+		if vars > self.mu {
+			return Err(Error::ArgumentRangeError {
+				arg: "vars".to_string(),
+				range: 0..self.mu + 1,
+			});
+		}
+		if dst.len() * PE::WIDTH != 1 << vars {
+			return Err(Error::ArgumentRangeError {
+				arg: "dst.len()".to_string(),
+				range: (1 << vars) / PE::WIDTH..(1 << vars) / PE::WIDTH + 1,
+			});
+		}
+		if index >= 1 << (self.mu - vars) {
+			return Err(Error::ArgumentRangeError {
+				arg: "index".to_string(),
+				range: 0..(1 << (self.mu - vars)),
+			});
+		}
+
+		dst.fill(PE::default());
+		let offset = index << vars;
+		let positions = self.entries_in_range(offset..((index + 1) << vars));
+		for (&eval_index, &value) in self.indices[positions.clone()]
+			.iter()
+			.zip(&self.values[positions])
+		{
+			set_packed_slice(dst, eval_index - offset, value.into());
+		}
+		Ok(())
+	}
+
+	fn sparse_support(&self) -> Option<&[usize]> {
+		Some(&self.indices)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use binius_field::{BinaryField128b, BinaryField32b, PackedBinaryField4x32b};
+	use rand::{rngs::StdRng, Rng, SeedableRng};
+	use std::iter::repeat_with;
+
+	type F = BinaryField32b;
+	type FE = BinaryField128b;
+
+	fn random_sparse_values(rng: &mut StdRng, n_vars: usize) -> Vec<F> {
+		(0..1 << n_vars)
+			.map(|_| {
+				if rng.gen_bool(0.2) {
+					F::random(&mut *rng)
+				} else {
+					F::ZERO
+				}
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_sparse_matches_dense() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let n_vars = 8;
+
+		let values = random_sparse_values(&mut rng, n_vars);
+		let sparse = SparseMultilinearExtension::<F, FE>::from_dense_values(&values).unwrap();
+		let dense = MultilinearExtension::from_values(values.clone())
+			.unwrap()
+			.specialize::<FE>();
+		assert_eq!(sparse.n_nonzero(), values.iter().filter(|&&v| v != F::ZERO).count());
+
+		for index in 0..1 << n_vars {
+			assert_eq!(
+				sparse.evaluate_on_hypercube(index).unwrap(),
+				dense.evaluate_on_hypercube(index).unwrap()
+			);
+		}
+
+		let point = repeat_with(|| FE::random(&mut rng))
+			.take(n_vars)
+			.collect::<Vec<_>>();
+		let query = MultilinearQuery::<FE>::with_full_query(&point).unwrap();
+		assert_eq!(sparse.evaluate(&query).unwrap(), dense.evaluate(&query).unwrap());
+
+		for n_query_vars in 0..=n_vars {
+			let query = MultilinearQuery::<FE>::with_full_query(&point[..n_query_vars]).unwrap();
+			assert_eq!(
+				sparse.evaluate_partial_low(&query).unwrap(),
+				dense.evaluate_partial_low(&query).unwrap()
+			);
+			assert_eq!(
+				sparse.evaluate_partial_high(&query).unwrap(),
+				dense.evaluate_partial_high(&query).unwrap()
+			);
+			for index in 0..1 << (n_vars - n_query_vars) {
+				assert_eq!(
+					sparse.evaluate_subcube(index, &query).unwrap(),
+					dense.evaluate_subcube(index, &query).unwrap()
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn test_subcube_evals_matches_dense() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let n_vars = 6;
+
+		let values = random_sparse_values(&mut rng, n_vars);
+		let sparse =
+			SparseMultilinearExtension::<F, PackedBinaryField4x32b>::from_dense_values(&values)
+				.unwrap();
+		let dense = MultilinearExtension::from_values(values)
+			.unwrap()
+			.specialize::<PackedBinaryField4x32b>();
+
+		let vars = 3;
+		for index in 0..1 << (n_vars - vars) {
+			let mut sparse_dst = vec![PackedBinaryField4x32b::default(); 2];
+			let mut dense_dst = vec![PackedBinaryField4x32b::default(); 2];
+			sparse.subcube_evals(vars, index, &mut sparse_dst).unwrap();
+			dense.subcube_evals(vars, index, &mut dense_dst).unwrap();
+			assert_eq!(sparse_dst, dense_dst);
+		}
+	}
+
+	#[test]
+	fn test_from_entries_rejects_invalid_indices() {
+		let one = F::ONE;
+		assert!(matches!(
+			SparseMultilinearExtension::<F>::from_entries(2, [(4, one)]),
+			Err(Error::HypercubeIndexOutOfRange { index: 4 })
+		));
+		assert!(matches!(
+			SparseMultilinearExtension::<F>::from_entries(2, [(1, one), (1, one)]),
+			Err(Error::DuplicateHypercubeIndex { index: 1 })
+		));
+		assert!(matches!(
+			SparseMultilinearExtension::<F>::from_entries(2, [(1, F::ZERO), (1, one)]),
+			Err(Error::DuplicateHypercubeIndex { index: 1 })
+		));
+		assert!(matches!(
+			SparseMultilinearExtension::<F>::from_entries(2, [(4, F::ZERO)]),
+			Err(Error::HypercubeIndexOutOfRange { index: 4 })
+		));
+
+		let sparse =
+			SparseMultilinearExtension::<F>::from_entries(2, [(3, one), (1, F::ZERO)]).unwrap();
+		assert_eq!(sparse.iter_nonzero().collect::<Vec<_>>(), vec![(3, one)]);
+	}
+}
@@ -1,7 +1,8 @@
 // Copyright 2023 Ulvetanna Inc.
 
 use super::{
-	error::Error, multilinear_query::MultilinearQuery, MultilinearExtension, MultilinearPoly,
+	composition::LinearFactor, error::Error, multilinear_query::MultilinearQuery,
+	MultilinearExtension, MultilinearPoly,
 };
 use binius_field::{ExtensionField, Field, PackedField, TowerField};
 use std::{borrow::Borrow, fmt::Debug, marker::PhantomData, sync::Arc};
@@ -54,8 +55,51 @@ pub trait MultivariatePoly<F>: Debug + Send + Sync {
 	/// Evaluate the polynomial at a point in the extension field.
 	fn evaluate(&self, query: &[F]) -> Result<F, Error>;
 
+	/// Evaluate the polynomial at the hypercube vertex whose coordinates are the bits of `index`,
+	/// low bit first.
+	///
+	/// The default implementation calls [`Self::evaluate`] at the vertex. Polynomials that are
+	/// represented by their hypercube evaluations override it with a direct lookup.
+	fn evaluate_on_hypercube(&self, index: usize) -> Result<F, Error>
+	where
+		F: Field,
+	{
+		let n_vars = self.n_vars();
+		if index >= 1 << n_vars {
+			return Err(Error::HypercubeIndexOutOfRange { index });
+		}
+		let query = (0..n_vars)
+			.map(|i| {
+				if (index >> i) & 1 == 1 {
+					F::ONE
+				} else {
+					F::ZERO
+				}
+			})
+			.collect::<Vec<_>>();
+		self.evaluate(&query)
+	}
+
 	/// Returns the maximum binary tower level of all constants in the arithmetic expression.
 	fn binary_tower_level(&self) -> usize;
+
+	/// Whether the polynomial evaluates to zero whenever variable `var` is zero.
+	///
+	/// This holds when the variable divides the polynomial, like a selector multiplying a
+	/// constraint. Sumcheck provers use it to skip the hypercube vertices where a sparse
+	/// multilinear vanishes. The default implementation conservatively returns `false`.
+	fn vanishes_with_var(&self, _var: usize) -> bool {
+		false
+	}
+
+	/// The affine factors of the polynomial, if it is a product of affine forms in its variables.
+	///
+	/// On a line through two points, each factor restricts to a linear univariate, so provers can
+	/// evaluate the polynomial at many points on the line with one multiplication per factor and
+	/// point. The default implementation returns `None`.
+	fn linear_factors(&self) -> Option<Vec<LinearFactor<F>>> {
+		None
+	}
 }
 
 /// A multivariate polynomial that defines a composition of `MultilinearComposite`.
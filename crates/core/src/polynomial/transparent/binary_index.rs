@@ -0,0 +1,140 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::polynomial::{Error, MultilinearExtension, MultivariatePoly};
+use binius_field::{PackedField, TowerField};
+use std::marker::PhantomData;
+
+/// Maps each hypercube vertex to its index, encoded as a binary field element.
+///
+/// The binary digits of the index $i = \sum_k 2^k x_k$ are taken as the coordinates of the field
+/// element in the $\mathbb{F}_2$-basis of the tower, so that the multilinear extension is the
+/// $\mathbb{F}_2$-linear function
+/// * $\sum_k x_k \beta_k$
+/// where $\beta_k$ is the $k$-th $\mathcal{T}_0$-basis element. Distinct vertices map to distinct
+/// field elements.
+#[derive(Debug, Copy, Clone)]
+pub struct BinaryIndex<F: TowerField> {
+	n_vars: usize,
+	_marker: PhantomData<F>,
+}
+
+impl<F: TowerField> BinaryIndex<F> {
+	pub fn new(n_vars: usize) -> Result<Self, Error> {
+		if n_vars > F::N_BITS {
+			return Err(Error::ArgumentRangeError {
+				arg: "n_vars".into(),
+				range: 0..F::N_BITS + 1,
+			});
+		}
+		Ok(Self {
+			n_vars,
+			_marker: Default::default(),
+		})
+	}
+
+	pub fn multilinear_extension<P: PackedField<Scalar = F>>(
+		&self,
+	) -> Result<MultilinearExtension<'static, P>, Error> {
+		let basis = self.basis()?;
+		let n_values = (1 << self.n_vars) / P::WIDTH;
+		let values = (0..n_values)
+			.map(|i| {
+				let mut packed_value = P::default();
+				for j in 0..P::WIDTH {
+					let index = i * P::WIDTH + j;
+					let value = basis
+						.iter()
+						.enumerate()
+						.filter(|(k, _)| (index >> k) & 1 == 1)
+						.map(|(_, &basis_elt)| basis_elt)
+						.sum();
+					packed_value.set(j, value);
+				}
+				packed_value
+			})
+			.collect();
+
+		MultilinearExtension::from_values(values)
+	}
+
+	fn basis(&self) -> Result<Vec<F>, Error> {
+		let basis = (0..self.n_vars)
+			.map(|k| <F as TowerField>::basis(0, k))
+			.collect::<Result<_, _>>()?;
+		Ok(basis)
+	}
+}
+
+impl<F: TowerField> MultivariatePoly<F> for BinaryIndex<F> {
+	fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	fn degree(&self) -> usize {
+		1
+	}
+
+	fn evaluate(&self, query: &[F]) -> Result<F, Error> {
+		if query.len() != self.n_vars {
+			return Err(Error::IncorrectQuerySize {
+				expected: self.n_vars,
+			});
+		}
+
+		let result = query
+			.iter()
+			.zip(self.basis()?)
+			.map(|(&query_k, basis_elt)| query_k * basis_elt)
+			.sum();
+		Ok(result)
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		self.n_vars.next_power_of_two().ilog2() as usize
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::polynomial::multilinear_query::MultilinearQuery;
+	use binius_field::{BinaryField128b, BinaryField32b, Field, PackedBinaryField4x32b};
+	use rand::{rngs::StdRng, SeedableRng};
+	use std::iter::repeat_with;
+
+	#[test]
+	fn test_hypercube_values() {
+		type P = PackedBinaryField4x32b;
+		let index = BinaryIndex::<BinaryField32b>::new(5).unwrap();
+		let mle = index.multilinear_extension::<P>().unwrap();
+		for i in 0..32 {
+			assert_eq!(mle.evaluate_on_hypercube(i).unwrap(), BinaryField32b::new(i as u32));
+		}
+	}
+
+	#[test]
+	fn test_consistency() {
+		type F = BinaryField128b;
+		let mut rng = StdRng::seed_from_u64(0);
+
+		for n_vars in [0, 1, 3, 7] {
+			let index = BinaryIndex::<F>::new(n_vars).unwrap();
+			let challenge = repeat_with(|| <F as Field>::random(&mut rng))
+				.take(n_vars)
+				.collect::<Vec<_>>();
+
+			let eval1 = index.evaluate(&challenge).unwrap();
+			let multilin_query = MultilinearQuery::<F>::with_full_query(&challenge).unwrap();
+			let mle = index.multilinear_extension::<F>().unwrap();
+			let eval2 = mle.evaluate(&multilin_query).unwrap();
+
+			assert_eq!(eval1, eval2);
+			assert!(index.binary_tower_level() <= 3);
+		}
+	}
+
+	#[test]
+	fn test_too_many_variables() {
+		assert!(BinaryIndex::<BinaryField32b>::new(33).is_err());
+	}
+}
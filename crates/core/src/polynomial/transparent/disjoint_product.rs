@@ -34,6 +34,17 @@ where
 		Ok(eval0 * eval1)
 	}
 
+	fn evaluate_on_hypercube(&self, index: usize) -> Result<F, Error> {
+		let p0_vars = self.0.n_vars();
+		if index >= 1 << self.n_vars() {
+			return Err(Error::HypercubeIndexOutOfRange { index });
+		}
+
+		let eval0 = self.0.evaluate_on_hypercube(index & ((1 << p0_vars) - 1))?;
+		let eval1 = self.1.evaluate_on_hypercube(index >> p0_vars)?;
+		Ok(eval0 * eval1)
+	}
+
 	fn binary_tower_level(&self) -> usize {
 		self.0.binary_tower_level().max(self.1.binary_tower_level())
 	}
@@ -1,5 +1,6 @@
 // Copyright 2024 Ulvetanna Inc.
 
+pub mod binary_index;
 pub mod constant;
 pub mod disjoint_product;
 pub mod eq_ind;
@@ -33,6 +33,10 @@ where
 		self.0.evaluate(&query)
 	}
 
+	fn evaluate_on_hypercube(&self, index: usize) -> Result<F, Error> {
+		self.0.evaluate_on_hypercube(index)
+	}
+
 	fn binary_tower_level(&self) -> usize {
 		F::TOWER_LEVEL - self.0.extension_degree().ilog2() as usize
 	}
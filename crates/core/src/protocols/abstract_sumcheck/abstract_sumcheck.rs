@@ -2,7 +2,11 @@
 
 use binius_field::Field;
 
-use crate::{oracle::CompositePolyOracle, protocols::evalcheck::EvalcheckClaim};
+use crate::{
+	oracle::CompositePolyOracle,
+	protocols::evalcheck::EvalcheckClaim,
+	serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes},
+};
 
 #[derive(Debug, Clone)]
 pub struct AbstractSumcheckRound<F> {
@@ -20,6 +24,32 @@ pub struct AbstractSumcheckProof<F> {
 	pub rounds: Vec<AbstractSumcheckRound<F>>,
 }
 
+impl<F: SerializeBytes> SerializeBytes for AbstractSumcheckRound<F> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.coeffs.serialize(write_buf);
+	}
+}
+
+impl<F: DeserializeBytes> DeserializeBytes for AbstractSumcheckRound<F> {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		let coeffs = Vec::deserialize(read_buf)?;
+		Ok(Self { coeffs })
+	}
+}
+
+impl<F: SerializeBytes> SerializeBytes for AbstractSumcheckProof<F> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.rounds.serialize(write_buf);
+	}
+}
+
+impl<F: DeserializeBytes> DeserializeBytes for AbstractSumcheckProof<F> {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		let rounds = Vec::deserialize(read_buf)?;
+		Ok(Self { rounds })
+	}
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct AbstractSumcheckRoundClaim<F: Field> {
 	pub partial_point: Vec<F>,
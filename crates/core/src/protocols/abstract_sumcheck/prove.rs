@@ -2,14 +2,14 @@
 
 use super::{AbstractSumcheckProver, AbstractSumcheckRound};
 use crate::{
-	challenger::{CanObserve, CanSample},
+	challenger::{CanObserve, CanSample, DomainSeparator},
 	polynomial::{
 		Error as PolynomialError, MultilinearExtensionSpecialized, MultilinearPoly,
-		MultilinearQuery,
+		MultilinearQuery, SparseMultilinearExtension,
 	},
 	protocols::evalcheck::EvalcheckClaim,
 };
-use binius_field::{Field, PackedField};
+use binius_field::{packed::get_packed_slice, Field, PackedField};
 use rayon::prelude::*;
 use std::{borrow::Borrow, cmp};
 
@@ -25,6 +25,11 @@ enum SumcheckMultilinear<P: PackedField, M> {
 	Folded {
 		large_field_folded_multilin: MultilinearExtensionSpecialized<'static, P, P>,
 	},
+	/// Large field polynomial with a sparse support - halved in size each round, keeping only the
+	/// nonzero evaluations
+	SparseFolded {
+		large_field_folded_multilin: SparseMultilinearExtension<P::Scalar, P>,
+	},
 }
 
 /// Parallel fold state, consisting of scratch area and result accumulator.
@@ -79,6 +84,17 @@ pub trait AbstractSumcheckEvaluator<F: Field>: Sync {
 		round_evals: &mut [F],
 	);
 
+	/// Whether the round evaluations are unaffected by the hypercube vertices where the
+	/// multilinear at `multilinear_index` evaluates to zero at both 0 and 1.
+	///
+	/// [`ProverState::calculate_sparse_round_coeffs`] skips such vertices when the multilinear has
+	/// a [`MultilinearPoly::sparse_support`]. This may only return `true` if
+	/// [`Self::process_vertex`] has no effect on the round evaluations at those vertices. The
+	/// default implementation processes every vertex.
+	fn skips_zeros_of(&self, _multilinear_index: usize) -> bool {
+		false
+	}
+
 	/// Given evaluations of the round polynomial, interpolate and return monomial coefficients
 	///
 	/// ## Arguments
@@ -117,6 +133,14 @@ pub trait AbstractSumcheckEvaluator<F: Field>: Sync {
 ///
 /// NB. Note that `switchover=0` does not make sense, as first round is never folded.//
 ///
+/// Multilinears with a [`MultilinearPoly::sparse_support`] stay sparse after switchover, so that
+/// folding them takes time proportional to their number of nonzero evaluations. If the evaluator
+/// allows it through [`AbstractSumcheckEvaluator::skips_zeros_of`],
+/// [`Self::calculate_sparse_round_coeffs`] only visits the hypercube vertices within their
+/// support. When every multilinear is sparse, or the dense ones have been folded, the work per
+/// round then scales with the number of nonzero evaluations rather than with the size of the
+/// hypercube.
+///
 /// [Gruen24]: https://eprint.iacr.org/2024/108
 #[derive(Debug)]
 pub struct ProverState<PW, M>
@@ -124,7 +148,10 @@ where
 	PW: PackedField,
 	M: MultilinearPoly<PW> + Sync,
 {
+	n_vars: usize,
 	multilinears: Vec<SumcheckMultilinear<PW, M>>,
+	// Nonzero hypercube indices of the sparse multilinears, kept after switchover
+	supports: Vec<Option<Vec<usize>>>,
 	query: Option<MultilinearQuery<PW>>,
 	round: usize,
 }
@@ -140,6 +167,7 @@ where
 		switchover_fn: impl Fn(usize) -> usize,
 	) -> Result<Self, PolynomialError> {
 		let mut max_query_vars = 1;
+		let mut supports = Vec::new();
 		let multilinears = multilinears
 			.into_iter()
 			.map(|small_field_multilin| {
@@ -152,6 +180,7 @@ where
 
 				let switchover = switchover_fn(small_field_multilin.extension_degree());
 				max_query_vars = cmp::max(max_query_vars, switchover);
+				supports.push(small_field_multilin.sparse_support().map(<[usize]>::to_vec));
 				Ok(SumcheckMultilinear::Transparent {
 					switchover,
 					small_field_multilin,
@@ -162,7 +191,9 @@ where
 		let query = Some(MultilinearQuery::new(max_query_vars)?);
 
 		Ok(Self {
+			n_vars: n_rounds,
 			multilinears,
+			supports,
 			query,
 			round: 0,
 		})
@@ -210,12 +241,26 @@ where
 						);
 						// At switchover, perform inner products in large field and save them
 						// in a newly created MLE.
-						let large_field_folded_multilin = small_field_multilin
-							.borrow()
-							.evaluate_partial_low(query_ref)?;
-
-						*multilin = SumcheckMultilinear::Folded {
-							large_field_folded_multilin,
+						*multilin = match small_field_multilin.sparse_support() {
+							Some(support) => {
+								let entries = support
+									.iter()
+									.map(|&i| {
+										Ok((i, small_field_multilin.evaluate_on_hypercube(i)?))
+									})
+									.collect::<Result<Vec<_>, PolynomialError>>()?;
+								SumcheckMultilinear::SparseFolded {
+									large_field_folded_multilin: fold_sparse(
+										small_field_multilin.n_vars(),
+										entries,
+										query_ref,
+									)?,
+								}
+							}
+							None => SumcheckMultilinear::Folded {
+								large_field_folded_multilin: small_field_multilin
+									.evaluate_partial_low(query_ref)?,
+							},
 						};
 					} else {
 						any_transparent_left = true;
@@ -229,6 +274,16 @@ where
 					*large_field_folded_multilin =
 						large_field_folded_multilin.evaluate_partial_low(&partial_query)?;
 				}
+
+				SumcheckMultilinear::SparseFolded {
+					ref mut large_field_folded_multilin,
+				} => {
+					*large_field_folded_multilin = fold_sparse(
+						large_field_folded_multilin.n_vars(),
+						large_field_folded_multilin.iter_nonzero(),
+						&partial_query,
+					)?;
+				}
 			}
 		}
 
@@ -246,6 +301,44 @@ where
 		evaluator: impl AbstractSumcheckEvaluator<PW::Scalar, VertexState = S>,
 		current_round_sum: PW::Scalar,
 		vertex_state_iterator: impl IndexedParallelIterator<Item = S>,
+	) -> Result<Vec<PW::Scalar>, PolynomialError> {
+		self.calculate_round_coeffs_with_vertices(
+			evaluator,
+			current_round_sum,
+			vertex_state_iterator.enumerate(),
+		)
+	}
+
+	/// Compute the sum of the partial polynomial evaluations over the hypercube, visiting only the
+	/// vertices within the supports of the sparse multilinears whose zeros the evaluator skips.
+	///
+	/// The evaluator keeps no per-vertex state, since the skipped vertices are never visited.
+	pub fn calculate_sparse_round_coeffs(
+		&self,
+		evaluator: impl AbstractSumcheckEvaluator<PW::Scalar, VertexState = ()>,
+		current_round_sum: PW::Scalar,
+	) -> Result<Vec<PW::Scalar>, PolynomialError> {
+		match self.active_vertices(&evaluator) {
+			Some(active_vertices) => self.calculate_round_coeffs_with_vertices(
+				evaluator,
+				current_round_sum,
+				active_vertices.into_par_iter().map(|i| (i, ())),
+			),
+			None => self.calculate_round_coeffs_with_vertices(
+				evaluator,
+				current_round_sum,
+				(0..1 << (self.n_vars - self.round - 1))
+					.into_par_iter()
+					.map(|i| (i, ())),
+			),
+		}
+	}
+
+	fn calculate_round_coeffs_with_vertices<S>(
+		&self,
+		evaluator: impl AbstractSumcheckEvaluator<PW::Scalar, VertexState = S>,
+		current_round_sum: PW::Scalar,
+		vertex_iterator: impl ParallelIterator<Item = (usize, S)>,
 	) -> Result<Vec<PW::Scalar>, PolynomialError> {
 		// Extract multilinears & round
 		let &Self {
@@ -262,16 +355,19 @@ where
 		let any_folded = multilinears
 			.iter()
 			.any(|ml| matches!(ml, SumcheckMultilinear::Folded { .. }));
+		let any_sparse_folded = multilinears
+			.iter()
+			.any(|ml| matches!(ml, SumcheckMultilinear::SparseFolded { .. }));
 
-		match (any_transparent, any_folded) {
-			(true, false) => {
+		match (any_transparent, any_folded, any_sparse_folded) {
+			(true, false, false) => {
 				if round == 0 {
 					// All transparent, first round - direct sampling
 					self.calculate_round_coeffs_helper(
 						Self::only_transparent,
 						Self::direct_sample,
 						evaluator,
-						vertex_state_iterator,
+						vertex_iterator,
 						current_round_sum,
 					)
 				} else {
@@ -280,18 +376,18 @@ where
 						Self::only_transparent,
 						|multilin, i| self.subcube_inner_product(multilin, i),
 						evaluator,
-						vertex_state_iterator,
+						vertex_iterator,
 						current_round_sum,
 					)
 				}
 			}
 
 			// All folded - direct sampling
-			(false, true) => self.calculate_round_coeffs_helper(
+			(false, true, false) => self.calculate_round_coeffs_helper(
 				Self::only_folded,
 				Self::direct_sample,
 				evaluator,
-				vertex_state_iterator,
+				vertex_iterator,
 				current_round_sum,
 			),
 
@@ -307,14 +403,47 @@ where
 					SumcheckMultilinear::Folded {
 						large_field_folded_multilin,
 					} => Self::direct_sample(large_field_folded_multilin, i),
+
+					SumcheckMultilinear::SparseFolded {
+						large_field_folded_multilin,
+					} => Self::direct_sample(large_field_folded_multilin, i),
 				},
 				evaluator,
-				vertex_state_iterator,
+				vertex_iterator,
 				current_round_sum,
 			),
 		}
 	}
 
+	/// The indices of the hypercube vertices in the current round that may contribute to the
+	/// round evaluations, in increasing order, or `None` if every vertex must be processed.
+	///
+	/// A vertex is inactive if any sparse multilinear, whose zeros the evaluator skips, vanishes
+	/// on the whole subcube of original hypercube indices that the vertex covers.
+	fn active_vertices<S>(
+		&self,
+		evaluator: &impl AbstractSumcheckEvaluator<PW::Scalar, VertexState = S>,
+	) -> Option<Vec<usize>> {
+		// In round $i$, vertex $v$ covers the original indices with $v$ in the high bits
+		let shift = self.round + 1;
+		self.supports
+			.iter()
+			.enumerate()
+			.filter(|&(j, _)| evaluator.skips_zeros_of(j))
+			.filter_map(|(_, support)| support.as_deref())
+			.map(|support| {
+				let mut vertices = support.iter().map(|&i| i >> shift).collect::<Vec<_>>();
+				vertices.dedup();
+				vertices
+			})
+			.reduce(|active, vertices| {
+				active
+					.into_iter()
+					.filter(|i| vertices.binary_search(i).is_ok())
+					.collect()
+			})
+	}
+
 	// The gist of sumcheck - summing over evaluations of the multivariate composite on evaluation domain
 	// for the remaining variables: there are `round-1` already assigned variables with values from large
 	// field, and `rd_vars = n_vars - round` remaining variables that are being summed over. `eval01` closure
@@ -325,7 +454,7 @@ where
 		precomp: impl Fn(&'b SumcheckMultilinear<PW, M>) -> T,
 		eval01: impl Fn(T, usize) -> (PW::Scalar, PW::Scalar) + Sync,
 		evaluator: impl AbstractSumcheckEvaluator<PW::Scalar, VertexState = S>,
-		vertex_state_iterator: impl IndexedParallelIterator<Item = S>,
+		vertex_iterator: impl ParallelIterator<Item = (usize, S)>,
 		current_round_sum: PW::Scalar,
 	) -> Result<Vec<PW::Scalar>, PolynomialError>
 	where
@@ -339,8 +468,7 @@ where
 		// For performance, it's ideal to hoist this out of the tight loop.
 		let precomps = self.multilinears.iter().map(precomp).collect::<Vec<_>>();
 
-		let evals = vertex_state_iterator
-			.enumerate()
+		let evals = vertex_iterator
 			.fold(
 				|| ParFoldState::new(n_multilinears, n_round_evals),
 				|mut par_fold_state, (i, vertex_state)| {
@@ -431,14 +559,50 @@ where
 	}
 }
 
+/// Partially evaluate a sparse multilinear at the low variables, given its nonzero evaluations in
+/// increasing index order, and keep the result sparse.
+fn fold_sparse<P: PackedField>(
+	n_vars: usize,
+	entries: impl IntoIterator<Item = (usize, P::Scalar)>,
+	query: &MultilinearQuery<P>,
+) -> Result<SparseMultilinearExtension<P::Scalar, P>, PolynomialError> {
+	let n_query_vars = query.n_vars();
+	if n_query_vars > n_vars {
+		return Err(PolynomialError::IncorrectQuerySize { expected: n_vars });
+	}
+	let low_mask = (1 << n_query_vars) - 1;
+	let query_expansion = query.expansion();
+
+	// The indices are sorted, so the entries of each folded index are consecutive
+	let mut folded_entries = Vec::<(usize, P::Scalar)>::new();
+	for (index, value) in entries {
+		let folded_index = index >> n_query_vars;
+		let folded_value = get_packed_slice(query_expansion, index & low_mask) * value;
+		match folded_entries.last_mut() {
+			Some((last_index, last_value)) if *last_index == folded_index => {
+				*last_value += folded_value
+			}
+			_ => folded_entries.push((folded_index, folded_value)),
+		}
+	}
+
+	SparseMultilinearExtension::from_entries(n_vars - n_query_vars, folded_entries)
+}
+
+/// Run the prover side of a generalized sumcheck.
+///
+/// Every round message is observed by the challenger, so running the prover against a
+/// [`TranscriptWriter`](crate::challenger::TranscriptWriter) also writes the rounds to the proof.
+/// Each round message is observed under `round_label`, which identifies the protocol instance.
 pub fn prove<F, CH, E>(
 	n_vars: usize,
 	mut sumcheck_prover: impl AbstractSumcheckProver<F, Error = E>,
+	round_label: &str,
 	mut challenger: CH,
 ) -> Result<(EvalcheckClaim<F>, Vec<AbstractSumcheckRound<F>>), E>
 where
 	F: Field,
-	CH: CanSample<F> + CanObserve<F>,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
 	E: From<PolynomialError> + Sync,
 {
 	let mut prev_rd_challenge = None;
@@ -446,7 +610,7 @@ where
 
 	for _round in 0..n_vars {
 		let sumcheck_round = sumcheck_prover.execute_round(prev_rd_challenge)?;
-		challenger.observe_slice(&sumcheck_round.coeffs);
+		challenger.observe_slice_labeled(round_label, &sumcheck_round.coeffs);
 		prev_rd_challenge = Some(challenger.sample());
 		rd_proofs.push(sumcheck_round);
 	}
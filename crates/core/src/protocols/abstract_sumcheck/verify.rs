@@ -1,30 +1,37 @@
 // Copyright 2024 Ulvetanna Inc.
 
-use crate::challenger::{CanObserve, CanSample};
-use binius_field::Field;
+use crate::{
+	challenger::{CanObserve, CanSample, DomainSeparator, TranscriptReader},
+	serialization::{DeserializeBytes, Error as SerializationError},
+};
+use binius_field::{BinaryField8b, Field};
 
 use crate::{
 	oracle::CompositePolyOracle, polynomial::Error as PolynomialError,
 	protocols::evalcheck::EvalcheckClaim,
 };
 
-use super::{AbstractSumcheckProof, AbstractSumcheckReductor, AbstractSumcheckRoundClaim};
+use super::{
+	AbstractSumcheckProof, AbstractSumcheckReductor, AbstractSumcheckRound,
+	AbstractSumcheckRoundClaim,
+};
 
 pub fn verify<F, CH, E>(
 	poly_oracle: &CompositePolyOracle<F>,
 	first_round_claim: AbstractSumcheckRoundClaim<F>,
 	proof: AbstractSumcheckProof<F>,
 	reductor: impl AbstractSumcheckReductor<F, Error = E>,
+	round_label: &str,
 	mut challenger: CH,
 ) -> Result<EvalcheckClaim<F>, E>
 where
 	F: Field,
-	CH: CanSample<F> + CanObserve<F>,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
 	E: From<PolynomialError> + Sync,
 {
 	let mut rd_claim = first_round_claim;
 	for (which_round, round_proof) in proof.rounds.into_iter().enumerate() {
-		challenger.observe_slice(round_proof.coeffs.as_slice());
+		challenger.observe_slice_labeled(round_label, round_proof.coeffs.as_slice());
 		let sumcheck_round_challenge = challenger.sample();
 
 		rd_claim = reductor.reduce_intermediate_round_claim(
@@ -37,3 +44,38 @@ where
 
 	reductor.reduce_final_round_claim(poly_oracle, rd_claim)
 }
+
+/// Verify a proof written to a transcript by [`super::prove`].
+///
+/// The round messages are read from the transcript instead of being passed as a proof object.
+/// Each round message consists of `n_round_coeffs` coefficients.
+pub fn verify_with_transcript<F, C, E>(
+	poly_oracle: &CompositePolyOracle<F>,
+	first_round_claim: AbstractSumcheckRoundClaim<F>,
+	n_rounds: usize,
+	n_round_coeffs: usize,
+	reductor: impl AbstractSumcheckReductor<F, Error = E>,
+	round_label: &str,
+	transcript: &mut TranscriptReader<C>,
+) -> Result<EvalcheckClaim<F>, E>
+where
+	F: Field + DeserializeBytes,
+	C: CanObserve<BinaryField8b> + CanSample<F> + DomainSeparator,
+	E: From<PolynomialError> + From<SerializationError> + Sync,
+{
+	let mut rd_claim = first_round_claim;
+	for which_round in 0..n_rounds {
+		transcript.observe_label(round_label.as_bytes());
+		let coeffs = transcript.read_vec(n_round_coeffs)?;
+		let sumcheck_round_challenge = transcript.sample();
+
+		rd_claim = reductor.reduce_intermediate_round_claim(
+			which_round,
+			rd_claim,
+			sumcheck_round_challenge,
+			AbstractSumcheckRound { coeffs },
+		)?;
+	}
+
+	reductor.reduce_final_round_claim(poly_oracle, rd_claim)
+}
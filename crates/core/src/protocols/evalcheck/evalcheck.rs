@@ -1,8 +1,10 @@
 // Copyright 2023 Ulvetanna Inc.
 
 use super::error::Error;
-use crate::oracle::{
-	BatchId, CommittedBatch, CommittedId, CompositePolyOracle, MultilinearPolyOracle,
+use crate::{
+	oracle::{BatchId, CommittedBatch, CommittedId, CompositePolyOracle, MultilinearPolyOracle},
+	poly_commit::MultiPointEvalClaim,
+	serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes},
 };
 use binius_field::Field;
 use tracing::instrument;
@@ -31,7 +33,7 @@ pub struct EvalcheckMultilinearClaim<F: Field> {
 	pub is_random_point: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum EvalcheckProof<F: Field> {
 	Transparent,
 	Committed,
@@ -55,6 +57,114 @@ pub enum EvalcheckProof<F: Field> {
 	},
 }
 
+/// Maximum nesting depth of an [`EvalcheckProof`] accepted by deserialization.
+///
+/// Honest proofs follow the oracle definitions and are only a few levels deep. The limit keeps the
+/// recursive decoder from exhausting the stack on adversarial input.
+pub const MAX_EVALCHECK_PROOF_DEPTH: usize = 64;
+
+impl<F: Field + SerializeBytes> SerializeBytes for EvalcheckProof<F> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		match self {
+			EvalcheckProof::Transparent => 0u8.serialize(write_buf),
+			EvalcheckProof::Committed => 1u8.serialize(write_buf),
+			EvalcheckProof::Shifted => 2u8.serialize(write_buf),
+			EvalcheckProof::Packed => 3u8.serialize(write_buf),
+			EvalcheckProof::Repeating(subproof) => {
+				4u8.serialize(write_buf);
+				subproof.serialize(write_buf);
+			}
+			EvalcheckProof::Interleaved {
+				eval1,
+				eval2,
+				subproof1,
+				subproof2,
+			} => {
+				5u8.serialize(write_buf);
+				eval1.serialize(write_buf);
+				eval2.serialize(write_buf);
+				subproof1.serialize(write_buf);
+				subproof2.serialize(write_buf);
+			}
+			EvalcheckProof::Merged {
+				eval1,
+				eval2,
+				subproof1,
+				subproof2,
+			} => {
+				6u8.serialize(write_buf);
+				eval1.serialize(write_buf);
+				eval2.serialize(write_buf);
+				subproof1.serialize(write_buf);
+				subproof2.serialize(write_buf);
+			}
+			EvalcheckProof::Composite { subproofs } => {
+				7u8.serialize(write_buf);
+				subproofs.serialize(write_buf);
+			}
+		}
+	}
+}
+
+impl<F: Field + DeserializeBytes> DeserializeBytes for EvalcheckProof<F> {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		Self::deserialize_at_depth(read_buf, 0)
+	}
+}
+
+impl<F: Field + DeserializeBytes> EvalcheckProof<F> {
+	fn deserialize_at_depth(
+		read_buf: &mut &[u8],
+		depth: usize,
+	) -> Result<Self, SerializationError> {
+		if depth > MAX_EVALCHECK_PROOF_DEPTH {
+			return Err(SerializationError::RecursionLimitExceeded {
+				name: "EvalcheckProof",
+				max_depth: MAX_EVALCHECK_PROOF_DEPTH,
+			});
+		}
+
+		let subproof =
+			|read_buf: &mut &[u8]| Self::deserialize_at_depth(read_buf, depth + 1).map(Box::new);
+
+		let proof = match u8::deserialize(read_buf)? {
+			0 => EvalcheckProof::Transparent,
+			1 => EvalcheckProof::Committed,
+			2 => EvalcheckProof::Shifted,
+			3 => EvalcheckProof::Packed,
+			4 => EvalcheckProof::Repeating(subproof(read_buf)?),
+			5 => EvalcheckProof::Interleaved {
+				eval1: F::deserialize(read_buf)?,
+				eval2: F::deserialize(read_buf)?,
+				subproof1: subproof(read_buf)?,
+				subproof2: subproof(read_buf)?,
+			},
+			6 => EvalcheckProof::Merged {
+				eval1: F::deserialize(read_buf)?,
+				eval2: F::deserialize(read_buf)?,
+				subproof1: subproof(read_buf)?,
+				subproof2: subproof(read_buf)?,
+			},
+			7 => {
+				let n_subproofs = usize::deserialize(read_buf)?;
+				let mut subproofs = Vec::with_capacity(n_subproofs.min(read_buf.len()));
+				for _ in 0..n_subproofs {
+					let eval = F::deserialize(read_buf)?;
+					subproofs.push((eval, *subproof(read_buf)?));
+				}
+				EvalcheckProof::Composite { subproofs }
+			}
+			index => {
+				return Err(SerializationError::UnknownEnumVariant {
+					name: "EvalcheckProof",
+					index,
+				})
+			}
+		};
+		Ok(proof)
+	}
+}
+
 #[derive(Debug, Clone)]
 pub struct CommittedEvalClaim<F: Field> {
 	pub id: CommittedId,
@@ -169,4 +279,22 @@ impl<F: Field> BatchCommittedEvalClaims<F> {
 
 		Ok(std::mem::take(claims))
 	}
+
+	/// Take out the claims of all batches as multi-point PCS claims.
+	///
+	/// The claims can be proven directly with
+	/// [`PolyCommitScheme::prove_multi_point_evaluation`](crate::poly_commit::PolyCommitScheme::prove_multi_point_evaluation),
+	/// given the committed batches in batch id order.
+	pub fn take_multi_point_claims(&mut self) -> Vec<MultiPointEvalClaim<F>> {
+		self.claims_by_batch
+			.iter_mut()
+			.flat_map(std::mem::take)
+			.map(|claim| MultiPointEvalClaim {
+				batch_index: claim.id.batch_id,
+				poly_index: claim.id.index,
+				eval_point: claim.eval_point,
+				eval: claim.eval,
+			})
+			.collect()
+	}
 }
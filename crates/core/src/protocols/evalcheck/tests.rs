@@ -11,9 +11,13 @@ use crate::{
 		MultilinearPoly, MultilinearQuery, MultivariatePoly,
 	},
 	protocols::{
-		evalcheck::{EvalcheckClaim, EvalcheckProof, EvalcheckProver, EvalcheckVerifier},
+		evalcheck::{
+			EvalcheckClaim, EvalcheckProof, EvalcheckProver, EvalcheckVerifier,
+			MAX_EVALCHECK_PROOF_DEPTH,
+		},
 		sumcheck::SumcheckClaim,
 	},
+	serialization::{from_versioned_bytes, to_versioned_bytes, Error as SerializationError},
 	witness::MultilinearWitnessIndex,
 };
 use assert_matches::assert_matches;
@@ -730,6 +734,49 @@ fn test_evalcheck_interleaved() {
 		panic!("Proof should be Composite.");
 	}
 
+	let proof_bytes = to_versioned_bytes(&proof);
+	let proof = from_versioned_bytes::<EvalcheckProof<EF>>(&proof_bytes).unwrap();
+
 	let mut verifier_state = EvalcheckVerifier::new(&mut oracles);
 	verifier_state.verify(claim, proof).unwrap();
 }
+
+#[test]
+fn test_evalcheck_proof_serialization() {
+	let proof = EvalcheckProof::Composite {
+		subproofs: vec![
+			(EF::new(3), EvalcheckProof::Committed),
+			(
+				EF::new(5),
+				EvalcheckProof::Merged {
+					eval1: EF::new(7),
+					eval2: EF::new(11),
+					subproof1: Box::new(EvalcheckProof::Repeating(Box::new(
+						EvalcheckProof::Transparent,
+					))),
+					subproof2: Box::new(EvalcheckProof::Shifted),
+				},
+			),
+		],
+	};
+	let proof_bytes = to_versioned_bytes(&proof);
+	assert_eq!(from_versioned_bytes::<EvalcheckProof<EF>>(&proof_bytes).unwrap(), proof);
+
+	// Unknown variant tag
+	let mut bad_tag = to_versioned_bytes(&EvalcheckProof::<EF>::Packed);
+	bad_tag[2] = 8;
+	assert_matches!(
+		from_versioned_bytes::<EvalcheckProof<EF>>(&bad_tag),
+		Err(SerializationError::UnknownEnumVariant { index: 8, .. })
+	);
+
+	// Deeply nested Repeating chains are rejected before recursing past the limit
+	let mut deep = to_versioned_bytes(&EvalcheckProof::<EF>::Transparent);
+	deep.splice(2..2, [4u8; MAX_EVALCHECK_PROOF_DEPTH]);
+	assert!(from_versioned_bytes::<EvalcheckProof<EF>>(&deep).is_ok());
+	deep.insert(2, 4u8);
+	assert_matches!(
+		from_versioned_bytes::<EvalcheckProof<EF>>(&deep),
+		Err(SerializationError::RecursionLimitExceeded { .. })
+	);
+}
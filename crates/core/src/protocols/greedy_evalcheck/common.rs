@@ -6,9 +6,15 @@ use crate::{
 		evalcheck::{EvalcheckProof, SameQueryPcsClaim},
 		sumcheck::SumcheckBatchProof,
 	},
+	serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes},
 };
 use binius_field::Field;
 
+/// Challenger label for the sumchecks reducing virtual polynomial openings.
+pub(super) const VIRTUAL_OPENING_LABEL: &str = "greedy_evalcheck.virtual_opening";
+/// Challenger label for the sumchecks reducing a committed batch to a single opening point.
+pub(super) const BATCH_OPENING_LABEL: &str = "greedy_evalcheck.batch_opening";
+
 #[derive(Debug, Default)]
 pub struct GreedyEvalcheckProof<F: Field> {
 	pub initial_evalcheck_proofs: Vec<EvalcheckProof<F>>,
@@ -17,6 +23,27 @@ pub struct GreedyEvalcheckProof<F: Field> {
 	pub batch_opening_proof: Vec<Option<(SumcheckBatchProof<F>, Vec<EvalcheckProof<F>>)>>,
 }
 
+impl<F: Field + SerializeBytes> SerializeBytes for GreedyEvalcheckProof<F> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.initial_evalcheck_proofs.serialize(write_buf);
+		self.virtual_opening_proofs.serialize(write_buf);
+		self.batch_opening_proof.serialize(write_buf);
+	}
+}
+
+impl<F: Field + DeserializeBytes> DeserializeBytes for GreedyEvalcheckProof<F> {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		let initial_evalcheck_proofs = Vec::deserialize(read_buf)?;
+		let virtual_opening_proofs = Vec::deserialize(read_buf)?;
+		let batch_opening_proof = Vec::deserialize(read_buf)?;
+		Ok(Self {
+			initial_evalcheck_proofs,
+			virtual_opening_proofs,
+			batch_opening_proof,
+		})
+	}
+}
+
 #[derive(Debug)]
 pub struct GreedyEvalcheckProveOutput<F: Field> {
 	pub same_query_claims: Vec<(BatchId, SameQueryPcsClaim<F>)>,
@@ -1,11 +1,14 @@
 // Copyright 2024 Ulvetanna Inc.
 
 use super::{
-	common::{GreedyEvalcheckProof, GreedyEvalcheckProveOutput},
+	common::{
+		GreedyEvalcheckProof, GreedyEvalcheckProveOutput, BATCH_OPENING_LABEL,
+		VIRTUAL_OPENING_LABEL,
+	},
 	error::Error,
 };
 use crate::{
-	challenger::{CanObserve, CanSample},
+	challenger::{CanObserve, CanSample, DomainSeparator},
 	oracle::MultilinearOracleSet,
 	protocols::{
 		evalcheck::{EvalcheckClaim, EvalcheckProver},
@@ -28,7 +31,7 @@ pub fn prove<F, PW, Challenger>(
 where
 	F: TowerField + Step + From<PW::Scalar>,
 	PW: PackedFieldIndexable<Scalar: TowerField + From<F>>,
-	Challenger: CanObserve<F> + CanSample<F>,
+	Challenger: CanObserve<F> + CanSample<F> + DomainSeparator,
 {
 	let committed_batches = oracles.committed_batches();
 	let mut proof = GreedyEvalcheckProof::default();
@@ -47,6 +50,7 @@ where
 		}
 
 		// Reduce the new sumcheck claims for virtual polynomial openings to new evalcheck claims.
+		challenger.observe_label(VIRTUAL_OPENING_LABEL.as_bytes());
 		let (batch_sumcheck_proof, new_evalcheck_claims) =
 			prove_bivariate_sumchecks_with_switchover(
 				new_sumchecks,
@@ -83,6 +87,7 @@ where
 				let non_sqpcs_sumchecks =
 					make_non_same_query_pcs_sumchecks(&mut evalcheck_prover, &non_sqpcs_claims)?;
 
+				challenger.observe_label(BATCH_OPENING_LABEL.as_bytes());
 				let (sumcheck_proof, new_evalcheck_claims) =
 					prove_bivariate_sumchecks_with_switchover(
 						non_sqpcs_sumchecks,
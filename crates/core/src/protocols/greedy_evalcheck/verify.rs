@@ -1,8 +1,11 @@
 // Copyright 2024 Ulvetanna Inc.
 
-use super::{common::GreedyEvalcheckProof, error::Error};
+use super::{
+	common::{GreedyEvalcheckProof, BATCH_OPENING_LABEL, VIRTUAL_OPENING_LABEL},
+	error::Error,
+};
 use crate::{
-	challenger::{CanObserve, CanSample},
+	challenger::{CanObserve, CanSample, DomainSeparator},
 	oracle::{BatchId, MultilinearOracleSet},
 	protocols::{
 		evalcheck::{EvalcheckClaim, EvalcheckVerifier, SameQueryPcsClaim},
@@ -21,7 +24,7 @@ pub fn verify<F, Challenger>(
 ) -> Result<Vec<(BatchId, SameQueryPcsClaim<F>)>, Error>
 where
 	F: TowerField,
-	Challenger: CanObserve<F> + CanSample<F>,
+	Challenger: CanObserve<F> + CanSample<F> + DomainSeparator,
 {
 	let committed_batches = oracles.committed_batches();
 	let mut evalcheck_verifier = EvalcheckVerifier::new(oracles);
@@ -45,6 +48,7 @@ where
 		}
 
 		// Reduce the new sumcheck claims for virtual polynomial openings to new evalcheck claims.
+		challenger.observe_label(VIRTUAL_OPENING_LABEL.as_bytes());
 		let new_evalcheck_claims =
 			batch_verify(new_sumchecks, sumcheck_batch_proof, &mut challenger)?;
 
@@ -95,6 +99,7 @@ where
 					&non_sqpcs_claims,
 				)?;
 
+				challenger.observe_label(BATCH_OPENING_LABEL.as_bytes());
 				let evalcheck_claims =
 					batch_verify(non_sqpcs_sumchecks, sumcheck_proof, &mut challenger)?;
 
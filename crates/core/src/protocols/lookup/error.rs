@@ -0,0 +1,31 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::{oracle::Error as IOPolynomialError, polynomial::Error as PolynomialError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("witness dimensionality does not match the claim")]
+	WitnessDimensionalityMismatch,
+	#[error("the number of variables in some witness multilinear does not match the claim")]
+	WitnessNumVariablesMismatch,
+	#[error("looked up value at index {index} is not contained in the table")]
+	ValueNotInTable { index: usize },
+	#[error("polynomial error: {0}")]
+	Polynomial(#[from] PolynomialError),
+	#[error("verification failure: {0}")]
+	Verification(#[from] VerificationError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerificationError {
+	#[error("lookup table and looked up relation dimensionality differs")]
+	IncorrectDimensions,
+	#[error("the lookup table relation is nullary")]
+	NullaryRelation,
+	#[error("the number of variables in some multilinear oracle is not equal to the rest")]
+	NumVariablesMismatch,
+	#[error("at most 2^{max_log_lookups} values may be looked up")]
+	TooManyLookups { max_log_lookups: usize },
+	#[error("iopolynomial error: {0}")]
+	IOPolynomial(#[from] IOPolynomialError),
+}
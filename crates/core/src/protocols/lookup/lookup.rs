@@ -0,0 +1,351 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::error::VerificationError;
+use crate::{
+	oracle::{CompositePolyOracle, MultilinearOracleSet, MultilinearPolyOracle, OracleId},
+	polynomial::{transparent::constant::Constant, MultilinearExtension},
+	protocols::{
+		msetcheck::{MsetcheckClaim, MsetcheckWitness},
+		prodcheck::SimpleMultGateComposition,
+		zerocheck::{ZerocheckClaim, ZerocheckWitness},
+	},
+	witness::MultilinearWitness,
+};
+use binius_field::{BinaryField, BinaryField32b, ExtensionField, Field, TowerField};
+use getset::Getters;
+
+/// Base-2 logarithm of the maximum number of looked up rows.
+///
+/// A looked up relation smaller than the table is repeated to the size of the table, and every
+/// copy counts as looked up.
+///
+/// Access counters are powers of the multiplicative generator of [`BinaryField32b`], which must
+/// not wrap around while counting the accesses to a single table row.
+pub const MAX_LOG_LOOKUPS: usize = 31;
+
+/// A claim that every row of the looked up relation is a row of the lookup table.
+///
+/// The lookup multiplicities are not taken as a separate oracle of integer counts. Instead, the
+/// final counters play the role of the multiplicity column: a table row looked up $m$ times has
+/// the final counter $g^m$, where $g$ is the generator of the counter group. This is a deliberate
+/// narrowing. The offline memory checking argument needs the multiplicities in exactly this
+/// exponential encoding, and the map $m \mapsto g^m$ is not a linear relation, so it cannot be
+/// expressed with the virtual oracles of a [`MultilinearOracleSet`]. Binding an integer
+/// multiplicity column to the final counters would take a bit decomposition of $m$ and a
+/// zerocheck of degree [`MAX_LOG_LOOKUPS`] + 1, which costs more than the lookup itself. Callers
+/// that need the multiplicities elsewhere in the constraint system can constrain the final
+/// counters oracle directly.
+#[derive(Debug, Getters)]
+pub struct LookupClaim<F: Field> {
+	/// Oracles to the columns of the lookup table
+	#[get = "pub"]
+	table_oracles: Vec<MultilinearPolyOracle<F>>,
+	/// Oracles to the columns of the looked up relation
+	#[get = "pub"]
+	lookup_oracles: Vec<MultilinearPolyOracle<F>>,
+	/// Oracle to the counter read by each lookup, over the larger of the table and the looked up
+	/// relation
+	#[get = "pub"]
+	read_counts_oracle: MultilinearPolyOracle<F>,
+	/// Oracle to the multiplicative inverses of the read counters
+	#[get = "pub"]
+	read_counts_inverse_oracle: MultilinearPolyOracle<F>,
+	/// Oracle to the final counter of each table row, which is $g^m$ for a row looked up $m$
+	/// times
+	#[get = "pub"]
+	final_counts_oracle: MultilinearPolyOracle<F>,
+}
+
+impl<F: Field> LookupClaim<F> {
+	/// Claim constructor
+	pub fn new(
+		table_oracles: impl IntoIterator<Item = MultilinearPolyOracle<F>>,
+		lookup_oracles: impl IntoIterator<Item = MultilinearPolyOracle<F>>,
+		read_counts_oracle: MultilinearPolyOracle<F>,
+		read_counts_inverse_oracle: MultilinearPolyOracle<F>,
+		final_counts_oracle: MultilinearPolyOracle<F>,
+	) -> Result<Self, VerificationError> {
+		let table_oracles = table_oracles.into_iter().collect::<Vec<_>>();
+		let lookup_oracles = lookup_oracles.into_iter().collect::<Vec<_>>();
+
+		relation_sanity_checks(
+			&table_oracles,
+			&lookup_oracles,
+			[
+				&read_counts_oracle,
+				&read_counts_inverse_oracle,
+				&final_counts_oracle,
+			],
+			|oracle| oracle.n_vars(),
+		)?;
+
+		Ok(Self {
+			table_oracles,
+			lookup_oracles,
+			read_counts_oracle,
+			read_counts_inverse_oracle,
+			final_counts_oracle,
+		})
+	}
+
+	/// Dimensions of the table and looked up relations.
+	pub fn dimensions(&self) -> usize {
+		self.table_oracles.len()
+	}
+
+	/// Number of variables in each of the table oracles.
+	pub fn table_n_vars(&self) -> usize {
+		self.table_oracles.first().expect("non nullary").n_vars()
+	}
+
+	/// Number of variables in each of the looked up relation oracles.
+	pub fn lookup_n_vars(&self) -> usize {
+		self.lookup_oracles.first().expect("non nullary").n_vars()
+	}
+
+	/// Number of variables in each of the counter oracles, which is the larger of
+	/// [`Self::table_n_vars`] and [`Self::lookup_n_vars`].
+	pub fn n_vars(&self) -> usize {
+		self.read_counts_oracle.n_vars()
+	}
+}
+
+#[derive(Debug, Getters)]
+pub struct LookupWitness<'a, FW: TowerField> {
+	/// Witnesses to the columns of the lookup table
+	#[get = "pub"]
+	table_polynomials: Vec<MultilinearWitness<'a, FW>>,
+	/// Witnesses to the columns of the looked up relation
+	#[get = "pub"]
+	lookup_polynomials: Vec<MultilinearWitness<'a, FW>>,
+	/// Witness to the read counters
+	#[get = "pub"]
+	read_counts: MultilinearWitness<'a, FW>,
+	/// Witness to the inverses of the read counters
+	#[get = "pub"]
+	read_counts_inverse: MultilinearWitness<'a, FW>,
+	/// Witness to the final counters
+	#[get = "pub"]
+	final_counts: MultilinearWitness<'a, FW>,
+}
+
+impl<'a, FW: TowerField> LookupWitness<'a, FW> {
+	/// Witness constructor
+	pub fn new(
+		table_polynomials: impl IntoIterator<Item = MultilinearWitness<'a, FW>>,
+		lookup_polynomials: impl IntoIterator<Item = MultilinearWitness<'a, FW>>,
+		read_counts: MultilinearWitness<'a, FW>,
+		read_counts_inverse: MultilinearWitness<'a, FW>,
+		final_counts: MultilinearWitness<'a, FW>,
+	) -> Result<Self, VerificationError> {
+		let table_polynomials = table_polynomials.into_iter().collect::<Vec<_>>();
+		let lookup_polynomials = lookup_polynomials.into_iter().collect::<Vec<_>>();
+
+		relation_sanity_checks(
+			&table_polynomials,
+			&lookup_polynomials,
+			[&read_counts, &read_counts_inverse, &final_counts],
+			|witness| witness.n_vars(),
+		)?;
+
+		Ok(Self {
+			table_polynomials,
+			lookup_polynomials,
+			read_counts,
+			read_counts_inverse,
+			final_counts,
+		})
+	}
+
+	/// Dimensions of the table and looked up relations.
+	pub fn dimensions(&self) -> usize {
+		self.table_polynomials.len()
+	}
+
+	/// Number of variables in each of the table multilinears.
+	pub fn table_n_vars(&self) -> usize {
+		self.table_polynomials
+			.first()
+			.expect("non nullary")
+			.n_vars()
+	}
+
+	/// Number of variables in each of the looked up relation multilinears.
+	pub fn lookup_n_vars(&self) -> usize {
+		self.lookup_polynomials
+			.first()
+			.expect("non nullary")
+			.n_vars()
+	}
+
+	/// Number of variables in each of the counter multilinears.
+	pub fn n_vars(&self) -> usize {
+		self.read_counts.n_vars()
+	}
+}
+
+/// The counter multilinears that the prover commits alongside the looked up relation.
+///
+/// See [`super::lookup_counts`].
+#[derive(Debug)]
+pub struct LookupCounts {
+	pub read_counts: MultilinearExtension<'static, BinaryField32b>,
+	pub read_counts_inverse: MultilinearExtension<'static, BinaryField32b>,
+	pub final_counts: MultilinearExtension<'static, BinaryField32b>,
+}
+
+#[derive(Debug)]
+pub struct ReducedLookupClaims<F: Field> {
+	/// Multiset equality of the initial and written tuples with the read and final tuples
+	pub msetcheck_claim: MsetcheckClaim<F>,
+	/// Zerocheck of $1 - R(x) \cdot R^{-1}(x)$, which shows that the read counters are non-zero
+	pub nonzero_claim: ZerocheckClaim<F>,
+}
+
+#[derive(Debug)]
+pub struct LookupProveOutput<'a, F: Field, FW: TowerField> {
+	pub reduced_lookup_claims: ReducedLookupClaims<F>,
+	pub msetcheck_witness: MsetcheckWitness<'a, FW>,
+	pub nonzero_witness: ZerocheckWitness<'a, FW, SimpleMultGateComposition>,
+}
+
+/// The reduced claims along with the derived oracles that the prover must provide witnesses for.
+pub(super) struct LookupReduction<F: Field> {
+	pub reduced_lookup_claims: ReducedLookupClaims<F>,
+	/// Table oracles repeated to the number of variables of the counters
+	pub lifted_table_ids: Vec<OracleId>,
+	/// Looked up relation oracles repeated to the number of variables of the counters
+	pub lifted_lookup_ids: Vec<OracleId>,
+	pub one_id: OracleId,
+	pub write_counts_id: OracleId,
+}
+
+/// The generator of the multiplicative group in which the access counters live.
+pub(super) fn counter_generator<F: ExtensionField<BinaryField32b>>() -> F {
+	BinaryField32b::MULTIPLICATIVE_GENERATOR.into()
+}
+
+pub(super) fn reduce_lookup_claim<F>(
+	oracles: &mut MultilinearOracleSet<F>,
+	lookup_claim: &LookupClaim<F>,
+) -> Result<LookupReduction<F>, VerificationError>
+where
+	F: TowerField + ExtensionField<BinaryField32b>,
+{
+	let n_vars = lookup_claim.n_vars();
+
+	// The smaller of the table and the looked up relation is repeated to the size of the
+	// counters. The final counters track the accesses to each copy of the table separately, and
+	// every copy of a looked up row reads its own counter.
+	let mut lift = |columns: &[MultilinearPolyOracle<F>]| {
+		columns
+			.iter()
+			.map(|oracle| {
+				let log_copies = n_vars - oracle.n_vars();
+				if log_copies == 0 {
+					Ok(oracle.id())
+				} else {
+					oracles.add_repeating(oracle.id(), log_copies)
+				}
+			})
+			.collect::<Result<Vec<_>, _>>()
+	};
+	let lifted_table_ids = lift(&lookup_claim.table_oracles)?;
+	let lifted_lookup_ids = lift(&lookup_claim.lookup_oracles)?;
+
+	let one_id = oracles.add_transparent(Constant {
+		n_vars,
+		value: F::ONE,
+	})?;
+
+	// Each lookup writes back its read counter multiplied by the generator
+	let write_counts_id = oracles.add_linear_combination(
+		n_vars,
+		[(lookup_claim.read_counts_oracle.id(), counter_generator())],
+	)?;
+
+	// The initial tuples (t, 1) and written tuples (f, g * r) on one side, the read tuples (f, r)
+	// and final tuples (t, m) on the other.
+	let mut merged_oracle = |id0, id1| -> Result<_, VerificationError> {
+		let id = oracles.add_merged(id0, id1)?;
+		Ok(oracles.oracle(id))
+	};
+
+	let mut t_oracles = Vec::with_capacity(lookup_claim.dimensions() + 1);
+	let mut u_oracles = Vec::with_capacity(lookup_claim.dimensions() + 1);
+	for (&lifted_table_id, &lifted_lookup_id) in lifted_table_ids.iter().zip(&lifted_lookup_ids) {
+		t_oracles.push(merged_oracle(lifted_table_id, lifted_lookup_id)?);
+		u_oracles.push(merged_oracle(lifted_lookup_id, lifted_table_id)?);
+	}
+	t_oracles.push(merged_oracle(one_id, write_counts_id)?);
+	u_oracles.push(merged_oracle(
+		lookup_claim.read_counts_oracle.id(),
+		lookup_claim.final_counts_oracle.id(),
+	)?);
+
+	let msetcheck_claim = MsetcheckClaim::new(t_oracles, u_oracles)
+		.expect("merged oracles have equal number of variables");
+
+	let nonzero_claim = ZerocheckClaim {
+		poly: CompositePolyOracle::new(
+			n_vars,
+			vec![
+				oracles.oracle(one_id),
+				lookup_claim.read_counts_oracle.clone(),
+				lookup_claim.read_counts_inverse_oracle.clone(),
+			],
+			SimpleMultGateComposition,
+		)?,
+	};
+
+	Ok(LookupReduction {
+		reduced_lookup_claims: ReducedLookupClaims {
+			msetcheck_claim,
+			nonzero_claim,
+		},
+		lifted_table_ids,
+		lifted_lookup_ids,
+		one_id,
+		write_counts_id,
+	})
+}
+
+fn relation_sanity_checks<Column>(
+	table: &[Column],
+	lookup: &[Column],
+	counts: [&Column; 3],
+	n_vars: impl Fn(&Column) -> usize,
+) -> Result<(), VerificationError> {
+	// same dimensionality
+	if table.len() != lookup.len() {
+		return Err(VerificationError::IncorrectDimensions);
+	}
+
+	// non-nullary
+	if table.is_empty() {
+		return Err(VerificationError::NullaryRelation);
+	}
+
+	// same n_vars within the table and within the looked up relation, and counters over the
+	// larger of the two
+	let table_n_vars = n_vars(table.first().expect("non nullary"));
+	let lookup_n_vars = n_vars(lookup.first().expect("non nullary"));
+	let counts_n_vars = table_n_vars.max(lookup_n_vars);
+	let equal_n_vars = table.iter().all(|column| n_vars(column) == table_n_vars)
+		&& lookup.iter().all(|column| n_vars(column) == lookup_n_vars)
+		&& counts
+			.into_iter()
+			.all(|column| n_vars(column) == counts_n_vars);
+
+	if !equal_n_vars {
+		return Err(VerificationError::NumVariablesMismatch);
+	}
+
+	if counts_n_vars > MAX_LOG_LOOKUPS {
+		return Err(VerificationError::TooManyLookups {
+			max_log_lookups: MAX_LOG_LOOKUPS,
+		});
+	}
+
+	Ok(())
+}
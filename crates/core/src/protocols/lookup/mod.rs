@@ -0,0 +1,26 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! The lookup polynomial protocol.
+//!
+//! Lookup check provides a deterministic reduction of claims that the rows of a relation are
+//! contained in a fixed table to a multiset check claim and a zerocheck claim. The reduction
+//! follows the offline memory checking technique of Lasso, with access counters taken from a
+//! multiplicative group so that they can be incremented in a binary field. The lookup
+//! multiplicities are carried by the final counters, in the exponent of the counter group; see
+//! [`LookupClaim`] for why there is no separate multiplicity oracle.
+
+mod error;
+#[allow(clippy::module_inception)]
+mod lookup;
+mod prove;
+#[cfg(test)]
+mod tests;
+mod verify;
+
+pub use error::*;
+pub use lookup::{
+	LookupClaim, LookupCounts, LookupProveOutput, LookupWitness, ReducedLookupClaims,
+	MAX_LOG_LOOKUPS,
+};
+pub use prove::*;
+pub use verify::*;
@@ -0,0 +1,230 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	error::Error,
+	lookup::{
+		counter_generator, reduce_lookup_claim, LookupClaim, LookupCounts, LookupProveOutput,
+		LookupReduction, LookupWitness,
+	},
+};
+use crate::{
+	oracle::MultilinearOracleSet,
+	polynomial::{Error as PolynomialError, MultilinearComposite, MultilinearExtension},
+	protocols::{msetcheck::MsetcheckWitness, prodcheck::SimpleMultGateComposition},
+	witness::{MultilinearWitness, MultilinearWitnessIndex},
+};
+use binius_field::{BinaryField32b, ExtensionField, Field, TowerField};
+use rayon::prelude::*;
+use std::{collections::HashMap, hash::Hash};
+
+/// Compute the counter multilinears for a lookup of the given relation into the given table.
+///
+/// Every lookup reads the counter of the first table row that matches it. A row that is read
+/// $m$ times ends with the final counter $g^m$, where $g$ is the multiplicative generator of
+/// [`BinaryField32b`]. The counters range over the larger of the table and the looked up
+/// relation, with the smaller one repeated. When the looked up relation is larger, the final
+/// counters are indexed by the rows of the repeated table, with all accesses attributed to the
+/// first copy. When the table is larger, every copy of the looked up relation is counted, so a
+/// table of $2^{16}$ rows costs counters over $2^{16}$ rows however few values are looked up.
+pub fn lookup_counts<FW>(
+	table_polynomials: &[MultilinearWitness<FW>],
+	lookup_polynomials: &[MultilinearWitness<FW>],
+) -> Result<LookupCounts, Error>
+where
+	FW: TowerField + Hash,
+{
+	let row = |polys: &[MultilinearWitness<FW>], index| {
+		polys
+			.iter()
+			.map(|poly| poly.evaluate_on_hypercube(index))
+			.collect::<Result<Vec<_>, _>>()
+	};
+
+	let table_len = 1 << table_polynomials.first().map_or(0, |poly| poly.n_vars());
+	let lookup_len = 1 << lookup_polynomials.first().map_or(0, |poly| poly.n_vars());
+	let counts_len = table_len.max(lookup_len);
+
+	let mut table_rows = HashMap::new();
+	for index in 0..table_len {
+		table_rows
+			.entry(row(table_polynomials, index)?)
+			.or_insert(index);
+	}
+
+	let generator = counter_generator::<BinaryField32b>();
+	let lookup_table_indices = (0..lookup_len)
+		.map(|index| {
+			table_rows
+				.get(&row(lookup_polynomials, index)?)
+				.copied()
+				.ok_or(Error::ValueNotInTable { index })
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let mut counters = vec![BinaryField32b::ONE; counts_len];
+	let mut read_counts = Vec::with_capacity(counts_len);
+	for &table_index in lookup_table_indices.iter().cycle().take(counts_len) {
+		read_counts.push(counters[table_index]);
+		counters[table_index] *= generator;
+	}
+
+	let read_counts_inverse = read_counts
+		.iter()
+		.map(|count| {
+			count
+				.invert()
+				.expect("counters are powers of the generator")
+		})
+		.collect();
+
+	Ok(LookupCounts {
+		read_counts: MultilinearExtension::from_values(read_counts)?,
+		read_counts_inverse: MultilinearExtension::from_values(read_counts_inverse)?,
+		final_counts: MultilinearExtension::from_values(counters)?,
+	})
+}
+
+/// Prove a lookup instance reduction.
+///
+/// Given an $n$-arity table $(T_1, \ldots, T_n)$ of $\kappa$-variate multilins and an $n$-arity
+/// relation $(F_1, \ldots, F_n)$ of $\nu$-variate multilins, this protocol reduces the claim that
+/// every row of the relation is a row of the table to
+///
+/// 1) a multiset check between $\{(T(x), 1)\} \cup \{(F(x), g \cdot R(x))\}$ and
+///    $\{(F(x), R(x))\} \cup \{(T(x), M(x))\}$, where $T$ and $F$ are repeated to
+///    $\max(\kappa, \nu)$ variables, and
+/// 2) a zerocheck of $1 - R(x) \cdot R^{-1}(x)$,
+///
+/// where $R$ are the read counters, $M$ are the final counters and $g$ is the generator of the
+/// counter group. The witness oracles of the repeated table and relation, the written counters
+/// and all merged relation columns are added to the witness index.
+pub fn prove<'a, F, FW>(
+	oracles: &mut MultilinearOracleSet<F>,
+	witness_index: &mut MultilinearWitnessIndex<'a, FW>,
+	lookup_claim: &LookupClaim<F>,
+	lookup_witness: LookupWitness<'a, FW>,
+) -> Result<LookupProveOutput<'a, F, FW>, Error>
+where
+	F: TowerField + ExtensionField<BinaryField32b>,
+	FW: TowerField + ExtensionField<BinaryField32b>,
+{
+	let LookupReduction {
+		reduced_lookup_claims,
+		lifted_table_ids,
+		lifted_lookup_ids,
+		one_id,
+		write_counts_id,
+	} = reduce_lookup_claim(oracles, lookup_claim)?;
+
+	let n_vars = lookup_claim.n_vars();
+
+	if lookup_witness.dimensions() != lookup_claim.dimensions() {
+		return Err(Error::WitnessDimensionalityMismatch);
+	}
+
+	if lookup_witness.n_vars() != n_vars
+		|| lookup_witness.table_n_vars() != lookup_claim.table_n_vars()
+		|| lookup_witness.lookup_n_vars() != lookup_claim.lookup_n_vars()
+	{
+		return Err(Error::WitnessNumVariablesMismatch);
+	}
+
+	let to_witness = |values: Vec<FW>| -> Result<MultilinearWitness<'a, FW>, Error> {
+		Ok(MultilinearExtension::from_values(values)?.specialize_arc_dyn())
+	};
+
+	// Evaluations of a witness over the hypercube, repeated to n_vars variables
+	let lifted_values = |poly: &MultilinearWitness<'a, FW>| -> Result<Vec<FW>, Error> {
+		let mask = (1 << poly.n_vars()) - 1;
+		let values = (0..1 << n_vars)
+			.into_par_iter()
+			.map(|i| poly.evaluate_on_hypercube(i & mask))
+			.collect::<Result<_, PolynomialError>>()?;
+		Ok(values)
+	};
+
+	let table_values = lookup_witness
+		.table_polynomials()
+		.iter()
+		.map(lifted_values)
+		.collect::<Result<Vec<_>, _>>()?;
+	let lookup_values = lookup_witness
+		.lookup_polynomials()
+		.iter()
+		.map(lifted_values)
+		.collect::<Result<Vec<_>, _>>()?;
+	let read_counts_values = lifted_values(lookup_witness.read_counts())?;
+	let final_counts_values = lifted_values(lookup_witness.final_counts())?;
+
+	for (&lifted_id, values) in lifted_table_ids
+		.iter()
+		.zip(&table_values)
+		.chain(lifted_lookup_ids.iter().zip(&lookup_values))
+	{
+		witness_index.set(lifted_id, to_witness(values.clone())?);
+	}
+
+	let one_values = vec![FW::ONE; 1 << n_vars];
+	let one = to_witness(one_values.clone())?;
+	witness_index.set(one_id, one.clone());
+
+	let generator = counter_generator::<FW>();
+	let write_counts_values = read_counts_values
+		.par_iter()
+		.map(|&count| count * generator)
+		.collect::<Vec<_>>();
+	witness_index.set(write_counts_id, to_witness(write_counts_values.clone())?);
+
+	let mut merged_witness = |oracle_id, values0: &[FW], values1: &[FW]| {
+		let merged = to_witness([values0, values1].concat())?;
+		witness_index.set(oracle_id, merged.clone());
+		Ok::<_, Error>(merged)
+	};
+
+	let msetcheck_claim = &reduced_lookup_claims.msetcheck_claim;
+	let relations = table_values
+		.iter()
+		.zip(&lookup_values)
+		.map(|(table, lookup)| (table.as_slice(), lookup.as_slice()));
+	let mut t_polynomials = Vec::with_capacity(msetcheck_claim.dimensions());
+	let mut u_polynomials = Vec::with_capacity(msetcheck_claim.dimensions());
+	for ((table, lookup), (t_oracle, u_oracle)) in relations.zip(
+		msetcheck_claim
+			.t_oracles()
+			.iter()
+			.zip(msetcheck_claim.u_oracles()),
+	) {
+		t_polynomials.push(merged_witness(t_oracle.id(), table, lookup)?);
+		u_polynomials.push(merged_witness(u_oracle.id(), lookup, table)?);
+	}
+
+	let (t_counter_oracle, u_counter_oracle) = (
+		msetcheck_claim.t_oracles().last().expect("non nullary"),
+		msetcheck_claim.u_oracles().last().expect("non nullary"),
+	);
+	t_polynomials.push(merged_witness(t_counter_oracle.id(), &one_values, &write_counts_values)?);
+	u_polynomials.push(merged_witness(
+		u_counter_oracle.id(),
+		&read_counts_values,
+		&final_counts_values,
+	)?);
+
+	let msetcheck_witness = MsetcheckWitness::new(t_polynomials, u_polynomials)
+		.expect("merged witnesses have equal number of variables");
+
+	let nonzero_witness = MultilinearComposite::new(
+		n_vars,
+		SimpleMultGateComposition,
+		vec![
+			one,
+			lookup_witness.read_counts().clone(),
+			lookup_witness.read_counts_inverse().clone(),
+		],
+	)?;
+
+	Ok(LookupProveOutput {
+		reduced_lookup_claims,
+		msetcheck_witness,
+		nonzero_witness,
+	})
+}
@@ -0,0 +1,284 @@
+use crate::{
+	oracle::MultilinearOracleSet,
+	polynomial::MultilinearExtension,
+	protocols::{
+		lookup::{
+			lookup_counts, prove, verify, Error, LookupClaim, LookupWitness, VerificationError,
+		},
+		msetcheck,
+		test_utils::{assert_msetcheck_witnesses, committed_oracles, grand_product},
+	},
+	witness::{MultilinearWitness, MultilinearWitnessIndex},
+};
+use assert_matches::assert_matches;
+use binius_field::{
+	BinaryField, BinaryField128b, BinaryField16b, BinaryField32b, BinaryField8b, ExtensionField,
+	Field, TowerField,
+};
+use p3_util::log2_strict_usize;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+type F = BinaryField128b;
+
+// A byte substitution in the style of an AES/Grøstl S-box
+fn sbox(x: BinaryField8b) -> BinaryField8b {
+	x.invert().unwrap_or(BinaryField8b::ZERO) * BinaryField8b::new(0x1f) + BinaryField8b::new(0x63)
+}
+
+fn to_witness<FS>(values: Vec<FS>) -> MultilinearWitness<'static, F>
+where
+	FS: Field,
+	F: ExtensionField<FS>,
+{
+	MultilinearExtension::from_values(values)
+		.unwrap()
+		.specialize_arc_dyn()
+}
+
+type LookupInstance = (
+	MultilinearOracleSet<F>,
+	LookupClaim<F>,
+	LookupWitness<'static, F>,
+	MultilinearWitnessIndex<'static, F>,
+);
+
+/// Sets up an S-box lookup of `2^n_vars` random inputs into the full table of `2^8` rows.
+fn sbox_lookup(n_vars: usize) -> LookupInstance {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let table_x = (0..=255).map(BinaryField8b::new).collect::<Vec<_>>();
+	let table_y = table_x.iter().copied().map(sbox).collect::<Vec<_>>();
+	let lookup_x = (0..1 << n_vars)
+		.map(|_| BinaryField8b::new(rng.gen()))
+		.collect::<Vec<_>>();
+	let lookup_y = lookup_x.iter().copied().map(sbox).collect::<Vec<_>>();
+
+	lookup_instance(vec![table_x, table_y], vec![lookup_x, lookup_y])
+}
+
+/// Sets up a lookup of the given columns, committing the table, the looked up relation and the
+/// counters in separate batches.
+fn lookup_instance<FS>(table: Vec<Vec<FS>>, lookup: Vec<Vec<FS>>) -> LookupInstance
+where
+	FS: TowerField,
+	F: ExtensionField<FS>,
+{
+	let table_n_vars = log2_strict_usize(table[0].len());
+	let lookup_n_vars = log2_strict_usize(lookup[0].len());
+	let n_vars = table_n_vars.max(lookup_n_vars);
+	let dimensions = table.len();
+
+	let table_polynomials = table.into_iter().map(to_witness).collect::<Vec<_>>();
+	let lookup_polynomials = lookup.into_iter().map(to_witness).collect::<Vec<_>>();
+	let counts = lookup_counts(&table_polynomials, &lookup_polynomials).unwrap();
+
+	let mut oracles = MultilinearOracleSet::new();
+	let table_oracles = committed_oracles(&mut oracles, table_n_vars, dimensions, FS::TOWER_LEVEL);
+	let lookup_oracles =
+		committed_oracles(&mut oracles, lookup_n_vars, dimensions, FS::TOWER_LEVEL);
+	let count_oracles = committed_oracles(&mut oracles, n_vars, 3, BinaryField32b::TOWER_LEVEL);
+
+	let mut witness_index = MultilinearWitnessIndex::new();
+	let count_polynomials = [
+		counts.read_counts.specialize_arc_dyn(),
+		counts.read_counts_inverse.specialize_arc_dyn(),
+		counts.final_counts.specialize_arc_dyn(),
+	];
+	for (oracle, poly) in table_oracles
+		.iter()
+		.chain(&lookup_oracles)
+		.chain(&count_oracles)
+		.zip(
+			table_polynomials
+				.iter()
+				.chain(&lookup_polynomials)
+				.chain(&count_polynomials),
+		) {
+		witness_index.set(oracle.id(), poly.clone());
+	}
+
+	let [read_counts, read_counts_inverse, final_counts] = count_polynomials;
+	let witness = LookupWitness::new(
+		table_polynomials,
+		lookup_polynomials,
+		read_counts,
+		read_counts_inverse,
+		final_counts,
+	)
+	.unwrap();
+
+	let [read_counts_oracle, read_counts_inverse_oracle, final_counts_oracle] =
+		<[_; 3]>::try_from(count_oracles).unwrap();
+	let claim = LookupClaim::new(
+		table_oracles,
+		lookup_oracles,
+		read_counts_oracle,
+		read_counts_inverse_oracle,
+		final_counts_oracle,
+	)
+	.unwrap();
+
+	(oracles, claim, witness, witness_index)
+}
+
+/// Runs the lookup reduction and checks the reduced claims against the witness.
+fn check_prove_verify(lookup: LookupInstance) {
+	let (oracles, claim, witness, mut witness_index) = lookup;
+	let n_vars = claim.n_vars();
+
+	// PROVER
+	let mut prover_oracles = oracles.clone();
+	let prove_output = prove(&mut prover_oracles, &mut witness_index, &claim, witness).unwrap();
+
+	// VERIFIER
+	let verified_reduced_claims = verify(&mut oracles.clone(), &claim).unwrap();
+
+	// The read counters are non-zero
+	let nonzero_witness = &prove_output.nonzero_witness;
+	for i in 0..1 << n_vars {
+		assert_eq!(nonzero_witness.evaluate_on_hypercube(i).unwrap(), F::ZERO);
+	}
+
+	// The initial and written tuples equal the read and final tuples as multisets
+	let msetcheck_claim = &prove_output.reduced_lookup_claims.msetcheck_claim;
+	let gamma = F::new(0x123);
+	let alpha = F::new(0x346);
+	let msetcheck_output = msetcheck::prove(
+		&mut prover_oracles,
+		&mut witness_index,
+		msetcheck_claim,
+		prove_output.msetcheck_witness,
+		gamma,
+		Some(alpha),
+	)
+	.unwrap();
+	assert_eq!(
+		grand_product(&msetcheck_output.prodcheck_witness.t_polynomial),
+		grand_product(&msetcheck_output.prodcheck_witness.u_polynomial)
+	);
+
+	assert_msetcheck_witnesses(msetcheck_claim, &witness_index);
+
+	let verified_msetcheck_claim = &verified_reduced_claims.msetcheck_claim;
+	assert_eq!(verified_msetcheck_claim.dimensions(), claim.dimensions() + 1);
+	assert_eq!(verified_msetcheck_claim.n_vars(), n_vars + 1);
+	assert_eq!(verified_reduced_claims.nonzero_claim.poly.n_vars(), n_vars);
+}
+
+#[test]
+fn test_prove_verify_interaction() {
+	for n_vars in [8, 10] {
+		check_prove_verify(sbox_lookup(n_vars));
+	}
+}
+
+#[test]
+fn test_range_check_table_larger_than_lookups() {
+	// 16-bit range check of 2^10 values, with the looked up relation repeated 64 times
+	let mut rng = StdRng::seed_from_u64(0);
+	let table = (0..=u16::MAX).map(BinaryField16b::new).collect::<Vec<_>>();
+	let lookup = (0..1 << 10)
+		.map(|_| BinaryField16b::new(rng.gen()))
+		.collect::<Vec<_>>();
+
+	let lookup = lookup_instance(vec![table], vec![lookup]);
+	let claim = &lookup.1;
+	assert_eq!(claim.table_n_vars(), 16);
+	assert_eq!(claim.lookup_n_vars(), 10);
+	assert_eq!(claim.n_vars(), 16);
+	check_prove_verify(lookup);
+}
+
+#[test]
+fn test_forged_lookup_breaks_multiset_equality() {
+	let n_vars = 9;
+	let (_, _, witness, _) = sbox_lookup(n_vars);
+
+	// A looked up row that is not in the table cannot be counted
+	let mut lookup_y = (0..1 << n_vars)
+		.map(|i| {
+			witness.lookup_polynomials()[1]
+				.evaluate_on_hypercube(i)
+				.unwrap()
+		})
+		.collect::<Vec<_>>();
+	lookup_y[17] += F::ONE;
+	let forged_lookup_polynomials = [
+		witness.lookup_polynomials()[0].clone(),
+		MultilinearExtension::from_values(lookup_y)
+			.unwrap()
+			.specialize_arc_dyn(),
+	];
+	assert_matches!(
+		lookup_counts(witness.table_polynomials(), &forged_lookup_polynomials),
+		Err(Error::ValueNotInTable { index: 17 })
+	);
+
+	// Reusing the honest counters for the forged relation violates the multiset equality
+	let (mut oracles, claim, _, mut witness_index) = sbox_lookup(n_vars);
+	let forged_witness = LookupWitness::new(
+		witness.table_polynomials().clone(),
+		forged_lookup_polynomials,
+		witness.read_counts().clone(),
+		witness.read_counts_inverse().clone(),
+		witness.final_counts().clone(),
+	)
+	.unwrap();
+	let prove_output = prove(&mut oracles, &mut witness_index, &claim, forged_witness).unwrap();
+	let msetcheck_output = msetcheck::prove(
+		&mut oracles,
+		&mut witness_index,
+		&prove_output.reduced_lookup_claims.msetcheck_claim,
+		prove_output.msetcheck_witness,
+		F::new(0x123),
+		Some(F::new(0x346)),
+	)
+	.unwrap();
+	assert_ne!(
+		grand_product(&msetcheck_output.prodcheck_witness.t_polynomial),
+		grand_product(&msetcheck_output.prodcheck_witness.u_polynomial)
+	);
+}
+
+#[test]
+fn test_claim_shape_checks() {
+	let mut oracles = MultilinearOracleSet::<F>::new();
+	let table_oracles = committed_oracles(&mut oracles, 8, 1, BinaryField8b::TOWER_LEVEL);
+	let lookup_oracles = committed_oracles(&mut oracles, 6, 1, BinaryField8b::TOWER_LEVEL);
+	// The counters range over the larger table, not the looked up relation
+	let count_oracles = committed_oracles(&mut oracles, 6, 3, BinaryField32b::TOWER_LEVEL);
+
+	assert_matches!(
+		LookupClaim::new(
+			table_oracles,
+			lookup_oracles,
+			count_oracles[0].clone(),
+			count_oracles[1].clone(),
+			count_oracles[2].clone(),
+		)
+		.err(),
+		Some(VerificationError::NumVariablesMismatch)
+	);
+}
+
+#[test]
+fn test_range_check_final_counts() {
+	// 4-bit range check of 2^6 values, with the table repeated four times
+	let table = (0..16).map(BinaryField8b::new).collect::<Vec<_>>();
+	let lookup = (0..64)
+		.map(|i| BinaryField8b::new(i % 5))
+		.collect::<Vec<_>>();
+	let counts = lookup_counts(&[to_witness(table)], &[to_witness(lookup)]).unwrap();
+
+	let g = BinaryField32b::MULTIPLICATIVE_GENERATOR;
+	let final_counts = counts.final_counts.evals();
+	for (i, &count) in final_counts.iter().enumerate() {
+		let multiplicity = match i {
+			0..=3 => 13,
+			4 => 12,
+			_ => 0,
+		};
+		assert_eq!(count, g.pow([multiplicity]));
+	}
+	assert_eq!(counts.read_counts.evals()[63], g.pow([12]));
+}
@@ -0,0 +1,19 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	error::VerificationError,
+	lookup::{reduce_lookup_claim, LookupClaim, ReducedLookupClaims},
+};
+use crate::oracle::MultilinearOracleSet;
+use binius_field::{BinaryField32b, ExtensionField, TowerField};
+
+/// Verify a lookup instance reduction.
+pub fn verify<F>(
+	oracles: &mut MultilinearOracleSet<F>,
+	claim: &LookupClaim<F>,
+) -> Result<ReducedLookupClaims<F>, VerificationError>
+where
+	F: TowerField + ExtensionField<BinaryField32b>,
+{
+	Ok(reduce_lookup_claim(oracles, claim)?.reduced_lookup_claims)
+}
@@ -30,7 +30,9 @@
 pub mod abstract_sumcheck;
 pub mod evalcheck;
 pub mod greedy_evalcheck;
+pub mod lookup;
 pub mod msetcheck;
+pub mod permutation;
 pub mod prodcheck;
 pub mod sumcheck;
 #[allow(dead_code)]
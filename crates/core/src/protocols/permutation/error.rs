@@ -0,0 +1,37 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::{oracle::Error as IOPolynomialError, polynomial::Error as PolynomialError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("the number of witness columns does not match the claim")]
+	WitnessNumColumnsMismatch,
+	#[error("the number of variables in some witness multilinear does not match the claim")]
+	WitnessNumVariablesMismatch,
+	#[error("polynomial error: {0}")]
+	Polynomial(#[from] PolynomialError),
+	#[error("verification failure: {0}")]
+	Verification(#[from] VerificationError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerificationError {
+	#[error("the permuted relation has no columns")]
+	NoColumns,
+	#[error("the number of columns does not match the permutation")]
+	NumColumnsMismatch,
+	#[error("the number of variables in some multilinear oracle does not match the permutation")]
+	NumVariablesMismatch,
+	#[error("at most 2^{max_log_positions} positions may be permuted, including padding columns")]
+	TooManyPositions { max_log_positions: usize },
+	#[error("the permutation mapping length does not match the number of positions")]
+	MappingLengthMismatch,
+	#[error("the permutation mapping is not a bijection")]
+	NotBijective,
+	#[error("position (column {column}, row {row}) is out of range")]
+	PositionOutOfRange { column: usize, row: usize },
+	#[error("position (column {column}, row {row}) appears in more than one cycle")]
+	PositionInMultipleCycles { column: usize, row: usize },
+	#[error("iopolynomial error: {0}")]
+	IOPolynomial(#[from] IOPolynomialError),
+}
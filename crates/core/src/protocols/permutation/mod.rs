@@ -0,0 +1,24 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! The permutation polynomial protocol.
+//!
+//! Permutation check provides a deterministic reduction of copy constraints between the cells of
+//! a set of columns to a multiset check claim. Following PLONK, the columns are laid out end to
+//! end and the claim that the values are invariant under a permutation $\sigma$ of the positions
+//! is reduced to the multiset equality of $\{(i, V(i))\}$ and $\{(\sigma(i), V(i))\}$, where the
+//! identity and $\sigma$ are encoded as transparent oracles.
+
+mod error;
+#[allow(clippy::module_inception)]
+mod permutation;
+mod prove;
+#[cfg(test)]
+mod tests;
+mod verify;
+
+pub use error::*;
+pub use permutation::{
+	Permutation, PermutationClaim, PermutationProveOutput, PermutationWitness, MAX_LOG_POSITIONS,
+};
+pub use prove::*;
+pub use verify::*;
@@ -0,0 +1,317 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::error::VerificationError;
+use crate::{
+	oracle::{MultilinearOracleSet, MultilinearPolyOracle, OracleId},
+	polynomial::{
+		transparent::{
+			binary_index::BinaryIndex, constant::Constant,
+			multilinear_extension::MultilinearExtensionTransparent,
+		},
+		MultilinearExtension,
+	},
+	protocols::msetcheck::{MsetcheckClaim, MsetcheckWitness},
+	witness::MultilinearWitness,
+};
+use binius_field::{BinaryField32b, ExtensionField, Field, TowerField};
+use getset::{CopyGetters, Getters};
+
+/// Base-2 logarithm of the maximum number of permuted positions.
+///
+/// Positions are encoded as elements of [`BinaryField32b`].
+pub const MAX_LOG_POSITIONS: usize = 32;
+
+/// A permutation of the cells of `n_columns` columns with `2^n_vars` rows each.
+///
+/// The cell in row $r$ of column $c$ is identified with the position $c \cdot 2^n + r$, which is
+/// its index in the concatenation of the columns.
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+pub struct Permutation {
+	/// Number of variables of each column
+	#[get_copy = "pub"]
+	n_vars: usize,
+	/// Number of permuted columns
+	#[get_copy = "pub"]
+	n_columns: usize,
+	mapping: Vec<usize>,
+}
+
+impl Permutation {
+	/// Construct a permutation from the image of each position.
+	pub fn new(
+		n_vars: usize,
+		n_columns: usize,
+		mapping: Vec<usize>,
+	) -> Result<Self, VerificationError> {
+		check_positions(n_vars, n_columns)?;
+
+		if mapping.len() != n_columns << n_vars {
+			return Err(VerificationError::MappingLengthMismatch);
+		}
+
+		let mut seen = vec![false; mapping.len()];
+		for &image in &mapping {
+			if image >= seen.len() || seen[image] {
+				return Err(VerificationError::NotBijective);
+			}
+			seen[image] = true;
+		}
+
+		Ok(Self {
+			n_vars,
+			n_columns,
+			mapping,
+		})
+	}
+
+	/// The permutation fixing every position.
+	pub fn identity(n_vars: usize, n_columns: usize) -> Result<Self, VerificationError> {
+		check_positions(n_vars, n_columns)?;
+
+		Ok(Self {
+			n_vars,
+			n_columns,
+			mapping: (0..n_columns << n_vars).collect(),
+		})
+	}
+
+	/// Construct a permutation from copy constraints.
+	///
+	/// Each cycle is a class of `(column, row)` cells that must hold equal values; the permutation
+	/// maps every cell of a class to the next one, and the last cell back to the first. Cells that
+	/// appear in no cycle are fixed.
+	pub fn from_cycles<Cycle>(
+		n_vars: usize,
+		n_columns: usize,
+		cycles: impl IntoIterator<Item = Cycle>,
+	) -> Result<Self, VerificationError>
+	where
+		Cycle: IntoIterator<Item = (usize, usize)>,
+	{
+		let mut permutation = Self::identity(n_vars, n_columns)?;
+		let mut assigned = vec![false; permutation.mapping.len()];
+
+		for cycle in cycles {
+			let positions = cycle
+				.into_iter()
+				.map(|(column, row)| {
+					if column >= n_columns || row >= 1 << n_vars {
+						return Err(VerificationError::PositionOutOfRange { column, row });
+					}
+					let position = permutation.position(column, row);
+					if assigned[position] {
+						return Err(VerificationError::PositionInMultipleCycles { column, row });
+					}
+					assigned[position] = true;
+					Ok(position)
+				})
+				.collect::<Result<Vec<_>, _>>()?;
+
+			for (i, &position) in positions.iter().enumerate() {
+				permutation.mapping[position] = positions[(i + 1) % positions.len()];
+			}
+		}
+
+		Ok(permutation)
+	}
+
+	/// The position of the cell in the given column and row.
+	pub fn position(&self, column: usize, row: usize) -> usize {
+		(column << self.n_vars) | row
+	}
+
+	/// The image of each position under the permutation.
+	pub fn mapping(&self) -> &[usize] {
+		&self.mapping
+	}
+
+	/// Base-2 logarithm of the number of columns after padding to a power of two.
+	pub fn log_n_columns(&self) -> usize {
+		self.n_columns.next_power_of_two().ilog2() as usize
+	}
+
+	/// Evaluations of $\sigma$ over the hypercube, with padding positions fixed.
+	pub(super) fn sigma_multilinear_extension(
+		&self,
+	) -> MultilinearExtension<'static, BinaryField32b> {
+		let n_positions = 1 << (self.n_vars + self.log_n_columns());
+		let values = (0..n_positions)
+			.map(|position| {
+				let image = self.mapping.get(position).copied().unwrap_or(position);
+				BinaryField32b::new(image as u32)
+			})
+			.collect();
+		MultilinearExtension::from_values(values).expect("number of positions is a power of two")
+	}
+}
+
+fn check_positions(n_vars: usize, n_columns: usize) -> Result<(), VerificationError> {
+	if n_columns == 0 {
+		return Err(VerificationError::NoColumns);
+	}
+
+	if n_vars + n_columns.next_power_of_two().ilog2() as usize > MAX_LOG_POSITIONS {
+		return Err(VerificationError::TooManyPositions {
+			max_log_positions: MAX_LOG_POSITIONS,
+		});
+	}
+
+	Ok(())
+}
+
+#[derive(Debug, Getters)]
+pub struct PermutationClaim<F: Field> {
+	/// Oracles to the permuted columns
+	#[get = "pub"]
+	column_oracles: Vec<MultilinearPolyOracle<F>>,
+	/// Permutation of the cells that leaves the column values invariant
+	#[get = "pub"]
+	permutation: Permutation,
+}
+
+impl<F: Field> PermutationClaim<F> {
+	/// Claim constructor
+	pub fn new(
+		column_oracles: impl IntoIterator<Item = MultilinearPolyOracle<F>>,
+		permutation: Permutation,
+	) -> Result<Self, VerificationError> {
+		let column_oracles = column_oracles.into_iter().collect::<Vec<_>>();
+
+		if column_oracles.len() != permutation.n_columns() {
+			return Err(VerificationError::NumColumnsMismatch);
+		}
+
+		if column_oracles
+			.iter()
+			.any(|oracle| oracle.n_vars() != permutation.n_vars())
+		{
+			return Err(VerificationError::NumVariablesMismatch);
+		}
+
+		Ok(Self {
+			column_oracles,
+			permutation,
+		})
+	}
+
+	/// Number of variables in each of the column oracles.
+	pub fn n_vars(&self) -> usize {
+		self.permutation.n_vars()
+	}
+}
+
+#[derive(Debug, Getters)]
+pub struct PermutationWitness<'a, FW: TowerField> {
+	/// Witnesses to the permuted columns
+	#[get = "pub"]
+	column_polynomials: Vec<MultilinearWitness<'a, FW>>,
+}
+
+impl<'a, FW: TowerField> PermutationWitness<'a, FW> {
+	/// Witness constructor
+	pub fn new(
+		column_polynomials: impl IntoIterator<Item = MultilinearWitness<'a, FW>>,
+	) -> Result<Self, VerificationError> {
+		let column_polynomials = column_polynomials.into_iter().collect::<Vec<_>>();
+
+		let n_vars = column_polynomials
+			.first()
+			.ok_or(VerificationError::NoColumns)?
+			.n_vars();
+		if column_polynomials
+			.iter()
+			.any(|poly| poly.n_vars() != n_vars)
+		{
+			return Err(VerificationError::NumVariablesMismatch);
+		}
+
+		Ok(Self { column_polynomials })
+	}
+
+	/// Number of variables in each of the column multilinears.
+	pub fn n_vars(&self) -> usize {
+		self.column_polynomials
+			.first()
+			.expect("non nullary")
+			.n_vars()
+	}
+}
+
+#[derive(Debug)]
+pub struct PermutationProveOutput<'a, F: Field, FW: TowerField> {
+	pub msetcheck_claim: MsetcheckClaim<F>,
+	pub msetcheck_witness: MsetcheckWitness<'a, FW>,
+}
+
+/// The reduced claim along with the derived oracles that the prover must provide witnesses for.
+pub(super) struct PermutationReduction<F: Field> {
+	pub msetcheck_claim: MsetcheckClaim<F>,
+	/// Zero column padding the number of columns to a power of two
+	pub padding_id: Option<OracleId>,
+	/// Nodes of the merge tree over the columns, by level starting above the leaves
+	pub merged_ids: Vec<Vec<OracleId>>,
+	pub identity_id: OracleId,
+	pub sigma_id: OracleId,
+}
+
+pub(super) fn reduce_permutation_claim<F>(
+	oracles: &mut MultilinearOracleSet<F>,
+	permutation_claim: &PermutationClaim<F>,
+) -> Result<PermutationReduction<F>, VerificationError>
+where
+	F: TowerField + ExtensionField<BinaryField32b>,
+{
+	let n_vars = permutation_claim.n_vars();
+	let permutation = &permutation_claim.permutation;
+	let log_n_columns = permutation.log_n_columns();
+
+	let mut layer = permutation_claim
+		.column_oracles
+		.iter()
+		.map(|oracle| oracle.id())
+		.collect::<Vec<_>>();
+
+	let padding_id = if layer.len() < 1 << log_n_columns {
+		let padding_id = oracles.add_transparent(Constant {
+			n_vars,
+			value: F::ZERO,
+		})?;
+		layer.resize(1 << log_n_columns, padding_id);
+		Some(padding_id)
+	} else {
+		None
+	};
+
+	// Merging adjacent pairs makes the column index the high variables, so that the merged
+	// oracle V evaluates to the value at each position.
+	let mut merged_ids = Vec::with_capacity(log_n_columns);
+	while layer.len() > 1 {
+		layer = layer
+			.chunks_exact(2)
+			.map(|pair| oracles.add_merged(pair[0], pair[1]))
+			.collect::<Result<Vec<_>, _>>()?;
+		merged_ids.push(layer.clone());
+	}
+	let values_oracle = oracles.oracle(layer[0]);
+
+	let identity_id = oracles.add_transparent(
+		BinaryIndex::new(n_vars + log_n_columns).expect("checked by the permutation constructor"),
+	)?;
+	let sigma_id = oracles.add_transparent(MultilinearExtensionTransparent(
+		permutation.sigma_multilinear_extension().specialize::<F>(),
+	))?;
+
+	let msetcheck_claim = MsetcheckClaim::new(
+		[oracles.oracle(identity_id), values_oracle.clone()],
+		[oracles.oracle(sigma_id), values_oracle],
+	)
+	.expect("position and value oracles have equal number of variables");
+
+	Ok(PermutationReduction {
+		msetcheck_claim,
+		padding_id,
+		merged_ids,
+		identity_id,
+		sigma_id,
+	})
+}
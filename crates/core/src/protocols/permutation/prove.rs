@@ -0,0 +1,107 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	error::Error,
+	permutation::{
+		reduce_permutation_claim, PermutationClaim, PermutationProveOutput, PermutationReduction,
+		PermutationWitness,
+	},
+};
+use crate::{
+	oracle::MultilinearOracleSet,
+	polynomial::{
+		transparent::binary_index::BinaryIndex, Error as PolynomialError, MultilinearExtension,
+	},
+	protocols::msetcheck::MsetcheckWitness,
+	witness::{MultilinearWitness, MultilinearWitnessIndex},
+};
+use binius_field::{BinaryField32b, ExtensionField, TowerField};
+use rayon::prelude::*;
+
+/// Prove a permutation instance reduction.
+///
+/// Given $m$ columns $(C_0, \ldots, C_{m-1})$ of $\nu$-variate multilins, padded with zero columns
+/// to $2^l$ columns, and a permutation $\sigma$ of the $2^{\nu + l}$ positions, this protocol
+/// reduces the claim that $C_c(r) = C_{c'}(r')$ whenever $\sigma$ maps position $(c, r)$ to
+/// $(c', r')$ to a multiset check between $\{(I(x), V(x))\}$ and $\{(\sigma(x), V(x))\}$, where $V$
+/// is the merge of all columns and $I$ is the identity on positions. The witness oracles of the
+/// padding column, the merged columns and the position transparents are added to the witness
+/// index.
+pub fn prove<'a, F, FW>(
+	oracles: &mut MultilinearOracleSet<F>,
+	witness_index: &mut MultilinearWitnessIndex<'a, FW>,
+	permutation_claim: &PermutationClaim<F>,
+	permutation_witness: PermutationWitness<'a, FW>,
+) -> Result<PermutationProveOutput<'a, F, FW>, Error>
+where
+	F: TowerField + ExtensionField<BinaryField32b>,
+	FW: TowerField + ExtensionField<BinaryField32b>,
+{
+	let PermutationReduction {
+		msetcheck_claim,
+		padding_id,
+		merged_ids,
+		identity_id,
+		sigma_id,
+	} = reduce_permutation_claim(oracles, permutation_claim)?;
+
+	let n_vars = permutation_claim.n_vars();
+	let permutation = permutation_claim.permutation();
+
+	if permutation_witness.column_polynomials().len() != permutation.n_columns() {
+		return Err(Error::WitnessNumColumnsMismatch);
+	}
+
+	if permutation_witness.n_vars() != n_vars {
+		return Err(Error::WitnessNumVariablesMismatch);
+	}
+
+	let to_witness = |values: Vec<FW>| -> Result<MultilinearWitness<'a, FW>, Error> {
+		Ok(MultilinearExtension::from_values(values)?.specialize_arc_dyn())
+	};
+
+	// Evaluations of the merged columns over the hypercube, indexed by position
+	let n_positions = 1 << (n_vars + permutation.log_n_columns());
+	let column_polynomials = permutation_witness.column_polynomials();
+	let values = (0..n_positions)
+		.into_par_iter()
+		.map(|position| match column_polynomials.get(position >> n_vars) {
+			Some(poly) => poly.evaluate_on_hypercube(position & ((1 << n_vars) - 1)),
+			None => Ok(FW::ZERO),
+		})
+		.collect::<Result<Vec<_>, PolynomialError>>()?;
+
+	if let Some(padding_id) = padding_id {
+		witness_index.set(padding_id, to_witness(vec![FW::ZERO; 1 << n_vars])?);
+	}
+
+	// The node at index i on level k of the merge tree covers the positions of columns
+	// i * 2^k to (i + 1) * 2^k - 1
+	let mut values_polynomial = column_polynomials.first().expect("non nullary").clone();
+	for (level, level_ids) in merged_ids.iter().enumerate() {
+		let node_len = 1 << (n_vars + level + 1);
+		for (&merged_id, node_values) in level_ids.iter().zip(values.chunks_exact(node_len)) {
+			values_polynomial = to_witness(node_values.to_vec())?;
+			witness_index.set(merged_id, values_polynomial.clone());
+		}
+	}
+
+	let identity = BinaryIndex::<FW>::new(n_vars + permutation.log_n_columns())?
+		.multilinear_extension::<FW>()?
+		.specialize_arc_dyn();
+	witness_index.set(identity_id, identity.clone());
+
+	let sigma = permutation
+		.sigma_multilinear_extension()
+		.specialize_arc_dyn();
+	witness_index.set(sigma_id, sigma.clone());
+
+	let msetcheck_witness =
+		MsetcheckWitness::new([identity, values_polynomial.clone()], [sigma, values_polynomial])
+			.expect("position and value witnesses have equal number of variables");
+
+	Ok(PermutationProveOutput {
+		msetcheck_claim,
+		msetcheck_witness,
+	})
+}
@@ -0,0 +1,155 @@
+use crate::{
+	oracle::MultilinearOracleSet,
+	polynomial::MultilinearExtension,
+	protocols::{
+		msetcheck,
+		permutation::{
+			prove, verify, Permutation, PermutationClaim, PermutationWitness, VerificationError,
+		},
+		test_utils::{assert_msetcheck_witnesses, committed_oracles, grand_product},
+	},
+	witness::{MultilinearWitness, MultilinearWitnessIndex},
+};
+use assert_matches::assert_matches;
+use binius_field::{BinaryField128b, BinaryField32b, Field, TowerField};
+use rand::{rngs::StdRng, SeedableRng};
+
+type F = BinaryField128b;
+
+/// Copy constraints that wire row r of each column to row r + 1 of the next one, along with
+/// column values that satisfy them.
+fn wired_columns(n_vars: usize, n_columns: usize) -> (Permutation, Vec<Vec<BinaryField32b>>) {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let cycles = (0..(1 << n_vars) - n_columns + 1)
+		.map(|row| {
+			(0..n_columns)
+				.map(|column| (column, row + column))
+				.collect::<Vec<_>>()
+		})
+		.collect::<Vec<_>>();
+	let permutation = Permutation::from_cycles(n_vars, n_columns, cycles.clone()).unwrap();
+
+	let mut columns = (0..n_columns)
+		.map(|_| {
+			(0..1 << n_vars)
+				.map(|_| <BinaryField32b as Field>::random(&mut rng))
+				.collect::<Vec<_>>()
+		})
+		.collect::<Vec<_>>();
+	for cycle in cycles {
+		let (first_column, first_row) = cycle[0];
+		let value = columns[first_column][first_row];
+		for (column, row) in cycle {
+			columns[column][row] = value;
+		}
+	}
+
+	(permutation, columns)
+}
+
+/// Reduces the permutation claim and returns whether the multiset check grand products agree.
+fn products_agree(
+	n_vars: usize,
+	permutation: Permutation,
+	columns: Vec<Vec<BinaryField32b>>,
+) -> bool {
+	let n_columns = columns.len();
+	let mut oracles = MultilinearOracleSet::new();
+	let column_oracles =
+		committed_oracles(&mut oracles, n_vars, n_columns, BinaryField32b::TOWER_LEVEL);
+
+	let column_polynomials = columns
+		.into_iter()
+		.map(|values| {
+			MultilinearExtension::from_values(values)
+				.unwrap()
+				.specialize_arc_dyn()
+		})
+		.collect::<Vec<MultilinearWitness<F>>>();
+	let mut witness_index = MultilinearWitnessIndex::new();
+	for (oracle, poly) in column_oracles.iter().zip(&column_polynomials) {
+		witness_index.set(oracle.id(), poly.clone());
+	}
+
+	let claim = PermutationClaim::new(column_oracles, permutation).unwrap();
+	let witness = PermutationWitness::new(column_polynomials).unwrap();
+
+	// PROVER
+	let mut prover_oracles = oracles.clone();
+	let prove_output = prove(&mut prover_oracles, &mut witness_index, &claim, witness).unwrap();
+
+	// VERIFIER
+	let verified_msetcheck_claim = verify(&mut oracles, &claim).unwrap();
+	let log_n_columns = n_columns.next_power_of_two().ilog2() as usize;
+	assert_eq!(verified_msetcheck_claim.dimensions(), 2);
+	assert_eq!(verified_msetcheck_claim.n_vars(), n_vars + log_n_columns);
+
+	let msetcheck_claim = &prove_output.msetcheck_claim;
+	assert_msetcheck_witnesses(msetcheck_claim, &witness_index);
+
+	let msetcheck_output = msetcheck::prove(
+		&mut prover_oracles,
+		&mut witness_index,
+		msetcheck_claim,
+		prove_output.msetcheck_witness,
+		F::new(0x123),
+		Some(F::new(0x346)),
+	)
+	.unwrap();
+	grand_product(&msetcheck_output.prodcheck_witness.t_polynomial)
+		== grand_product(&msetcheck_output.prodcheck_witness.u_polynomial)
+}
+
+#[test]
+fn test_prove_verify_interaction() {
+	for (n_vars, n_columns) in [(4, 1), (4, 3), (5, 4), (3, 5)] {
+		let (permutation, columns) = wired_columns(n_vars, n_columns);
+		assert!(products_agree(n_vars, permutation, columns));
+	}
+}
+
+#[test]
+fn test_broken_wiring_breaks_multiset_equality() {
+	let n_vars = 4;
+	let (permutation, mut columns) = wired_columns(n_vars, 3);
+	columns[1][7] += BinaryField32b::ONE;
+	assert!(!products_agree(n_vars, permutation, columns));
+}
+
+#[test]
+fn test_permutation_checks() {
+	assert_matches!(
+		Permutation::new(2, 2, vec![0, 1, 2, 3, 4, 5, 6, 6]),
+		Err(VerificationError::NotBijective)
+	);
+	assert_matches!(
+		Permutation::new(2, 2, vec![0, 1, 2, 3]),
+		Err(VerificationError::MappingLengthMismatch)
+	);
+	assert_matches!(Permutation::identity(2, 0), Err(VerificationError::NoColumns));
+	assert_matches!(
+		Permutation::identity(30, 5),
+		Err(VerificationError::TooManyPositions {
+			max_log_positions: 32
+		})
+	);
+	assert_matches!(
+		Permutation::from_cycles(2, 2, [vec![(0, 1), (2, 0)]]),
+		Err(VerificationError::PositionOutOfRange { column: 2, row: 0 })
+	);
+	assert_matches!(
+		Permutation::from_cycles(2, 2, [vec![(0, 1), (1, 0)], vec![(1, 1), (0, 1)]]),
+		Err(VerificationError::PositionInMultipleCycles { column: 0, row: 1 })
+	);
+
+	let permutation = Permutation::from_cycles(2, 2, [vec![(0, 1), (1, 0), (1, 3)]]).unwrap();
+	assert_eq!(permutation.mapping(), &[0, 4, 2, 3, 7, 5, 6, 1]);
+
+	let mut oracles = MultilinearOracleSet::<F>::new();
+	let column_oracles = committed_oracles(&mut oracles, 2, 3, BinaryField32b::TOWER_LEVEL);
+	assert_matches!(
+		PermutationClaim::new(column_oracles, permutation).err(),
+		Some(VerificationError::NumColumnsMismatch)
+	);
+}
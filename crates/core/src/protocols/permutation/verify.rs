@@ -0,0 +1,19 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	error::VerificationError,
+	permutation::{reduce_permutation_claim, PermutationClaim},
+};
+use crate::{oracle::MultilinearOracleSet, protocols::msetcheck::MsetcheckClaim};
+use binius_field::{BinaryField32b, ExtensionField, TowerField};
+
+/// Verify a permutation instance reduction.
+pub fn verify<F>(
+	oracles: &mut MultilinearOracleSet<F>,
+	claim: &PermutationClaim<F>,
+) -> Result<MsetcheckClaim<F>, VerificationError>
+where
+	F: TowerField + ExtensionField<BinaryField32b>,
+{
+	Ok(reduce_permutation_claim(oracles, claim)?.msetcheck_claim)
+}
@@ -0,0 +1,404 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Grand product argument over a layered circuit of multiplication gates.
+//!
+//! For a $\nu$-variate multilinear $V_\nu$, define the layers $V_k$ for $k < \nu$ by
+//! $V_k(x) = V_{k+1}(x, 0) \cdot V_{k+1}(x, 1)$, so that $V_0$ is the product of all hypercube
+//! evaluations of $V_\nu$. Following [Tha13], a claim $V_k(r_k) = c_k$ is reduced with a sumcheck
+//! over $\sum_{y \in \{0, 1\}^k} \widetilde{eq}(r_k, y) \cdot V_{k+1}(y, 0) \cdot V_{k+1}(y, 1)$
+//! to a claim on $V_{k+1}$. Only the final claim on $V_\nu$ concerns an oracle, so the argument
+//! needs no commitments beyond those of the inputs.
+//!
+//! [Tha13]: https://eprint.iacr.org/2013/351
+
+use super::{
+	error::{Error, VerificationError},
+	prodcheck::{ProdcheckClaim, ProdcheckWitness},
+};
+use crate::{
+	challenger::{CanObserve, CanSample, DomainSeparator},
+	polynomial::{
+		extrapolate_line, transparent::eq_ind::EqIndPartialEval, Error as PolynomialError,
+		EvaluationDomain, MultilinearExtension, MultilinearPoly, MultilinearQuery,
+		MultivariatePoly,
+	},
+	protocols::{
+		abstract_sumcheck::{AbstractSumcheckEvaluator, AbstractSumcheckReductor, ProverState},
+		evalcheck::EvalcheckClaim,
+		sumcheck::{SumcheckReductor, SumcheckRound, SumcheckRoundClaim},
+	},
+	witness::MultilinearWitness,
+};
+use binius_field::{BinaryField8b, ExtensionField, Field, TowerField};
+use rayon::prelude::*;
+use tracing::instrument;
+
+/// Challenger label for the claimed grand product.
+const PRODUCT_LABEL: &str = "prodcheck.gkr.product";
+/// Challenger label for the layer sumcheck round messages.
+const ROUND_LABEL: &str = "prodcheck.gkr.round";
+/// Challenger label for the evaluations of the next layer at the end of a layer sumcheck.
+const EVALS_LABEL: &str = "prodcheck.gkr.evals";
+
+/// Degree of the layer sumcheck composition $\widetilde{eq} \cdot V(\cdot, 0) \cdot V(\cdot, 1)$.
+const LAYER_DEGREE: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct GkrLayerProof<F> {
+	/// Round messages of the layer sumcheck, with the highest-degree coefficients trimmed
+	pub rounds: Vec<SumcheckRound<F>>,
+	/// Evaluations of the next layer at the sumcheck point extended by 0 and 1
+	pub evals: [F; 2],
+}
+
+#[derive(Debug, Clone)]
+pub struct GrandProductProof<F> {
+	/// The product of all hypercube evaluations
+	pub product: F,
+	/// Layer proofs, from the root layer down to the input layer
+	pub layers: Vec<GkrLayerProof<F>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GkrProdcheckProof<F> {
+	pub t_proof: GrandProductProof<F>,
+	pub u_proof: GrandProductProof<F>,
+}
+
+#[derive(Debug)]
+pub struct GkrProdcheckProveOutput<F: Field> {
+	pub proof: GkrProdcheckProof<F>,
+	/// Evalcheck claims on the T and U oracles
+	pub evalcheck_claims: Vec<EvalcheckClaim<F>>,
+}
+
+/// Prove a prodcheck instance reduction with a GKR grand product argument.
+///
+/// The grand products of T and U are each proven with [`prove_grand_product`], reducing the
+/// prodcheck claim to evalcheck claims on the T and U oracles at random points.
+#[instrument(skip_all, name = "prodcheck::prove_gkr")]
+pub fn prove_gkr<F, FW, CH>(
+	prodcheck_claim: &ProdcheckClaim<F>,
+	prodcheck_witness: ProdcheckWitness<FW>,
+	mut challenger: CH,
+) -> Result<GkrProdcheckProveOutput<F>, Error>
+where
+	F: TowerField + From<FW>,
+	FW: TowerField + ExtensionField<BinaryField8b> + From<F>,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	let n_vars = prodcheck_claim
+		.n_vars()
+		.ok_or(Error::NumeratorDenominatorSizeMismatch)?;
+	if prodcheck_witness.t_polynomial.n_vars() != n_vars
+		|| prodcheck_witness.u_polynomial.n_vars() != n_vars
+	{
+		return Err(Error::NumVariablesMismatch);
+	}
+
+	let (t_proof, t_eval_point, t_eval) =
+		prove_grand_product(&prodcheck_witness.t_polynomial, &mut challenger)?;
+	let (u_proof, u_eval_point, u_eval) =
+		prove_grand_product(&prodcheck_witness.u_polynomial, &mut challenger)?;
+
+	let evalcheck_claims = vec![
+		EvalcheckClaim {
+			poly: prodcheck_claim.t_oracle.clone().into_composite(),
+			eval_point: t_eval_point,
+			eval: t_eval,
+			is_random_point: true,
+		},
+		EvalcheckClaim {
+			poly: prodcheck_claim.u_oracle.clone().into_composite(),
+			eval_point: u_eval_point,
+			eval: u_eval,
+			is_random_point: true,
+		},
+	];
+
+	Ok(GkrProdcheckProveOutput {
+		proof: GkrProdcheckProof { t_proof, u_proof },
+		evalcheck_claims,
+	})
+}
+
+/// Verify a prodcheck instance reduction with a GKR grand product argument.
+///
+/// The grand products of T and U must be equal and non-zero.
+#[instrument(skip_all, name = "prodcheck::verify_gkr")]
+pub fn verify_gkr<F, CH>(
+	prodcheck_claim: &ProdcheckClaim<F>,
+	proof: GkrProdcheckProof<F>,
+	mut challenger: CH,
+) -> Result<Vec<EvalcheckClaim<F>>, Error>
+where
+	F: TowerField,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	let n_vars = prodcheck_claim
+		.n_vars()
+		.ok_or(Error::NumeratorDenominatorSizeMismatch)?;
+
+	let GkrProdcheckProof { t_proof, u_proof } = proof;
+	if t_proof.product != u_proof.product || u_proof.product == F::ZERO {
+		return Err(VerificationError::ProductMismatch.into());
+	}
+
+	let (t_eval_point, t_eval) = verify_grand_product(n_vars, t_proof, &mut challenger)?;
+	let (u_eval_point, u_eval) = verify_grand_product(n_vars, u_proof, &mut challenger)?;
+
+	Ok(vec![
+		EvalcheckClaim {
+			poly: prodcheck_claim.t_oracle.clone().into_composite(),
+			eval_point: t_eval_point,
+			eval: t_eval,
+			is_random_point: true,
+		},
+		EvalcheckClaim {
+			poly: prodcheck_claim.u_oracle.clone().into_composite(),
+			eval_point: u_eval_point,
+			eval: u_eval,
+			is_random_point: true,
+		},
+	])
+}
+
+/// Prove the product of the hypercube evaluations of a multilinear.
+///
+/// Returns the proof along with the evaluation point and value of the multilinear that the product
+/// claim reduces to.
+#[allow(clippy::type_complexity)]
+pub fn prove_grand_product<F, FW, CH>(
+	poly: &MultilinearWitness<FW>,
+	mut challenger: CH,
+) -> Result<(GrandProductProof<F>, Vec<F>, F), Error>
+where
+	F: TowerField + From<FW>,
+	FW: TowerField + ExtensionField<BinaryField8b> + From<F>,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	let n_vars = poly.n_vars();
+
+	// layers[k] holds the 2^k evaluations of V_k
+	let mut layers = Vec::with_capacity(n_vars + 1);
+	let input_layer = (0..1 << n_vars)
+		.into_par_iter()
+		.map(|i| poly.evaluate_on_hypercube(i))
+		.collect::<Result<Vec<_>, _>>()?;
+	layers.push(input_layer);
+	for k in (0..n_vars).rev() {
+		let next_layer = layers.last().expect("input layer was pushed");
+		let (lo, hi) = next_layer.split_at(1 << k);
+		let layer = lo
+			.par_iter()
+			.zip(hi.par_iter())
+			.map(|(&lo_i, &hi_i)| lo_i * hi_i)
+			.collect();
+		layers.push(layer);
+	}
+	layers.reverse();
+
+	let product = F::from(layers[0][0]);
+	challenger.observe_labeled(PRODUCT_LABEL, product);
+
+	let domain = EvaluationDomain::<FW>::new_isomorphic::<BinaryField8b>(LAYER_DEGREE + 1)?;
+	let evaluator = LayerEvaluator { domain: &domain };
+
+	let mut eval_point = Vec::<F>::with_capacity(n_vars);
+	let mut eval = product;
+	let mut layer_proofs = Vec::with_capacity(n_vars);
+	for k in 0..n_vars {
+		let (lo, hi) = layers[k + 1].split_at(1 << k);
+		let lo = MultilinearExtension::from_values_slice(lo)?.specialize_arc_dyn::<FW>();
+		let hi = MultilinearExtension::from_values_slice(hi)?.specialize_arc_dyn::<FW>();
+
+		let mut round_claim = SumcheckRoundClaim {
+			partial_point: Vec::with_capacity(k),
+			current_round_sum: eval,
+		};
+		let mut rounds = Vec::with_capacity(k);
+		if k > 0 {
+			let wf_eval_point = eval_point.iter().copied().map(FW::from).collect();
+			let eq_ind = EqIndPartialEval::new(k, wf_eval_point)?
+				.multilinear_extension::<FW>()?
+				.specialize_arc_dyn::<FW>();
+			let mut state = ProverState::new(k, [eq_ind, lo.clone(), hi.clone()], |_| 1)?;
+
+			for round in 0..k {
+				if let Some(&prev_rd_challenge) = round_claim.partial_point.last() {
+					state.fold(FW::from(prev_rd_challenge))?;
+				}
+
+				let vertex_state_iterator = (0..1 << (k - round - 1)).into_par_iter().map(|_| ());
+				let coeffs = state.calculate_round_coeffs(
+					evaluator,
+					round_claim.current_round_sum.into(),
+					vertex_state_iterator,
+				)?;
+				let round_proof = SumcheckRound {
+					coeffs: coeffs.into_iter().map(F::from).collect(),
+				};
+
+				challenger.observe_slice_labeled(ROUND_LABEL, &round_proof.coeffs);
+				let challenge = challenger.sample();
+				round_claim = SumcheckReductor.reduce_intermediate_round_claim(
+					round,
+					round_claim,
+					challenge,
+					round_proof.clone(),
+				)?;
+				rounds.push(round_proof);
+			}
+		}
+
+		let SumcheckRoundClaim {
+			partial_point: sumcheck_point,
+			..
+		} = round_claim;
+		let query = MultilinearQuery::<FW>::with_full_query(
+			&sumcheck_point
+				.iter()
+				.copied()
+				.map(FW::from)
+				.collect::<Vec<_>>(),
+		)?;
+		let evals = [F::from(lo.evaluate(&query)?), F::from(hi.evaluate(&query)?)];
+
+		let challenge = reduce_layer_evals(&mut challenger, evals);
+		eval = evals[0] + challenge * (evals[1] - evals[0]);
+		eval_point = sumcheck_point;
+		eval_point.push(challenge);
+
+		layer_proofs.push(GkrLayerProof { rounds, evals });
+	}
+
+	let proof = GrandProductProof {
+		product,
+		layers: layer_proofs,
+	};
+	Ok((proof, eval_point, eval))
+}
+
+/// Verify the product of the hypercube evaluations of a `n_vars`-variate multilinear.
+///
+/// Returns the evaluation point and value of the multilinear that the product claim reduces to.
+pub fn verify_grand_product<F, CH>(
+	n_vars: usize,
+	proof: GrandProductProof<F>,
+	mut challenger: CH,
+) -> Result<(Vec<F>, F), Error>
+where
+	F: TowerField,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	let GrandProductProof { product, layers } = proof;
+	if layers.len() != n_vars {
+		return Err(VerificationError::NumberOfLayers.into());
+	}
+
+	challenger.observe_labeled(PRODUCT_LABEL, product);
+
+	let mut eval_point = Vec::<F>::with_capacity(n_vars);
+	let mut eval = product;
+	for (k, GkrLayerProof { rounds, evals }) in layers.into_iter().enumerate() {
+		if rounds.len() != k {
+			return Err(VerificationError::NumberOfRounds.into());
+		}
+
+		let mut round_claim = SumcheckRoundClaim {
+			partial_point: Vec::with_capacity(k),
+			current_round_sum: eval,
+		};
+		for (round, round_proof) in rounds.into_iter().enumerate() {
+			if round_proof.coeffs.len() != LAYER_DEGREE {
+				return Err(VerificationError::NumberOfCoefficients { round }.into());
+			}
+
+			challenger.observe_slice_labeled(ROUND_LABEL, &round_proof.coeffs);
+			let challenge = challenger.sample();
+			round_claim = SumcheckReductor.reduce_intermediate_round_claim(
+				round,
+				round_claim,
+				challenge,
+				round_proof,
+			)?;
+		}
+
+		let SumcheckRoundClaim {
+			partial_point: sumcheck_point,
+			current_round_sum,
+		} = round_claim;
+		let eq_ind_eval = EqIndPartialEval::new(k, eval_point)?.evaluate(&sumcheck_point)?;
+		if eq_ind_eval * evals[0] * evals[1] != current_round_sum {
+			return Err(VerificationError::IncorrectEvaluation.into());
+		}
+
+		let challenge = reduce_layer_evals(&mut challenger, evals);
+		eval = evals[0] + challenge * (evals[1] - evals[0]);
+		eval_point = sumcheck_point;
+		eval_point.push(challenge);
+	}
+
+	Ok((eval_point, eval))
+}
+
+/// Observe the next layer evaluations at the ends of a line and sample a point on it.
+fn reduce_layer_evals<F, CH>(mut challenger: CH, evals: [F; 2]) -> F
+where
+	F: Field,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	challenger.observe_slice_labeled(EVALS_LABEL, &evals);
+	challenger.sample()
+}
+
+/// Evaluator for the layer sumcheck, whose multilinears are $\widetilde{eq}(r_k, \cdot)$,
+/// $V_{k+1}(\cdot, 0)$ and $V_{k+1}(\cdot, 1)$.
+#[derive(Debug, Clone, Copy)]
+struct LayerEvaluator<'a, F: Field> {
+	domain: &'a EvaluationDomain<F>,
+}
+
+impl<'a, F: Field> AbstractSumcheckEvaluator<F> for LayerEvaluator<'a, F> {
+	type VertexState = ();
+
+	fn n_round_evals(&self) -> usize {
+		// NB: We skip evaluation of $r(X)$ at $X = 0$ as it is derivable from the
+		// current_round_sum - $r(1)$.
+		LAYER_DEGREE
+	}
+
+	fn process_vertex(
+		&self,
+		_i: usize,
+		_vertex_state: Self::VertexState,
+		evals_0: &[F],
+		evals_1: &[F],
+		evals_z: &mut [F],
+		round_evals: &mut [F],
+	) {
+		round_evals[0] += evals_1.iter().product::<F>();
+
+		for (d, &point) in self.domain.points().iter().enumerate().skip(2) {
+			for ((&evals_0_j, &evals_1_j), evals_z_j) in
+				evals_0.iter().zip(evals_1).zip(evals_z.iter_mut())
+			{
+				*evals_z_j = extrapolate_line::<F, F>(evals_0_j, evals_1_j, point);
+			}
+			round_evals[d - 1] += evals_z.iter().product::<F>();
+		}
+	}
+
+	fn round_evals_to_coeffs(
+		&self,
+		current_round_sum: F,
+		mut round_evals: Vec<F>,
+	) -> Result<Vec<F>, PolynomialError> {
+		round_evals.insert(0, current_round_sum - round_evals[0]);
+
+		let coeffs = self.domain.interpolate(&round_evals)?;
+
+		// Trimming highest degree coefficient as it can be recovered by the verifier
+		Ok(coeffs[..coeffs.len() - 1].to_vec())
+	}
+}
@@ -0,0 +1,340 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	error::Error,
+	gkr::{prove_gkr, verify_gkr, GkrProdcheckProof},
+	prodcheck::{ProdcheckClaim, ProdcheckWitness, SimpleMultGateComposition},
+	prove::prove,
+	verify::verify,
+};
+use crate::{
+	challenger::{CanObserve, CanSample, DomainSeparator},
+	oracle::{CommittedId, MultilinearOracleSet},
+	protocols::{
+		evalcheck::EvalcheckClaim,
+		zerocheck::{ZerocheckClaim, ZerocheckWitness},
+	},
+	witness::MultilinearWitnessIndex,
+};
+use binius_field::{BinaryField8b, ExtensionField, Field, TowerField};
+
+/// The claims that a product check is reduced to.
+#[derive(Debug)]
+pub struct ProdcheckReducedClaims<F: Field> {
+	pub zerocheck_claims: Vec<ZerocheckClaim<F>>,
+	pub evalcheck_claims: Vec<EvalcheckClaim<F>>,
+}
+
+#[derive(Debug)]
+pub struct GrandProductProveOutput<'a, F: Field, FW: Field, Proof> {
+	pub reduced_claims: ProdcheckReducedClaims<F>,
+	/// Witnesses to the zerocheck claims, in the same order
+	pub zerocheck_witnesses: Vec<ZerocheckWitness<'a, FW, SimpleMultGateComposition>>,
+	pub proof: Proof,
+}
+
+/// A protocol that proves a product check claim, i.e. that the products of the hypercube
+/// evaluations of T and U are equal.
+///
+/// Implementations differ in their cost: [`CommittedGrandProduct`] needs a committed grand product
+/// polynomial and a zerocheck, while [`GkrGrandProduct`] needs no commitments but has a
+/// logarithmic number of sumchecks in its proof.
+pub trait GrandProductArgument<F: TowerField> {
+	type Proof;
+
+	fn prove<'a, FW, CH>(
+		&self,
+		oracles: &mut MultilinearOracleSet<F>,
+		witness_index: &mut MultilinearWitnessIndex<'a, FW>,
+		claim: &ProdcheckClaim<F>,
+		witness: ProdcheckWitness<'a, FW>,
+		challenger: CH,
+	) -> Result<GrandProductProveOutput<'a, F, FW, Self::Proof>, Error>
+	where
+		F: From<FW>,
+		FW: TowerField + ExtensionField<BinaryField8b> + From<F>,
+		CH: CanSample<F> + CanObserve<F> + DomainSeparator;
+
+	fn verify<CH>(
+		&self,
+		oracles: &mut MultilinearOracleSet<F>,
+		claim: &ProdcheckClaim<F>,
+		proof: Self::Proof,
+		challenger: CH,
+	) -> Result<ProdcheckReducedClaims<F>, Error>
+	where
+		CH: CanSample<F> + CanObserve<F> + DomainSeparator;
+}
+
+/// Product check with a committed grand product polynomial $f'$, see [`prove`].
+#[derive(Debug, Clone, Copy)]
+pub struct CommittedGrandProduct {
+	/// The committed oracle for $f'$, which has one more variable than T and U
+	pub f_prime_committed_id: CommittedId,
+}
+
+impl<F: TowerField> GrandProductArgument<F> for CommittedGrandProduct {
+	type Proof = ();
+
+	fn prove<'a, FW, CH>(
+		&self,
+		oracles: &mut MultilinearOracleSet<F>,
+		witness_index: &mut MultilinearWitnessIndex<'a, FW>,
+		claim: &ProdcheckClaim<F>,
+		witness: ProdcheckWitness<'a, FW>,
+		_challenger: CH,
+	) -> Result<GrandProductProveOutput<'a, F, FW, Self::Proof>, Error>
+	where
+		F: From<FW>,
+		FW: TowerField + ExtensionField<BinaryField8b> + From<F>,
+		CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+	{
+		let output = prove(oracles, witness_index, claim, witness, self.f_prime_committed_id)?;
+		let claims = output.reduced_product_check_claims;
+		Ok(GrandProductProveOutput {
+			reduced_claims: ProdcheckReducedClaims {
+				zerocheck_claims: vec![claims.t_prime_claim],
+				evalcheck_claims: vec![claims.grand_product_poly_claim],
+			},
+			zerocheck_witnesses: vec![output.t_prime_witness],
+			proof: (),
+		})
+	}
+
+	fn verify<CH>(
+		&self,
+		oracles: &mut MultilinearOracleSet<F>,
+		claim: &ProdcheckClaim<F>,
+		_proof: Self::Proof,
+		_challenger: CH,
+	) -> Result<ProdcheckReducedClaims<F>, Error>
+	where
+		CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+	{
+		let f_prime_oracle = oracles.committed_oracle(self.f_prime_committed_id);
+		let claims = verify(oracles, claim, f_prime_oracle)?;
+		Ok(ProdcheckReducedClaims {
+			zerocheck_claims: vec![claims.t_prime_claim],
+			evalcheck_claims: vec![claims.grand_product_poly_claim],
+		})
+	}
+}
+
+/// Product check with a GKR grand product argument, see [`prove_gkr`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GkrGrandProduct;
+
+impl<F: TowerField> GrandProductArgument<F> for GkrGrandProduct {
+	type Proof = GkrProdcheckProof<F>;
+
+	fn prove<'a, FW, CH>(
+		&self,
+		_oracles: &mut MultilinearOracleSet<F>,
+		_witness_index: &mut MultilinearWitnessIndex<'a, FW>,
+		claim: &ProdcheckClaim<F>,
+		witness: ProdcheckWitness<'a, FW>,
+		challenger: CH,
+	) -> Result<GrandProductProveOutput<'a, F, FW, Self::Proof>, Error>
+	where
+		F: From<FW>,
+		FW: TowerField + ExtensionField<BinaryField8b> + From<F>,
+		CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+	{
+		let output = prove_gkr(claim, witness, challenger)?;
+		Ok(GrandProductProveOutput {
+			reduced_claims: ProdcheckReducedClaims {
+				zerocheck_claims: Vec::new(),
+				evalcheck_claims: output.evalcheck_claims,
+			},
+			zerocheck_witnesses: Vec::new(),
+			proof: output.proof,
+		})
+	}
+
+	fn verify<CH>(
+		&self,
+		_oracles: &mut MultilinearOracleSet<F>,
+		claim: &ProdcheckClaim<F>,
+		proof: Self::Proof,
+		challenger: CH,
+	) -> Result<ProdcheckReducedClaims<F>, Error>
+	where
+		CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+	{
+		Ok(ProdcheckReducedClaims {
+			zerocheck_claims: Vec::new(),
+			evalcheck_claims: verify_gkr(claim, proof, challenger)?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		challenger::HashChallenger,
+		oracle::CommittedBatchSpec,
+		polynomial::{MultilinearExtension, MultilinearQuery},
+		protocols::prodcheck::VerificationError,
+	};
+	use assert_matches::assert_matches;
+	use binius_field::BinaryField128b;
+	use binius_hash::GroestlHasher;
+	use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+	use std::iter::repeat_with;
+
+	type F = BinaryField128b;
+
+	#[allow(clippy::type_complexity)]
+	fn setup(
+		n_vars: usize,
+		permuted: bool,
+	) -> (MultilinearOracleSet<F>, ProdcheckClaim<F>, ProdcheckWitness<'static, F>, CommittedId) {
+		let mut rng = StdRng::seed_from_u64(0);
+		let t_values = repeat_with(|| <F as Field>::random(&mut rng))
+			.take(1 << n_vars)
+			.collect::<Vec<_>>();
+		let mut u_values = t_values.clone();
+		if permuted {
+			u_values.shuffle(&mut rng);
+		} else {
+			u_values[3] += F::ONE;
+		}
+
+		let mut oracles = MultilinearOracleSet::new();
+		let batch_id = oracles.add_committed_batch(CommittedBatchSpec {
+			n_vars,
+			n_polys: 2,
+			tower_level: F::TOWER_LEVEL,
+		});
+		let f_prime_batch_id = oracles.add_committed_batch(CommittedBatchSpec {
+			n_vars: n_vars + 1,
+			n_polys: 1,
+			tower_level: F::TOWER_LEVEL,
+		});
+
+		let claim = ProdcheckClaim {
+			t_oracle: oracles.committed_oracle(CommittedId { batch_id, index: 0 }),
+			u_oracle: oracles.committed_oracle(CommittedId { batch_id, index: 1 }),
+		};
+		let witness = ProdcheckWitness {
+			t_polynomial: MultilinearExtension::from_values(t_values)
+				.unwrap()
+				.specialize_arc_dyn(),
+			u_polynomial: MultilinearExtension::from_values(u_values)
+				.unwrap()
+				.specialize_arc_dyn(),
+		};
+		let f_prime_committed_id = CommittedId {
+			batch_id: f_prime_batch_id,
+			index: 0,
+		};
+		(oracles, claim, witness, f_prime_committed_id)
+	}
+
+	fn prove_verify<GPA: GrandProductArgument<F>>(
+		grand_product: GPA,
+		n_vars: usize,
+	) -> (ProdcheckReducedClaims<F>, ProdcheckWitness<'static, F>) {
+		let (oracles, claim, witness, _) = setup(n_vars, true);
+		let challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+
+		let prove_output = grand_product
+			.prove(
+				&mut oracles.clone(),
+				&mut MultilinearWitnessIndex::new(),
+				&claim,
+				witness.clone(),
+				challenger.clone(),
+			)
+			.unwrap();
+		let verified_claims = grand_product
+			.verify(&mut oracles.clone(), &claim, prove_output.proof, challenger)
+			.unwrap();
+
+		assert_eq!(
+			prove_output.reduced_claims.zerocheck_claims.len(),
+			verified_claims.zerocheck_claims.len()
+		);
+		assert_eq!(prove_output.zerocheck_witnesses.len(), verified_claims.zerocheck_claims.len());
+		for (prover_claim, verifier_claim) in prove_output
+			.reduced_claims
+			.evalcheck_claims
+			.iter()
+			.zip(&verified_claims.evalcheck_claims)
+		{
+			assert_eq!(prover_claim.eval_point, verifier_claim.eval_point);
+			assert_eq!(prover_claim.eval, verifier_claim.eval);
+		}
+		(verified_claims, witness)
+	}
+
+	#[test]
+	fn test_gkr_grand_product_reduces_to_input_evaluations() {
+		for n_vars in [0, 1, 5] {
+			let (claims, witness) = prove_verify(GkrGrandProduct, n_vars);
+			assert!(claims.zerocheck_claims.is_empty());
+
+			let [t_claim, u_claim] = <[_; 2]>::try_from(claims.evalcheck_claims).unwrap();
+			for (claim, poly) in [
+				(t_claim, &witness.t_polynomial),
+				(u_claim, &witness.u_polynomial),
+			] {
+				assert_eq!(claim.eval_point.len(), n_vars);
+				let query = MultilinearQuery::with_full_query(&claim.eval_point).unwrap();
+				assert_eq!(poly.evaluate(&query).unwrap(), claim.eval);
+			}
+		}
+	}
+
+	#[test]
+	fn test_committed_grand_product_behind_common_interface() {
+		let n_vars = 4;
+		let (_, _, _, f_prime_committed_id) = setup(n_vars, true);
+		let (claims, _) = prove_verify(
+			CommittedGrandProduct {
+				f_prime_committed_id,
+			},
+			n_vars,
+		);
+		assert_eq!(claims.zerocheck_claims.len(), 1);
+		assert_eq!(claims.evalcheck_claims.len(), 1);
+		assert_eq!(claims.zerocheck_claims[0].poly.n_vars(), n_vars + 1);
+	}
+
+	#[test]
+	fn test_gkr_grand_product_rejects_unequal_products() {
+		let n_vars = 4;
+		let (mut oracles, claim, witness, _) = setup(n_vars, false);
+		let challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+
+		let prove_output = GkrGrandProduct
+			.prove(
+				&mut oracles.clone(),
+				&mut MultilinearWitnessIndex::new(),
+				&claim,
+				witness,
+				challenger.clone(),
+			)
+			.unwrap();
+
+		// Case: honest proof of unequal products
+		assert_matches!(
+			GkrGrandProduct.verify(
+				&mut oracles,
+				&claim,
+				prove_output.proof.clone(),
+				challenger.clone()
+			),
+			Err(Error::Verification(VerificationError::ProductMismatch))
+		);
+
+		// Case: forged product of U
+		let mut proof = prove_output.proof;
+		proof.u_proof.product = proof.t_proof.product;
+		assert_matches!(
+			GkrGrandProduct.verify(&mut oracles, &claim, proof, challenger),
+			Err(Error::Verification(VerificationError::IncorrectEvaluation))
+		);
+	}
+}
@@ -1,14 +1,19 @@
 // Copyright 2024 Ulvetanna Inc.
 
 mod error;
+mod gkr;
+mod grand_product;
 #[allow(clippy::module_inception)]
 mod prodcheck;
 mod prove;
 mod verify;
 
 pub use error::*;
+pub use gkr::*;
+pub use grand_product::*;
 pub use prodcheck::{
 	ProdcheckClaim, ProdcheckProveOutput, ProdcheckWitness, ReducedProductCheckClaims,
+	SimpleMultGateComposition,
 };
 pub use prove::*;
 pub use verify::*;
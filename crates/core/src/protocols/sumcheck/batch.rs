@@ -10,18 +10,21 @@
 //! after the last sumcheck round message has been sent by the prover.
 
 use super::{
-	error::Error, prove::SumcheckProver, sumcheck::SumcheckReductor, SumcheckClaim, SumcheckRound,
-	SumcheckRoundClaim, VerificationError,
+	error::Error,
+	prove::SumcheckProver,
+	sumcheck::{SumcheckReductor, BATCH_COEFFS_LABEL, EVALS_LABEL, ROUND_LABEL},
+	SumcheckClaim, SumcheckRound, SumcheckRoundClaim, VerificationError,
 };
 use crate::{
-	challenger::{CanObserve, CanSample},
+	challenger::{CanObserve, CanSample, DomainSeparator, TranscriptReader},
 	polynomial::{CompositionPoly, MultilinearPoly},
 	protocols::{
 		abstract_sumcheck::{AbstractSumcheckProver, AbstractSumcheckReductor},
 		evalcheck::EvalcheckClaim,
 	},
+	serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes},
 };
-use binius_field::{Field, PackedField};
+use binius_field::{BinaryField8b, Field, PackedField};
 
 #[derive(Debug, Clone)]
 pub struct SumcheckBatchProof<F> {
@@ -30,6 +33,21 @@ pub struct SumcheckBatchProof<F> {
 	pub evals: Vec<F>,
 }
 
+impl<F: SerializeBytes> SerializeBytes for SumcheckBatchProof<F> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.rounds.serialize(write_buf);
+		self.evals.serialize(write_buf);
+	}
+}
+
+impl<F: DeserializeBytes> DeserializeBytes for SumcheckBatchProof<F> {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		let rounds = Vec::deserialize(read_buf)?;
+		let evals = Vec::deserialize(read_buf)?;
+		Ok(Self { rounds, evals })
+	}
+}
+
 #[derive(Debug)]
 pub struct SumcheckBatchProveOutput<F: Field> {
 	pub evalcheck_claims: Vec<EvalcheckClaim<F>>,
@@ -38,7 +56,9 @@ pub struct SumcheckBatchProveOutput<F: Field> {
 
 /// Prove a batched sumcheck instance.
 ///
-/// See module documentation for details.
+/// See module documentation for details. Running the prover against a
+/// [`TranscriptWriter`](crate::challenger::TranscriptWriter) writes the round messages and the
+/// final evaluations to the transcript.
 pub fn batch_prove<'a, F, PW, CW, M, CH>(
 	provers: impl IntoIterator<Item = SumcheckProver<'a, F, PW, CW, M>>,
 	mut challenger: CH,
@@ -49,7 +69,7 @@ where
 	PW::Scalar: From<F>,
 	CW: CompositionPoly<PW::Scalar>,
 	M: MultilinearPoly<PW> + Sync,
-	CH: CanObserve<F> + CanSample<F>,
+	CH: CanObserve<F> + CanSample<F> + DomainSeparator,
 {
 	let mut provers_vec = provers.into_iter().collect::<Vec<_>>();
 	// NOTE: Important to use stable sorting for prover-verifier consistency!
@@ -79,14 +99,14 @@ where
 				break;
 			}
 
-			let coeff = challenger.sample();
+			let coeff = challenger.sample_labeled(BATCH_COEFFS_LABEL);
 			batch_coeffs.push(coeff);
 
 			let proof = next_prover.execute_round(None)?;
 			mix_round_proofs(&mut batch_round_proof, &proof, coeff);
 		}
 
-		challenger.observe_slice(&batch_round_proof.coeffs);
+		challenger.observe_slice_labeled(ROUND_LABEL, &batch_round_proof.coeffs);
 		round_proofs.push(batch_round_proof);
 		prev_rd_challenge = Some(challenger.sample());
 	}
@@ -102,7 +122,11 @@ where
 		})
 		.collect::<Result<Vec<_>, _>>()?;
 
-	let evals = evalcheck_claims.iter().map(|claim| claim.eval).collect();
+	let evals = evalcheck_claims
+		.iter()
+		.map(|claim| claim.eval)
+		.collect::<Vec<_>>();
+	challenger.observe_slice_labeled(EVALS_LABEL, &evals);
 
 	let sumcheck_batch_proof = SumcheckBatchProof {
 		rounds: round_proofs,
@@ -125,27 +149,88 @@ pub fn batch_verify<F, CH>(
 ) -> Result<Vec<EvalcheckClaim<F>>, Error>
 where
 	F: Field,
-	CH: CanSample<F> + CanObserve<F>,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	let claims_vec = claims.into_iter().collect::<Vec<_>>();
+	let n_rounds = claims_vec
+		.iter()
+		.map(|claim| claim.n_vars())
+		.max()
+		.unwrap_or(0);
+
+	let SumcheckBatchProof { rounds, evals } = proof;
+	if rounds.len() != n_rounds {
+		return Err(VerificationError::NumberOfRounds.into());
+	}
+
+	let mut rounds = rounds.into_iter();
+	batch_verify_with(
+		claims_vec,
+		&mut challenger,
+		|challenger, _| {
+			let round_proof = rounds.next().expect("number of rounds is checked above");
+			challenger.observe_slice(round_proof.coeffs.as_slice());
+			Ok(round_proof)
+		},
+		|challenger, _| {
+			challenger.observe_slice(&evals);
+			Ok(evals)
+		},
+	)
+}
+
+/// Verify a batched sumcheck instance, reading the proof from a transcript.
+///
+/// The transcript is expected to be written by running [`batch_prove`] against a
+/// [`TranscriptWriter`](crate::challenger::TranscriptWriter).
+pub fn batch_verify_with_transcript<F, C>(
+	claims: impl IntoIterator<Item = SumcheckClaim<F>>,
+	transcript: &mut TranscriptReader<C>,
+) -> Result<Vec<EvalcheckClaim<F>>, Error>
+where
+	F: Field + DeserializeBytes,
+	C: CanObserve<BinaryField8b> + CanSample<F> + DomainSeparator,
+{
+	batch_verify_with(
+		claims.into_iter().collect(),
+		transcript,
+		|transcript, n_coeffs| {
+			let coeffs = transcript.read_vec(n_coeffs)?;
+			Ok(SumcheckRound { coeffs })
+		},
+		|transcript, n_evals| Ok(transcript.read_vec(n_evals)?),
+	)
+}
+
+/// Shared implementation of the batched sumcheck verifier.
+///
+/// The prover messages are obtained through `read_round` and `read_evals`, which are responsible
+/// for observing them with the challenger after the message label has been absorbed. Both are
+/// passed the expected number of values in the message.
+fn batch_verify_with<F, CH>(
+	mut claims_vec: Vec<SumcheckClaim<F>>,
+	challenger: &mut CH,
+	mut read_round: impl FnMut(&mut CH, usize) -> Result<SumcheckRound<F>, Error>,
+	read_evals: impl FnOnce(&mut CH, usize) -> Result<Vec<F>, Error>,
+) -> Result<Vec<EvalcheckClaim<F>>, Error>
+where
+	F: Field,
+	CH: CanSample<F> + DomainSeparator,
 {
 	let sumcheck_reductor = SumcheckReductor;
-	let mut claims_vec = claims.into_iter().collect::<Vec<_>>();
 	// NOTE: Important to use stable sorting for prover-verifier consistency!
 	claims_vec.sort_by_key(|claim| claim.poly.n_vars());
 	claims_vec.reverse();
 
 	let n_rounds = claims_vec.first().map(|claim| claim.n_vars()).unwrap_or(0);
 
-	if proof.rounds.len() != n_rounds {
-		return Err(VerificationError::NumberOfRounds.into());
-	}
-
 	let mut batch_coeffs = Vec::with_capacity(claims_vec.len());
 	let mut rd_claim = SumcheckRoundClaim {
 		partial_point: Vec::with_capacity(n_rounds),
 		current_round_sum: F::ZERO,
 	};
 
-	for (round_no, round_proof) in proof.rounds.iter().enumerate() {
+	for round_no in 0..n_rounds {
 		let n_vars = n_rounds - round_no;
 
 		// Mix in new sumcheck claims with the appropriate number of variables
@@ -154,32 +239,41 @@ where
 				break;
 			}
 
-			let challenge = challenger.sample();
+			let challenge = challenger.sample_labeled(BATCH_COEFFS_LABEL);
 			batch_coeffs.push(challenge);
 
 			rd_claim.current_round_sum += next_claim.sum * challenge;
 		}
 
-		challenger.observe_slice(round_proof.coeffs.as_slice());
+		let n_round_coeffs = claims_vec[..batch_coeffs.len()]
+			.iter()
+			.map(|claim| claim.poly.max_individual_degree())
+			.max()
+			.unwrap_or(0);
+		challenger.observe_label(ROUND_LABEL.as_bytes());
+		let round_proof = read_round(challenger, n_round_coeffs)?;
 		rd_claim = sumcheck_reductor.reduce_intermediate_round_claim(
 			round_no,
 			rd_claim,
 			challenger.sample(),
-			round_proof.clone(),
+			round_proof,
 		)?;
 	}
 
+	challenger.observe_label(EVALS_LABEL.as_bytes());
+	let evals = read_evals(challenger, claims_vec.len())?;
+
 	// Mix in remaining sumcheck claims with 0 variables
 	for claim in claims_vec[batch_coeffs.len()..].iter() {
 		debug_assert_eq!(claim.n_vars(), 0);
 
-		let challenge = challenger.sample();
+		let challenge = challenger.sample_labeled(BATCH_COEFFS_LABEL);
 		batch_coeffs.push(challenge);
 
 		rd_claim.current_round_sum += claim.sum * challenge;
 	}
 
-	batch_verify_final(&claims_vec, &batch_coeffs, &proof.evals, rd_claim)
+	batch_verify_final(&claims_vec, &batch_coeffs, &evals, rd_claim)
 }
 
 /// Verifies a batch sumcheck proof final step, reducing the final claim to evaluation claims.
@@ -1,6 +1,9 @@
 // Copyright 2023-2024 Ulvetanna Inc.
 
-use crate::{oracle::Error as IOPolynomialError, polynomial::Error as PolynomialError};
+use crate::{
+	oracle::Error as IOPolynomialError, polynomial::Error as PolynomialError,
+	serialization::Error as SerializationError,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -12,6 +15,8 @@ pub enum Error {
 	OraclesOutOfOrder,
 	#[error("the evaluation domain does not match the expected size")]
 	EvaluationDomainMismatch,
+	#[error("the sumcheck mask must have degree {expected}, got {actual}")]
+	IncorrectMaskDegree { expected: usize, actual: usize },
 	#[error("prover has mismatch between claim and witness: {0}")]
 	ProverClaimWitnessMismatch(String),
 	#[error("IOPolynomial error: {0}")]
@@ -20,6 +25,8 @@ pub enum Error {
 	Polynomial(#[from] PolynomialError),
 	#[error("verification failure: {0}")]
 	Verification(#[from] VerificationError),
+	#[error("transcript error: {0}")]
+	Transcript(#[from] SerializationError),
 }
 
 #[derive(Debug, thiserror::Error)]
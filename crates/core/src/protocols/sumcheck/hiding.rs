@@ -0,0 +1,595 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Opt-in hiding mode for the sumcheck protocol.
+//!
+//! The round messages of a plain sumcheck are partial sums of the witness, so they leak
+//! information about it. In hiding mode, the prover first sends the sum of a random masking
+//! polynomial $g$ that it has committed to ahead of time. The verifier then samples a
+//! coefficient $\rho$, and the parties run the sumcheck on $f + \rho g$. The masking polynomial is
+//!
+//! $$
+//! g(X) = \sum_{k=1}^d M_k(X) E_k(X), \quad E_1(X) = 1, \quad
+//! E_k(X) = \sum_{i=0}^{n-1} X_i^{k-1} \text{ for } k > 1,
+//! $$
+//!
+//! where $M_1, \ldots, M_d$ are uniformly random multilinears and $d$ is the degree of the round
+//! polynomials. Every coefficient sent in a round is shifted by a fresh partial sum of one of the
+//! $M_k$, so for any fixed verifier challenges, the round messages and the reduced evaluation claim
+//! are distributed independently of the witness. This follows the masking technique of [XZZPS19],
+//! with multilinear masks in place of univariate ones, because a sum of univariate masks vanishes
+//! over the hypercube in characteristic 2.
+//!
+//! The reduced evaluation claim is randomized by $\rho g(r)$. It is a claim on the composite of the
+//! witness and mask multilinears, so the mask multilinears must be committed alongside the witness,
+//! using a hiding polynomial commitment.
+//!
+//! Hiding mode covers the round messages and, together with a hiding commitment, the committed
+//! witness. It does not make a full proof zero-knowledge: evalcheck subsequently reduces the claim
+//! to evaluations of the individual witness multilinears at the challenge point, and these
+//! evaluations are sent in the clear.
+//!
+//! [XZZPS19]: https://eprint.iacr.org/2019/317
+
+use super::{
+	error::Error,
+	sumcheck::{SumcheckReductor, ROUND_LABEL},
+	SumcheckClaim, SumcheckRound, SumcheckRoundClaim, SumcheckWitness, VerificationError,
+};
+use crate::{
+	challenger::{CanObserve, CanSample, DomainSeparator},
+	oracle::{CompositePolyOracle, Error as OracleError, MultilinearPolyOracle},
+	polynomial::{
+		evaluate_univariate, CompositionPoly, Error as PolynomialError, EvaluationDomain,
+		MultilinearExtension, MultilinearPoly, MultivariatePoly,
+	},
+	protocols::{
+		abstract_sumcheck::{AbstractSumcheckProver, AbstractSumcheckReductor},
+		evalcheck::EvalcheckClaim,
+		sumcheck::SumcheckProver,
+	},
+	serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes},
+};
+use binius_field::{Field, PackedField, TowerField};
+use rand::RngCore;
+use std::{iter::repeat_with, sync::Arc};
+use tracing::instrument;
+
+/// Challenger label for the sum of the masking polynomial.
+const MASK_SUM_LABEL: &str = "sumcheck.mask_sum";
+/// Challenger label for the coefficient of the masking polynomial.
+const MASK_COEFF_LABEL: &str = "sumcheck.mask_coeff";
+
+/// The random multilinears $M_1, \ldots, M_d$ defining the masking polynomial of a hiding
+/// sumcheck.
+///
+/// See the module documentation for the definition of the masking polynomial.
+#[derive(Debug, Clone)]
+pub struct SumcheckMask<F: Field> {
+	multilinears: Vec<MultilinearExtension<'static, F>>,
+}
+
+impl<F: Field> SumcheckMask<F> {
+	/// Construct a mask from the multilinears $M_1, \ldots, M_d$.
+	///
+	/// Throws if no multilinears are given or if they do not all have the same number of variables.
+	pub fn new(multilinears: Vec<MultilinearExtension<'static, F>>) -> Result<Self, Error> {
+		let n_vars = multilinears
+			.first()
+			.ok_or_else(|| Error::ImproperInput("a sumcheck mask must not be empty".to_string()))?
+			.n_vars();
+		if let Some(multilinear) = multilinears.iter().find(|m| m.n_vars() != n_vars) {
+			return Err(PolynomialError::IncorrectNumberOfVariables {
+				expected: n_vars,
+				actual: multilinear.n_vars(),
+			}
+			.into());
+		}
+		Ok(Self { multilinears })
+	}
+
+	/// Sample a uniformly random mask for round polynomials of the given degree.
+	pub fn random(n_vars: usize, degree: usize, mut rng: impl RngCore) -> Result<Self, Error> {
+		let multilinears = repeat_with(|| {
+			let evals = repeat_with(|| F::random(&mut rng))
+				.take(1 << n_vars)
+				.collect();
+			MultilinearExtension::from_values(evals)
+		})
+		.take(degree)
+		.collect::<Result<_, _>>()?;
+		Self::new(multilinears)
+	}
+
+	pub fn n_vars(&self) -> usize {
+		self.multilinears[0].n_vars()
+	}
+
+	/// The degree of the round polynomials of the masking polynomial.
+	pub fn degree(&self) -> usize {
+		self.multilinears.len()
+	}
+
+	/// The multilinears $M_1, \ldots, M_d$, which the prover must commit to.
+	pub fn multilinears(&self) -> &[MultilinearExtension<'static, F>] {
+		&self.multilinears
+	}
+
+	/// The sum of the masking polynomial over the hypercube.
+	pub fn sum(&self) -> F {
+		let coeffs = MaskProverState::new(self).round_coeffs();
+		evaluate_univariate(&coeffs, F::ZERO) + evaluate_univariate(&coeffs, F::ONE)
+	}
+
+	/// Evaluate the masking polynomial at a point.
+	pub fn evaluate(&self, point: &[F]) -> Result<F, PolynomialError> {
+		if point.len() != self.n_vars() {
+			return Err(PolynomialError::IncorrectQuerySize {
+				expected: self.n_vars(),
+			});
+		}
+		let mut state = MaskProverState::new(self);
+		for &challenge in point {
+			state.fold(challenge);
+		}
+		Ok(inner_product(&state.evals(), &mask_weights(point, self.degree())))
+	}
+}
+
+/// A hiding sumcheck proof, consisting of the sum of the masking polynomial and the masked round
+/// messages.
+#[derive(Debug, Clone)]
+pub struct HidingSumcheckProof<F> {
+	pub mask_sum: F,
+	pub rounds: Vec<SumcheckRound<F>>,
+}
+
+impl<F: SerializeBytes> SerializeBytes for HidingSumcheckProof<F> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.mask_sum.serialize(write_buf);
+		self.rounds.serialize(write_buf);
+	}
+}
+
+impl<F: DeserializeBytes> DeserializeBytes for HidingSumcheckProof<F> {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		let mask_sum = F::deserialize(read_buf)?;
+		let rounds = Vec::deserialize(read_buf)?;
+		Ok(Self { mask_sum, rounds })
+	}
+}
+
+#[derive(Debug)]
+pub struct HidingSumcheckProveOutput<F: Field> {
+	pub evalcheck_claim: EvalcheckClaim<F>,
+	pub sumcheck_proof: HidingSumcheckProof<F>,
+}
+
+/// The composition $s \cdot C(Y) + \sum_k w_k Z_k$ of a masked evaluation claim.
+///
+/// The first variables $Y$ are the inputs of the original composition $C$, and the remaining
+/// variables $Z$ are the evaluations of the mask multilinears. The weights $w_k$ fold together the
+/// masking coefficient and the public factors $E_k$ evaluated at the challenge point.
+#[derive(Debug, Clone)]
+pub struct MaskedComposition<F: Field> {
+	inner: Arc<dyn MultivariatePoly<F>>,
+	scale: F,
+	mask_weights: Vec<F>,
+}
+
+impl<F: TowerField> CompositionPoly<F> for MaskedComposition<F> {
+	fn n_vars(&self) -> usize {
+		self.inner.n_vars() + self.mask_weights.len()
+	}
+
+	fn degree(&self) -> usize {
+		self.inner.degree().max(1)
+	}
+
+	fn evaluate<P: PackedField<Scalar = F>>(&self, query: &[P]) -> Result<P, PolynomialError> {
+		if query.len() != CompositionPoly::<F>::n_vars(self) {
+			return Err(PolynomialError::IncorrectQuerySize {
+				expected: CompositionPoly::<F>::n_vars(self),
+			});
+		}
+
+		let (inner_query, mask_query) = query.split_at(self.inner.n_vars());
+		let mut scalar_query = vec![F::ZERO; inner_query.len()];
+		let mut result = P::zero();
+		for i in 0..P::WIDTH {
+			for (scalar, packed) in scalar_query.iter_mut().zip(inner_query) {
+				*scalar = packed.get(i);
+			}
+			result.set(i, self.inner.evaluate(&scalar_query)?);
+		}
+
+		result *= self.scale;
+		for (&mask_eval, &weight) in mask_query.iter().zip(&self.mask_weights) {
+			result += mask_eval * weight;
+		}
+		Ok(result)
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		self.inner.binary_tower_level().max(F::TOWER_LEVEL)
+	}
+}
+
+/// Prove a sumcheck to evalcheck reduction in hiding mode.
+///
+/// The mask multilinears must already be committed, and `mask_oracles` are their oracles. The
+/// degree of the mask must equal the degree of the composition in the claim.
+#[instrument(skip_all, name = "sumcheck::prove_hiding")]
+#[allow(clippy::too_many_arguments)]
+pub fn prove_hiding<F, PW, CW, M, CH>(
+	claim: &SumcheckClaim<F>,
+	witness: SumcheckWitness<PW, CW, M>,
+	mask: &SumcheckMask<F>,
+	mask_oracles: &[MultilinearPolyOracle<F>],
+	domain: &EvaluationDomain<PW::Scalar>,
+	mut challenger: CH,
+	switchover_fn: impl Fn(usize) -> usize,
+) -> Result<HidingSumcheckProveOutput<F>, Error>
+where
+	F: TowerField + From<PW::Scalar>,
+	PW: PackedField,
+	PW::Scalar: From<F>,
+	CW: CompositionPoly<PW::Scalar>,
+	M: MultilinearPoly<PW> + Clone + Sync,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	let n_vars = claim.n_vars();
+	check_mask(n_vars, claim.poly.max_individual_degree(), mask, mask_oracles)?;
+
+	let mut sumcheck_prover = SumcheckProver::new(domain, claim.clone(), witness, switchover_fn)?;
+	let masked_rounds =
+		prove_masked_rounds(n_vars, mask, ROUND_LABEL, &mut challenger, |prev_rd_challenge| {
+			let SumcheckRound { mut coeffs } =
+				AbstractSumcheckProver::execute_round(&mut sumcheck_prover, prev_rd_challenge)?;
+			// Recover the highest degree coefficient, which the prover trims from the message
+			let current_round_sum = sumcheck_prover.round_claim().current_round_sum;
+			coeffs.push(current_round_sum - coeffs[0] - coeffs.iter().sum::<F>());
+			Ok::<_, Error>(coeffs)
+		})?;
+
+	let evalcheck_claim = AbstractSumcheckProver::finalize(
+		sumcheck_prover,
+		masked_rounds.challenges.last().copied(),
+	)?;
+	let evalcheck_claim = masked_evalcheck_claim(
+		&claim.poly,
+		mask_oracles,
+		F::ONE,
+		masked_rounds.mask_coeff,
+		&evalcheck_claim.eval_point,
+		evalcheck_claim.eval,
+		&masked_rounds.mask_evals,
+	)?;
+
+	Ok(HidingSumcheckProveOutput {
+		evalcheck_claim,
+		sumcheck_proof: HidingSumcheckProof {
+			mask_sum: masked_rounds.mask_sum,
+			rounds: masked_rounds.rounds,
+		},
+	})
+}
+
+/// Verify a sumcheck to evalcheck reduction in hiding mode.
+///
+/// The returned evalcheck claim is on the composite of the claim's multilinears and the mask
+/// multilinears.
+#[instrument(skip_all, name = "sumcheck::verify_hiding")]
+pub fn verify_hiding<F, CH>(
+	claim: &SumcheckClaim<F>,
+	mask_oracles: &[MultilinearPolyOracle<F>],
+	proof: HidingSumcheckProof<F>,
+	mut challenger: CH,
+) -> Result<EvalcheckClaim<F>, Error>
+where
+	F: TowerField,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	let degree = claim.poly.max_individual_degree();
+	if mask_oracles.len() != degree {
+		return Err(Error::IncorrectMaskDegree {
+			expected: degree,
+			actual: mask_oracles.len(),
+		});
+	}
+
+	let (mask_coeff, round_claim) = verify_masked_rounds(
+		claim.sum,
+		claim.n_vars(),
+		degree,
+		proof,
+		ROUND_LABEL,
+		&mut challenger,
+	)?;
+	let evalcheck_claim = masked_evalcheck_claim(
+		&claim.poly,
+		mask_oracles,
+		F::ONE,
+		mask_coeff,
+		&round_claim.partial_point,
+		round_claim.current_round_sum,
+		&[],
+	)?;
+	Ok(evalcheck_claim)
+}
+
+/// The result of the prover side of [`prove_masked_rounds`].
+pub(crate) struct MaskedRounds<F> {
+	pub mask_sum: F,
+	pub mask_coeff: F,
+	pub rounds: Vec<SumcheckRound<F>>,
+	/// The verifier challenges of all rounds.
+	pub challenges: Vec<F>,
+	/// The evaluations of the mask multilinears at the challenge point.
+	pub mask_evals: Vec<F>,
+}
+
+/// Run the prover side of the masked sumcheck rounds.
+///
+/// `round_poly` is called once per round with the previous round's challenge and returns all
+/// monomial coefficients of the unmasked round polynomial, whose degree must not exceed the degree
+/// of the mask. The masked round messages follow the sumcheck convention of omitting the highest
+/// degree coefficient.
+pub(crate) fn prove_masked_rounds<F, CH, E>(
+	n_vars: usize,
+	mask: &SumcheckMask<F>,
+	round_label: &str,
+	challenger: &mut CH,
+	mut round_poly: impl FnMut(Option<F>) -> Result<Vec<F>, E>,
+) -> Result<MaskedRounds<F>, E>
+where
+	F: Field,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	let degree = mask.degree();
+	let mask_sum = mask.sum();
+	challenger.observe_labeled(MASK_SUM_LABEL, mask_sum);
+	let mask_coeff = challenger.sample_labeled(MASK_COEFF_LABEL);
+
+	let mut mask_state = MaskProverState::new(mask);
+	let mut prev_rd_challenge = None;
+	let mut rounds = Vec::with_capacity(n_vars);
+	let mut challenges = Vec::with_capacity(n_vars);
+	for _ in 0..n_vars {
+		if let Some(challenge) = prev_rd_challenge {
+			mask_state.fold(challenge);
+		}
+
+		let mut coeffs = round_poly(prev_rd_challenge)?;
+		debug_assert!(coeffs.len() <= degree + 1);
+		coeffs.resize(degree + 1, F::ZERO);
+		for (coeff, mask_coeff_i) in coeffs.iter_mut().zip(mask_state.round_coeffs()) {
+			*coeff += mask_coeff * mask_coeff_i;
+		}
+		coeffs.truncate(degree);
+
+		challenger.observe_slice_labeled(round_label, &coeffs);
+		let challenge = challenger.sample();
+		rounds.push(SumcheckRound { coeffs });
+		challenges.push(challenge);
+		prev_rd_challenge = Some(challenge);
+	}
+
+	if let Some(challenge) = prev_rd_challenge {
+		mask_state.fold(challenge);
+	}
+
+	Ok(MaskedRounds {
+		mask_sum,
+		mask_coeff,
+		rounds,
+		challenges,
+		mask_evals: mask_state.evals(),
+	})
+}
+
+/// Run the verifier side of the masked sumcheck rounds.
+///
+/// Returns the masking coefficient and the final round claim.
+pub(crate) fn verify_masked_rounds<F, CH>(
+	sum: F,
+	n_vars: usize,
+	degree: usize,
+	proof: HidingSumcheckProof<F>,
+	round_label: &str,
+	challenger: &mut CH,
+) -> Result<(F, SumcheckRoundClaim<F>), Error>
+where
+	F: Field,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	let HidingSumcheckProof { mask_sum, rounds } = proof;
+	if rounds.len() != n_vars {
+		return Err(VerificationError::NumberOfRounds.into());
+	}
+
+	challenger.observe_labeled(MASK_SUM_LABEL, mask_sum);
+	let mask_coeff: F = challenger.sample_labeled(MASK_COEFF_LABEL);
+
+	let reductor = SumcheckReductor;
+	let mut round_claim = SumcheckRoundClaim {
+		partial_point: Vec::with_capacity(n_vars),
+		current_round_sum: sum + mask_coeff * mask_sum,
+	};
+	for (round, round_proof) in rounds.into_iter().enumerate() {
+		if round_proof.coeffs.len() != degree {
+			return Err(VerificationError::NumberOfCoefficients.into());
+		}
+		challenger.observe_slice_labeled(round_label, &round_proof.coeffs);
+		let challenge = challenger.sample();
+		round_claim =
+			reductor.reduce_intermediate_round_claim(round, round_claim, challenge, round_proof)?;
+	}
+
+	Ok((mask_coeff, round_claim))
+}
+
+/// Construct the evalcheck claim on the composite of the claim's multilinears and the mask
+/// multilinears.
+///
+/// The composition is `scale` times the original composition plus the masking polynomial times
+/// `mask_coeff`. If `mask_evals` is not empty, it is used to add the masking polynomial to `eval`,
+/// which is the prover's view of the unmasked evaluation; otherwise, `eval` is already masked.
+pub(crate) fn masked_evalcheck_claim<F: TowerField>(
+	poly: &CompositePolyOracle<F>,
+	mask_oracles: &[MultilinearPolyOracle<F>],
+	scale: F,
+	mask_coeff: F,
+	eval_point: &[F],
+	eval: F,
+	mask_evals: &[F],
+) -> Result<EvalcheckClaim<F>, OracleError> {
+	let mask_weights = mask_weights(eval_point, mask_oracles.len())
+		.into_iter()
+		.map(|weight| weight * mask_coeff)
+		.collect::<Vec<_>>();
+	let eval = if mask_evals.is_empty() {
+		eval
+	} else {
+		scale * eval + inner_product(mask_evals, &mask_weights)
+	};
+
+	let inner = poly
+		.inner_polys()
+		.into_iter()
+		.chain(mask_oracles.iter().cloned())
+		.collect();
+	let composition = MaskedComposition {
+		inner: poly.composition(),
+		scale,
+		mask_weights,
+	};
+
+	Ok(EvalcheckClaim {
+		poly: CompositePolyOracle::new(poly.n_vars(), inner, composition)?,
+		eval_point: eval_point.to_vec(),
+		eval,
+		is_random_point: true,
+	})
+}
+
+/// Check that a mask and its oracles are compatible with a claim.
+pub(crate) fn check_mask<F: Field>(
+	n_vars: usize,
+	degree: usize,
+	mask: &SumcheckMask<F>,
+	mask_oracles: &[MultilinearPolyOracle<F>],
+) -> Result<(), Error> {
+	if mask.degree() != degree || mask_oracles.len() != degree {
+		return Err(Error::IncorrectMaskDegree {
+			expected: degree,
+			actual: mask.degree().min(mask_oracles.len()),
+		});
+	}
+	if mask.n_vars() != n_vars || mask_oracles.iter().any(|oracle| oracle.n_vars() != n_vars) {
+		return Err(Error::ImproperInput(format!(
+			"the sumcheck mask must have {n_vars} variables"
+		)));
+	}
+	Ok(())
+}
+
+/// The factors $E_1(r), \ldots, E_d(r)$ of the mask multilinears in the masking polynomial.
+fn mask_weights<F: Field>(point: &[F], degree: usize) -> Vec<F> {
+	let mut weights = vec![F::ZERO; degree];
+	if let Some(first) = weights.first_mut() {
+		*first = F::ONE;
+	}
+	for &r in point {
+		let mut power = r;
+		for weight in weights.iter_mut().skip(1) {
+			*weight += power;
+			power *= r;
+		}
+	}
+	weights
+}
+
+fn inner_product<F: Field>(a: &[F], b: &[F]) -> F {
+	a.iter().zip(b).map(|(&a_i, &b_i)| a_i * b_i).sum()
+}
+
+/// The sum of the bits of a hypercube vertex index, as a field element.
+fn hamming_weight<F: Field>(index: usize) -> F {
+	repeat_with(|| F::ONE)
+		.take(index.count_ones() as usize)
+		.sum()
+}
+
+/// Prover state computing the round polynomials of the masking polynomial.
+struct MaskProverState<F: Field> {
+	/// The mask multilinears, partially evaluated at the challenges of the previous rounds.
+	tables: Vec<Vec<F>>,
+	/// The sums $\sum_{j < i} r_j^{k-1}$ over the challenges of the previous rounds, which are the
+	/// contributions of the bound variables to $E_k$.
+	power_sums: Vec<F>,
+}
+
+impl<F: Field> MaskProverState<F> {
+	fn new(mask: &SumcheckMask<F>) -> Self {
+		let tables = mask
+			.multilinears
+			.iter()
+			.map(|multilinear| multilinear.evals().to_vec())
+			.collect::<Vec<_>>();
+		let power_sums = vec![F::ZERO; tables.len()];
+		Self { tables, power_sums }
+	}
+
+	/// The monomial coefficients of the current round polynomial of the masking polynomial.
+	///
+	/// Writing $A_k(X)$ for the sum of $M_k$ over the remaining hypercube with the current
+	/// variable set to $X$ and $P_k(X)$ for the same sum weighted by the Hamming weight of the
+	/// remaining vertex, the round polynomial is
+	/// $A_1(X) + \sum_{k > 1} (S_k + X^{k-1}) A_k(X) + P_k(X)$, where $S_k$ is the power sum of the
+	/// previous challenges.
+	fn round_coeffs(&self) -> Vec<F> {
+		let degree = self.tables.len();
+		let mut coeffs = vec![F::ZERO; degree + 1];
+		for (k, (table, &power_sum)) in self.tables.iter().zip(&self.power_sums).enumerate() {
+			let (mut a_0, mut a_1, mut p_0, mut p_1) = (F::ZERO, F::ZERO, F::ZERO, F::ZERO);
+			for (i, pair) in table.chunks_exact(2).enumerate() {
+				let weight = hamming_weight::<F>(i);
+				a_0 += pair[0];
+				a_1 += pair[1];
+				p_0 += pair[0] * weight;
+				p_1 += pair[1] * weight;
+			}
+
+			if k == 0 {
+				coeffs[0] += a_0;
+				coeffs[1] += a_1 - a_0;
+			} else {
+				coeffs[0] += power_sum * a_0 + p_0;
+				coeffs[1] += power_sum * (a_1 - a_0) + (p_1 - p_0);
+				coeffs[k] += a_0;
+				coeffs[k + 1] += a_1 - a_0;
+			}
+		}
+		coeffs
+	}
+
+	fn fold(&mut self, challenge: F) {
+		for table in self.tables.iter_mut() {
+			*table = table
+				.chunks_exact(2)
+				.map(|pair| pair[0] + challenge * (pair[1] - pair[0]))
+				.collect();
+		}
+
+		let mut power = challenge;
+		for power_sum in self.power_sums.iter_mut().skip(1) {
+			*power_sum += power;
+			power *= challenge;
+		}
+	}
+
+	/// The evaluations of the mask multilinears, once all variables have been folded.
+	fn evals(&self) -> Vec<F> {
+		self.tables.iter().map(|table| table[0]).collect()
+	}
+}
@@ -7,6 +7,7 @@
 
 mod batch;
 mod error;
+mod hiding;
 mod prove;
 #[allow(clippy::module_inception)]
 mod sumcheck;
@@ -16,7 +17,15 @@ mod verify;
 
 pub use batch::*;
 pub use error::*;
+pub(crate) use hiding::{
+	check_mask, masked_evalcheck_claim, prove_masked_rounds, verify_masked_rounds,
+};
+pub use hiding::{
+	prove_hiding, verify_hiding, HidingSumcheckProof, HidingSumcheckProveOutput, MaskedComposition,
+	SumcheckMask,
+};
 pub use prove::*;
+pub(crate) use sumcheck::SumcheckReductor;
 pub use sumcheck::{
 	SumcheckClaim, SumcheckProof, SumcheckProveOutput, SumcheckRound, SumcheckRoundClaim,
 	SumcheckWitness,
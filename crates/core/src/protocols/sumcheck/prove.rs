@@ -4,11 +4,11 @@ use super::{
 	error::Error,
 	sumcheck::{
 		SumcheckClaim, SumcheckProveOutput, SumcheckReductor, SumcheckRound, SumcheckRoundClaim,
-		SumcheckWitness,
+		SumcheckWitness, ROUND_LABEL,
 	},
 };
 use crate::{
-	challenger::{CanObserve, CanSample},
+	challenger::{CanObserve, CanSample, DomainSeparator},
 	oracle::CompositePolyOracle,
 	polynomial::{
 		extrapolate_line, CompositionPoly, Error as PolynomialError, EvaluationDomain,
@@ -25,7 +25,6 @@ use crate::{
 };
 use binius_field::{Field, PackedField};
 use getset::Getters;
-use rayon::prelude::*;
 use std::fmt::Debug;
 use tracing::instrument;
 
@@ -44,12 +43,12 @@ where
 	PW::Scalar: From<F>,
 	CW: CompositionPoly<PW::Scalar>,
 	M: MultilinearPoly<PW> + Clone + Sync,
-	CH: CanSample<F> + CanObserve<F>,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
 {
 	let sumcheck_prover = SumcheckProver::new(domain, claim.clone(), witness, switchover_fn)?;
 
 	let (evalcheck_claim, rounds) =
-		abstract_sumcheck::prove(claim.n_vars(), sumcheck_prover, challenger)?;
+		abstract_sumcheck::prove(claim.n_vars(), sumcheck_prover, ROUND_LABEL, challenger)?;
 
 	let sumcheck_proof = SumcheckProof { rounds };
 	let output = SumcheckProveOutput {
@@ -205,14 +204,9 @@ where
 			domain_points: self.domain.points(),
 		};
 
-		let rd_vars = self.n_vars() - self.round;
-		let vertex_state_iterator = (0..1 << (rd_vars - 1)).into_par_iter().map(|_i| ());
-
-		let round_coeffs = self.state.calculate_round_coeffs(
-			evaluator,
-			self.round_claim.current_round_sum.into(),
-			vertex_state_iterator,
-		)?;
+		let round_coeffs = self
+			.state
+			.calculate_sparse_round_coeffs(evaluator, self.round_claim.current_round_sum.into())?;
 		let coeffs = round_coeffs.into_iter().map(Into::into).collect::<Vec<F>>();
 
 		let proof_round = SumcheckRound { coeffs };
@@ -328,6 +322,11 @@ impl<'a, F: Field, C: CompositionPoly<F>> AbstractSumcheckEvaluator<F>
 		}
 	}
 
+	fn skips_zeros_of(&self, multilinear_index: usize) -> bool {
+		// Vertices where a factor of the composition vanishes contribute zero at every point
+		self.composition.vanishes_with_var(multilinear_index)
+	}
+
 	fn round_evals_to_coeffs(
 		&self,
 		current_round_sum: F,
@@ -14,6 +14,13 @@ use crate::{
 };
 use binius_field::Field;
 
+/// Challenger label for the sumcheck round messages.
+pub(super) const ROUND_LABEL: &str = "sumcheck.round";
+/// Challenger label for the coefficients mixing claims into a batched sumcheck.
+pub(super) const BATCH_COEFFS_LABEL: &str = "sumcheck.batch_coeffs";
+/// Challenger label for the multilinear evaluations at the end of a batched sumcheck.
+pub(super) const EVALS_LABEL: &str = "sumcheck.evals";
+
 pub type SumcheckRound<F> = AbstractSumcheckRound<F>;
 pub type SumcheckProof<F> = AbstractSumcheckProof<F>;
 
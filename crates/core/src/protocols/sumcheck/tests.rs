@@ -1,16 +1,27 @@
 // Copyright 2024 Ulvetanna Inc.
 
 use crate::{
-	challenger::HashChallenger,
-	oracle::{CommittedBatchSpec, CommittedId, CompositePolyOracle, MultilinearOracleSet},
+	challenger::{HashChallenger, TranscriptReader, TranscriptWriter},
+	oracle::{
+		CommittedBatchSpec, CommittedId, CompositePolyOracle, MultilinearOracleSet,
+		MultilinearPolyOracle,
+	},
 	polynomial::{
 		CompositionPoly, Error as PolynomialError, EvaluationDomain, MultilinearComposite,
 		MultilinearExtension, MultilinearExtensionSpecialized, MultilinearQuery,
+		SparseMultilinearExtension,
 	},
 	protocols::{
-		sumcheck::{batch_prove, batch_verify, prove, verify, SumcheckClaim, SumcheckProver},
-		test_utils::{transform_poly, TestProductComposition},
+		sumcheck::{
+			batch_prove, batch_verify, batch_verify_with_transcript, prove, prove_hiding, verify,
+			verify_hiding, verify_with_transcript, HidingSumcheckProof, SumcheckBatchProof,
+			SumcheckClaim, SumcheckMask, SumcheckProver,
+		},
+		test_utils::{
+			solve_linear_system, transform_poly, FixedChallenger, TestProductComposition,
+		},
 	},
+	serialization::{from_versioned_bytes, to_versioned_bytes},
 	witness::MultilinearWitnessIndex,
 };
 use binius_field::{
@@ -21,7 +32,7 @@ use binius_hash::GroestlHasher;
 use p3_util::log2_ceil_usize;
 use rand::{rngs::StdRng, SeedableRng};
 use rayon::current_num_threads;
-use std::iter::repeat_with;
+use std::iter::{self, repeat_with};
 
 fn generate_poly_and_sum_helper<F, FE>(
 	rng: &mut StdRng,
@@ -222,6 +233,98 @@ fn test_prove_verify_interaction_pigeonhole_cores() {
 	}
 }
 
+#[test]
+fn test_prove_verify_with_sparse_multilinear() {
+	type F = BinaryField32b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+	let n_vars = 8;
+	let n_multilinears = 3;
+
+	// A sparse selector multiplying dense columns
+	let selector_entries = (0..1 << n_vars)
+		.step_by(37)
+		.map(|i| (i, <F as Field>::random(&mut rng)))
+		.collect::<Vec<_>>();
+	let sparse_selector =
+		SparseMultilinearExtension::<F, FE>::from_entries(n_vars, selector_entries).unwrap();
+	let mut dense_selector_values = vec![F::ZERO; 1 << n_vars];
+	for (i, value) in sparse_selector.iter_nonzero() {
+		dense_selector_values[i] = value;
+	}
+	let dense_columns = repeat_with(|| {
+		let values = repeat_with(|| Field::random(&mut rng))
+			.take(1 << n_vars)
+			.collect::<Vec<F>>();
+		MultilinearExtension::from_values(values).unwrap()
+	})
+	.take(n_multilinears - 1)
+	.collect::<Vec<_>>();
+
+	let composition = TestProductComposition::new(n_multilinears);
+	let dense_poly = MultilinearComposite::<FE, _, _>::new(
+		n_vars,
+		composition.clone(),
+		iter::once(MultilinearExtension::from_values(dense_selector_values).unwrap())
+			.chain(dense_columns.iter().cloned())
+			.map(|multilin| multilin.specialize_arc_dyn())
+			.collect(),
+	)
+	.unwrap();
+	let sparse_poly = MultilinearComposite::<FE, _, _>::new(
+		n_vars,
+		composition.clone(),
+		iter::once(sparse_selector.upcast_arc_dyn())
+			.chain(
+				dense_columns
+					.iter()
+					.map(|multilin| multilin.clone().specialize_arc_dyn()),
+			)
+			.collect(),
+	)
+	.unwrap();
+
+	let sum = (0..1 << n_vars)
+		.map(|i| dense_poly.evaluate_on_hypercube(i).unwrap())
+		.sum::<FE>();
+
+	let mut oracles = MultilinearOracleSet::new();
+	let batch_id = oracles.add_committed_batch(CommittedBatchSpec {
+		n_vars,
+		n_polys: n_multilinears,
+		tower_level: F::TOWER_LEVEL,
+	});
+	let h = (0..n_multilinears)
+		.map(|i| oracles.committed_oracle(CommittedId { batch_id, index: i }))
+		.collect();
+	let sumcheck_claim = SumcheckClaim {
+		sum,
+		poly: CompositePolyOracle::new(n_vars, h, composition).unwrap(),
+	};
+
+	let domain = EvaluationDomain::new(n_multilinears + 1).unwrap();
+	let challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+
+	// Skipping the zero vertices of the selector must not change the proof
+	let dense_output =
+		prove(&sumcheck_claim, dense_poly, &domain, challenger.clone(), |_| 3).unwrap();
+	let sparse_output =
+		prove(&sumcheck_claim, sparse_poly.clone(), &domain, challenger.clone(), |_| 3).unwrap();
+	assert_eq!(dense_output.sumcheck_proof.rounds.len(), n_vars);
+	for (dense_round, sparse_round) in dense_output
+		.sumcheck_proof
+		.rounds
+		.iter()
+		.zip(sparse_output.sumcheck_proof.rounds.iter())
+	{
+		assert_eq!(dense_round.coeffs, sparse_round.coeffs);
+	}
+
+	let verified_claim = verify(&sumcheck_claim, sparse_output.sumcheck_proof, challenger).unwrap();
+	let query = MultilinearQuery::with_full_query(&verified_claim.eval_point).unwrap();
+	assert_eq!(sparse_poly.evaluate(&query).unwrap(), verified_claim.eval);
+}
+
 #[test]
 fn test_prove_verify_interaction_with_monomial_basis_conversion_basic() {
 	for n_vars in 2..8 {
@@ -373,6 +476,396 @@ fn test_prove_verify_batch() {
 	let proof = prove_output.proof;
 	assert_eq!(proof.rounds.len(), 8);
 
+	let proof_bytes = to_versioned_bytes(&proof);
+	let proof = from_versioned_bytes::<SumcheckBatchProof<FE>>(&proof_bytes).unwrap();
+
 	let _evalcheck_claims =
 		batch_verify(sumcheck_claims.iter().cloned(), proof, challenger.clone()).unwrap();
 }
+
+#[test]
+fn test_prove_verify_with_transcript() {
+	type F = BinaryField32b;
+	type FE = BinaryField128b;
+
+	let n_vars = 6;
+	let n_multilinears = 3;
+	let mut rng = StdRng::seed_from_u64(0);
+	let (poly, sum) = generate_poly_and_sum_helper::<F, FE>(&mut rng, n_vars, n_multilinears);
+
+	let mut oracles = MultilinearOracleSet::new();
+	let batch_id = oracles.add_committed_batch(CommittedBatchSpec {
+		n_vars,
+		n_polys: n_multilinears,
+		tower_level: F::TOWER_LEVEL,
+	});
+	let h = (0..n_multilinears)
+		.map(|i| oracles.committed_oracle(CommittedId { batch_id, index: i }))
+		.collect();
+	let sumcheck_claim = SumcheckClaim {
+		sum: sum.into(),
+		poly: CompositePolyOracle::new(n_vars, h, TestProductComposition::new(n_multilinears))
+			.unwrap(),
+	};
+
+	let domain = EvaluationDomain::new(n_multilinears + 1).unwrap();
+	let challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+
+	// The transcript run must agree with a run on a plain challenger
+	let prove_output =
+		prove(&sumcheck_claim, poly.clone(), &domain, challenger.clone(), |_| 2).unwrap();
+	let verify_output =
+		verify(&sumcheck_claim, prove_output.sumcheck_proof, challenger.clone()).unwrap();
+
+	let mut prover_transcript = TranscriptWriter::new(challenger.clone());
+	prove(&sumcheck_claim, poly.clone(), &domain, &mut prover_transcript, |_| 2).unwrap();
+	let transcript = prover_transcript.finalize();
+
+	let mut verifier_transcript = TranscriptReader::new(challenger, &transcript);
+	let transcript_verify_output =
+		verify_with_transcript(&sumcheck_claim, &mut verifier_transcript).unwrap();
+	verifier_transcript.finalize().unwrap();
+
+	assert_eq!(transcript_verify_output.eval_point, verify_output.eval_point);
+	assert_eq!(transcript_verify_output.eval, verify_output.eval);
+
+	let multilin_query =
+		MultilinearQuery::with_full_query(&transcript_verify_output.eval_point).unwrap();
+	assert_eq!(poly.evaluate(&multilin_query).unwrap(), transcript_verify_output.eval);
+}
+
+#[test]
+fn test_prove_verify_batch_with_transcript() {
+	type F = BinaryField32b;
+	type FE = BinaryField128b;
+
+	let n_multilinears = 2;
+	let mut rng = StdRng::seed_from_u64(0);
+	let mut oracles = MultilinearOracleSet::<FE>::new();
+
+	let (witnesses, sumcheck_claims): (Vec<_>, Vec<_>) = [4, 6]
+		.into_iter()
+		.map(|n_vars| {
+			let (poly, sum) =
+				generate_poly_and_sum_helper::<F, FE>(&mut rng, n_vars, n_multilinears);
+			let batch_id = oracles.add_committed_batch(CommittedBatchSpec {
+				n_vars,
+				n_polys: n_multilinears,
+				tower_level: F::TOWER_LEVEL,
+			});
+			let h = (0..n_multilinears)
+				.map(|i| oracles.committed_oracle(CommittedId { batch_id, index: i }))
+				.collect();
+			let claim = SumcheckClaim {
+				sum: sum.into(),
+				poly: CompositePolyOracle::new(
+					n_vars,
+					h,
+					TestProductComposition::new(n_multilinears),
+				)
+				.unwrap(),
+			};
+			(poly, claim)
+		})
+		.unzip();
+
+	let domain = EvaluationDomain::new(n_multilinears + 1).unwrap();
+	let new_provers = || {
+		iter::zip(witnesses.clone(), &sumcheck_claims)
+			.map(|(witness, claim)| {
+				SumcheckProver::new(&domain, claim.clone(), witness, |_| 2).unwrap()
+			})
+			.collect::<Vec<_>>()
+	};
+
+	let challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+
+	// The transcript run must agree with a run on a plain challenger
+	let prove_output = batch_prove(new_provers(), challenger.clone()).unwrap();
+	let evalcheck_claims =
+		batch_verify(sumcheck_claims.iter().cloned(), prove_output.proof, challenger.clone())
+			.unwrap();
+
+	let mut prover_transcript = TranscriptWriter::new(challenger.clone());
+	batch_prove(new_provers(), &mut prover_transcript).unwrap();
+	let transcript = prover_transcript.finalize();
+
+	let mut verifier_transcript = TranscriptReader::new(challenger, &transcript);
+	let transcript_evalcheck_claims =
+		batch_verify_with_transcript(sumcheck_claims.iter().cloned(), &mut verifier_transcript)
+			.unwrap();
+	verifier_transcript.finalize().unwrap();
+
+	assert_eq!(transcript_evalcheck_claims.len(), evalcheck_claims.len());
+	for (claim, expected) in iter::zip(&transcript_evalcheck_claims, &evalcheck_claims) {
+		assert_eq!(claim.eval_point, expected.eval_point);
+		assert_eq!(claim.eval, expected.eval);
+	}
+}
+
+/// Setup a product sumcheck claim on committed multilinears, together with committed mask oracles.
+fn hiding_claim_helper(
+	n_vars: usize,
+	n_multilinears: usize,
+	sum: BinaryField128b,
+) -> (SumcheckClaim<BinaryField128b>, Vec<MultilinearPolyOracle<BinaryField128b>>) {
+	let mut oracles = MultilinearOracleSet::new();
+	let batch_id = oracles.add_committed_batch(CommittedBatchSpec {
+		n_vars,
+		n_polys: n_multilinears,
+		tower_level: BinaryField128b::TOWER_LEVEL,
+	});
+	let mask_batch_id = oracles.add_committed_batch(CommittedBatchSpec {
+		n_vars,
+		n_polys: n_multilinears,
+		tower_level: BinaryField128b::TOWER_LEVEL,
+	});
+
+	let h = (0..n_multilinears)
+		.map(|index| oracles.committed_oracle(CommittedId { batch_id, index }))
+		.collect();
+	let mask_oracles = (0..n_multilinears)
+		.map(|index| {
+			oracles.committed_oracle(CommittedId {
+				batch_id: mask_batch_id,
+				index,
+			})
+		})
+		.collect();
+
+	let poly =
+		CompositePolyOracle::new(n_vars, h, TestProductComposition::new(n_multilinears)).unwrap();
+	(SumcheckClaim { poly, sum }, mask_oracles)
+}
+
+fn product_witness_helper(
+	multilinears: &[Vec<BinaryField128b>],
+) -> MultilinearComposite<
+	BinaryField128b,
+	TestProductComposition,
+	MultilinearExtensionSpecialized<'static, BinaryField128b, BinaryField128b>,
+> {
+	let n_vars = log2_ceil_usize(multilinears[0].len());
+	MultilinearComposite::new(
+		n_vars,
+		TestProductComposition::new(multilinears.len()),
+		multilinears
+			.iter()
+			.map(|values| {
+				MultilinearExtension::from_values(values.clone())
+					.unwrap()
+					.specialize()
+			})
+			.collect(),
+	)
+	.unwrap()
+}
+
+/// Evaluate the witness multilinears followed by the mask multilinears at a point.
+fn hiding_evals_helper(
+	multilinears: &[Vec<BinaryField128b>],
+	mask: &SumcheckMask<BinaryField128b>,
+	point: &[BinaryField128b],
+) -> Vec<BinaryField128b> {
+	let query = MultilinearQuery::<BinaryField128b>::with_full_query(point).unwrap();
+	multilinears
+		.iter()
+		.map(Vec::as_slice)
+		.chain(
+			mask.multilinears()
+				.iter()
+				.map(|multilinear| multilinear.evals()),
+		)
+		.map(|values| {
+			MultilinearExtension::from_values_slice(values)
+				.unwrap()
+				.evaluate(&query)
+				.unwrap()
+		})
+		.collect()
+}
+
+fn product_sum_helper(multilinears: &[Vec<BinaryField128b>]) -> BinaryField128b {
+	(0..multilinears[0].len())
+		.map(|i| {
+			multilinears
+				.iter()
+				.map(|values| values[i])
+				.product::<BinaryField128b>()
+		})
+		.sum()
+}
+
+#[test]
+fn test_prove_verify_hiding() {
+	type FE = BinaryField128b;
+	let n_vars = 5;
+	let n_multilinears = 3;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let multilinears = repeat_with(|| {
+		repeat_with(|| <FE as Field>::random(&mut rng))
+			.take(1 << n_vars)
+			.collect::<Vec<_>>()
+	})
+	.take(n_multilinears)
+	.collect::<Vec<_>>();
+	let sum = product_sum_helper(&multilinears);
+	let (claim, mask_oracles) = hiding_claim_helper(n_vars, n_multilinears, sum);
+	let mask = SumcheckMask::random(n_vars, n_multilinears, &mut rng).unwrap();
+	assert_eq!(mask.sum(), {
+		let points = (0..1 << n_vars).map(|i| {
+			(0..n_vars)
+				.map(|j| if (i >> j) & 1 == 1 { FE::ONE } else { FE::ZERO })
+				.collect::<Vec<_>>()
+		});
+		points
+			.map(|point| mask.evaluate(&point).unwrap())
+			.sum::<FE>()
+	});
+
+	let domain = EvaluationDomain::new(n_multilinears + 1).unwrap();
+	let challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+
+	let prove_output = prove_hiding(
+		&claim,
+		product_witness_helper(&multilinears),
+		&mask,
+		&mask_oracles,
+		&domain,
+		challenger.clone(),
+		|_| 2,
+	)
+	.unwrap();
+
+	let proof_bytes = to_versioned_bytes(&prove_output.sumcheck_proof);
+	let proof = from_versioned_bytes::<HidingSumcheckProof<FE>>(&proof_bytes).unwrap();
+	let verify_output = verify_hiding(&claim, &mask_oracles, proof, challenger.clone()).unwrap();
+	assert_eq!(verify_output.eval, prove_output.evalcheck_claim.eval);
+	assert_eq!(verify_output.eval_point, prove_output.evalcheck_claim.eval_point);
+
+	// The randomized evaluation claim holds for the witness and mask multilinears
+	let evals = hiding_evals_helper(&multilinears, &mask, &verify_output.eval_point);
+	let expected_eval = verify_output.poly.composition().evaluate(&evals).unwrap();
+	assert_eq!(verify_output.eval, expected_eval);
+
+	// A proof for an incorrect sum reduces to a false evaluation claim
+	let (wrong_claim, _) = hiding_claim_helper(n_vars, n_multilinears, sum + FE::ONE);
+	let wrong_verify_output =
+		verify_hiding(&wrong_claim, &mask_oracles, prove_output.sumcheck_proof, challenger)
+			.unwrap();
+	let evals = hiding_evals_helper(&multilinears, &mask, &wrong_verify_output.eval_point);
+	let expected_eval = wrong_verify_output
+		.poly
+		.composition()
+		.evaluate(&evals)
+		.unwrap();
+	assert_ne!(wrong_verify_output.eval, expected_eval);
+}
+
+#[test]
+fn test_hiding_transcript_independent_of_witness() {
+	type FE = BinaryField128b;
+	let n_vars = 3;
+	let n_multilinears = 2;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mut random_multilinears = || {
+		repeat_with(|| {
+			repeat_with(|| <FE as Field>::random(&mut rng))
+				.take(1 << n_vars)
+				.collect::<Vec<_>>()
+		})
+		.take(n_multilinears)
+		.collect::<Vec<_>>()
+	};
+	let witness_a = random_multilinears();
+	let mut witness_b = random_multilinears();
+	// The sum is public, so the two witnesses must have the same sum
+	let sum = product_sum_helper(&witness_a);
+	let correction = (sum - product_sum_helper(&witness_b)) * witness_b[1][0].invert().unwrap();
+	witness_b[0][0] += correction;
+	assert_eq!(product_sum_helper(&witness_b), sum);
+
+	let (claim, mask_oracles) = hiding_claim_helper(n_vars, n_multilinears, sum);
+	let domain = EvaluationDomain::new(n_multilinears + 1).unwrap();
+	// Fix the verifier challenges, so that the transcript is an affine function of the mask
+	let challenger = FixedChallenger::new(
+		repeat_with(|| <FE as Field>::random(&mut rng))
+			.take(n_vars + 1)
+			.collect(),
+	);
+
+	// The prover messages and the randomized evaluation claim
+	let transcript = |witness: &[Vec<FE>], mask: &[Vec<FE>]| {
+		let mask = SumcheckMask::new(
+			mask.iter()
+				.map(|values| MultilinearExtension::from_values(values.clone()).unwrap())
+				.collect(),
+		)
+		.unwrap();
+		let mut challenger = challenger.clone();
+		let output = prove_hiding(
+			&claim,
+			product_witness_helper(witness),
+			&mask,
+			&mask_oracles,
+			&domain,
+			&mut challenger,
+			|_| 1,
+		)
+		.unwrap();
+		let mut transcript = challenger.observed;
+		transcript.push(output.evalcheck_claim.eval);
+		transcript
+	};
+
+	let mask_len = n_multilinears << n_vars;
+	let unflatten = |values: &[FE]| {
+		values
+			.chunks(1 << n_vars)
+			.map(|chunk| chunk.to_vec())
+			.collect::<Vec<_>>()
+	};
+
+	// Compute the linear part of the transcript as a function of the mask
+	let zero_mask = vec![FE::ZERO; mask_len];
+	let transcript_a = transcript(&witness_a, &unflatten(&zero_mask));
+	let transcript_b = transcript(&witness_b, &unflatten(&zero_mask));
+	let columns = (0..mask_len)
+		.map(|i| {
+			let mut unit_mask = zero_mask.clone();
+			unit_mask[i] = FE::ONE;
+			transcript(&witness_a, &unflatten(&unit_mask))
+				.into_iter()
+				.zip(&transcript_a)
+				.map(|(entry, &offset)| entry - offset)
+				.collect::<Vec<_>>()
+		})
+		.collect::<Vec<_>>();
+	let matrix = (0..transcript_a.len())
+		.map(|row| columns.iter().map(|column| column[row]).collect())
+		.collect();
+	let rhs = transcript_b
+		.iter()
+		.zip(&transcript_a)
+		.map(|(&b, &a)| b - a)
+		.collect();
+
+	// Shifting the mask by delta is a bijection on masks that maps the transcripts for witness B to
+	// the transcripts for witness A, so the transcript distributions for both witnesses are equal
+	let delta =
+		solve_linear_system(matrix, rhs).expect("transcript must be independent of witness");
+	let mask = repeat_with(|| <FE as Field>::random(&mut rng))
+		.take(mask_len)
+		.collect::<Vec<_>>();
+	let shifted_mask = mask
+		.iter()
+		.zip(&delta)
+		.map(|(&m, &d)| m + d)
+		.collect::<Vec<_>>();
+	assert_eq!(
+		transcript(&witness_a, &unflatten(&shifted_mask)),
+		transcript(&witness_b, &unflatten(&mask))
+	);
+}
@@ -2,14 +2,15 @@
 
 use super::{
 	error::Error,
-	sumcheck::{SumcheckClaim, SumcheckReductor, SumcheckRoundClaim},
+	sumcheck::{SumcheckClaim, SumcheckReductor, SumcheckRoundClaim, ROUND_LABEL},
 	SumcheckProof, VerificationError,
 };
 use crate::{
-	challenger::{CanObserve, CanSample},
+	challenger::{CanObserve, CanSample, DomainSeparator, TranscriptReader},
 	protocols::{abstract_sumcheck, evalcheck::EvalcheckClaim},
+	serialization::DeserializeBytes,
 };
-use binius_field::Field;
+use binius_field::{BinaryField8b, Field};
 use tracing::instrument;
 
 /// Verify a sumcheck to evalcheck reduction.
@@ -21,7 +22,7 @@ pub fn verify<F, CH>(
 ) -> Result<EvalcheckClaim<F>, Error>
 where
 	F: Field,
-	CH: CanSample<F> + CanObserve<F>,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
 {
 	let n_vars = claim.poly.n_vars();
 	let n_rounds = proof.rounds.len();
@@ -31,8 +32,38 @@ where
 
 	let first_round_claim = setup_first_round_claim(claim);
 	let reductor = SumcheckReductor;
-	let evalcheck_claim =
-		abstract_sumcheck::verify(&claim.poly, first_round_claim, proof, reductor, challenger)?;
+	let evalcheck_claim = abstract_sumcheck::verify(
+		&claim.poly,
+		first_round_claim,
+		proof,
+		reductor,
+		ROUND_LABEL,
+		challenger,
+	)?;
+	Ok(evalcheck_claim)
+}
+
+/// Verify a sumcheck to evalcheck reduction, reading the proof from a transcript.
+#[instrument(skip_all, name = "sumcheck::verify_with_transcript")]
+pub fn verify_with_transcript<F, C>(
+	claim: &SumcheckClaim<F>,
+	transcript: &mut TranscriptReader<C>,
+) -> Result<EvalcheckClaim<F>, Error>
+where
+	F: Field + DeserializeBytes,
+	C: CanObserve<BinaryField8b> + CanSample<F> + DomainSeparator,
+{
+	let first_round_claim = setup_first_round_claim(claim);
+	let reductor = SumcheckReductor;
+	let evalcheck_claim = abstract_sumcheck::verify_with_transcript(
+		&claim.poly,
+		first_round_claim,
+		claim.poly.n_vars(),
+		claim.poly.max_individual_degree(),
+		reductor,
+		ROUND_LABEL,
+		transcript,
+	)?;
 	Ok(evalcheck_claim)
 }
 
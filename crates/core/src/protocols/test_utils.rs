@@ -1,7 +1,8 @@
 // Copyright 2023 Ulvetanna Inc.
 
 use crate::{
-	challenger::{CanObserve, CanSample},
+	challenger::{CanObserve, CanSample, DomainSeparator},
+	oracle::{CommittedBatchSpec, CommittedId, MultilinearOracleSet, MultilinearPolyOracle},
 	polynomial::{
 		CompositionPoly, Error as PolynomialError, EvaluationDomain, MultilinearExtension,
 		MultivariatePoly,
@@ -15,11 +16,13 @@ use crate::{
 			CommittedEvalClaim, Error as EvalcheckError, EvalcheckClaim, EvalcheckProver,
 			EvalcheckVerifier,
 		},
+		msetcheck::MsetcheckClaim,
 		sumcheck::{
 			batch_prove, Error as SumcheckError, SumcheckBatchProof, SumcheckBatchProveOutput,
 			SumcheckClaim, SumcheckProver,
 		},
 	},
+	witness::{MultilinearWitness, MultilinearWitnessIndex},
 };
 use binius_field::{packed::set_packed_slice, BinaryField1b, Field, PackedField, TowerField};
 use std::iter::Step;
@@ -123,6 +126,10 @@ where
 	fn binary_tower_level(&self) -> usize {
 		0
 	}
+
+	fn vanishes_with_var(&self, var: usize) -> bool {
+		var < self.arity
+	}
 }
 
 pub fn transform_poly<F, OF>(
@@ -155,7 +162,7 @@ where
 	F: Field + Step + From<PW::Scalar>,
 	PW: PackedField,
 	PW::Scalar: From<F>,
-	CH: CanObserve<F> + CanSample<F>,
+	CH: CanObserve<F> + CanSample<F> + DomainSeparator,
 {
 	let bivariate_domain = EvaluationDomain::new_isomorphic::<F>(3)?;
 
@@ -229,3 +236,134 @@ where
 
 	Ok(sumchecks)
 }
+
+/// A challenger that returns preset samples and records all observed values.
+///
+/// Fixing the verifier challenges is useful for reasoning about the distribution of the prover
+/// messages in an honest-verifier transcript.
+#[derive(Clone, Debug)]
+pub struct FixedChallenger<F> {
+	samples: Vec<F>,
+	n_sampled: usize,
+	pub observed: Vec<F>,
+}
+
+impl<F> FixedChallenger<F> {
+	/// Construct a challenger that cycles through the given samples.
+	pub fn new(samples: Vec<F>) -> Self {
+		assert!(!samples.is_empty());
+		Self {
+			samples,
+			n_sampled: 0,
+			observed: Vec::new(),
+		}
+	}
+}
+
+impl<F> CanObserve<F> for FixedChallenger<F> {
+	fn observe(&mut self, value: F) {
+		self.observed.push(value);
+	}
+}
+
+impl<F: Copy> CanSample<F> for FixedChallenger<F> {
+	fn sample(&mut self) -> F {
+		let sample = self.samples[self.n_sampled % self.samples.len()];
+		self.n_sampled += 1;
+		sample
+	}
+}
+
+impl<F> DomainSeparator for FixedChallenger<F> {
+	fn observe_label(&mut self, _label: &[u8]) {}
+}
+
+/// Solve the linear system $A x = b$ by Gaussian elimination.
+///
+/// The matrix is given as a list of rows. Returns `None` if the system has no solution, and an
+/// arbitrary solution otherwise.
+pub fn solve_linear_system<F: Field>(mut matrix: Vec<Vec<F>>, mut rhs: Vec<F>) -> Option<Vec<F>> {
+	let n_cols = matrix.first().map_or(0, |row| row.len());
+	let mut pivot_cols = Vec::new();
+	for col in 0..n_cols {
+		let rank = pivot_cols.len();
+		let Some(pivot_row) = (rank..matrix.len()).find(|&row| matrix[row][col] != F::ZERO) else {
+			continue;
+		};
+		matrix.swap(rank, pivot_row);
+		rhs.swap(rank, pivot_row);
+
+		let inv = matrix[rank][col].invert().expect("pivot is non-zero");
+		matrix[rank].iter_mut().for_each(|entry| *entry *= inv);
+		rhs[rank] *= inv;
+		for row in 0..matrix.len() {
+			let factor = matrix[row][col];
+			if row == rank || factor == F::ZERO {
+				continue;
+			}
+			for j in 0..n_cols {
+				let entry = matrix[rank][j];
+				matrix[row][j] -= factor * entry;
+			}
+			let entry = rhs[rank];
+			rhs[row] -= factor * entry;
+		}
+		pivot_cols.push(col);
+	}
+
+	if rhs[pivot_cols.len()..]
+		.iter()
+		.any(|&entry| entry != F::ZERO)
+	{
+		return None;
+	}
+
+	let mut solution = vec![F::ZERO; n_cols];
+	for (row, &col) in pivot_cols.iter().enumerate() {
+		solution[col] = rhs[row];
+	}
+	Some(solution)
+}
+
+/// Add a committed batch of `n_polys` polynomials and return their oracles.
+pub fn committed_oracles<F: TowerField>(
+	oracles: &mut MultilinearOracleSet<F>,
+	n_vars: usize,
+	n_polys: usize,
+	tower_level: usize,
+) -> Vec<MultilinearPolyOracle<F>> {
+	let batch_id = oracles.add_committed_batch(CommittedBatchSpec {
+		n_vars,
+		n_polys,
+		tower_level,
+	});
+	(0..n_polys)
+		.map(|index| oracles.committed_oracle(CommittedId { batch_id, index }))
+		.collect()
+}
+
+/// The product of the values of a multilinear over the hypercube.
+pub fn grand_product<F: Field>(poly: &MultilinearWitness<F>) -> F {
+	(0..1 << poly.n_vars())
+		.map(|i| poly.evaluate_on_hypercube(i).unwrap())
+		.product()
+}
+
+/// Assert that every oracle of a multiset check claim, and both halves of the merged ones, has a
+/// witness, so that evalcheck can open any of them.
+pub fn assert_msetcheck_witnesses<F: Field, PW: PackedField>(
+	msetcheck_claim: &MsetcheckClaim<F>,
+	witness_index: &MultilinearWitnessIndex<PW>,
+) {
+	for oracle in msetcheck_claim
+		.t_oracles()
+		.iter()
+		.chain(msetcheck_claim.u_oracles())
+	{
+		assert!(witness_index.get(oracle.id()).is_some());
+		if let MultilinearPolyOracle::Merged(_, poly0, poly1) = oracle {
+			assert!(witness_index.get(poly0.id()).is_some());
+			assert!(witness_index.get(poly1.id()).is_some());
+		}
+	}
+}
@@ -0,0 +1,352 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Batch proving and verification of the zerocheck protocol.
+//!
+//! Zerocheck instances over polynomials with different numbers of variables are batched like
+//! sumcheck instances in [`crate::protocols::sumcheck::batch_prove`]: the round polynomials are
+//! mixed with random coefficients, an instance over $n$ variables joins the batch in the round when
+//! $n$ variables remain, and the later round challenges are shared.
+//!
+//! The zerocheck challenges $\alpha_1, \ldots, \alpha_{n-1}$ are sampled once for the largest
+//! instance, and an instance over $m$ variables uses the last $m - 1$ of them. This way, all the
+//! instances in a round share the same zerocheck challenge, and the batched round polynomial
+//! satisfies the same identity as the round polynomials of a single zerocheck. The first round
+//! polynomial of a joining instance vanishes at both 0 and 1, so it satisfies that identity too.
+
+use super::{
+	error::{Error, VerificationError},
+	prove::ZerocheckProver,
+	zerocheck::{
+		recover_round_coeffs, ZerocheckClaim, ZerocheckReductor, ZerocheckRound,
+		ZerocheckRoundClaim, ZerocheckWitness, BATCH_COEFFS_LABEL, CHALLENGES_LABEL, EVALS_LABEL,
+		ROUND_LABEL,
+	},
+};
+use crate::{
+	challenger::{CanObserve, CanSample, DomainSeparator},
+	polynomial::{CompositionPoly, EvaluationDomain},
+	protocols::{
+		abstract_sumcheck::{AbstractSumcheckProver, AbstractSumcheckReductor},
+		evalcheck::EvalcheckClaim,
+	},
+	serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes},
+};
+use binius_field::{ExtensionField, Field, PackedField, TowerField};
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct ZerocheckBatchProof<F> {
+	pub rounds: Vec<ZerocheckRound<F>>,
+	/// Evaluations of each multivariate in the batch at the challenge point.
+	pub evals: Vec<F>,
+}
+
+impl<F: SerializeBytes> SerializeBytes for ZerocheckBatchProof<F> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.rounds.serialize(write_buf);
+		self.evals.serialize(write_buf);
+	}
+}
+
+impl<F: DeserializeBytes> DeserializeBytes for ZerocheckBatchProof<F> {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		let rounds = Vec::deserialize(read_buf)?;
+		let evals = Vec::deserialize(read_buf)?;
+		Ok(Self { rounds, evals })
+	}
+}
+
+#[derive(Debug)]
+pub struct ZerocheckBatchProveOutput<F: Field> {
+	pub evalcheck_claims: Vec<EvalcheckClaim<F>>,
+	pub proof: ZerocheckBatchProof<F>,
+}
+
+/// Prove a batched zerocheck instance.
+///
+/// Each instance is proven over the prefix of `domain` with one more point than the degree of its
+/// composition, so `domain` must be large enough for the instance with the largest degree. See
+/// module documentation for details.
+#[instrument(skip_all, name = "zerocheck::batch_prove")]
+pub fn batch_prove<'a, F, PW, FS, CW, CH>(
+	claims_and_witnesses: impl IntoIterator<Item = (ZerocheckClaim<F>, ZerocheckWitness<'a, PW, CW>)>,
+	domain: &EvaluationDomain<FS>,
+	mut challenger: CH,
+	switchover_fn: impl Fn(usize) -> usize,
+) -> Result<ZerocheckBatchProveOutput<F>, Error>
+where
+	F: TowerField + From<PW::Scalar>,
+	PW: PackedField,
+	PW::Scalar: TowerField + From<F> + ExtensionField<FS>,
+	FS: Field,
+	CW: CompositionPoly<PW::Scalar>,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	let mut claims_and_witnesses = claims_and_witnesses.into_iter().collect::<Vec<_>>();
+	// NOTE: Important to use stable sorting for prover-verifier consistency!
+	claims_and_witnesses.sort_by_key(|(claim, _)| claim.poly.n_vars());
+	claims_and_witnesses.reverse();
+
+	if claims_and_witnesses
+		.iter()
+		.any(|(claim, _)| claim.poly.n_vars() == 0)
+	{
+		return Err(Error::ImproperInput(
+			"zerocheck claims must have at least one variable".into(),
+		));
+	}
+
+	let n_rounds = claims_and_witnesses
+		.first()
+		.map(|(claim, _)| claim.poly.n_vars())
+		.unwrap_or(0);
+	let zerocheck_challenges =
+		challenger.sample_vec_labeled(CHALLENGES_LABEL, n_rounds.saturating_sub(1));
+
+	let domains = claims_and_witnesses
+		.iter()
+		.map(|(claim, _)| {
+			let domain_size = claim.poly.max_individual_degree() + 1;
+			if domain_size > domain.size() {
+				return Err(Error::EvaluationDomainMismatch);
+			}
+			Ok(EvaluationDomain::from_points(domain.points()[..domain_size].to_vec())?)
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+
+	let mut provers = claims_and_witnesses
+		.into_iter()
+		.zip(domains.iter())
+		.map(|((claim, witness), domain)| {
+			let n_vars = claim.poly.n_vars();
+			ZerocheckProver::<F, PW, FS, CW>::new(
+				domain,
+				claim,
+				witness,
+				zerocheck_challenges[n_rounds - n_vars..].to_vec(),
+				&switchover_fn,
+			)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let mut batch_coeffs = Vec::with_capacity(provers.len());
+	let mut round_proofs = Vec::with_capacity(n_rounds);
+
+	let mut prev_rd_challenge = None;
+	for round_no in 0..n_rounds {
+		let n_vars = n_rounds - round_no;
+
+		let mut batch_round_proof = ZerocheckRound { coeffs: Vec::new() };
+
+		// Process the reduced zerocheck instances
+		for (prover, &coeff) in provers.iter_mut().zip(batch_coeffs.iter()) {
+			let proof = AbstractSumcheckProver::execute_round(prover, prev_rd_challenge)?;
+			mix_round_proofs(&mut batch_round_proof, &proof, coeff);
+		}
+
+		// Mix in the new zerocheck instances with number of variables matching the current round.
+		while let Some(next_prover) = provers.get_mut(batch_coeffs.len()) {
+			if next_prover.n_vars() != n_vars {
+				break;
+			}
+
+			let coeff = challenger.sample_labeled(BATCH_COEFFS_LABEL);
+			batch_coeffs.push(coeff);
+
+			let proof = AbstractSumcheckProver::execute_round(next_prover, None)?;
+			let proof = if round_no == 0 {
+				proof
+			} else {
+				first_round_to_later_round(proof)?
+			};
+			mix_round_proofs(&mut batch_round_proof, &proof, coeff);
+		}
+
+		challenger.observe_slice_labeled(ROUND_LABEL, &batch_round_proof.coeffs);
+		round_proofs.push(batch_round_proof);
+		prev_rd_challenge = Some(challenger.sample());
+	}
+
+	let evalcheck_claims = provers
+		.into_iter()
+		.map(|prover| AbstractSumcheckProver::finalize(prover, prev_rd_challenge))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let evals = evalcheck_claims
+		.iter()
+		.map(|claim| claim.eval)
+		.collect::<Vec<_>>();
+	challenger.observe_slice_labeled(EVALS_LABEL, &evals);
+
+	Ok(ZerocheckBatchProveOutput {
+		evalcheck_claims,
+		proof: ZerocheckBatchProof {
+			rounds: round_proofs,
+			evals,
+		},
+	})
+}
+
+/// Verify a batched zerocheck instance.
+///
+/// See module documentation for details.
+#[instrument(skip_all, name = "zerocheck::batch_verify")]
+pub fn batch_verify<F, CH>(
+	claims: impl IntoIterator<Item = ZerocheckClaim<F>>,
+	proof: ZerocheckBatchProof<F>,
+	mut challenger: CH,
+) -> Result<Vec<EvalcheckClaim<F>>, Error>
+where
+	F: TowerField,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	let mut claims_vec = claims.into_iter().collect::<Vec<_>>();
+	// NOTE: Important to use stable sorting for prover-verifier consistency!
+	claims_vec.sort_by_key(|claim| claim.poly.n_vars());
+	claims_vec.reverse();
+
+	for claim in claims_vec.iter() {
+		if claim.poly.n_vars() == 0 {
+			return Err(Error::ImproperInput(
+				"zerocheck claims must have at least one variable".into(),
+			));
+		}
+		if claim.poly.max_individual_degree() == 0 {
+			return Err(Error::PolynomialDegreeIsZero);
+		}
+	}
+
+	let n_rounds = claims_vec
+		.first()
+		.map(|claim| claim.poly.n_vars())
+		.unwrap_or(0);
+
+	let ZerocheckBatchProof { rounds, evals } = proof;
+	if rounds.len() != n_rounds {
+		return Err(VerificationError::NumberOfRounds.into());
+	}
+
+	let zerocheck_challenges =
+		challenger.sample_vec_labeled(CHALLENGES_LABEL, n_rounds.saturating_sub(1));
+	let reductor = ZerocheckReductor {
+		alphas: &zerocheck_challenges,
+	};
+
+	let mut batch_coeffs = Vec::with_capacity(claims_vec.len());
+	let mut rd_claim = ZerocheckRoundClaim {
+		partial_point: Vec::with_capacity(n_rounds),
+		current_round_sum: F::ZERO,
+	};
+
+	for (round_no, round_proof) in rounds.into_iter().enumerate() {
+		let n_vars = n_rounds - round_no;
+
+		// Mix in new zerocheck claims with the appropriate number of variables. Their claimed
+		// sums are zero, so the round claim is unchanged.
+		while let Some(next_claim) = claims_vec.get(batch_coeffs.len()) {
+			if next_claim.poly.n_vars() != n_vars {
+				break;
+			}
+			batch_coeffs.push(challenger.sample_labeled(BATCH_COEFFS_LABEL));
+		}
+
+		// The prover omits the coefficients that the verifier recovers, which are two in the first
+		// round and one in later rounds.
+		let max_degree = claims_vec[..batch_coeffs.len()]
+			.iter()
+			.map(|claim| claim.poly.max_individual_degree())
+			.max()
+			.unwrap_or(0);
+		let max_round_coeffs = if round_no == 0 {
+			max_degree - 1
+		} else {
+			max_degree
+		};
+		if round_proof.coeffs.len() > max_round_coeffs {
+			return Err(VerificationError::NumberOfCoefficients.into());
+		}
+
+		challenger.observe_slice_labeled(ROUND_LABEL, &round_proof.coeffs);
+		rd_claim = reductor.reduce_intermediate_round_claim(
+			round_no,
+			rd_claim,
+			challenger.sample(),
+			round_proof,
+		)?;
+	}
+
+	challenger.observe_slice_labeled(EVALS_LABEL, &evals);
+
+	batch_verify_final(&claims_vec, &batch_coeffs, &evals, rd_claim)
+}
+
+/// Verifies a batch zerocheck proof final step, reducing the final claim to evaluation claims.
+fn batch_verify_final<F: Field>(
+	claims: &[ZerocheckClaim<F>],
+	batch_coeffs: &[F],
+	evals: &[F],
+	final_claim: ZerocheckRoundClaim<F>,
+) -> Result<Vec<EvalcheckClaim<F>>, Error> {
+	let ZerocheckRoundClaim {
+		partial_point: eval_point,
+		current_round_sum: final_eval,
+	} = final_claim;
+
+	let n_rounds = claims.first().map(|claim| claim.poly.n_vars()).unwrap_or(0);
+
+	if eval_point.len() != n_rounds {
+		return Err(VerificationError::NumberOfRounds.into());
+	}
+	if evals.len() != claims.len() {
+		return Err(VerificationError::NumberOfFinalEvaluations.into());
+	}
+
+	let batched_eval = evals
+		.iter()
+		.zip(batch_coeffs)
+		.map(|(eval, coeff)| *eval * *coeff)
+		.sum::<F>();
+
+	if batched_eval != final_eval {
+		return Err(VerificationError::IncorrectBatchEvaluation.into());
+	}
+
+	let eval_claims = evals
+		.iter()
+		.zip(claims)
+		.map(|(eval, claim)| EvalcheckClaim {
+			poly: claim.poly.clone(),
+			eval_point: eval_point[n_rounds - claim.poly.n_vars()..].to_vec(),
+			eval: *eval,
+			is_random_point: true,
+		})
+		.collect();
+
+	Ok(eval_claims)
+}
+
+/// Convert a first round message to the format of the later rounds.
+///
+/// The first round message omits the two lowest coefficients, which are determined by the round
+/// polynomial vanishing at 0 and 1, whereas later round messages only omit the constant term.
+fn first_round_to_later_round<F: Field>(
+	round_proof: ZerocheckRound<F>,
+) -> Result<ZerocheckRound<F>, Error> {
+	let mut coeffs = recover_round_coeffs(0, F::ZERO, round_proof.coeffs, None)?;
+	coeffs.remove(0);
+	Ok(ZerocheckRound { coeffs })
+}
+
+fn mix_round_proofs<F: Field>(
+	batch_proof: &mut ZerocheckRound<F>,
+	new_proof: &ZerocheckRound<F>,
+	coeff: F,
+) {
+	if batch_proof.coeffs.len() < new_proof.coeffs.len() {
+		batch_proof.coeffs.resize(new_proof.coeffs.len(), F::ZERO);
+	}
+
+	for (batch_proof_i, &proof_i) in batch_proof.coeffs.iter_mut().zip(new_proof.coeffs.iter()) {
+		*batch_proof_i += coeff * proof_i;
+	}
+}
@@ -1,6 +1,9 @@
 // Copyright 2023 Ulvetanna Inc.
 
-use crate::{oracle::Error as IOPolynomialError, polynomial::Error as PolynomialError};
+use crate::{
+	oracle::Error as IOPolynomialError, polynomial::Error as PolynomialError,
+	protocols::sumcheck::Error as SumcheckError,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -10,12 +13,18 @@ pub enum Error {
 	PolynomialDegreeIsZero,
 	#[error("the input was not well formed: {0}")]
 	ImproperInput(String),
+	#[error("cannot skip {skip_vars} variables of a zerocheck over {n_vars} variables")]
+	InvalidSkipVars { skip_vars: usize, n_vars: usize },
 	#[error("the evaluation domain does not match the expected size")]
 	EvaluationDomainMismatch,
+	#[error("the witness takes values outside of the skip domain field")]
+	WitnessNotInSkipDomainField,
 	#[error("IOPolynomial error: {0}")]
 	IOPolynomial(#[from] IOPolynomialError),
 	#[error("polynomial error: {0}")]
 	Polynomial(#[from] PolynomialError),
+	#[error("sumcheck error: {0}")]
+	Sumcheck(#[from] SumcheckError),
 	#[error("verification failure: {0}")]
 	Verification(#[from] VerificationError),
 }
@@ -32,6 +41,14 @@ pub enum VerificationError {
 	NumberOfCoefficients,
 	#[error("incorrect number of rounds")]
 	NumberOfRounds,
+	#[error("incorrect number of skipped evaluations")]
+	NumberOfSkippedEvaluations,
+	#[error("the skipped evaluations are inconsistent with the final round claim")]
+	IncorrectSkippedEvaluations,
+	#[error("incorrect number of final evaluations")]
+	NumberOfFinalEvaluations,
+	#[error("the final evaluations are inconsistent with the batched round claim")]
+	IncorrectBatchEvaluation,
 	#[error("mismatch between received and expected proof type (multilinear vs multivariate)")]
 	ProofTypeMismatch,
 	#[error("IOPolynomial error: {0}")]
@@ -0,0 +1,191 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Opt-in hiding mode for the zerocheck protocol.
+//!
+//! The round polynomials $r_i$ of the zerocheck prover are the quotients of the round polynomials
+//! $h_i(X) = c_i \cdot eq(\alpha_i, X) \cdot r_i(X)$ of the sumcheck on
+//! $eq(\alpha, X_1, \ldots, X_{n-1}) \cdot C(X)$, where $c_i = \prod_{j=1}^{i-1} eq(\alpha_j, r_j)$.
+//! In hiding mode, the prover sends the full polynomials $h_i$ masked with a random polynomial, as
+//! in [`crate::protocols::sumcheck::prove_hiding`]. The mask degree is one more than the degree of
+//! the composition, due to the $eq$ factor. As for the sumcheck, the evaluations of the witness
+//! multilinears that the reduced claim leads to are not hidden.
+
+use super::{
+	error::Error,
+	prove::ZerocheckProver,
+	zerocheck::{
+		recover_round_coeffs, ZerocheckClaim, ZerocheckRound, ZerocheckWitness, CHALLENGES_LABEL,
+		ROUND_LABEL,
+	},
+};
+use crate::{
+	challenger::{CanObserve, CanSample, DomainSeparator},
+	oracle::MultilinearPolyOracle,
+	polynomial::{CompositionPoly, EvaluationDomain},
+	protocols::{
+		abstract_sumcheck::AbstractSumcheckProver,
+		evalcheck::EvalcheckClaim,
+		sumcheck::{
+			self, check_mask, masked_evalcheck_claim, prove_masked_rounds, verify_masked_rounds,
+			HidingSumcheckProof, SumcheckMask,
+		},
+	},
+};
+use binius_field::{ExtensionField, Field, PackedField, TowerField};
+use tracing::instrument;
+
+pub type HidingZerocheckProof<F> = HidingSumcheckProof<F>;
+
+#[derive(Debug)]
+pub struct HidingZerocheckProveOutput<F: Field> {
+	pub evalcheck_claim: EvalcheckClaim<F>,
+	pub zerocheck_proof: HidingZerocheckProof<F>,
+}
+
+/// Prove a zerocheck to evalcheck reduction in hiding mode.
+///
+/// The mask multilinears must already be committed, and `mask_oracles` are their oracles. The
+/// degree of the mask must be one more than the degree of the composition in the claim.
+#[instrument(skip_all, name = "zerocheck::prove_hiding")]
+#[allow(clippy::too_many_arguments)]
+pub fn prove_hiding<F, PW, FS, CW, CH>(
+	claim: &ZerocheckClaim<F>,
+	witness: ZerocheckWitness<'_, PW, CW>,
+	mask: &SumcheckMask<F>,
+	mask_oracles: &[MultilinearPolyOracle<F>],
+	domain: &EvaluationDomain<FS>,
+	mut challenger: CH,
+	switchover_fn: impl Fn(usize) -> usize,
+) -> Result<HidingZerocheckProveOutput<F>, Error>
+where
+	F: TowerField + From<PW::Scalar>,
+	PW: PackedField,
+	PW::Scalar: TowerField + From<F> + ExtensionField<FS>,
+	FS: Field,
+	CW: CompositionPoly<PW::Scalar>,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	let n_vars = witness.n_vars();
+	check_mask(n_vars, claim.poly.max_individual_degree() + 1, mask, mask_oracles)?;
+
+	let zerocheck_challenges = challenger.sample_vec_labeled(CHALLENGES_LABEL, n_vars - 1);
+	let mut zerocheck_prover: ZerocheckProver<F, PW, FS, _> = ZerocheckProver::new(
+		domain,
+		claim.clone(),
+		witness,
+		zerocheck_challenges.clone(),
+		switchover_fn,
+	)?;
+
+	let mut eq_factor = F::ONE;
+	let masked_rounds =
+		prove_masked_rounds(n_vars, mask, ROUND_LABEL, &mut challenger, |prev_rd_challenge| {
+			let ZerocheckRound { coeffs } =
+				AbstractSumcheckProver::execute_round(&mut zerocheck_prover, prev_rd_challenge)?;
+			let round_claim = zerocheck_prover.round_claim();
+			let round = round_claim.partial_point.len();
+			let alpha_i = round.checked_sub(1).map(|i| zerocheck_challenges[i]);
+			let coeffs =
+				recover_round_coeffs(round, round_claim.current_round_sum, coeffs, alpha_i)?;
+
+			let (Some(alpha_i), Some(prev_rd_challenge)) = (alpha_i, prev_rd_challenge) else {
+				return Ok::<_, Error>(coeffs);
+			};
+			if round >= 2 {
+				eq_factor *= eq(zerocheck_challenges[round - 2], prev_rd_challenge);
+			}
+
+			// Multiply the round polynomial by $c_i \cdot eq(\alpha_i, X)$
+			let (eq_0, eq_1) =
+				(eq_factor * (F::ONE - alpha_i), eq_factor * (alpha_i.double() - F::ONE));
+			let mut round_coeffs = vec![F::ZERO; coeffs.len() + 1];
+			for (j, &coeff) in coeffs.iter().enumerate() {
+				round_coeffs[j] += eq_0 * coeff;
+				round_coeffs[j + 1] += eq_1 * coeff;
+			}
+			Ok(round_coeffs)
+		})?;
+
+	let evalcheck_claim = AbstractSumcheckProver::finalize(
+		zerocheck_prover,
+		masked_rounds.challenges.last().copied(),
+	)?;
+	let scale = eq_product(&zerocheck_challenges, &evalcheck_claim.eval_point);
+	let evalcheck_claim = masked_evalcheck_claim(
+		&claim.poly,
+		mask_oracles,
+		scale,
+		masked_rounds.mask_coeff,
+		&evalcheck_claim.eval_point,
+		evalcheck_claim.eval,
+		&masked_rounds.mask_evals,
+	)?;
+
+	Ok(HidingZerocheckProveOutput {
+		evalcheck_claim,
+		zerocheck_proof: HidingZerocheckProof {
+			mask_sum: masked_rounds.mask_sum,
+			rounds: masked_rounds.rounds,
+		},
+	})
+}
+
+/// Verify a zerocheck to evalcheck reduction in hiding mode.
+///
+/// The returned evalcheck claim is on the composite of the claim's multilinears and the mask
+/// multilinears.
+#[instrument(skip_all, name = "zerocheck::verify_hiding")]
+pub fn verify_hiding<F, CH>(
+	claim: &ZerocheckClaim<F>,
+	mask_oracles: &[MultilinearPolyOracle<F>],
+	proof: HidingZerocheckProof<F>,
+	mut challenger: CH,
+) -> Result<EvalcheckClaim<F>, Error>
+where
+	F: TowerField,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	let degree = claim.poly.max_individual_degree();
+	if degree == 0 {
+		return Err(Error::PolynomialDegreeIsZero);
+	}
+	if mask_oracles.len() != degree + 1 {
+		return Err(sumcheck::Error::IncorrectMaskDegree {
+			expected: degree + 1,
+			actual: mask_oracles.len(),
+		}
+		.into());
+	}
+
+	let n_vars = claim.poly.n_vars();
+	let zerocheck_challenges = challenger.sample_vec_labeled(CHALLENGES_LABEL, n_vars - 1);
+	let (mask_coeff, round_claim) =
+		verify_masked_rounds(F::ZERO, n_vars, degree + 1, proof, ROUND_LABEL, &mut challenger)?;
+
+	let scale = eq_product(&zerocheck_challenges, &round_claim.partial_point);
+	let evalcheck_claim = masked_evalcheck_claim(
+		&claim.poly,
+		mask_oracles,
+		scale,
+		mask_coeff,
+		&round_claim.partial_point,
+		round_claim.current_round_sum,
+		&[],
+	)?;
+	Ok(evalcheck_claim)
+}
+
+/// The equality indicator $eq(a, x) = (1 - a)(1 - x) + a x$ on a single variable.
+fn eq<F: Field>(a: F, x: F) -> F {
+	(F::ONE - a) * (F::ONE - x) + a * x
+}
+
+/// The product $\prod_{j=1}^{n-1} eq(\alpha_j, r_j)$ of the equality indicators on all but the
+/// first variable.
+fn eq_product<F: Field>(zerocheck_challenges: &[F], point: &[F]) -> F {
+	zerocheck_challenges
+		.iter()
+		.zip(point.iter().skip(1))
+		.map(|(&alpha, &r)| eq(alpha, r))
+		.product()
+}
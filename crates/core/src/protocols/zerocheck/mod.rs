@@ -1,14 +1,23 @@
 // Copyright 2023-2024 Ulvetanna Inc.
 
+mod batch;
 mod error;
+mod hiding;
 mod prove;
 #[cfg(test)]
 mod tests;
+mod univariate_skip;
 mod verify;
 #[allow(clippy::module_inception)]
 mod zerocheck;
 
+pub use batch::{batch_prove, batch_verify, ZerocheckBatchProof, ZerocheckBatchProveOutput};
 pub use error::*;
+pub use hiding::{prove_hiding, verify_hiding, HidingZerocheckProof, HidingZerocheckProveOutput};
 pub use prove::prove;
+pub use univariate_skip::{
+	prove_univariate_skip, verify_univariate_skip, UnivariateSkipZerocheckProof,
+	UnivariateSkipZerocheckProveOutput,
+};
 pub use verify::verify;
 pub use zerocheck::{ZerocheckClaim, ZerocheckProof, ZerocheckProveOutput, ZerocheckWitness};
@@ -4,15 +4,16 @@ use super::{
 	error::Error,
 	zerocheck::{
 		ZerocheckClaim, ZerocheckProof, ZerocheckProveOutput, ZerocheckReductor, ZerocheckRound,
-		ZerocheckRoundClaim, ZerocheckWitness,
+		ZerocheckRoundClaim, ZerocheckWitness, CHALLENGES_LABEL, ROUND_LABEL,
 	},
 };
 use crate::{
-	challenger::{CanObserve, CanSample},
+	challenger::{CanObserve, CanSample, DomainSeparator},
 	oracle::CompositePolyOracle,
 	polynomial::{
-		extrapolate_line, transparent::eq_ind::EqIndPartialEval, CompositionPoly,
-		Error as PolynomialError, EvaluationDomain, MultilinearExtension, MultilinearQuery,
+		composition::LinearFactor, extrapolate_line, transparent::eq_ind::EqIndPartialEval,
+		CompositionPoly, Error as PolynomialError, EvaluationDomain, MultilinearExtension,
+		MultilinearQuery,
 	},
 	protocols::{
 		abstract_sumcheck::{
@@ -30,6 +31,9 @@ use tracing::instrument;
 
 /// Prove a zerocheck to evalcheck reduction.
 /// FS is the domain type.
+///
+/// See [`super::prove_univariate_skip`] for the univariate skip mode, which binds several variables
+/// in the first round.
 #[instrument(skip_all, name = "zerocheck::prove")]
 pub fn prove<'a, F, PW, FS, CW, CH>(
 	claim: &ZerocheckClaim<F>,
@@ -44,16 +48,16 @@ where
 	PW::Scalar: TowerField + From<F> + ExtensionField<FS>,
 	FS: Field,
 	CW: CompositionPoly<PW::Scalar>,
-	CH: CanSample<F> + CanObserve<F>,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
 {
 	let n_vars = witness.n_vars();
-	let zerocheck_challenges = challenger.sample_vec(n_vars - 1);
+	let zerocheck_challenges = challenger.sample_vec_labeled(CHALLENGES_LABEL, n_vars - 1);
 
 	let zerocheck_prover: ZerocheckProver<F, PW, FS, _> =
 		ZerocheckProver::new(domain, claim.clone(), witness, zerocheck_challenges, switchover_fn)?;
 
 	let (evalcheck_claim, rounds) =
-		abstract_sumcheck::prove(claim.poly.n_vars(), zerocheck_prover, challenger)?;
+		abstract_sumcheck::prove(claim.poly.n_vars(), zerocheck_prover, ROUND_LABEL, challenger)?;
 
 	let zerocheck_proof = ZerocheckProof { rounds };
 	let output = ZerocheckProveOutput {
@@ -87,11 +91,17 @@ where
 {
 	oracle: CompositePolyOracle<F>,
 	composition: CW,
+	// The affine factors of the composition, if it is a product of them
+	linear_factors: Option<Vec<LinearFactor<PW::Scalar>>>,
 	domain: &'a EvaluationDomain<FS>,
 	#[getset(get = "pub")]
 	round_claim: ZerocheckRoundClaim<F>,
 
 	round: usize,
+	// The first round executed by this prover, which is non-zero when an earlier round was handled
+	// by a univariate skip
+	first_round: usize,
+	n_rounds: usize,
 	last_round_proof: Option<ZerocheckRound<F>>,
 	state: ProverState<PW, MultilinearWitness<'a, PW>>,
 
@@ -158,6 +168,7 @@ where
 		let state = ProverState::new(n_vars, witness.multilinears, switchover_fn)?;
 
 		let composition = witness.composition;
+		let linear_factors = composition.linear_factors();
 
 		let round_claim = ZerocheckRoundClaim {
 			partial_point: Vec::new(),
@@ -182,9 +193,12 @@ where
 		let zerocheck_prover = ZerocheckProver {
 			oracle: claim.poly,
 			composition,
+			linear_factors,
 			domain,
 			round_claim,
 			round: 0,
+			first_round: 0,
+			n_rounds: n_vars,
 			last_round_proof: None,
 			zerocheck_challenges,
 			round_eq_ind,
@@ -198,14 +212,101 @@ where
 		Ok(zerocheck_prover)
 	}
 
-	fn n_vars(&self) -> usize {
-		self.oracle.n_vars()
+	/// Start a zerocheck prover for the rounds following a univariate skip round.
+	///
+	/// The witness multilinears must already be partially evaluated at the univariate skip
+	/// challenge, and `round_q_bar` must hold the composition evaluated on them over the hypercube.
+	/// Round 0 is the univariate skip round, and the prover executes rounds $1, \ldots, n\\_vars$,
+	/// where $n\\_vars$ is the number of variables of the folded multilinears.
+	#[allow(clippy::too_many_arguments)]
+	pub(super) fn new_after_univariate_skip(
+		domain: &'a EvaluationDomain<FS>,
+		oracle: CompositePolyOracle<F>,
+		composition: CW,
+		multilinears: Vec<MultilinearWitness<'a, PW>>,
+		zerocheck_challenges: Vec<F>,
+		switchover_fn: impl Fn(usize) -> usize,
+		round_claim: ZerocheckRoundClaim<F>,
+		round_q_bar: MultilinearExtension<'static, PW::Scalar>,
+	) -> Result<Self, Error> {
+		let n_vars = round_q_bar.n_vars();
+		let degree = oracle.max_individual_degree();
+
+		if degree == 0 {
+			return Err(Error::PolynomialDegreeIsZero);
+		}
+		if n_vars == 0 || zerocheck_challenges.len() != n_vars {
+			return Err(Error::ImproperInput(format!(
+				"expected {} zerocheck challenges, got {}",
+				n_vars,
+				zerocheck_challenges.len()
+			)));
+		}
+
+		check_evaluation_domain(degree, domain)?;
+
+		let state = ProverState::new(n_vars, multilinears, switchover_fn)?;
+
+		// The first variable is bound by the univariate skip round, so the equality indicator
+		// starts out as in round 1 of a regular zerocheck
+		let pw_challenges = zerocheck_challenges[1..]
+			.iter()
+			.map(|&f| f.into())
+			.collect::<Vec<PW::Scalar>>();
+		let round_eq_ind =
+			EqIndPartialEval::new(n_vars - 1, pw_challenges)?.multilinear_extension()?;
+
+		let round_q = vec![PW::Scalar::default(); (1 << (n_vars - 1)) * (degree - 1)];
+		let smaller_domain_points = domain.points()[2..].to_vec();
+		let smaller_denom_inv = domain.points()[2..]
+			.iter()
+			.map(|&x| (x * (x - FS::ONE)).invert().unwrap())
+			.collect::<Vec<_>>();
+		let smaller_domain = EvaluationDomain::from_points(smaller_domain_points)?;
+
+		let linear_factors = composition.linear_factors();
+
+		Ok(ZerocheckProver {
+			oracle,
+			composition,
+			linear_factors,
+			domain,
+			round_claim,
+			round: 1,
+			first_round: 1,
+			n_rounds: n_vars + 1,
+			last_round_proof: None,
+			zerocheck_challenges,
+			round_eq_ind,
+			state,
+			round_q,
+			round_q_bar: Some(round_q_bar),
+			smaller_denom_inv,
+			smaller_domain,
+		})
+	}
+
+	pub(super) fn n_vars(&self) -> usize {
+		self.n_rounds
 	}
 
 	#[instrument(skip_all, name = "zerocheck::finalize")]
 	fn finalize(mut self, prev_rd_challenge: Option<F>) -> Result<EvalcheckClaim<F>, Error> {
+		let round_claim = self.finalize_round_claim(prev_rd_challenge)?;
+		let reductor = ZerocheckReductor {
+			alphas: &self.zerocheck_challenges,
+		};
+		let evalcheck_claim = reductor.reduce_final_round_claim(&self.oracle, round_claim)?;
+		Ok(evalcheck_claim)
+	}
+
+	/// Reduce the last round claim without converting it to an evalcheck claim.
+	pub(super) fn finalize_round_claim(
+		&mut self,
+		prev_rd_challenge: Option<F>,
+	) -> Result<ZerocheckRoundClaim<F>, Error> {
 		// First round has no challenge, other rounds should have it
-		validate_rd_challenge(prev_rd_challenge, self.round)?;
+		validate_rd_challenge(prev_rd_challenge, self.round - self.first_round)?;
 
 		if self.round != self.n_vars() {
 			return Err(Error::ImproperInput(format!(
@@ -220,11 +321,7 @@ where
 			self.reduce_claim(prev_rd_challenge)?;
 		}
 
-		let reductor = ZerocheckReductor {
-			alphas: &self.zerocheck_challenges,
-		};
-		let evalcheck_claim = reductor.reduce_final_round_claim(&self.oracle, self.round_claim)?;
-		Ok(evalcheck_claim)
+		Ok(self.round_claim.clone())
 	}
 
 	// Update the round_eq_ind for the next sumcheck round
@@ -333,6 +430,7 @@ where
 				evaluation_domain: self.domain,
 				domain_points: self.domain.points(),
 				composition: &self.composition,
+				linear_factors: self.linear_factors.as_deref(),
 				denom_inv: &self.smaller_denom_inv,
 			};
 			self.state.calculate_round_coeffs(
@@ -348,6 +446,7 @@ where
 				evaluation_domain: self.domain,
 				domain_points: self.domain.points(),
 				composition: &self.composition,
+				linear_factors: self.linear_factors.as_deref(),
 				denom_inv: &self.smaller_denom_inv,
 				round_q_bar: self
 					.round_q_bar
@@ -367,7 +466,7 @@ where
 	#[instrument(skip_all, name = "sumcheck::execute_round")]
 	fn execute_round(&mut self, prev_rd_challenge: Option<F>) -> Result<ZerocheckRound<F>, Error> {
 		// First round has no challenge, other rounds should have it
-		validate_rd_challenge(prev_rd_challenge, self.round)?;
+		validate_rd_challenge(prev_rd_challenge, self.round - self.first_round)?;
 
 		if self.round >= self.n_vars() {
 			return Err(Error::ImproperInput("too many execute_round calls".to_string()));
@@ -454,6 +553,61 @@ where
 	}
 }
 
+/// Evaluate the composition on the line through `evals_0` and `evals_1` at the domain points
+/// following 0 and 1, writing the values to `composite_evals`.
+///
+/// Generic compositions are evaluated at every point after extrapolating each multilinear. When
+/// the composition is a product of affine factors, each factor restricts to a linear univariate
+/// $a_k + b_k X$ on the line, so the factors are evaluated only at 0 and 1, and every further point
+/// costs one multiplication per factor. This keeps high-degree product constraints cheap.
+fn evaluate_composite_on_line<F, FS, C>(
+	composition: &C,
+	linear_factors: Option<&[LinearFactor<F>]>,
+	domain_points: &[FS],
+	evals_0: &[F],
+	evals_1: &[F],
+	evals_z: &mut [F],
+	composite_evals: &mut [F],
+) where
+	F: ExtensionField<FS>,
+	FS: Field,
+	C: CompositionPoly<F>,
+{
+	let points = &domain_points[2..];
+	debug_assert_eq!(points.len(), composite_evals.len());
+
+	if let Some(linear_factors) = linear_factors {
+		composite_evals.fill(F::ONE);
+		for factor in linear_factors {
+			let factor_0 = factor
+				.evaluate(evals_0)
+				.expect("linear factor variables are within the composition arity");
+			let factor_1 = factor
+				.evaluate(evals_1)
+				.expect("linear factor variables are within the composition arity");
+			let slope = factor_1 - factor_0;
+			for (composite_eval, &point) in composite_evals.iter_mut().zip(points) {
+				*composite_eval *= factor_0 + slope * point;
+			}
+		}
+		return;
+	}
+
+	for (composite_eval, &point) in composite_evals.iter_mut().zip(points) {
+		evals_0
+			.iter()
+			.zip(evals_1.iter())
+			.zip(evals_z.iter_mut())
+			.for_each(|((&evals_0_j, &evals_1_j), evals_z_j)| {
+				*evals_z_j = extrapolate_line::<F, FS>(evals_0_j, evals_1_j, point);
+			});
+
+		*composite_eval = composition
+			.evaluate(evals_z)
+			.expect("evals_z is initialized with a length of poly.composition.n_vars()");
+	}
+}
+
 /// Evaluator for the first round of the zerocheck protocol.
 ///
 /// In the first round, we do not need to evaluate at the point F::ONE, because the value is known
@@ -467,6 +621,7 @@ where
 	F: ExtensionField<FS>,
 {
 	pub composition: &'a C,
+	pub linear_factors: Option<&'a [LinearFactor<F>]>,
 	pub domain_points: &'a [FS],
 	pub evaluation_domain: &'a EvaluationDomain<FS>,
 	pub degree: usize,
@@ -500,24 +655,22 @@ where
 
 		let eq_ind_factor = self.eq_ind.evaluate_on_hypercube(i).unwrap_or(F::ZERO);
 
-		// The rest require interpolation.
-		for d in 2..self.domain_points.len() {
-			evals_0
-				.iter()
-				.zip(evals_1.iter())
-				.zip(evals_z.iter_mut())
-				.for_each(|((&evals_0_j, &evals_1_j), evals_z_j)| {
-					*evals_z_j =
-						extrapolate_line::<F, FS>(evals_0_j, evals_1_j, self.domain_points[d]);
-				});
-
-			let composite_value = self
-				.composition
-				.evaluate(evals_z)
-				.expect("evals_z is initialized with a length of poly.composition.n_vars()");
-
-			round_evals[d - 2] += composite_value * eq_ind_factor;
-			round_q_chunk[d - 2] = composite_value * self.denom_inv[d - 2];
+		// The rest require interpolation. The composite values are written to round_q_chunk
+		// first, and then replaced with the Q_i values derived from them.
+		evaluate_composite_on_line(
+			self.composition,
+			self.linear_factors,
+			self.domain_points,
+			evals_0,
+			evals_1,
+			evals_z,
+			round_q_chunk,
+		);
+
+		for (d, round_q) in round_q_chunk.iter_mut().enumerate() {
+			let composite_value = *round_q;
+			round_evals[d] += composite_value * eq_ind_factor;
+			*round_q = composite_value * self.denom_inv[d];
 		}
 	}
 
@@ -549,6 +702,7 @@ where
 	F: ExtensionField<FS>,
 {
 	pub composition: &'a C,
+	pub linear_factors: Option<&'a [LinearFactor<F>]>,
 	pub domain_points: &'a [FS],
 	pub evaluation_domain: &'a EvaluationDomain<FS>,
 	pub degree: usize,
@@ -596,22 +750,20 @@ where
 		// See section 4 of [https://eprint.iacr.org/2024/108] for details
 		round_evals[0] += q_bar_one * eq_ind_factor;
 
-		// The rest require interpolation.
-		for d in 2..self.domain_points.len() {
-			evals_0
-				.iter()
-				.zip(evals_1.iter())
-				.zip(evals_z.iter_mut())
-				.for_each(|((&evals_0_j, &evals_1_j), evals_z_j)| {
-					*evals_z_j =
-						extrapolate_line::<F, FS>(evals_0_j, evals_1_j, self.domain_points[d]);
-				});
-
-			let composite_value = self
-				.composition
-				.evaluate(evals_z)
-				.expect("evals_z is initialized with a length of poly.composition.n_vars()");
+		// The rest require interpolation. The composite values are written to round_q_chunk
+		// first, and then replaced with the Q_i values derived from them.
+		evaluate_composite_on_line(
+			self.composition,
+			self.linear_factors,
+			self.domain_points,
+			evals_0,
+			evals_1,
+			evals_z,
+			round_q_chunk,
+		);
 
+		for d in 2..self.domain_points.len() {
+			let composite_value = round_q_chunk[d - 2];
 			round_evals[d - 1] += composite_value * eq_ind_factor;
 
 			// We compute Q_i(r, domain[d], x) values with minimal additional work (linear extrapolation, multiplication, and inversion)
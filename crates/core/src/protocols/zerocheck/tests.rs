@@ -1,19 +1,41 @@
 // Copyright 2024 Ulvetanna Inc.
 
 use crate::{
-	challenger::HashChallenger,
-	oracle::{CommittedBatchSpec, CommittedId, CompositePolyOracle, MultilinearOracleSet},
-	polynomial::{EvaluationDomain, MultilinearComposite, MultilinearExtension, MultilinearQuery},
+	challenger::{CanObserve, CanSample, DomainSeparator, HashChallenger, Vision32bChallenger},
+	oracle::{
+		CommittedBatchSpec, CommittedId, CompositePolyOracle, MultilinearOracleSet,
+		MultilinearPolyOracle,
+	},
+	polynomial::{
+		composition::{LinearFactor, LinearProductComposition},
+		CompositionPoly, Error as PolynomialError, EvaluationDomain, MultilinearComposite,
+		MultilinearExtension, MultilinearQuery,
+	},
 	protocols::{
-		test_utils::TestProductComposition,
-		zerocheck::{prove, verify, zerocheck::ZerocheckProveOutput, ZerocheckClaim},
+		greedy_evalcheck::{self, GreedyEvalcheckProveOutput},
+		sumcheck::SumcheckMask,
+		test_utils::{solve_linear_system, FixedChallenger, TestProductComposition},
+		zerocheck::{
+			batch_prove, batch_verify, prove, prove_hiding, prove_univariate_skip, verify,
+			verify_hiding, verify_univariate_skip, zerocheck::ZerocheckProveOutput, Error,
+			HidingZerocheckProveOutput, UnivariateSkipZerocheckProof,
+			UnivariateSkipZerocheckProveOutput, VerificationError, ZerocheckBatchProof,
+			ZerocheckBatchProveOutput, ZerocheckClaim,
+		},
 	},
+	serialization::{from_versioned_bytes, to_versioned_bytes},
+	witness::MultilinearWitnessIndex,
+};
+use assert_matches::assert_matches;
+use binius_field::{
+	BinaryField128b, BinaryField1b, BinaryField32b, BinaryField8b, ExtensionField, Field,
+	PackedBinaryField4x128b, PackedField, TowerField,
 };
-use binius_field::{BinaryField128b, BinaryField32b, Field, TowerField};
 use binius_hash::GroestlHasher;
 use p3_util::log2_ceil_usize;
-use rand::{rngs::StdRng, SeedableRng};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use rayon::current_num_threads;
+use std::iter::{repeat_with, Step};
 
 fn generate_poly_helper<F>(
 	rng: &mut StdRng,
@@ -149,3 +171,792 @@ fn test_zerocheck_prove_verify_interaction_pigeonhole_cores() {
 		}
 	}
 }
+
+#[test]
+fn test_zerocheck_prove_verify_with_vision_challenger() {
+	type F = BinaryField32b;
+	type FE = BinaryField128b;
+
+	let n_vars = 5;
+	let n_multilinears = 3;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let multilins = generate_poly_helper::<F>(&mut rng, n_vars, n_multilinears);
+	let zc_witness = MultilinearComposite::<FE, _, _>::new(
+		n_vars,
+		TestProductComposition::new(n_multilinears),
+		multilins
+			.into_iter()
+			.map(|m| m.specialize_arc_dyn())
+			.collect(),
+	)
+	.unwrap();
+
+	let mut oracles = MultilinearOracleSet::new();
+	let batch_id = oracles.add_committed_batch(CommittedBatchSpec {
+		n_vars,
+		n_polys: n_multilinears,
+		tower_level: F::TOWER_LEVEL,
+	});
+	let h = (0..n_multilinears)
+		.map(|i| oracles.committed_oracle(CommittedId { batch_id, index: i }))
+		.collect();
+	let zc_claim = ZerocheckClaim {
+		poly: CompositePolyOracle::new(n_vars, h, TestProductComposition::new(n_multilinears))
+			.unwrap(),
+	};
+
+	let domain: EvaluationDomain<F> = EvaluationDomain::new(n_multilinears + 1).unwrap();
+	let mut prover_challenger = Vision32bChallenger::new();
+	let mut verifier_challenger = prover_challenger.clone();
+
+	let ZerocheckProveOutput {
+		evalcheck_claim,
+		zerocheck_proof,
+	} = prove::<FE, FE, F, _, _>(
+		&zc_claim,
+		zc_witness.clone(),
+		&domain,
+		&mut prover_challenger,
+		|_| 1,
+	)
+	.unwrap();
+	let verified_evalcheck_claim =
+		verify(&zc_claim, zerocheck_proof, &mut verifier_challenger).unwrap();
+	assert_eq!(evalcheck_claim.eval, verified_evalcheck_claim.eval);
+
+	let multilin_query =
+		MultilinearQuery::with_full_query(&verified_evalcheck_claim.eval_point).unwrap();
+	assert_eq!(zc_witness.evaluate(&multilin_query).unwrap(), verified_evalcheck_claim.eval);
+}
+
+/// Setup a product zerocheck claim on committed multilinears, together with committed mask oracles.
+fn hiding_claim_helper(
+	n_vars: usize,
+	n_multilinears: usize,
+) -> (ZerocheckClaim<BinaryField128b>, Vec<MultilinearPolyOracle<BinaryField128b>>) {
+	let mut oracles = MultilinearOracleSet::new();
+	let batch_id = oracles.add_committed_batch(CommittedBatchSpec {
+		n_vars,
+		n_polys: n_multilinears,
+		tower_level: BinaryField128b::TOWER_LEVEL,
+	});
+	let mask_batch_id = oracles.add_committed_batch(CommittedBatchSpec {
+		n_vars,
+		n_polys: n_multilinears + 1,
+		tower_level: BinaryField128b::TOWER_LEVEL,
+	});
+
+	let h = (0..n_multilinears)
+		.map(|index| oracles.committed_oracle(CommittedId { batch_id, index }))
+		.collect();
+	let mask_oracles = (0..n_multilinears + 1)
+		.map(|index| {
+			oracles.committed_oracle(CommittedId {
+				batch_id: mask_batch_id,
+				index,
+			})
+		})
+		.collect();
+
+	let poly =
+		CompositePolyOracle::new(n_vars, h, TestProductComposition::new(n_multilinears)).unwrap();
+	(ZerocheckClaim { poly }, mask_oracles)
+}
+
+fn hiding_prove_helper<CH>(
+	claim: &ZerocheckClaim<BinaryField128b>,
+	multilinears: &[MultilinearExtension<'static, BinaryField128b>],
+	mask: &SumcheckMask<BinaryField128b>,
+	mask_oracles: &[MultilinearPolyOracle<BinaryField128b>],
+	challenger: CH,
+) -> HidingZerocheckProveOutput<BinaryField128b>
+where
+	CH: CanObserve<BinaryField128b> + CanSample<BinaryField128b> + DomainSeparator,
+{
+	type FE = BinaryField128b;
+	let n_vars = claim.poly.n_vars();
+	let witness = MultilinearComposite::<FE, _, _>::new(
+		n_vars,
+		TestProductComposition::new(multilinears.len()),
+		multilinears
+			.iter()
+			.map(|multilinear| multilinear.clone().specialize_arc_dyn())
+			.collect(),
+	)
+	.unwrap();
+	let domain: EvaluationDomain<FE> = EvaluationDomain::new(multilinears.len() + 1).unwrap();
+	prove_hiding::<FE, FE, FE, _, _>(claim, witness, mask, mask_oracles, &domain, challenger, |_| 1)
+		.expect("failed to prove zerocheck")
+}
+
+#[test]
+fn test_zerocheck_prove_verify_hiding() {
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+	for n_vars in 2..6 {
+		for n_multilinears in 1..4 {
+			let multilinears = generate_poly_helper::<FE>(&mut rng, n_vars, n_multilinears);
+			let (claim, mask_oracles) = hiding_claim_helper(n_vars, n_multilinears);
+			let mask = SumcheckMask::random(n_vars, n_multilinears + 1, &mut rng).unwrap();
+			let challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+
+			let HidingZerocheckProveOutput {
+				evalcheck_claim,
+				zerocheck_proof,
+			} = hiding_prove_helper(&claim, &multilinears, &mask, &mask_oracles, challenger.clone());
+			let verified_evalcheck_claim =
+				verify_hiding(&claim, &mask_oracles, zerocheck_proof, challenger)
+					.expect("failed to verify zerocheck");
+			assert_eq!(evalcheck_claim.eval, verified_evalcheck_claim.eval);
+			assert_eq!(evalcheck_claim.eval_point, verified_evalcheck_claim.eval_point);
+
+			// Verify that the randomized evalcheck claim is correct
+			let query =
+				MultilinearQuery::<FE>::with_full_query(&verified_evalcheck_claim.eval_point)
+					.unwrap();
+			let evals = multilinears
+				.iter()
+				.chain(mask.multilinears())
+				.map(|multilinear| multilinear.evaluate(&query).unwrap())
+				.collect::<Vec<FE>>();
+			let actual = verified_evalcheck_claim
+				.poly
+				.composition()
+				.evaluate(&evals)
+				.unwrap();
+			assert_eq!(actual, verified_evalcheck_claim.eval);
+		}
+	}
+}
+
+#[test]
+fn test_zerocheck_hiding_transcript_independent_of_witness() {
+	type FE = BinaryField128b;
+	let n_vars = 3;
+	let n_multilinears = 2;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let witness_a = generate_poly_helper::<FE>(&mut rng, n_vars, n_multilinears);
+	let witness_b = generate_poly_helper::<FE>(&mut rng, n_vars, n_multilinears);
+	let (claim, mask_oracles) = hiding_claim_helper(n_vars, n_multilinears);
+	// Fix the verifier challenges, so that the transcript is an affine function of the mask
+	let challenger = FixedChallenger::new(
+		repeat_with(|| <FE as Field>::random(&mut rng))
+			.take(2 * n_vars)
+			.collect(),
+	);
+
+	// The prover messages and the randomized evaluation claim
+	let mask_len = (n_multilinears + 1) << n_vars;
+	let transcript = |witness: &[MultilinearExtension<'static, FE>], mask: &[FE]| {
+		let mask = SumcheckMask::new(
+			mask.chunks(1 << n_vars)
+				.map(|values| MultilinearExtension::from_values(values.to_vec()).unwrap())
+				.collect(),
+		)
+		.unwrap();
+		let mut challenger = challenger.clone();
+		let output = hiding_prove_helper(&claim, witness, &mask, &mask_oracles, &mut challenger);
+		let mut transcript = challenger.observed;
+		transcript.push(output.evalcheck_claim.eval);
+		transcript
+	};
+
+	// Compute the linear part of the transcript as a function of the mask
+	let zero_mask = vec![FE::ZERO; mask_len];
+	let transcript_a = transcript(&witness_a, &zero_mask);
+	let transcript_b = transcript(&witness_b, &zero_mask);
+	let columns = (0..mask_len)
+		.map(|i| {
+			let mut unit_mask = zero_mask.clone();
+			unit_mask[i] = FE::ONE;
+			transcript(&witness_a, &unit_mask)
+				.into_iter()
+				.zip(&transcript_a)
+				.map(|(entry, &offset)| entry - offset)
+				.collect::<Vec<_>>()
+		})
+		.collect::<Vec<_>>();
+	let matrix = (0..transcript_a.len())
+		.map(|row| columns.iter().map(|column| column[row]).collect())
+		.collect();
+	let rhs = transcript_b
+		.iter()
+		.zip(&transcript_a)
+		.map(|(&b, &a)| b - a)
+		.collect();
+
+	// Shifting the mask by delta is a bijection on masks that maps the transcripts for witness B to
+	// the transcripts for witness A, so the transcript distributions for both witnesses are equal
+	let delta =
+		solve_linear_system(matrix, rhs).expect("transcript must be independent of witness");
+	let mask = repeat_with(|| <FE as Field>::random(&mut rng))
+		.take(mask_len)
+		.collect::<Vec<_>>();
+	let shifted_mask = mask
+		.iter()
+		.zip(&delta)
+		.map(|(&m, &d)| m + d)
+		.collect::<Vec<_>>();
+	assert_eq!(transcript(&witness_a, &shifted_mask), transcript(&witness_b, &mask));
+}
+
+/// Prove and verify a univariate skip zerocheck on a witness over `FW`, with the skip domain and
+/// the domain of the later rounds over `FS`.
+fn univariate_skip_helper<FW, FS>(
+	n_vars: usize,
+	n_multilinears: usize,
+	skip_vars: usize,
+	switchover_rd: usize,
+) where
+	FW: TowerField,
+	FS: TowerField + Step,
+	BinaryField128b: ExtensionField<FW> + ExtensionField<FS>,
+{
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let multilins = generate_poly_helper::<FW>(&mut rng, n_vars, n_multilinears);
+	let zc_witness = MultilinearComposite::<FE, _, _>::new(
+		n_vars,
+		TestProductComposition::new(n_multilinears),
+		multilins
+			.iter()
+			.map(|multilin| multilin.clone().specialize_arc_dyn())
+			.collect(),
+	)
+	.unwrap();
+
+	let mut oracles = MultilinearOracleSet::new();
+	let batch_id = oracles.add_committed_batch(CommittedBatchSpec {
+		n_vars,
+		n_polys: n_multilinears,
+		tower_level: FW::TOWER_LEVEL,
+	});
+	let h = (0..n_multilinears)
+		.map(|index| oracles.committed_oracle(CommittedId { batch_id, index }))
+		.collect();
+	let zc_claim = ZerocheckClaim {
+		poly: CompositePolyOracle::new(n_vars, h, TestProductComposition::new(n_multilinears))
+			.unwrap(),
+	};
+
+	let skip_domain_size = n_multilinears * ((1 << skip_vars) - 1) + 1;
+	let skip_domain = EvaluationDomain::<FS>::new(skip_domain_size).unwrap();
+	let verifier_skip_domain =
+		EvaluationDomain::<FE>::new_isomorphic::<FS>(skip_domain_size).unwrap();
+	let domain = EvaluationDomain::<FS>::new(n_multilinears + 1).unwrap();
+	let mut prover_challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+	let mut verifier_challenger = prover_challenger.clone();
+
+	let UnivariateSkipZerocheckProveOutput {
+		evalcheck_claims,
+		zerocheck_proof,
+	} = prove_univariate_skip::<FE, FE, FS, _, _>(
+		&zc_claim,
+		zc_witness,
+		skip_vars,
+		&skip_domain,
+		&domain,
+		&mut prover_challenger,
+		|_| switchover_rd,
+	)
+	.expect("failed to prove zerocheck");
+
+	let verified_evalcheck_claims = verify_univariate_skip(
+		&zc_claim,
+		skip_vars,
+		&verifier_skip_domain,
+		zerocheck_proof,
+		&mut verifier_challenger,
+	)
+	.expect("failed to verify zerocheck");
+
+	// Check that the evalcheck claims are consistent and correct
+	assert_eq!(evalcheck_claims.len(), n_multilinears);
+	assert_eq!(verified_evalcheck_claims.len(), n_multilinears);
+	for ((claim, verified_claim), multilin) in evalcheck_claims
+		.iter()
+		.zip(verified_evalcheck_claims.iter())
+		.zip(multilins.iter())
+	{
+		assert_eq!(claim.eval, verified_claim.eval);
+		assert_eq!(claim.eval_point, verified_claim.eval_point);
+		assert_eq!(verified_claim.poly.n_vars(), n_vars);
+		assert!(verified_claim.is_random_point);
+
+		let query = MultilinearQuery::<FE>::with_full_query(&verified_claim.eval_point).unwrap();
+		assert_eq!(multilin.evaluate::<FE, FE>(&query).unwrap(), verified_claim.eval);
+	}
+
+	// The evalcheck claims can be reduced to a same query claim on the committed batch
+	let mut witness_index = MultilinearWitnessIndex::<FE>::new();
+	for (index, multilin) in multilins.iter().enumerate() {
+		let id = oracles.committed_oracle_id(CommittedId { batch_id, index });
+		witness_index.set(id, multilin.clone().specialize_arc_dyn());
+	}
+	let GreedyEvalcheckProveOutput {
+		same_query_claims,
+		proof,
+	} = greedy_evalcheck::prove(
+		&mut oracles,
+		&mut witness_index,
+		evalcheck_claims,
+		|_| switchover_rd,
+		&mut prover_challenger,
+	)
+	.expect("failed to prove evalcheck");
+	let verified_same_query_claims = greedy_evalcheck::verify(
+		&mut oracles,
+		verified_evalcheck_claims,
+		proof,
+		&mut verifier_challenger,
+	)
+	.expect("failed to verify evalcheck");
+
+	assert_eq!(same_query_claims.len(), 1);
+	assert_eq!(verified_same_query_claims.len(), 1);
+	let (_, same_query_claim) = &verified_same_query_claims[0];
+	assert_eq!(same_query_claims[0].1.eval_point, same_query_claim.eval_point);
+	assert_eq!(same_query_claims[0].1.evals, same_query_claim.evals);
+}
+
+#[test]
+fn test_zerocheck_univariate_skip_prove_verify() {
+	for n_vars in 2..6 {
+		for n_multilinears in 1..4 {
+			for skip_vars in 1..n_vars {
+				univariate_skip_helper::<BinaryField32b, BinaryField32b>(
+					n_vars,
+					n_multilinears,
+					skip_vars,
+					1,
+				);
+			}
+		}
+	}
+}
+
+#[test]
+fn test_zerocheck_univariate_skip_with_switchover() {
+	univariate_skip_helper::<BinaryField32b, BinaryField32b>(7, 3, 3, 3);
+}
+
+#[test]
+fn test_zerocheck_univariate_skip_small_field_witness() {
+	univariate_skip_helper::<BinaryField1b, BinaryField8b>(6, 3, 3, 1);
+	univariate_skip_helper::<BinaryField8b, BinaryField8b>(6, 2, 4, 1);
+}
+
+#[test]
+fn test_zerocheck_univariate_skip_rejects_witness_outside_domain_field() {
+	type F = BinaryField32b;
+	type FS = BinaryField8b;
+	type FE = BinaryField128b;
+	let (n_vars, n_multilinears, skip_vars) = (5, 2, 2);
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let (claim, _) = hiding_claim_helper(n_vars, n_multilinears);
+	let witness = MultilinearComposite::<FE, _, _>::new(
+		n_vars,
+		TestProductComposition::new(n_multilinears),
+		generate_poly_helper::<F>(&mut rng, n_vars, n_multilinears)
+			.into_iter()
+			.map(|multilin| multilin.specialize_arc_dyn())
+			.collect(),
+	)
+	.unwrap();
+	let skip_domain =
+		EvaluationDomain::<FS>::new(n_multilinears * ((1 << skip_vars) - 1) + 1).unwrap();
+	let domain = EvaluationDomain::<FS>::new(n_multilinears + 1).unwrap();
+
+	let result = prove_univariate_skip::<FE, FE, FS, _, _>(
+		&claim,
+		witness,
+		skip_vars,
+		&skip_domain,
+		&domain,
+		<HashChallenger<_, GroestlHasher<_>>>::new(),
+		|_| 1,
+	);
+	assert_matches!(result, Err(Error::WitnessNotInSkipDomainField));
+}
+
+#[test]
+fn test_zerocheck_univariate_skip_rejects_partial_packed_witness() {
+	type F = BinaryField32b;
+	type PW = PackedBinaryField4x128b;
+	let (n_vars, n_multilinears, skip_vars) = (4, 2, 3);
+	let mut rng = StdRng::seed_from_u64(0);
+
+	// Skipping 3 of 4 variables leaves folded multilinears of 2 evaluations, fewer than the
+	// width of the packed witness field
+	let (claim, _) = hiding_claim_helper(n_vars, n_multilinears);
+	let witness = MultilinearComposite::<PW, _, _>::new(
+		n_vars,
+		TestProductComposition::new(n_multilinears),
+		generate_poly_helper::<F>(&mut rng, n_vars, n_multilinears)
+			.into_iter()
+			.map(|multilin| multilin.specialize_arc_dyn())
+			.collect(),
+	)
+	.unwrap();
+	let skip_domain =
+		EvaluationDomain::<F>::new(n_multilinears * ((1 << skip_vars) - 1) + 1).unwrap();
+	let domain = EvaluationDomain::<F>::new(n_multilinears + 1).unwrap();
+
+	let result = prove_univariate_skip::<BinaryField128b, PW, F, _, _>(
+		&claim,
+		witness,
+		skip_vars,
+		&skip_domain,
+		&domain,
+		<HashChallenger<_, GroestlHasher<_>>>::new(),
+		|_| 1,
+	);
+	assert_matches!(result, Err(Error::ImproperInput(_)));
+}
+
+#[test]
+fn test_zerocheck_univariate_skip_rejects_invalid_skip_vars() {
+	type FE = BinaryField128b;
+	let (claim, _) = hiding_claim_helper(3, 2);
+	let skip_domain = EvaluationDomain::<FE>::new(2 * ((1 << 3) - 1) + 1).unwrap();
+	let proof = UnivariateSkipZerocheckProof {
+		rounds: Vec::new(),
+		skipped_evals: Vec::new(),
+	};
+	let result = verify_univariate_skip(
+		&claim,
+		3,
+		&skip_domain,
+		proof,
+		<HashChallenger<_, GroestlHasher<_>>>::new(),
+	);
+	assert_matches!(result, Err(Error::InvalidSkipVars { .. }));
+}
+
+/// Prove a univariate skip zerocheck with a product composition, returning the claim, the proof
+/// and the verifier's skip domain. Unless `satisfied`, the multilinears are random, so the claim
+/// is false.
+fn univariate_skip_proof(
+	n_vars: usize,
+	n_multilinears: usize,
+	skip_vars: usize,
+	satisfied: bool,
+) -> (
+	ZerocheckClaim<BinaryField128b>,
+	UnivariateSkipZerocheckProof<BinaryField128b>,
+	EvaluationDomain<BinaryField128b>,
+) {
+	type F = BinaryField32b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let multilins = if satisfied {
+		generate_poly_helper::<F>(&mut rng, n_vars, n_multilinears)
+	} else {
+		repeat_with(|| {
+			let values = repeat_with(|| <F as Field>::random(&mut rng))
+				.take(1 << n_vars)
+				.collect();
+			MultilinearExtension::from_values(values).unwrap()
+		})
+		.take(n_multilinears)
+		.collect()
+	};
+	let zc_witness = MultilinearComposite::<FE, _, _>::new(
+		n_vars,
+		TestProductComposition::new(n_multilinears),
+		multilins
+			.into_iter()
+			.map(|multilin| multilin.specialize_arc_dyn())
+			.collect(),
+	)
+	.unwrap();
+
+	let mut oracles = MultilinearOracleSet::new();
+	let batch_id = oracles.add_committed_batch(CommittedBatchSpec {
+		n_vars,
+		n_polys: n_multilinears,
+		tower_level: F::TOWER_LEVEL,
+	});
+	let h = (0..n_multilinears)
+		.map(|index| oracles.committed_oracle(CommittedId { batch_id, index }))
+		.collect();
+	let zc_claim = ZerocheckClaim {
+		poly: CompositePolyOracle::new(n_vars, h, TestProductComposition::new(n_multilinears))
+			.unwrap(),
+	};
+
+	let skip_domain_size = n_multilinears * ((1 << skip_vars) - 1) + 1;
+	let skip_domain = EvaluationDomain::<F>::new(skip_domain_size).unwrap();
+	let verifier_skip_domain =
+		EvaluationDomain::<FE>::new_isomorphic::<F>(skip_domain_size).unwrap();
+	let domain = EvaluationDomain::<F>::new(n_multilinears + 1).unwrap();
+
+	let UnivariateSkipZerocheckProveOutput {
+		zerocheck_proof, ..
+	} = prove_univariate_skip::<FE, FE, F, _, _>(
+		&zc_claim,
+		zc_witness,
+		skip_vars,
+		&skip_domain,
+		&domain,
+		<HashChallenger<_, GroestlHasher<_>>>::new(),
+		|_| 1,
+	)
+	.unwrap();
+
+	(zc_claim, zerocheck_proof, verifier_skip_domain)
+}
+
+#[test]
+fn test_zerocheck_univariate_skip_rejects_tampered_proof() {
+	let (n_vars, skip_vars) = (5, 2);
+	let verify = |claim: &ZerocheckClaim<BinaryField128b>, proof, skip_domain| {
+		verify_univariate_skip(
+			claim,
+			skip_vars,
+			skip_domain,
+			proof,
+			<HashChallenger<_, GroestlHasher<_>>>::new(),
+		)
+	};
+
+	let (claim, proof, skip_domain) = univariate_skip_proof(n_vars, 3, skip_vars, true);
+	assert!(verify(&claim, proof.clone(), &skip_domain).is_ok());
+
+	// The skipped evaluations must fold to the final round claim
+	let mut tampered_proof = proof.clone();
+	tampered_proof.skipped_evals[1][2] += BinaryField128b::ONE;
+	assert_matches!(
+		verify(&claim, tampered_proof, &skip_domain),
+		Err(Error::Verification(VerificationError::IncorrectSkippedEvaluations))
+	);
+
+	// Changing the univariate skip round changes the claim the later rounds reduce
+	let mut tampered_proof = proof.clone();
+	tampered_proof.rounds[0].coeffs[0] += BinaryField128b::ONE;
+	assert_matches!(verify(&claim, tampered_proof, &skip_domain), Err(Error::Verification(_)));
+
+	// Changing a later round breaks the sumcheck reduction
+	let mut tampered_proof = proof;
+	tampered_proof.rounds[2].coeffs[0] += BinaryField128b::ONE;
+	assert_matches!(verify(&claim, tampered_proof, &skip_domain), Err(Error::Verification(_)));
+}
+
+#[test]
+fn test_zerocheck_univariate_skip_rejects_false_claim() {
+	let (n_vars, skip_vars) = (5, 2);
+	let (claim, proof, skip_domain) = univariate_skip_proof(n_vars, 3, skip_vars, false);
+	let result = verify_univariate_skip(
+		&claim,
+		skip_vars,
+		&skip_domain,
+		proof,
+		<HashChallenger<_, GroestlHasher<_>>>::new(),
+	);
+	assert_matches!(result, Err(Error::Verification(_)));
+}
+
+#[test]
+fn test_zerocheck_prove_verify_batch() {
+	type F = BinaryField32b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let instances = [(4, 2), (6, 3), (6, 1), (8, 2)];
+	let max_degree = instances.iter().map(|&(_, degree)| degree).max().unwrap();
+
+	let mut oracles = MultilinearOracleSet::new();
+	let (claims, witnesses): (Vec<_>, Vec<_>) = instances
+		.iter()
+		.map(|&(n_vars, n_multilinears)| {
+			let multilins = generate_poly_helper::<F>(&mut rng, n_vars, n_multilinears)
+				.into_iter()
+				.map(|m| m.specialize_arc_dyn())
+				.collect();
+			let witness = MultilinearComposite::<FE, _, _>::new(
+				n_vars,
+				TestProductComposition::new(n_multilinears),
+				multilins,
+			)
+			.unwrap();
+
+			let batch_id = oracles.add_committed_batch(CommittedBatchSpec {
+				n_vars,
+				n_polys: n_multilinears,
+				tower_level: F::TOWER_LEVEL,
+			});
+			let h = (0..n_multilinears)
+				.map(|i| oracles.committed_oracle(CommittedId { batch_id, index: i }))
+				.collect();
+			let claim = ZerocheckClaim {
+				poly: CompositePolyOracle::new(
+					n_vars,
+					h,
+					TestProductComposition::new(n_multilinears),
+				)
+				.unwrap(),
+			};
+			(claim, witness)
+		})
+		.unzip();
+
+	let domain = EvaluationDomain::<F>::new(max_degree + 1).unwrap();
+	let challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+
+	let ZerocheckBatchProveOutput {
+		evalcheck_claims,
+		proof,
+	} = batch_prove::<FE, FE, F, _, _>(
+		claims.iter().cloned().zip(witnesses.iter().cloned()),
+		&domain,
+		challenger.clone(),
+		|_| 3,
+	)
+	.unwrap();
+
+	let proof_bytes = to_versioned_bytes(&proof);
+	let proof = from_versioned_bytes::<ZerocheckBatchProof<FE>>(&proof_bytes).unwrap();
+
+	let verified_evalcheck_claims =
+		batch_verify(claims.iter().cloned(), proof.clone(), challenger.clone()).unwrap();
+	assert_eq!(evalcheck_claims.len(), verified_evalcheck_claims.len());
+
+	// The evalcheck claims are ordered by decreasing number of variables, stably
+	let mut ordered_witnesses = witnesses.clone();
+	ordered_witnesses.sort_by_key(|witness| witness.n_vars());
+	ordered_witnesses.reverse();
+
+	for ((claim, verified_claim), witness) in evalcheck_claims
+		.iter()
+		.zip(verified_evalcheck_claims.iter())
+		.zip(ordered_witnesses.iter())
+	{
+		assert_eq!(claim.eval, verified_claim.eval);
+		assert_eq!(claim.eval_point, verified_claim.eval_point);
+		assert_eq!(verified_claim.poly.n_vars(), witness.n_vars());
+
+		let query = MultilinearQuery::with_full_query(&verified_claim.eval_point).unwrap();
+		assert_eq!(witness.evaluate(&query).unwrap(), verified_claim.eval);
+	}
+
+	// Tampering with a final evaluation must be detected
+	let mut bad_proof = proof.clone();
+	bad_proof.evals[0] += FE::ONE;
+	assert_matches!(
+		batch_verify(claims.iter().cloned(), bad_proof, challenger.clone()),
+		Err(Error::Verification(VerificationError::IncorrectBatchEvaluation))
+	);
+
+	// A round polynomial of higher degree than the claims in the round must be rejected, in the
+	// first round and in a round where new claims are mixed in
+	for round_no in [0, 2] {
+		let mut bad_proof = proof.clone();
+		bad_proof.rounds[round_no].coeffs.push(FE::ZERO);
+		assert_matches!(
+			batch_verify(claims.iter().cloned(), bad_proof, challenger.clone()),
+			Err(Error::Verification(VerificationError::NumberOfCoefficients))
+		);
+	}
+}
+
+/// Hides the linear factors of the inner composition, forcing the generic evaluation path.
+#[derive(Debug, Clone)]
+struct OpaqueComposition<C>(C);
+
+impl<F: Field, C: CompositionPoly<F>> CompositionPoly<F> for OpaqueComposition<C> {
+	fn n_vars(&self) -> usize {
+		self.0.n_vars()
+	}
+
+	fn degree(&self) -> usize {
+		self.0.degree()
+	}
+
+	fn evaluate<P: PackedField<Scalar = F>>(&self, query: &[P]) -> Result<P, PolynomialError> {
+		self.0.evaluate(query)
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		self.0.binary_tower_level()
+	}
+}
+
+#[test]
+fn test_zerocheck_linear_product_composition() {
+	type F = BinaryField32b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+	let n_vars = 6;
+
+	// An S-box style constraint X (X + 1) (X + a) (X + b) (X + c) (X + d) (X + e), which vanishes
+	// when X takes one of seven values
+	let roots = [0, 1, 2, 3, 5, 7, 11].map(F::new);
+	let factors = roots
+		.iter()
+		.map(|&root| LinearFactor {
+			constant: FE::from(root),
+			terms: vec![(0, FE::ONE)],
+		})
+		.collect();
+	let composition = LinearProductComposition::new(1, factors).unwrap();
+	let degree = composition.degree();
+	assert_eq!(degree, roots.len());
+
+	let values = repeat_with(|| roots[rng.gen_range(0..roots.len())])
+		.take(1 << n_vars)
+		.collect::<Vec<_>>();
+	let multilin = MultilinearExtension::from_values(values).unwrap();
+
+	let mut oracles = MultilinearOracleSet::new();
+	let batch_id = oracles.add_committed_batch(CommittedBatchSpec {
+		n_vars,
+		n_polys: 1,
+		tower_level: F::TOWER_LEVEL,
+	});
+	let h = vec![oracles.committed_oracle(CommittedId { batch_id, index: 0 })];
+	let claim = ZerocheckClaim {
+		poly: CompositePolyOracle::new(n_vars, h, composition.clone()).unwrap(),
+	};
+
+	let domain = EvaluationDomain::<F>::new(degree + 1).unwrap();
+	let challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+
+	let witness = MultilinearComposite::<FE, _, _>::new(
+		n_vars,
+		composition.clone(),
+		vec![multilin.clone().specialize_arc_dyn()],
+	)
+	.unwrap();
+	let opaque_witness = MultilinearComposite::<FE, _, _>::new(
+		n_vars,
+		OpaqueComposition(composition),
+		vec![multilin.specialize_arc_dyn()],
+	)
+	.unwrap();
+
+	let output =
+		prove::<FE, FE, F, _, _>(&claim, witness.clone(), &domain, challenger.clone(), |_| 2)
+			.unwrap();
+	let opaque_output =
+		prove::<FE, FE, F, _, _>(&claim, opaque_witness, &domain, challenger.clone(), |_| 2)
+			.unwrap();
+
+	// Both evaluation strategies produce the same round polynomials
+	for (round, opaque_round) in output
+		.zerocheck_proof
+		.rounds
+		.iter()
+		.zip(opaque_output.zerocheck_proof.rounds.iter())
+	{
+		assert_eq!(round.coeffs, opaque_round.coeffs);
+	}
+
+	let evalcheck_claim = verify(&claim, output.zerocheck_proof, challenger).unwrap();
+	let query = MultilinearQuery::with_full_query(&evalcheck_claim.eval_point).unwrap();
+	assert_eq!(witness.evaluate(&query).unwrap(), evalcheck_claim.eval);
+}
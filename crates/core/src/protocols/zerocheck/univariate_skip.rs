@@ -0,0 +1,536 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Opt-in univariate skip mode for the zerocheck protocol.
+//!
+//! The first round of a regular zerocheck binds a single variable. With univariate skip, the
+//! first round binds the lowest $k$ variables at once, following Section 5 of [Gruen24]. The
+//! hypercube $\{0, 1\}^k$ is identified with the first $2^k$ points $D$ of a univariate evaluation
+//! domain, and each multilinear $M_j$ is replaced by the polynomial $\hat{M}_j(X, x)$ which has
+//! degree less than $2^k$ in $X$ and agrees with $M_j$ on $D$. The prover sends the round
+//! polynomial
+//!
+//! $$r_0(X) = \sum_{x \in \{0, 1\}^{n-k}} eq(\alpha, x) C(\hat{M}(X, x)),$$
+//!
+//! which vanishes on $D$, as the quotient $q(X) = r_0(X) / Z_D(X)$ by the vanishing polynomial of
+//! $D$. The remaining $n - k$ variables are bound by the later rounds of a regular zerocheck.
+//!
+//! In the first round, the prover only evaluates the witness and the composition at points of the
+//! skip domain, all in the small field of the domain, and only lifts the weighted sums to the
+//! extension field. This saves most of the extension field work for small field witnesses.
+//!
+//! The claim obtained at the end of the sumcheck is on the polynomials $\hat{M}_j$, which are not
+//! multilinear. The prover therefore sends the evaluations $M_j(b, r')$ for all
+//! $b \in \{0, 1\}^k$, from which the verifier checks the final round claim and derives
+//! multilinear evaluation claims on the $M_j$ at a fresh random point $(s, r')$. These claims are
+//! suitable for `greedy_evalcheck`.
+//!
+//! The mode is selected by calling [`prove_univariate_skip`] and [`verify_univariate_skip`] in
+//! place of [`super::prove`] and [`super::verify`], like the hiding mode. It is not a flag on those
+//! functions because both its proof, which carries the skipped evaluations, and its output, which
+//! is one evaluation claim per multilinear rather than a single claim on the composite, differ
+//! from the regular mode, so every caller of a combined entry point would have to handle both.
+//!
+//! [Gruen24]: https://eprint.iacr.org/2024/108
+
+use super::{
+	error::{Error, VerificationError},
+	prove::ZerocheckProver,
+	zerocheck::{
+		ZerocheckClaim, ZerocheckReductor, ZerocheckRound, ZerocheckRoundClaim, ZerocheckWitness,
+		CHALLENGES_LABEL, ROUND_LABEL, SKIPPED_EVALS_LABEL, SKIP_POINT_LABEL,
+	},
+};
+use crate::{
+	challenger::{CanObserve, CanSample, DomainSeparator},
+	polynomial::{
+		evaluate_univariate, transparent::eq_ind::EqIndPartialEval, CompositionPoly,
+		EvaluationDomain, MultilinearExtension, MultilinearPoly, MultilinearQuery,
+	},
+	protocols::{
+		abstract_sumcheck::{AbstractSumcheckProver, AbstractSumcheckReductor},
+		evalcheck::EvalcheckClaim,
+	},
+	serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes},
+	witness::MultilinearWitness,
+};
+use binius_field::{util::inner_product_unchecked, ExtensionField, Field, PackedField, TowerField};
+use p3_util::log2_strict_usize;
+use rayon::prelude::*;
+use tracing::instrument;
+
+/// A zerocheck proof with a univariate skip first round.
+#[derive(Debug, Clone)]
+pub struct UnivariateSkipZerocheckProof<F> {
+	/// The round messages. The first message holds the coefficients of the quotient of the
+	/// univariate round polynomial by the vanishing polynomial of the skipped domain.
+	pub rounds: Vec<ZerocheckRound<F>>,
+	/// For each multilinear, its evaluations at the final sumcheck point on every vertex of the
+	/// skipped subcube.
+	pub skipped_evals: Vec<Vec<F>>,
+}
+
+impl<F: SerializeBytes> SerializeBytes for UnivariateSkipZerocheckProof<F> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.rounds.serialize(write_buf);
+		self.skipped_evals.serialize(write_buf);
+	}
+}
+
+impl<F: DeserializeBytes> DeserializeBytes for UnivariateSkipZerocheckProof<F> {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		let rounds = Vec::deserialize(read_buf)?;
+		let skipped_evals = Vec::deserialize(read_buf)?;
+		Ok(Self {
+			rounds,
+			skipped_evals,
+		})
+	}
+}
+
+#[derive(Debug)]
+pub struct UnivariateSkipZerocheckProveOutput<F: Field> {
+	/// Evaluation claims on each multilinear of the zerocheck claim, in order.
+	pub evalcheck_claims: Vec<EvalcheckClaim<F>>,
+	pub zerocheck_proof: UnivariateSkipZerocheckProof<F>,
+}
+
+/// Prove a zerocheck to evalcheck reduction with a univariate skip over the lowest `skip_vars`
+/// variables.
+///
+/// The first $2^{skip\\_vars}$ points of `skip_domain` are identified with the skipped subcube, and
+/// its size must be $d (2^{skip\\_vars} - 1) + 1$, where $d$ is the degree of the composition.
+/// `domain` is the evaluation domain for the later rounds, as in [`super::prove`].
+///
+/// The hypercube evaluations of the witness must lie in the skip domain field `FS`, over which
+/// the first round is computed. The remaining $n - skip\\_vars$ variables must be enough to fill
+/// a `PW` packed field element.
+#[instrument(skip_all, name = "zerocheck::prove_univariate_skip")]
+#[allow(clippy::too_many_arguments)]
+pub fn prove_univariate_skip<F, PW, FS, CW, CH>(
+	claim: &ZerocheckClaim<F>,
+	witness: ZerocheckWitness<'_, PW, CW>,
+	skip_vars: usize,
+	skip_domain: &EvaluationDomain<FS>,
+	domain: &EvaluationDomain<FS>,
+	mut challenger: CH,
+	switchover_fn: impl Fn(usize) -> usize,
+) -> Result<UnivariateSkipZerocheckProveOutput<F>, Error>
+where
+	F: TowerField + From<PW::Scalar>,
+	PW: PackedField,
+	PW::Scalar: TowerField + From<F> + ExtensionField<FS>,
+	FS: Field,
+	CW: CompositionPoly<PW::Scalar> + CompositionPoly<FS>,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	let n_vars = claim.poly.n_vars();
+	let degree = claim.poly.max_individual_degree();
+	if degree == 0 {
+		return Err(Error::PolynomialDegreeIsZero);
+	}
+	if witness.n_vars() != n_vars {
+		return Err(Error::ProverClaimWitnessMismatch(format!(
+			"Claim and Witness n_vars mismatch in zerocheck. Claim: {}, Witness: {}",
+			n_vars,
+			witness.n_vars(),
+		)));
+	}
+	check_skip_domain(n_vars, degree, skip_vars, skip_domain)?;
+
+	let n_rest_vars = n_vars - skip_vars;
+	if n_rest_vars < PW::LOG_WIDTH {
+		return Err(Error::ImproperInput(format!(
+			"skipping {skip_vars} of {n_vars} variables leaves {n_rest_vars} variables, which \
+			 do not fill a packed witness field of width {}",
+			PW::WIDTH
+		)));
+	}
+	let zerocheck_challenges = challenger.sample_vec_labeled(CHALLENGES_LABEL, n_rest_vars);
+
+	let (skip_points, extra_points) = skip_domain.points().split_at(1 << skip_vars);
+	let skip_round =
+		prove_skip_round(&witness, skip_vars, skip_points, extra_points, &zerocheck_challenges)?;
+	challenger.observe_slice_labeled(ROUND_LABEL, &skip_round.coeffs);
+	let skip_challenge: F = challenger.sample();
+
+	let f_skip_points = skip_points
+		.iter()
+		.map(|&point| PW::Scalar::from(point).into())
+		.collect::<Vec<F>>();
+	let round_claim = ZerocheckRoundClaim {
+		partial_point: vec![skip_challenge],
+		current_round_sum: skip_round_sum(&f_skip_points, &skip_round.coeffs, skip_challenge),
+	};
+
+	// Fold the skipped variables of each multilinear into the challenge
+	let pw_skip_points = skip_points
+		.iter()
+		.map(|&point| PW::Scalar::from(point))
+		.collect::<Vec<_>>();
+	let lagrange = lagrange_evals(&pw_skip_points, skip_challenge.into());
+	let folded_evals = witness
+		.multilinears
+		.iter()
+		.map(|multilin| {
+			(0..1 << n_rest_vars)
+				.into_par_iter()
+				.map(|i| {
+					(0..1 << skip_vars).try_fold(PW::Scalar::ZERO, |acc, j| {
+						let eval = multilin.evaluate_on_hypercube(j | i << skip_vars)?;
+						Ok::<_, Error>(acc + eval * lagrange[j])
+					})
+				})
+				.collect::<Result<Vec<_>, _>>()
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	// The later rounds expect the composition evaluated over the hypercube of the folded
+	// multilinears, see Section 4 of [Gruen24]
+	let round_q_bar_evals = (0..1 << n_rest_vars)
+		.into_par_iter()
+		.map(|i| {
+			let evals = folded_evals
+				.iter()
+				.map(|evals| evals[i])
+				.collect::<Vec<_>>();
+			CompositionPoly::<PW::Scalar>::evaluate(&witness.composition, &evals)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	let round_q_bar = MultilinearExtension::from_values(round_q_bar_evals)?;
+
+	let folded_multilinears = folded_evals
+		.into_iter()
+		.map(packed_multilinear)
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let mut zerocheck_prover: ZerocheckProver<F, PW, FS, _> =
+		ZerocheckProver::new_after_univariate_skip(
+			domain,
+			claim.poly.clone(),
+			witness.composition,
+			folded_multilinears,
+			zerocheck_challenges,
+			switchover_fn,
+			round_claim,
+			round_q_bar,
+		)?;
+
+	let mut rounds = Vec::with_capacity(n_rest_vars + 1);
+	rounds.push(skip_round);
+	let mut prev_rd_challenge = None;
+	for _round in 0..n_rest_vars {
+		let round =
+			AbstractSumcheckProver::execute_round(&mut zerocheck_prover, prev_rd_challenge)?;
+		challenger.observe_slice_labeled(ROUND_LABEL, &round.coeffs);
+		prev_rd_challenge = Some(challenger.sample());
+		rounds.push(round);
+	}
+	let round_claim = zerocheck_prover.finalize_round_claim(prev_rd_challenge)?;
+
+	// Evaluate the multilinears at the final point on the skipped subcube
+	let rest_point = round_claim.partial_point[1..]
+		.iter()
+		.map(|&r| r.into())
+		.collect::<Vec<PW::Scalar>>();
+	let query = MultilinearQuery::<PW>::with_full_query(&rest_point)?;
+	let skipped_evals = witness
+		.multilinears
+		.iter()
+		.map(|multilin| {
+			let partial_eval = multilin.evaluate_partial_high(&query)?;
+			(0..1 << skip_vars)
+				.map(|j| Ok(partial_eval.evaluate_on_hypercube(j)?.into()))
+				.collect::<Result<Vec<F>, Error>>()
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let evalcheck_claims =
+		reduce_skipped_evals(claim, &round_claim, &f_skip_points, &skipped_evals, &mut challenger)?;
+
+	Ok(UnivariateSkipZerocheckProveOutput {
+		evalcheck_claims,
+		zerocheck_proof: UnivariateSkipZerocheckProof {
+			rounds,
+			skipped_evals,
+		},
+	})
+}
+
+/// Verify a zerocheck to evalcheck reduction with a univariate skip over the lowest `skip_vars`
+/// variables.
+///
+/// `skip_domain` must consist of the same points as the one given to [`prove_univariate_skip`].
+/// Returns an evaluation claim on each multilinear of the zerocheck claim, in order.
+#[instrument(skip_all, name = "zerocheck::verify_univariate_skip")]
+pub fn verify_univariate_skip<F, CH>(
+	claim: &ZerocheckClaim<F>,
+	skip_vars: usize,
+	skip_domain: &EvaluationDomain<F>,
+	proof: UnivariateSkipZerocheckProof<F>,
+	mut challenger: CH,
+) -> Result<Vec<EvalcheckClaim<F>>, Error>
+where
+	F: TowerField,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	let n_vars = claim.poly.n_vars();
+	let degree = claim.poly.max_individual_degree();
+	if degree == 0 {
+		return Err(Error::PolynomialDegreeIsZero);
+	}
+	check_skip_domain(n_vars, degree, skip_vars, skip_domain)?;
+
+	let n_rest_vars = n_vars - skip_vars;
+	let UnivariateSkipZerocheckProof {
+		rounds,
+		skipped_evals,
+	} = proof;
+	if rounds.len() != n_rest_vars + 1 {
+		return Err(VerificationError::NumberOfRounds.into());
+	}
+
+	let zerocheck_challenges = challenger.sample_vec_labeled(CHALLENGES_LABEL, n_rest_vars);
+
+	let mut rounds = rounds.into_iter();
+	let skip_round = rounds.next().expect("rounds has n_rest_vars + 1 elements");
+	if skip_round.coeffs.len() != (degree - 1) * ((1 << skip_vars) - 1) {
+		return Err(VerificationError::NumberOfCoefficients.into());
+	}
+	challenger.observe_slice_labeled(ROUND_LABEL, &skip_round.coeffs);
+	let skip_challenge = challenger.sample();
+
+	let skip_points = &skip_domain.points()[..1 << skip_vars];
+	let mut round_claim = ZerocheckRoundClaim {
+		partial_point: vec![skip_challenge],
+		current_round_sum: skip_round_sum(skip_points, &skip_round.coeffs, skip_challenge),
+	};
+
+	let reductor = ZerocheckReductor {
+		alphas: &zerocheck_challenges,
+	};
+	for (which_round, round_proof) in rounds.enumerate() {
+		challenger.observe_slice_labeled(ROUND_LABEL, &round_proof.coeffs);
+		let round_challenge = challenger.sample();
+		round_claim = reductor.reduce_intermediate_round_claim(
+			which_round + 1,
+			round_claim,
+			round_challenge,
+			round_proof,
+		)?;
+	}
+
+	if skipped_evals.len() != claim.poly.n_multilinears()
+		|| skipped_evals
+			.iter()
+			.any(|evals| evals.len() != 1 << skip_vars)
+	{
+		return Err(VerificationError::NumberOfSkippedEvaluations.into());
+	}
+
+	// Check the final round claim against the evaluations of the folded multilinears
+	let lagrange = lagrange_evals(skip_points, skip_challenge);
+	let folded_evals = skipped_evals
+		.iter()
+		.map(|evals| inner_product_unchecked(evals.iter().copied(), lagrange.iter().copied()))
+		.collect::<Vec<_>>();
+	if claim.poly.composition().evaluate(&folded_evals)? != round_claim.current_round_sum {
+		return Err(VerificationError::IncorrectSkippedEvaluations.into());
+	}
+
+	reduce_skipped_evals(claim, &round_claim, skip_points, &skipped_evals, &mut challenger)
+}
+
+/// Compute the quotient of the univariate skip round polynomial by the vanishing polynomial of
+/// the skipped domain.
+///
+/// The witness is extrapolated and the composition evaluated over the skip domain field, and only
+/// the results are lifted to the extension field to be weighted by the equality indicator.
+fn prove_skip_round<F, PW, FS, CW>(
+	witness: &ZerocheckWitness<'_, PW, CW>,
+	skip_vars: usize,
+	skip_points: &[FS],
+	extra_points: &[FS],
+	zerocheck_challenges: &[F],
+) -> Result<ZerocheckRound<F>, Error>
+where
+	F: Field + From<PW::Scalar>,
+	PW: PackedField,
+	PW::Scalar: From<F> + ExtensionField<FS>,
+	FS: Field,
+	CW: CompositionPoly<FS>,
+{
+	// A linear composition vanishing on the skipped domain has a zero round polynomial
+	if extra_points.is_empty() {
+		return Ok(ZerocheckRound { coeffs: Vec::new() });
+	}
+
+	let n_rest_vars = zerocheck_challenges.len();
+	let pw_challenges = zerocheck_challenges
+		.iter()
+		.map(|&f| f.into())
+		.collect::<Vec<PW::Scalar>>();
+	let eq_ind =
+		EqIndPartialEval::new(n_rest_vars, pw_challenges)?.multilinear_extension::<PW::Scalar>()?;
+
+	let extra_lagrange = extra_points
+		.iter()
+		.map(|&point| lagrange_evals(skip_points, point))
+		.collect::<Vec<_>>();
+
+	// Evaluate $r_0$ on the points of the skip domain outside of the skipped subcube
+	let round_evals = (0..1 << n_rest_vars)
+		.into_par_iter()
+		.map(|i| {
+			let eq_ind_factor = eq_ind.evaluate_on_hypercube(i)?;
+			let subcube_evals = witness
+				.multilinears
+				.iter()
+				.map(|multilin| {
+					(0..1 << skip_vars)
+						.map(|j| {
+							let eval = multilin.evaluate_on_hypercube(j | i << skip_vars)?;
+							eval.try_into()
+								.map_err(|_| Error::WitnessNotInSkipDomainField)
+						})
+						.collect::<Result<Vec<FS>, _>>()
+				})
+				.collect::<Result<Vec<_>, _>>()?;
+
+			let mut evals_z = vec![FS::ZERO; subcube_evals.len()];
+			extra_lagrange
+				.iter()
+				.map(|lagrange| {
+					for (eval_z, evals) in evals_z.iter_mut().zip(subcube_evals.iter()) {
+						*eval_z = inner_product_unchecked(
+							evals.iter().copied(),
+							lagrange.iter().copied(),
+						);
+					}
+					Ok(eq_ind_factor * witness.composition.evaluate(&evals_z)?)
+				})
+				.collect::<Result<Vec<_>, Error>>()
+		})
+		.try_reduce(
+			|| vec![PW::Scalar::ZERO; extra_points.len()],
+			|mut acc, evals| {
+				for (acc_i, eval) in acc.iter_mut().zip(evals) {
+					*acc_i += eval;
+				}
+				Ok(acc)
+			},
+		)?;
+
+	let quotient_evals = round_evals
+		.into_iter()
+		.zip(extra_points.iter())
+		.map(|(eval, &point)| {
+			let vanishing_inv = vanishing_eval(skip_points, point)
+				.invert()
+				.expect("skip domain points are distinct");
+			eval * vanishing_inv
+		})
+		.collect::<Vec<_>>();
+	let quotient_domain = EvaluationDomain::from_points(extra_points.to_vec())?;
+	let coeffs = quotient_domain
+		.interpolate(&quotient_evals)?
+		.into_iter()
+		.map(Into::into)
+		.collect();
+
+	Ok(ZerocheckRound { coeffs })
+}
+
+/// Observe the skipped evaluations and reduce them to evaluation claims on the multilinears at a
+/// random point.
+fn reduce_skipped_evals<F, CH>(
+	claim: &ZerocheckClaim<F>,
+	round_claim: &ZerocheckRoundClaim<F>,
+	skip_points: &[F],
+	skipped_evals: &[Vec<F>],
+	challenger: &mut CH,
+) -> Result<Vec<EvalcheckClaim<F>>, Error>
+where
+	F: Field,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
+{
+	for evals in skipped_evals {
+		challenger.observe_slice_labeled(SKIPPED_EVALS_LABEL, evals);
+	}
+	let skip_point =
+		challenger.sample_vec_labeled(SKIP_POINT_LABEL, log2_strict_usize(skip_points.len()));
+	let query = MultilinearQuery::<F>::with_full_query(&skip_point)?;
+
+	let eval_point = [skip_point.as_slice(), &round_claim.partial_point[1..]].concat();
+	let evalcheck_claims = claim
+		.poly
+		.inner_polys()
+		.into_iter()
+		.zip(skipped_evals.iter())
+		.map(|(oracle, evals)| EvalcheckClaim {
+			poly: oracle.into_composite(),
+			eval_point: eval_point.clone(),
+			eval: inner_product_unchecked(evals.iter().copied(), query.expansion().iter().copied()),
+			is_random_point: true,
+		})
+		.collect();
+	Ok(evalcheck_claims)
+}
+
+/// Validate that `skip_vars` leaves at least one variable for the later rounds and that the size
+/// of the skip domain is exactly one greater than the degree of the univariate round polynomial.
+fn check_skip_domain<F: Field>(
+	n_vars: usize,
+	degree: usize,
+	skip_vars: usize,
+	skip_domain: &EvaluationDomain<F>,
+) -> Result<(), Error> {
+	if skip_vars == 0 || skip_vars >= n_vars {
+		return Err(Error::InvalidSkipVars { skip_vars, n_vars });
+	}
+	if skip_domain.size() != degree * ((1 << skip_vars) - 1) + 1 {
+		return Err(Error::EvaluationDomainMismatch);
+	}
+	Ok(())
+}
+
+/// Evaluate the univariate skip round polynomial $Z_D(X) q(X)$ at the challenge.
+fn skip_round_sum<F: Field>(skip_points: &[F], quotient_coeffs: &[F], challenge: F) -> F {
+	vanishing_eval(skip_points, challenge) * evaluate_univariate(quotient_coeffs, challenge)
+}
+
+/// Evaluate the vanishing polynomial of `points` at `x`.
+fn vanishing_eval<F: Field>(points: &[F], x: F) -> F {
+	points.iter().map(|&point| x - point).product()
+}
+
+/// Evaluate the Lagrange basis polynomials of `points` at `x`.
+fn lagrange_evals<F: Field>(points: &[F], x: F) -> Vec<F> {
+	points
+		.iter()
+		.enumerate()
+		.map(|(i, &point_i)| {
+			let (numerator, denominator) = points
+				.iter()
+				.enumerate()
+				.filter(|&(j, _)| j != i)
+				.fold((F::ONE, F::ONE), |(num, denom), (_, &point_j)| {
+					(num * (x - point_j), denom * (point_i - point_j))
+				});
+			numerator * denominator.invert().expect("points are distinct")
+		})
+		.collect()
+}
+
+/// Pack the hypercube evaluations of an extension field multilinear into a witness.
+///
+/// There must be at least `PW::WIDTH` evaluations, which [`prove_univariate_skip`] checks up front.
+fn packed_multilinear<PW: PackedField>(
+	evals: Vec<PW::Scalar>,
+) -> Result<MultilinearWitness<'static, PW>, Error> {
+	debug_assert!(evals.len() >= PW::WIDTH);
+	let packed = evals
+		.chunks(PW::WIDTH)
+		.map(|chunk| PW::from_fn(|i| chunk[i]))
+		.collect();
+	Ok(MultilinearExtension::from_values(packed)?.specialize_arc_dyn())
+}
@@ -1,17 +1,22 @@
 // Copyright 2023 Ulvetanna Inc.
 
 use super::{
-	zerocheck::{ZerocheckClaim, ZerocheckProof, ZerocheckReductor, ZerocheckRoundClaim},
+	zerocheck::{
+		ZerocheckClaim, ZerocheckProof, ZerocheckReductor, ZerocheckRoundClaim, CHALLENGES_LABEL,
+		ROUND_LABEL,
+	},
 	Error, VerificationError,
 };
 use crate::{
-	challenger::{CanObserve, CanSample},
+	challenger::{CanObserve, CanSample, DomainSeparator},
 	protocols::{abstract_sumcheck, evalcheck::EvalcheckClaim},
 };
 use binius_field::TowerField;
 use tracing::instrument;
 
 /// Verify a zerocheck to evalcheck reduction.
+///
+/// See [`super::verify_univariate_skip`] for the univariate skip mode.
 #[instrument(skip_all, name = "zerocheck::verify")]
 pub fn verify<F, CH>(
 	claim: &ZerocheckClaim<F>,
@@ -20,7 +25,7 @@ pub fn verify<F, CH>(
 ) -> Result<EvalcheckClaim<F>, Error>
 where
 	F: TowerField,
-	CH: CanSample<F> + CanObserve<F>,
+	CH: CanSample<F> + CanObserve<F> + DomainSeparator,
 {
 	if claim.poly.max_individual_degree() == 0 {
 		return Err(Error::PolynomialDegreeIsZero);
@@ -33,13 +38,19 @@ where
 		return Err(VerificationError::NumberOfRounds.into());
 	}
 
-	let zerocheck_challenges = challenger.sample_vec(n_vars - 1);
+	let zerocheck_challenges = challenger.sample_vec_labeled(CHALLENGES_LABEL, n_vars - 1);
 	let first_round_claim = setup_first_round_claim();
 	let reductor = ZerocheckReductor {
 		alphas: &zerocheck_challenges,
 	};
-	let evalcheck_claim =
-		abstract_sumcheck::verify(&claim.poly, first_round_claim, proof, reductor, challenger)?;
+	let evalcheck_claim = abstract_sumcheck::verify(
+		&claim.poly,
+		first_round_claim,
+		proof,
+		reductor,
+		ROUND_LABEL,
+		challenger,
+	)?;
 
 	Ok(evalcheck_claim)
 }
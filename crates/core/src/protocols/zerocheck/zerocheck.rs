@@ -17,6 +17,20 @@ use std::fmt::Debug;
 
 use super::{Error, VerificationError};
 
+/// Challenger label for the zerocheck challenges sampled before the first round.
+pub(super) const CHALLENGES_LABEL: &str = "zerocheck.challenges";
+/// Challenger label for the zerocheck round messages.
+pub(super) const ROUND_LABEL: &str = "zerocheck.round";
+/// Challenger label for the evaluations on the subcube skipped by a univariate skip round.
+pub(super) const SKIPPED_EVALS_LABEL: &str = "zerocheck.skipped_evals";
+/// Challenger label for the point on the skipped subcube at which the skipped evaluations are
+/// combined into evaluation claims.
+pub(super) const SKIP_POINT_LABEL: &str = "zerocheck.skip_point";
+/// Challenger label for the coefficients mixing the instances of a batched zerocheck.
+pub(super) const BATCH_COEFFS_LABEL: &str = "zerocheck.batch_coeffs";
+/// Challenger label for the final evaluations of a batched zerocheck.
+pub(super) const EVALS_LABEL: &str = "zerocheck.evals";
+
 #[derive(Debug, Clone)]
 pub struct ZerocheckClaim<F: Field> {
 	/// Virtual Polynomial Oracle of the function claimed to be zero on hypercube
@@ -84,9 +98,32 @@ fn reduce_intermediate_round_claim_helper<F: Field>(
 		current_round_sum,
 	} = claim;
 
-	let ZerocheckRound { mut coeffs } = proof;
-	let which_round = partial_point.len();
+	let ZerocheckRound { coeffs } = proof;
+	let coeffs = recover_round_coeffs(partial_point.len(), current_round_sum, coeffs, alpha_i)?;
+
+	let new_round_sum = evaluate_univariate(&coeffs, challenge);
+
+	partial_point.push(challenge);
+
+	Ok(ZerocheckRoundClaim {
+		partial_point,
+		current_round_sum: new_round_sum,
+	})
+}
 
+/// Recover the full monomial coefficients of a zerocheck round polynomial from a round message.
+///
+/// Arguments:
+/// * `which_round`: The index of the round.
+/// * `current_round_sum`: The claimed sum of the round.
+/// * `coeffs`: The coefficients sent by the prover in the round message.
+/// * `alpha_i`: The zerocheck challenge for round i, which is `None` in the first round.
+pub(super) fn recover_round_coeffs<F: Field>(
+	which_round: usize,
+	current_round_sum: F,
+	mut coeffs: Vec<F>,
+	alpha_i: Option<F>,
+) -> Result<Vec<F>, Error> {
 	// The prover has sent some coefficients for the purported ith round polynomial
 	// * $r_i(X) = \sum_{j=0}^d a_j * X^j$
 	// The verifier will need to recover the missing coefficient(s).
@@ -143,14 +180,7 @@ fn reduce_intermediate_round_claim_helper<F: Field>(
 		coeffs.insert(0, constant_term);
 	}
 
-	let new_round_sum = evaluate_univariate(&coeffs, challenge);
-
-	partial_point.push(challenge);
-
-	Ok(ZerocheckRoundClaim {
-		partial_point,
-		current_round_sum: new_round_sum,
-	})
+	Ok(coeffs)
 }
 
 fn reduce_final_round_claim_helper<F: Field>(
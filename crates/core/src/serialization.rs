@@ -0,0 +1,10 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Byte serialization of commitments and proofs.
+//!
+//! The protocol proof types in this crate implement the canonical encoding traits from
+//! [`binius_utils::serialization`], so that a proof produced by the prover can be written with
+//! [`to_versioned_bytes`] and consumed by a verifier running in a different process with
+//! [`from_versioned_bytes`].
+
+pub use binius_utils::serialization::*;
@@ -0,0 +1,101 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{error::Error, MultilinearWitnessIndex};
+use crate::{
+	oracle::{CompositePolyOracle, OracleId},
+	polynomial::CompositionPoly,
+};
+use binius_field::{Field, PackedField};
+
+/// A constraint that does not vanish on a row of the witness.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintViolation<F> {
+	/// Name of the violated constraint
+	pub name: String,
+	/// Index of the hypercube vertex on which the constraint does not vanish
+	pub row: usize,
+	/// Values of the columns on the row, as pairs of oracle ID and value
+	pub values: Vec<(OracleId, F)>,
+}
+
+/// Evaluate named constraints on every row of a witness and report the rows where they fail.
+///
+/// The columns are the inner polynomials of `oracle`, and every constraint is a composition over
+/// these columns, for example one of the compositions mixed into the composition of a zerocheck
+/// claim. Rows are visited in increasing order and all constraints are evaluated on each row, so
+/// the first `max_violations` violations are returned. An empty result means that every constraint
+/// vanishes on the whole hypercube, which is what the zerocheck prover requires.
+pub fn find_constraint_violations<F, PW, S, C>(
+	oracle: &CompositePolyOracle<F>,
+	constraints: impl IntoIterator<Item = (S, C)>,
+	witness_index: &MultilinearWitnessIndex<PW>,
+	max_violations: usize,
+) -> Result<Vec<ConstraintViolation<PW::Scalar>>, Error>
+where
+	F: Field,
+	PW: PackedField,
+	S: ToString,
+	C: CompositionPoly<PW::Scalar>,
+{
+	let columns = oracle.inner_polys();
+	let constraints = constraints
+		.into_iter()
+		.map(|(name, constraint)| {
+			let name = name.to_string();
+			if constraint.n_vars() != columns.len() {
+				return Err(Error::ConstraintNumberOfColumnsMismatch {
+					name,
+					expected: columns.len(),
+					n_vars: constraint.n_vars(),
+				});
+			}
+			Ok((name, constraint))
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let witnesses = columns
+		.iter()
+		.map(|column| {
+			let id = column.id();
+			let witness = witness_index.get(id).ok_or(Error::MissingWitness { id })?;
+			if witness.n_vars() != oracle.n_vars() {
+				return Err(Error::IncorrectNumberOfVariables {
+					id,
+					expected: oracle.n_vars(),
+					n_vars: witness.n_vars(),
+				});
+			}
+			Ok(witness)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let mut violations = Vec::new();
+	if max_violations == 0 {
+		return Ok(violations);
+	}
+	for row in 0..1 << oracle.n_vars() {
+		let query = witnesses
+			.iter()
+			.map(|witness| witness.evaluate_on_hypercube(row))
+			.collect::<Result<Vec<_>, _>>()?;
+		for (name, constraint) in constraints.iter() {
+			if constraint.evaluate(&query)? == PW::Scalar::ZERO {
+				continue;
+			}
+			violations.push(ConstraintViolation {
+				name: name.clone(),
+				row,
+				values: columns
+					.iter()
+					.map(|column| column.id())
+					.zip(query.iter().copied())
+					.collect(),
+			});
+			if violations.len() == max_violations {
+				return Ok(violations);
+			}
+		}
+	}
+
+	Ok(violations)
+}
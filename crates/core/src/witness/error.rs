@@ -0,0 +1,39 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::{oracle::OracleId, polynomial};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("missing witnesses for committed oracles {ids:?}")]
+	MissingCommittedWitnesses { ids: Vec<OracleId> },
+	#[error("missing witness for oracle {id}")]
+	MissingWitness { id: OracleId },
+	#[error("witness of oracle {id} has {n_vars} variables, but the oracle has {expected}")]
+	IncorrectNumberOfVariables {
+		id: OracleId,
+		expected: usize,
+		n_vars: usize,
+	},
+	#[error("witness of oracle {id} has tower level {tower_level}, but the oracle has tower level {expected}")]
+	TowerLevelTooHigh {
+		id: OracleId,
+		expected: usize,
+		tower_level: usize,
+	},
+	#[error("oracle {id} has {n_vars} variables, fewer than the log width {log_width} of the witness packing")]
+	NotEnoughVariables {
+		id: OracleId,
+		n_vars: usize,
+		log_width: usize,
+	},
+	#[error("constraint {name} has {n_vars} variables, but there are {expected} columns")]
+	ConstraintNumberOfColumnsMismatch {
+		name: String,
+		expected: usize,
+		n_vars: usize,
+	},
+	#[error("field error: {0}")]
+	Field(#[from] binius_field::Error),
+	#[error("polynomial error: {0}")]
+	Polynomial(#[from] polynomial::Error),
+}
@@ -0,0 +1,232 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{error::Error, MultilinearWitness, MultilinearWitnessIndex};
+use crate::{
+	oracle::{
+		CommittedId, MultilinearOracleSet, MultilinearPolyOracle, OracleId, ProjectionVariant,
+		ShiftVariant,
+	},
+	polynomial::{MultilinearExtension, MultilinearPoly, MultilinearQuery},
+};
+use binius_field::{PackedField, TowerField};
+use rayon::prelude::*;
+
+/// Compute the witnesses of all derived oracles in an oracle set from the witnesses of their inputs.
+///
+/// Oracles are visited in order of ID, so the inputs of a derived oracle are filled before it.
+/// Committed oracles must already have witnesses; if any are missing, this returns
+/// [`Error::MissingCommittedWitnesses`] listing all of them. Witnesses that are already in the
+/// index are kept, after checking their number of variables and tower level against the oracle.
+/// Transparent oracles are evaluated on the hypercube. The rows of each computed witness are
+/// evaluated in parallel.
+pub fn fill_derived_witnesses<F, PW>(
+	oracles: &MultilinearOracleSet<F>,
+	witness_index: &mut MultilinearWitnessIndex<PW>,
+) -> Result<(), Error>
+where
+	F: TowerField,
+	PW: PackedField<Scalar: TowerField + From<F>>,
+{
+	let missing_ids = oracles
+		.committed_batches()
+		.into_iter()
+		.flat_map(|batch| {
+			(0..batch.n_polys).map(move |index| {
+				oracles.committed_oracle_id(CommittedId {
+					batch_id: batch.id,
+					index,
+				})
+			})
+		})
+		.filter(|&id| witness_index.get(id).is_none())
+		.collect::<Vec<_>>();
+	if !missing_ids.is_empty() {
+		return Err(Error::MissingCommittedWitnesses { ids: missing_ids });
+	}
+
+	for id in 0..oracles.size() {
+		match witness_index.get(id) {
+			Some(witness) => check_witness(oracles, id, witness)?,
+			None => {
+				let witness = derive_witness(oracles, witness_index, id)?;
+				witness_index.set(id, witness);
+			}
+		}
+	}
+
+	Ok(())
+}
+
+fn check_witness<F, PW>(
+	oracles: &MultilinearOracleSet<F>,
+	id: OracleId,
+	witness: &MultilinearWitness<PW>,
+) -> Result<(), Error>
+where
+	F: TowerField,
+	PW: PackedField<Scalar: TowerField>,
+{
+	let n_vars = oracles.n_vars(id);
+	if witness.n_vars() != n_vars {
+		return Err(Error::IncorrectNumberOfVariables {
+			id,
+			expected: n_vars,
+			n_vars: witness.n_vars(),
+		});
+	}
+
+	// The witness values lie in the subfield over which PW::Scalar has the extension degree.
+	let tower_level =
+		PW::Scalar::TOWER_LEVEL.saturating_sub(witness.extension_degree().ilog2() as usize);
+	if tower_level > oracles.tower_level(id) {
+		return Err(Error::TowerLevelTooHigh {
+			id,
+			expected: oracles.tower_level(id),
+			tower_level,
+		});
+	}
+
+	Ok(())
+}
+
+fn derive_witness<F, PW>(
+	oracles: &MultilinearOracleSet<F>,
+	witness_index: &MultilinearWitnessIndex<PW>,
+	id: OracleId,
+) -> Result<MultilinearWitness<'static, PW>, Error>
+where
+	F: TowerField,
+	PW: PackedField<Scalar: TowerField + From<F>>,
+{
+	let n_vars = oracles.n_vars(id);
+	let get = |inner_id: OracleId| {
+		witness_index
+			.get(inner_id)
+			.ok_or(Error::MissingWitness { id: inner_id })
+	};
+
+	use MultilinearPolyOracle::*;
+	let witness = match oracles.oracle(id) {
+		Committed { .. } => return Err(Error::MissingWitness { id }),
+		Transparent(_, transparent) => {
+			let poly = transparent.poly();
+			witness_from_fn(id, n_vars, |index| Ok(poly.evaluate_on_hypercube(index)?.into()))?
+		}
+		Repeating { inner, .. } => {
+			let inner_witness = get(inner.id())?;
+			let mask = (1 << inner.n_vars()) - 1;
+			witness_from_fn(id, n_vars, |index| {
+				Ok(inner_witness.evaluate_on_hypercube(index & mask)?)
+			})?
+		}
+		Interleaved(_, inner_0, inner_1) => {
+			let inner_witnesses = [get(inner_0.id())?, get(inner_1.id())?];
+			witness_from_fn(id, n_vars, |index| {
+				Ok(inner_witnesses[index & 1].evaluate_on_hypercube(index >> 1)?)
+			})?
+		}
+		Merged(_, inner_0, inner_1) => {
+			let inner_witnesses = [get(inner_0.id())?, get(inner_1.id())?];
+			let inner_n_vars = inner_0.n_vars();
+			witness_from_fn(id, n_vars, |index| {
+				let inner_index = index & ((1 << inner_n_vars) - 1);
+				Ok(inner_witnesses[index >> inner_n_vars].evaluate_on_hypercube(inner_index)?)
+			})?
+		}
+		Shifted(_, shifted) => {
+			let inner_witness = get(shifted.inner().id())?;
+			let block_len = 1 << shifted.block_size();
+			let offset = shifted.shift_offset();
+			let variant = shifted.shift_variant();
+			witness_from_fn(id, n_vars, |index| {
+				let (block_start, i) = (index & !(block_len - 1), index & (block_len - 1));
+				let source = match variant {
+					ShiftVariant::CircularLeft => Some((i + block_len - offset) % block_len),
+					ShiftVariant::LogicalLeft => i.checked_sub(offset),
+					ShiftVariant::LogicalRight => Some(i + offset).filter(|&j| j < block_len),
+				};
+				match source {
+					Some(j) => Ok(inner_witness.evaluate_on_hypercube(block_start | j)?),
+					None => Ok(PW::Scalar::ZERO),
+				}
+			})?
+		}
+		Packed(_, packed) => {
+			let inner_witness = get(packed.inner().id())?;
+			let log_degree = packed.log_degree();
+			let inner_tower_level = oracles.tower_level(packed.inner().id());
+			let basis = (0..1 << log_degree)
+				.map(|i| <PW::Scalar as TowerField>::basis(inner_tower_level, i))
+				.collect::<Result<Vec<_>, _>>()?;
+			witness_from_fn(id, n_vars, |index| {
+				basis
+					.iter()
+					.enumerate()
+					.try_fold(PW::Scalar::ZERO, |acc, (i, &basis_elem)| {
+						let inner_index = (index << log_degree) | i;
+						Ok(acc
+							+ inner_witness
+								.evaluate_on_hypercube_and_scale(inner_index, basis_elem)?)
+					})
+			})?
+		}
+		Projected(_, projected) => {
+			let inner_witness = get(projected.inner().id())?;
+			let values = projected
+				.values()
+				.iter()
+				.map(|&value| PW::Scalar::from(value))
+				.collect::<Vec<_>>();
+			let query = MultilinearQuery::<PW>::with_full_query(&values)?;
+			let projected_witness = match projected.projection_variant() {
+				ProjectionVariant::FirstVars => inner_witness.evaluate_partial_low(&query)?,
+				ProjectionVariant::LastVars => inner_witness.evaluate_partial_high(&query)?,
+			};
+			projected_witness.upcast_arc_dyn()
+		}
+		LinearCombination(_, linear_combination) => {
+			let offset = PW::Scalar::from(linear_combination.offset());
+			let inner = linear_combination
+				.polys()
+				.zip(linear_combination.coefficients())
+				.map(|(poly, coeff)| Ok((get(poly.id())?, PW::Scalar::from(coeff))))
+				.collect::<Result<Vec<_>, Error>>()?;
+			witness_from_fn(id, n_vars, |index| {
+				inner
+					.iter()
+					.try_fold(offset, |acc, (inner_witness, coeff)| {
+						Ok(acc + inner_witness.evaluate_on_hypercube_and_scale(index, *coeff)?)
+					})
+			})?
+		}
+	};
+
+	Ok(witness)
+}
+
+/// Build a witness from a function computing its value at every vertex of the hypercube.
+fn witness_from_fn<PW: PackedField>(
+	id: OracleId,
+	n_vars: usize,
+	f: impl Fn(usize) -> Result<PW::Scalar, Error> + Sync,
+) -> Result<MultilinearWitness<'static, PW>, Error> {
+	if n_vars < PW::LOG_WIDTH {
+		return Err(Error::NotEnoughVariables {
+			id,
+			n_vars,
+			log_width: PW::LOG_WIDTH,
+		});
+	}
+
+	let values = (0..1 << (n_vars - PW::LOG_WIDTH))
+		.into_par_iter()
+		.map(|i| {
+			let scalars = (0..PW::WIDTH)
+				.map(|j| f((i << PW::LOG_WIDTH) | j))
+				.collect::<Result<Vec<_>, _>>()?;
+			Ok(PW::from_fn(|j| scalars[j]))
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+
+	Ok(MultilinearExtension::from_values(values)?.specialize_arc_dyn())
+}
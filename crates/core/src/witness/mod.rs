@@ -1,5 +1,15 @@
 // Copyright 2024 Ulvetanna Inc.
 
+mod checker;
+mod error;
+mod filler;
+#[cfg(test)]
+mod tests;
+
+pub use checker::*;
+pub use error::*;
+pub use filler::*;
+
 use crate::{oracle::OracleId, polynomial::MultilinearPoly};
 use binius_field::PackedField;
 use std::sync::Arc;
@@ -12,7 +22,7 @@ pub type MultilinearWitness<'a, P> = Arc<dyn MultilinearPoly<P> + Send + Sync +
 /// unique, sequential  oracle IDs. This index stores the corresponding witnesses, as
 /// [`MultilinearPoly`] trait objects. Not every oracle is required to have a stored witness -- in
 /// some cases, only a derived multilinear witness is required.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct MultilinearWitnessIndex<'a, P: PackedField> {
 	multilinears: Vec<Option<MultilinearWitness<'a, P>>>,
 }
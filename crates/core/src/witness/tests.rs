@@ -0,0 +1,224 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	fill_derived_witnesses, find_constraint_violations, ConstraintViolation, Error,
+	MultilinearWitnessIndex,
+};
+use crate::{
+	oracle::{CompositePolyOracle, MultilinearOracleSet, ProjectionVariant, ShiftVariant},
+	polynomial::{
+		composition::BivariateProduct, transparent::step_down::StepDown, IdentityCompositionPoly,
+		MultilinearExtension, MultilinearQuery,
+	},
+};
+use assert_matches::assert_matches;
+use binius_field::{BinaryField128b, BinaryField1b, BinaryField8b, Field, TowerField};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::iter::repeat_with;
+
+type F = BinaryField128b;
+
+#[test]
+fn test_fill_derived_witnesses() {
+	let n_vars = 6;
+	let mut rng = StdRng::seed_from_u64(0);
+	let bits = [(); 2].map(|_| {
+		repeat_with(|| BinaryField1b::from(rng.gen::<bool>() as u8))
+			.take(1 << n_vars)
+			.collect::<Vec<_>>()
+	});
+	let value = |column: usize, index: usize| F::from(bits[column][index]);
+
+	let mut oracles = MultilinearOracleSet::<F>::new();
+	let mut batch_scope = oracles.build_committed_batch(n_vars, BinaryField1b::TOWER_LEVEL);
+	let [a, b] = batch_scope.add_multiple();
+	batch_scope.build();
+
+	let circular = oracles
+		.add_shifted(a, 3, 3, ShiftVariant::CircularLeft)
+		.unwrap();
+	let left = oracles
+		.add_shifted(a, 3, 3, ShiftVariant::LogicalLeft)
+		.unwrap();
+	let right = oracles
+		.add_shifted(a, 3, 3, ShiftVariant::LogicalRight)
+		.unwrap();
+	let packed = oracles.add_packed(a, 3).unwrap();
+	let interleaved = oracles.add_interleaved(a, b).unwrap();
+	let merged = oracles.add_merged(a, b).unwrap();
+	let repeating = oracles.add_repeating(a, 2).unwrap();
+	let c = F::new(0x1234);
+	let lin_com = oracles
+		.add_linear_combination_with_offset(n_vars, c, [(a, F::ONE), (b, c)])
+		.unwrap();
+	let step_down = oracles
+		.add_transparent(StepDown::new(n_vars, 5).unwrap())
+		.unwrap();
+	let r = repeat_with(|| <F as Field>::random(&mut rng))
+		.take(2)
+		.collect::<Vec<_>>();
+	let projected = oracles
+		.add_projected(a, r.clone(), ProjectionVariant::LastVars)
+		.unwrap();
+
+	let mut witness_index = MultilinearWitnessIndex::<F>::new();
+	for (id, values) in [(a, &bits[0]), (b, &bits[1])] {
+		witness_index.set(
+			id,
+			MultilinearExtension::from_values(values.clone())
+				.unwrap()
+				.specialize_arc_dyn(),
+		);
+	}
+	fill_derived_witnesses(&oracles, &mut witness_index).unwrap();
+
+	let eval = |id, index| {
+		witness_index
+			.get(id)
+			.unwrap()
+			.evaluate_on_hypercube(index)
+			.unwrap()
+	};
+	for index in 0..1 << n_vars {
+		let (block_start, i) = (index & !7, index & 7);
+		assert_eq!(eval(circular, index), value(0, block_start | ((i + 5) % 8)));
+		let expected = if i >= 3 { value(0, index - 3) } else { F::ZERO };
+		assert_eq!(eval(left, index), expected);
+		let expected = if i < 5 { value(0, index + 3) } else { F::ZERO };
+		assert_eq!(eval(right, index), expected);
+		assert_eq!(eval(lin_com, index), c + value(0, index) + c * value(1, index));
+		let expected = if index < 5 { F::ONE } else { F::ZERO };
+		assert_eq!(eval(step_down, index), expected);
+	}
+	for index in 0..1 << (n_vars - 3) {
+		let byte = (0..8).fold(0u8, |acc, i| acc | (u8::from(bits[0][(index << 3) | i]) << i));
+		assert_eq!(eval(packed, index), F::from(BinaryField8b::new(byte)));
+	}
+	for index in 0..1 << (n_vars + 1) {
+		assert_eq!(eval(interleaved, index), value(index & 1, index >> 1));
+		assert_eq!(eval(merged, index), value(index >> n_vars, index & ((1 << n_vars) - 1)));
+	}
+	for index in 0..1 << (n_vars + 2) {
+		assert_eq!(eval(repeating, index), value(0, index & ((1 << n_vars) - 1)));
+	}
+
+	let point = repeat_with(|| <F as Field>::random(&mut rng))
+		.take(n_vars - 2)
+		.collect::<Vec<_>>();
+	let full_point = [point.as_slice(), &r].concat();
+	assert_eq!(
+		witness_index
+			.get(projected)
+			.unwrap()
+			.evaluate(&MultilinearQuery::with_full_query(&point).unwrap())
+			.unwrap(),
+		witness_index
+			.get(a)
+			.unwrap()
+			.evaluate(&MultilinearQuery::with_full_query(&full_point).unwrap())
+			.unwrap()
+	);
+}
+
+#[test]
+fn test_fill_derived_witnesses_errors() {
+	let n_vars = 4;
+	let mut oracles = MultilinearOracleSet::<F>::new();
+	let mut batch_scope = oracles.build_committed_batch(n_vars, BinaryField1b::TOWER_LEVEL);
+	let [a, b] = batch_scope.add_multiple();
+	batch_scope.build();
+	oracles
+		.add_shifted(a, 1, 2, ShiftVariant::CircularLeft)
+		.unwrap();
+
+	let mut witness_index = MultilinearWitnessIndex::<F>::new();
+	assert_matches!(
+		fill_derived_witnesses(&oracles, &mut witness_index),
+		Err(Error::MissingCommittedWitnesses { ids }) if ids == [a, b]
+	);
+
+	// Full field values do not fit in a 1-bit column
+	let values = vec![F::new(3); 1 << n_vars];
+	witness_index.set(
+		a,
+		MultilinearExtension::from_values(values)
+			.unwrap()
+			.specialize_arc_dyn(),
+	);
+	let values = vec![BinaryField1b::ONE; 1 << n_vars];
+	witness_index.set(
+		b,
+		MultilinearExtension::from_values(values)
+			.unwrap()
+			.specialize_arc_dyn(),
+	);
+	assert_matches!(
+		fill_derived_witnesses(&oracles, &mut witness_index),
+		Err(Error::TowerLevelTooHigh { id, .. }) if id == a
+	);
+
+	let values = vec![BinaryField1b::ONE; 1 << (n_vars - 1)];
+	witness_index.set(
+		a,
+		MultilinearExtension::from_values(values)
+			.unwrap()
+			.specialize_arc_dyn(),
+	);
+	assert_matches!(
+		fill_derived_witnesses(&oracles, &mut witness_index),
+		Err(Error::IncorrectNumberOfVariables { id, .. }) if id == a
+	);
+}
+
+#[test]
+fn test_find_constraint_violations() {
+	let n_vars = 4;
+	let mut oracles = MultilinearOracleSet::<F>::new();
+	let mut batch_scope = oracles.build_committed_batch(n_vars, BinaryField1b::TOWER_LEVEL);
+	let [a, b] = batch_scope.add_multiple();
+	batch_scope.build();
+	let oracle = CompositePolyOracle::new(
+		n_vars,
+		vec![oracles.oracle(a), oracles.oracle(b)],
+		BivariateProduct,
+	)
+	.unwrap();
+
+	let mut witness_index = MultilinearWitnessIndex::<F>::new();
+	let values = vec![BinaryField1b::ONE; 1 << n_vars];
+	witness_index.set(
+		a,
+		MultilinearExtension::from_values(values)
+			.unwrap()
+			.specialize_arc_dyn(),
+	);
+	let values = (0..1 << n_vars)
+		.map(|i| BinaryField1b::from([5, 9, 12].contains(&i) as u8))
+		.collect::<Vec<_>>();
+	witness_index.set(
+		b,
+		MultilinearExtension::from_values(values)
+			.unwrap()
+			.specialize_arc_dyn(),
+	);
+
+	let violations =
+		find_constraint_violations(&oracle, [("product", BivariateProduct)], &witness_index, 2)
+			.unwrap();
+	let expected = [5, 9].map(|row| ConstraintViolation {
+		name: "product".to_string(),
+		row,
+		values: vec![(a, F::ONE), (b, F::ONE)],
+	});
+	assert_eq!(violations, expected);
+
+	assert_matches!(
+		find_constraint_violations(
+			&oracle,
+			[("identity", IdentityCompositionPoly)],
+			&witness_index,
+			2
+		),
+		Err(Error::ConstraintNumberOfColumnsMismatch { n_vars: 1, .. })
+	);
+}
@@ -1,3 +1,4 @@
+use binius_utils::serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes};
 use bytemuck::{Pod, Zeroable};
 use rand::RngCore;
 use seq_macro::seq;
@@ -43,6 +44,18 @@ impl From<M128> for u128 {
 		value.0
 	}
 }
+
+impl SerializeBytes for M128 {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		u128::from(*self).serialize(write_buf);
+	}
+}
+
+impl DeserializeBytes for M128 {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		Ok(Self::from(u128::deserialize(read_buf)?))
+	}
+}
 impl From<M128> for uint8x16_t {
 	fn from(value: M128) -> Self {
 		unsafe { vreinterpretq_u8_p128(value.0) }
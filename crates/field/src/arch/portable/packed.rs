@@ -6,6 +6,7 @@ use crate::{
 	underlier::{NumCast, UnderlierType, WithUnderlier},
 	BinaryField, Error, PackedField,
 };
+use binius_utils::serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes};
 use bytemuck::{Pod, Zeroable};
 use rand::RngCore;
 use std::{
@@ -203,6 +204,22 @@ unsafe impl<U: UnderlierType + Zeroable, Scalar: BinaryField> Zeroable
 
 unsafe impl<U: UnderlierType + Pod, Scalar: BinaryField> Pod for PackedPrimitiveType<U, Scalar> {}
 
+impl<U: UnderlierType + SerializeBytes, Scalar: BinaryField> SerializeBytes
+	for PackedPrimitiveType<U, Scalar>
+{
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.0.serialize(write_buf);
+	}
+}
+
+impl<U: UnderlierType + DeserializeBytes, Scalar: BinaryField> DeserializeBytes
+	for PackedPrimitiveType<U, Scalar>
+{
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		Ok(Self::from_underlier(U::deserialize(read_buf)?))
+	}
+}
+
 impl<U: UnderlierType, Scalar: BinaryField> PackedField for PackedPrimitiveType<U, Scalar>
 where
 	Self: Broadcast<Scalar> + Square + InvertOrZero + Mul<Output = Self>,
@@ -8,6 +8,7 @@ use crate::{
 	packed::PackedBinaryField,
 	Error, PackedField,
 };
+use binius_utils::serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes};
 use bytemuck::{Pod, Zeroable};
 use std::{
 	array,
@@ -59,6 +60,18 @@ unsafe impl<PT: Zeroable, const N: usize> Zeroable for ScaledPackedField<PT, N>
 
 unsafe impl<PT: Pod, const N: usize> Pod for ScaledPackedField<PT, N> {}
 
+impl<PT: SerializeBytes, const N: usize> SerializeBytes for ScaledPackedField<PT, N> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.0.serialize(write_buf);
+	}
+}
+
+impl<PT: DeserializeBytes, const N: usize> DeserializeBytes for ScaledPackedField<PT, N> {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		Ok(Self(<[PT; N]>::deserialize(read_buf)?))
+	}
+}
+
 impl<PT: ConstantTimeEq, const N: usize> ConstantTimeEq for ScaledPackedField<PT, N> {
 	fn ct_eq(&self, other: &Self) -> subtle::Choice {
 		self.0.ct_eq(&other.0)
@@ -9,6 +9,7 @@ use crate::{
 	underlier::{NumCast, Random, SmallU, UnderlierType, WithUnderlier},
 	BinaryField,
 };
+use binius_utils::serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes};
 use bytemuck::{must_cast, Pod, Zeroable};
 use rand::{Rng, RngCore};
 use seq_macro::seq;
@@ -86,6 +87,18 @@ impl From<M128> for u128 {
 	}
 }
 
+impl SerializeBytes for M128 {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		u128::from(*self).serialize(write_buf);
+	}
+}
+
+impl DeserializeBytes for M128 {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		Ok(Self::from(u128::deserialize(read_buf)?))
+	}
+}
+
 impl From<M128> for __m128i {
 	#[inline(always)]
 	fn from(value: M128) -> Self {
@@ -9,6 +9,7 @@ use crate::{
 	underlier::{NumCast, Random, SmallU, UnderlierType, WithUnderlier},
 	BinaryField,
 };
+use binius_utils::serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes};
 use bytemuck::{must_cast, Pod, Zeroable};
 use rand::{Rng, RngCore};
 use std::{
@@ -92,6 +93,18 @@ impl From<M256> for [u128; 2] {
 	}
 }
 
+impl SerializeBytes for M256 {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		<[u128; 2]>::from(*self).serialize(write_buf);
+	}
+}
+
+impl DeserializeBytes for M256 {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		Ok(Self::from(<[u128; 2]>::deserialize(read_buf)?))
+	}
+}
+
 impl From<M256> for __m256i {
 	#[inline(always)]
 	fn from(value: M256) -> Self {
@@ -9,6 +9,7 @@ use crate::{
 	underlier::{NumCast, Random, SmallU, UnderlierType, WithUnderlier},
 	BinaryField,
 };
+use binius_utils::serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes};
 use bytemuck::{must_cast, Pod, Zeroable};
 use rand::{Rng, RngCore};
 use std::{
@@ -96,6 +97,18 @@ impl From<M512> for [u128; 4] {
 	}
 }
 
+impl SerializeBytes for M512 {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		<[u128; 4]>::from(*self).serialize(write_buf);
+	}
+}
+
+impl DeserializeBytes for M512 {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		Ok(Self::from(<[u128; 4]>::deserialize(read_buf)?))
+	}
+}
+
 impl From<M512> for __m512i {
 	#[inline(always)]
 	fn from(value: M512) -> Self {
@@ -274,6 +274,22 @@ macro_rules! binary_field {
 			}
 		}
 
+		impl binius_utils::serialization::SerializeBytes for $name {
+			fn serialize(&self, write_buf: &mut Vec<u8>) {
+				binius_utils::serialization::SerializeBytes::serialize(&self.0, write_buf);
+			}
+		}
+
+		impl binius_utils::serialization::DeserializeBytes for $name {
+			fn deserialize(
+				read_buf: &mut &[u8],
+			) -> Result<Self, binius_utils::serialization::Error> {
+				Ok(Self(<$typ as binius_utils::serialization::DeserializeBytes>::deserialize(
+					read_buf,
+				)?))
+			}
+		}
+
 		impl From<$name> for $typ {
 			fn from(val: $name) -> Self {
 				return val.0
@@ -1065,4 +1081,37 @@ pub(crate) mod tests {
 			assert_eq!(Choice::from(BinaryField1b::from(i)).unwrap_u8(), i);
 		}
 	}
+
+	#[test]
+	fn test_serialization_round_trip() {
+		use binius_utils::serialization::{DeserializeBytes, SerializeBytes};
+
+		let mut write_buf = Vec::new();
+		BF4::new(U4::new(0xa)).serialize(&mut write_buf);
+		BF16::new(0x1234).serialize(&mut write_buf);
+		BinaryField128b::new(u128::MAX - 1).serialize(&mut write_buf);
+		assert_eq!(write_buf.len(), 1 + 2 + 16);
+
+		let mut read_buf = write_buf.as_slice();
+		assert_eq!(BF4::deserialize(&mut read_buf).unwrap(), BF4::new(U4::new(0xa)));
+		assert_eq!(BF16::deserialize(&mut read_buf).unwrap(), BF16::new(0x1234));
+		assert_eq!(
+			BinaryField128b::deserialize(&mut read_buf).unwrap(),
+			BinaryField128b::new(u128::MAX - 1)
+		);
+		assert!(read_buf.is_empty());
+	}
+
+	#[test]
+	fn test_deserialize_rejects_unused_bits() {
+		use binius_utils::serialization::{DeserializeBytes, Error};
+
+		assert!(BF1::deserialize(&mut [1u8].as_slice()).is_ok());
+		assert!(matches!(BF1::deserialize(&mut [2u8].as_slice()), Err(Error::NonCanonical { .. })));
+		assert!(matches!(BF2::deserialize(&mut [4u8].as_slice()), Err(Error::NonCanonical { .. })));
+		assert!(matches!(
+			BF64::deserialize(&mut [0u8; 7].as_slice()),
+			Err(Error::NotEnoughBytes { .. })
+		));
+	}
 }
@@ -780,6 +780,7 @@ mod tests {
 		arithmetic_traits::MulAlpha,
 		BinaryField8b, Field, PackedField,
 	};
+	use binius_utils::serialization::{DeserializeBytes, SerializeBytes};
 	use proptest::prelude::*;
 	use rand::{rngs::StdRng, thread_rng, SeedableRng};
 	use std::{iter::repeat_with, ops::Mul};
@@ -1158,4 +1159,35 @@ mod tests {
 		implements_transformation_factory::<PackedBinaryField4x128b, PackedBinaryField4x128b>();
 		implements_transformation_factory::<PackedAESBinaryField4x128b, PackedBinaryField4x128b>();
 	}
+
+	fn check_serialization_is_scalar_order<P>()
+	where
+		P: PackedField<Scalar = BinaryField8b> + SerializeBytes + DeserializeBytes,
+	{
+		let mut rng = StdRng::seed_from_u64(0);
+		let packed = P::random(&mut rng);
+
+		let mut write_buf = Vec::new();
+		packed.serialize(&mut write_buf);
+		let expected = packed.iter().map(|scalar| scalar.val()).collect::<Vec<_>>();
+		assert_eq!(write_buf, expected);
+
+		let mut read_buf = write_buf.as_slice();
+		assert_eq!(P::deserialize(&mut read_buf).unwrap(), packed);
+		assert!(read_buf.is_empty());
+	}
+
+	#[test]
+	fn test_serialization_is_scalar_order() {
+		check_serialization_is_scalar_order::<PackedBinaryField2x8b>();
+		check_serialization_is_scalar_order::<PackedBinaryField16x8b>();
+		check_serialization_is_scalar_order::<PackedBinaryField32x8b>();
+		check_serialization_is_scalar_order::<PackedBinaryField64x8b>();
+	}
+
+	#[test]
+	fn test_serialization_rejects_unused_bits() {
+		assert!(PackedBinaryField2x1b::deserialize(&mut [0b11u8].as_slice()).is_ok());
+		assert!(PackedBinaryField2x1b::deserialize(&mut [0b111u8].as_slice()).is_err());
+	}
 }
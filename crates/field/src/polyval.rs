@@ -19,6 +19,7 @@ use crate::{
 	underlier::UnderlierType,
 	Field,
 };
+use binius_utils::serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes};
 use bytemuck::{Pod, Zeroable};
 use rand::{Rng, RngCore};
 use std::{
@@ -214,6 +215,18 @@ impl From<BinaryField128bPolyval> for u128 {
 	}
 }
 
+impl SerializeBytes for BinaryField128bPolyval {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.0.serialize(write_buf);
+	}
+}
+
+impl DeserializeBytes for BinaryField128bPolyval {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		Ok(Self(u128::deserialize(read_buf)?))
+	}
+}
+
 impl Display for BinaryField128bPolyval {
 	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
 		write!(f, "0x{repr:0>32x}", repr = self.from_montgomery().0)
@@ -1,6 +1,9 @@
 // Copyright 2024 Ulvetanna Inc.
 
-use binius_utils::checked_arithmetics::{checked_div, checked_log_2};
+use binius_utils::{
+	checked_arithmetics::{checked_div, checked_log_2},
+	serialization::{DeserializeBytes, Error as SerializationError, SerializeBytes},
+};
 use bytemuck::Zeroable;
 use derive_more::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
 use rand::{
@@ -382,6 +385,22 @@ impl From<SmallU<2>> for SmallU<4> {
 	}
 }
 
+impl<const N: usize> SerializeBytes for SmallU<N> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.val().serialize(write_buf);
+	}
+}
+
+impl<const N: usize> DeserializeBytes for SmallU<N> {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		let val = u8::deserialize(read_buf)?;
+		if val > Self::ONES.0 {
+			return Err(SerializationError::NonCanonical { name: "SmallU" });
+		}
+		Ok(Self(val))
+	}
+}
+
 pub type U1 = SmallU<1>;
 pub type U2 = SmallU<2>;
 pub type U4 = SmallU<4>;
@@ -73,16 +73,17 @@ where
 	}
 }
 
+/// Compression of `N` Groestl digests into one by hashing their concatenation.
 #[derive(Debug, Default, Clone)]
 pub struct GroestlDigestCompression;
 
-impl PseudoCompressionFunction<GroestlDigest, 2> for GroestlDigestCompression {
-	fn compress(&self, input: [GroestlDigest; 2]) -> GroestlDigest {
+impl<const N: usize> PseudoCompressionFunction<GroestlDigest, N> for GroestlDigestCompression {
+	fn compress(&self, input: [GroestlDigest; N]) -> GroestlDigest {
 		HasherDigest::<GroestlDigest, GroestlHasher<GroestlDigest>>::hash(&input[..])
 	}
 }
 
-impl CompressionFunction<GroestlDigest, 2> for GroestlDigestCompression {}
+impl<const N: usize> CompressionFunction<GroestlDigest, N> for GroestlDigestCompression {}
 
 #[cfg(test)]
 mod tests {
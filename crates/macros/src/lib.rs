@@ -16,6 +16,39 @@ pub fn composition_poly(input: TokenStream) -> TokenStream {
 	let n_vars = vars.len();
 	let i = 0..n_vars;
 	let degree = poly_degree(&poly);
+	let linear_factors = linear_factors(&poly, &vars).map(|factors| {
+		let factors = factors.into_iter().map(|terms| {
+			let constant = terms
+				.iter()
+				.filter(|(var, _)| var.is_none())
+				.map(|&(_, positive)| {
+					if positive {
+						quote!(+ F::ONE)
+					} else {
+						quote!(-F::ONE)
+					}
+				});
+			let terms = terms.iter().filter_map(|&(var, positive)| {
+				let var = var?;
+				Some(if positive {
+					quote!((#var, F::ONE))
+				} else {
+					quote!((#var, -F::ONE))
+				})
+			});
+			quote! {
+				binius_core::polynomial::composition::LinearFactor {
+					constant: F::ZERO #(#constant)*,
+					terms: vec![#(#terms),*],
+				}
+			}
+		});
+		quote! {
+			fn linear_factors(&self) -> Option<Vec<binius_core::polynomial::composition::LinearFactor<F>>> {
+				Some(vec![#(#factors),*])
+			}
+		}
+	});
 	rewrite_literals(&mut poly);
 
 	let result = quote! {
@@ -42,6 +75,8 @@ pub fn composition_poly(input: TokenStream) -> TokenStream {
 			fn binary_tower_level(&self) -> usize {
 				0
 			}
+
+			#linear_factors
 		}
 	};
 
@@ -81,6 +116,54 @@ fn poly_degree(expr: &syn::Expr) -> usize {
 	}
 }
 
+/// An affine form as a sum of signed unit terms, each a variable index or `None` for the constant 1.
+type AffineTerms = Vec<(Option<usize>, bool)>;
+
+/// Decomposes a product of affine forms into its factors, or returns `None` if the polynomial is
+/// not syntactically one.
+///
+/// Make sure to run this before rewrite_literals, like poly_degree.
+fn linear_factors(expr: &syn::Expr, vars: &[syn::Ident]) -> Option<Vec<AffineTerms>> {
+	match expr {
+		syn::Expr::Paren(paren) => linear_factors(&paren.expr, vars),
+		syn::Expr::Binary(binary) if matches!(binary.op, syn::BinOp::Mul(_)) => {
+			let mut factors = linear_factors(&binary.left, vars)?;
+			factors.extend(linear_factors(&binary.right, vars)?);
+			Some(factors)
+		}
+		_ => affine_terms(expr, vars).map(|terms| vec![terms]),
+	}
+}
+
+fn affine_terms(expr: &syn::Expr, vars: &[syn::Ident]) -> Option<AffineTerms> {
+	match expr {
+		syn::Expr::Lit(exprlit) => match &exprlit.lit {
+			syn::Lit::Int(int) if int.base10_digits() == "0" => Some(Vec::new()),
+			syn::Lit::Int(int) if int.base10_digits() == "1" => Some(vec![(None, true)]),
+			_ => None,
+		},
+		syn::Expr::Path(path) => {
+			let index = vars.iter().position(|var| path.path.is_ident(var))?;
+			Some(vec![(Some(index), true)])
+		}
+		syn::Expr::Paren(paren) => affine_terms(&paren.expr, vars),
+		syn::Expr::Binary(binary) => {
+			let left = affine_terms(&binary.left, vars)?;
+			let right = affine_terms(&binary.right, vars)?;
+			match binary.op {
+				syn::BinOp::Add(_) => Some([left, right].concat()),
+				syn::BinOp::Sub(_) => Some(
+					left.into_iter()
+						.chain(right.into_iter().map(|(var, positive)| (var, !positive)))
+						.collect(),
+				),
+				_ => None,
+			}
+		}
+		_ => None,
+	}
+}
+
 /// Rewrites 0 => P::zero(), 1 => P::one()
 fn rewrite_literals(expr: &mut syn::Expr) {
 	match expr {
@@ -4,4 +4,5 @@ pub mod checked_arithmetics;
 pub mod examples;
 pub mod iter;
 pub mod rayon;
+pub mod serialization;
 pub mod tracing;
@@ -0,0 +1,288 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Canonical byte serialization.
+//!
+//! Values are encoded as a flat little-endian byte string without any self-describing metadata.
+//! Lengths of variable-sized collections and `usize` values are written as `u64`. Decoding is
+//! strict: every value has exactly one accepted encoding, and the decoders reject encodings that
+//! are truncated, use unused bits, or do not satisfy the invariants of the decoded type.
+//!
+//! Standalone encodings, such as proofs written to disk or sent to another process, should be
+//! produced with [`to_versioned_bytes`] and read back with [`from_versioned_bytes`], which prefix
+//! the encoding with [`SERIALIZATION_VERSION`] and reject trailing data.
+
+use std::{array, mem};
+
+/// The version of the byte encoding produced by [`to_versioned_bytes`].
+pub const SERIALIZATION_VERSION: u16 = 1;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("not enough bytes in the buffer: expected {expected}, got {actual}")]
+	NotEnoughBytes { expected: usize, actual: usize },
+	#[error("unsupported serialization version {version}, expected {expected}")]
+	UnsupportedVersion { version: u16, expected: u16 },
+	#[error("{n_bytes} unexpected bytes trail the encoded value")]
+	TrailingBytes { n_bytes: usize },
+	#[error("non-canonical encoding of {name}")]
+	NonCanonical { name: &'static str },
+	#[error("the decoded value is not a valid {name}")]
+	InvalidConstruction { name: &'static str },
+	#[error("unknown variant {index} of {name}")]
+	UnknownEnumVariant { name: &'static str, index: u8 },
+	#[error("nesting depth of {name} exceeds the limit of {max_depth}")]
+	RecursionLimitExceeded {
+		name: &'static str,
+		max_depth: usize,
+	},
+	#[error("encoded length {length} does not fit into usize")]
+	LengthOverflow { length: u64 },
+}
+
+/// A type with a canonical byte encoding.
+pub trait SerializeBytes {
+	/// Append the canonical encoding of `self` to the buffer.
+	fn serialize(&self, write_buf: &mut Vec<u8>);
+}
+
+/// A type that can be decoded from its canonical byte encoding.
+pub trait DeserializeBytes: Sized {
+	/// Decode a value from the front of the buffer, advancing the buffer past the consumed bytes.
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, Error>;
+}
+
+/// Serialize a value into a standalone byte string prefixed with the encoding version.
+pub fn to_versioned_bytes<T: SerializeBytes + ?Sized>(value: &T) -> Vec<u8> {
+	let mut write_buf = Vec::new();
+	SERIALIZATION_VERSION.serialize(&mut write_buf);
+	value.serialize(&mut write_buf);
+	write_buf
+}
+
+/// Deserialize a value from a byte string produced by [`to_versioned_bytes`].
+///
+/// Throws if the version does not match [`SERIALIZATION_VERSION`] or if any bytes remain after the
+/// value is decoded.
+pub fn from_versioned_bytes<T: DeserializeBytes>(bytes: &[u8]) -> Result<T, Error> {
+	let mut read_buf = bytes;
+	let version = u16::deserialize(&mut read_buf)?;
+	if version != SERIALIZATION_VERSION {
+		return Err(Error::UnsupportedVersion {
+			version,
+			expected: SERIALIZATION_VERSION,
+		});
+	}
+	let value = T::deserialize(&mut read_buf)?;
+	if !read_buf.is_empty() {
+		return Err(Error::TrailingBytes {
+			n_bytes: read_buf.len(),
+		});
+	}
+	Ok(value)
+}
+
+/// Split off the first `n_bytes` bytes of the buffer.
+pub fn take_bytes<'a>(read_buf: &mut &'a [u8], n_bytes: usize) -> Result<&'a [u8], Error> {
+	if read_buf.len() < n_bytes {
+		return Err(Error::NotEnoughBytes {
+			expected: n_bytes,
+			actual: read_buf.len(),
+		});
+	}
+	let (head, tail) = read_buf.split_at(n_bytes);
+	*read_buf = tail;
+	Ok(head)
+}
+
+macro_rules! impl_serialize_bytes_for_uint {
+	($($typ:ty),*) => {
+		$(
+			impl SerializeBytes for $typ {
+				fn serialize(&self, write_buf: &mut Vec<u8>) {
+					write_buf.extend_from_slice(&self.to_le_bytes());
+				}
+			}
+
+			impl DeserializeBytes for $typ {
+				fn deserialize(read_buf: &mut &[u8]) -> Result<Self, Error> {
+					let bytes = take_bytes(read_buf, mem::size_of::<$typ>())?;
+					Ok(<$typ>::from_le_bytes(
+						bytes.try_into().expect("take_bytes returns exactly the requested length"),
+					))
+				}
+			}
+		)*
+	};
+}
+
+impl_serialize_bytes_for_uint!(u8, u16, u32, u64, u128);
+
+impl SerializeBytes for usize {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		(*self as u64).serialize(write_buf);
+	}
+}
+
+impl DeserializeBytes for usize {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, Error> {
+		let length = u64::deserialize(read_buf)?;
+		usize::try_from(length).map_err(|_| Error::LengthOverflow { length })
+	}
+}
+
+impl SerializeBytes for bool {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		u8::from(*self).serialize(write_buf);
+	}
+}
+
+impl DeserializeBytes for bool {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, Error> {
+		match u8::deserialize(read_buf)? {
+			0 => Ok(false),
+			1 => Ok(true),
+			_ => Err(Error::NonCanonical { name: "bool" }),
+		}
+	}
+}
+
+impl<T: SerializeBytes + ?Sized> SerializeBytes for &T {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		(**self).serialize(write_buf);
+	}
+}
+
+impl<T: SerializeBytes> SerializeBytes for [T] {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.len().serialize(write_buf);
+		for item in self {
+			item.serialize(write_buf);
+		}
+	}
+}
+
+impl<T: SerializeBytes> SerializeBytes for Vec<T> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.as_slice().serialize(write_buf);
+	}
+}
+
+impl<T: DeserializeBytes> DeserializeBytes for Vec<T> {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, Error> {
+		let len = usize::deserialize(read_buf)?;
+		// An adversarial length prefix must not force a large allocation, so only reserve as many
+		// elements as there are bytes left. This is a capacity hint, not a limit: elements with an
+		// empty encoding, such as zero-length arrays, are still decoded `len` times.
+		let mut items = Vec::with_capacity(len.min(read_buf.len()));
+		for _ in 0..len {
+			items.push(T::deserialize(read_buf)?);
+		}
+		Ok(items)
+	}
+}
+
+impl<T: SerializeBytes, const N: usize> SerializeBytes for [T; N] {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		for item in self {
+			item.serialize(write_buf);
+		}
+	}
+}
+
+impl<T: DeserializeBytes, const N: usize> DeserializeBytes for [T; N] {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, Error> {
+		let items = (0..N)
+			.map(|_| T::deserialize(read_buf))
+			.collect::<Result<Vec<_>, _>>()?;
+		let mut items = items.into_iter();
+		Ok(array::from_fn(|_| items.next().expect("items has exactly N elements")))
+	}
+}
+
+impl<T: SerializeBytes> SerializeBytes for Option<T> {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.is_some().serialize(write_buf);
+		if let Some(value) = self {
+			value.serialize(write_buf);
+		}
+	}
+}
+
+impl<T: DeserializeBytes> DeserializeBytes for Option<T> {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, Error> {
+		if bool::deserialize(read_buf)? {
+			Ok(Some(T::deserialize(read_buf)?))
+		} else {
+			Ok(None)
+		}
+	}
+}
+
+impl<A: SerializeBytes, B: SerializeBytes> SerializeBytes for (A, B) {
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.0.serialize(write_buf);
+		self.1.serialize(write_buf);
+	}
+}
+
+impl<A: DeserializeBytes, B: DeserializeBytes> DeserializeBytes for (A, B) {
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, Error> {
+		let a = A::deserialize(read_buf)?;
+		let b = B::deserialize(read_buf)?;
+		Ok((a, b))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_round_trip_nested() {
+		let value: Vec<(u32, Option<Vec<u128>>)> =
+			vec![(1, None), (2, Some(vec![])), (7, Some(vec![3, u128::MAX]))];
+		let bytes = to_versioned_bytes(&value);
+		assert_eq!(from_versioned_bytes::<Vec<(u32, Option<Vec<u128>>)>>(&bytes).unwrap(), value);
+	}
+
+	#[test]
+	fn test_integers_are_little_endian() {
+		let mut write_buf = Vec::new();
+		0x0102u16.serialize(&mut write_buf);
+		7usize.serialize(&mut write_buf);
+		assert_eq!(write_buf, [0x02, 0x01, 7, 0, 0, 0, 0, 0, 0, 0]);
+	}
+
+	#[test]
+	fn test_reject_malformed_encodings() {
+		let bytes = to_versioned_bytes(&vec![1u64, 2u64]);
+
+		assert!(matches!(
+			from_versioned_bytes::<Vec<u64>>(&bytes[..bytes.len() - 1]),
+			Err(Error::NotEnoughBytes { .. })
+		));
+
+		let mut trailing = bytes.clone();
+		trailing.push(0);
+		assert!(matches!(
+			from_versioned_bytes::<Vec<u64>>(&trailing),
+			Err(Error::TrailingBytes { n_bytes: 1 })
+		));
+
+		let mut wrong_version = bytes.clone();
+		wrong_version[0] ^= 0xff;
+		assert!(matches!(
+			from_versioned_bytes::<Vec<u64>>(&wrong_version),
+			Err(Error::UnsupportedVersion { .. })
+		));
+
+		let mut huge_length = bytes;
+		huge_length[2..10].copy_from_slice(&u64::MAX.to_le_bytes());
+		assert!(from_versioned_bytes::<Vec<u64>>(&huge_length).is_err());
+
+		assert!(matches!(
+			bool::deserialize(&mut [2u8].as_slice()),
+			Err(Error::NonCanonical { .. })
+		));
+	}
+}
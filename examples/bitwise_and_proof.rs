@@ -1,6 +1,6 @@
 use anyhow::Result;
 use binius_core::{
-	challenger::HashChallenger,
+	challenger::{CanGrind, DomainSeparator, HashChallenger},
 	oracle::{CommittedBatchSpec, CommittedId, CompositePolyOracle, MultilinearOracleSet},
 	poly_commit::{tensor_pcs, PolyCommitScheme},
 	polynomial::{EvaluationDomain, MultilinearComposite, MultilinearExtension},
@@ -28,6 +28,8 @@ use tracing::instrument;
 
 composition_poly!(BitwiseAndConstraint[a, b, c] = a * b - c);
 
+const DOMAIN_TAG: &str = "binius.example.bitwise_and";
+
 #[instrument(skip_all)]
 fn prove<PCS, CH>(
 	log_size: usize,
@@ -44,7 +46,9 @@ where
 	CH: CanObserve<BinaryField128b>
 		+ CanObserve<PCS::Commitment>
 		+ CanSample<BinaryField128b>
-		+ CanSampleBits<usize>,
+		+ CanSampleBits<usize>
+		+ CanGrind<BinaryField128b>
+		+ DomainSeparator,
 {
 	let commit_span = tracing::debug_span!("commit").entered();
 	assert_eq!(pcs.n_vars(), log_size);
@@ -54,6 +58,8 @@ where
 	assert_eq!(constraints.len(), 1);
 	let constraint = constraints[0].clone();
 
+	challenger.observe_domain_tag(DOMAIN_TAG, &(&*trace, log_size));
+
 	// Round 1
 	let (abc_comm, abc_committed) = pcs
 		.commit(&[
@@ -161,7 +167,8 @@ where
 	CH: CanObserve<BinaryField128b>
 		+ CanObserve<PCS::Commitment>
 		+ CanSample<BinaryField128b>
-		+ CanSampleBits<usize>,
+		+ CanSampleBits<usize>
+		+ DomainSeparator,
 {
 	assert_eq!(pcs.n_vars(), log_size);
 
@@ -175,6 +182,8 @@ where
 		evalcheck_proof,
 	} = proof;
 
+	challenger.observe_domain_tag(DOMAIN_TAG, &(&*trace, log_size));
+
 	// Observe the trace commitments
 	challenger.observe(abc_comm.clone());
 
@@ -321,7 +330,7 @@ fn main() {
 		PackedBinaryField8x16b,
 		_,
 		PackedBinaryField1x128b,
-	>(SECURITY_BITS, log_size, 3, log_inv_rate, false)
+	>(SECURITY_BITS, log_size, 3, log_inv_rate, false, 0)
 	.unwrap();
 
 	tracing::debug!(
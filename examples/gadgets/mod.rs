@@ -0,0 +1,10 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Reusable constraint gadgets shared by the example SNARKs.
+//!
+//! The word layout follows [`binius_core::constraint_system::gadgets`], which provides the
+//! gadgets that the core tests share with the examples.
+
+pub mod sha256;
+
+pub use binius_core::constraint_system::gadgets::{u32add, LOG_U32_BITS};
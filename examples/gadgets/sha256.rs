@@ -0,0 +1,447 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! The SHA-256 compression function.
+//!
+//! Each compression instance occupies one 32-bit word slot of every column, so a trace with
+//! `log_size` variables holds `2^(log_size - 5)` independent compressions. All 64 rounds and the
+//! message schedule are laid out side by side in separate columns. The working variables
+//! `b, c, d` and `f, g, h` are the values of `a` and `e` from the previous three rounds, so only
+//! `a` and `e` get columns of their own.
+//!
+//! Rotations and shifts are shifted oracles, XORs of them are linear combinations, and each
+//! 32-bit addition is a [`U32AddOracle`] with committed sum and carry columns. The gadget adds its
+//! oracles and constraints to a [`ConstraintSystemBuilder`].
+
+use super::{u32add::U32AddOracle, LOG_U32_BITS};
+use anyhow::{ensure, Result};
+use binius_core::{
+	constraint_system::{ConstraintSystemBuilder, ConstraintSystemWitness},
+	oracle::{BatchId, OracleId, ShiftVariant},
+	polynomial::{
+		composition::ArithExpr,
+		transparent::multilinear_extension::MultilinearExtensionTransparent, MultilinearExtension,
+	},
+};
+use binius_field::{
+	BinaryField1b, ExtensionField, PackedBinaryField32x1b, PackedField, TowerField,
+};
+use bytemuck::{must_cast_slice_mut, Pod};
+use rayon::prelude::*;
+use std::{array, collections::BTreeMap, iter, ops::Range};
+
+/// The SHA-256 round constants.
+pub const ROUND_CONSTS_K: [u32; 64] = [
+	0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+	0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+	0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+	0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+	0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+	0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+	0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+	0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// The SHA-256 initial hash value.
+pub const INIT_STATE: [u32; 8] = [
+	0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const SMALL_SIGMA_0: [WordShift; 3] = [WordShift::Rotr(7), WordShift::Rotr(18), WordShift::Shr(3)];
+const SMALL_SIGMA_1: [WordShift; 3] =
+	[WordShift::Rotr(17), WordShift::Rotr(19), WordShift::Shr(10)];
+const BIG_SIGMA_0: [WordShift; 3] = [WordShift::Rotr(2), WordShift::Rotr(13), WordShift::Rotr(22)];
+const BIG_SIGMA_1: [WordShift; 3] = [WordShift::Rotr(6), WordShift::Rotr(11), WordShift::Rotr(25)];
+
+/// A right rotation or right shift of a 32-bit word.
+#[derive(Debug, Clone, Copy)]
+enum WordShift {
+	Rotr(usize),
+	Shr(usize),
+}
+
+impl WordShift {
+	fn apply(self, x: u32) -> u32 {
+		match self {
+			WordShift::Rotr(n) => x.rotate_right(n as u32),
+			WordShift::Shr(n) => x >> n,
+		}
+	}
+
+	fn add_oracle<F: TowerField>(
+		self,
+		builder: &mut ConstraintSystemBuilder<F>,
+		id: OracleId,
+	) -> Result<OracleId> {
+		let shifted = match self {
+			WordShift::Rotr(n) => {
+				builder.add_shifted(id, 32 - n, LOG_U32_BITS, ShiftVariant::CircularLeft)?
+			}
+			WordShift::Shr(n) => {
+				builder.add_shifted(id, n, LOG_U32_BITS, ShiftVariant::LogicalRight)?
+			}
+		};
+		Ok(shifted)
+	}
+}
+
+/// Oracles of one of the σ/Σ functions, the XOR of three rotations or shifts of a word.
+#[derive(Debug, Clone, Copy)]
+pub struct XorShiftsOracle {
+	pub shifted: [OracleId; 3],
+	/// The XOR of the shifted words, a linear combination
+	pub out: OracleId,
+	spec: [WordShift; 3],
+}
+
+impl XorShiftsOracle {
+	fn new<F: TowerField>(
+		builder: &mut ConstraintSystemBuilder<F>,
+		log_size: usize,
+		input: OracleId,
+		spec: [WordShift; 3],
+	) -> Result<Self> {
+		let shifted = array::try_from_fn(|i| spec[i].add_oracle(builder, input))?;
+		let out = builder.add_linear_combination(log_size, shifted.map(|id| (id, F::ONE)))?;
+		Ok(Self { shifted, out, spec })
+	}
+
+	fn assign(&self, x: u32, assignment: &mut Vec<(OracleId, u32)>) -> u32 {
+		let shifted = self.spec.map(|shift| shift.apply(x));
+		assignment.extend(iter::zip(self.shifted, shifted));
+		let out = shifted[0] ^ shifted[1] ^ shifted[2];
+		assignment.push((self.out, out));
+		out
+	}
+}
+
+/// Oracles computing the message schedule word `W_t` for `t >= 16`.
+#[derive(Debug, Clone, Copy)]
+pub struct ScheduleOracle {
+	/// σ0(W_{t-15})
+	pub sigma0: XorShiftsOracle,
+	/// σ1(W_{t-2})
+	pub sigma1: XorShiftsOracle,
+	/// σ1(W_{t-2}) + W_{t-7}, then + σ0(W_{t-15}), then + W_{t-16}, which is `W_t`
+	pub adds: [U32AddOracle; 3],
+}
+
+/// Oracles of one compression round.
+#[derive(Debug, Clone, Copy)]
+pub struct RoundOracle {
+	/// Σ0(a)
+	pub big_sigma0: XorShiftsOracle,
+	/// Σ1(e)
+	pub big_sigma1: XorShiftsOracle,
+	/// Ch(e, f, g), committed
+	pub ch: OracleId,
+	/// Maj(a, b, c), committed
+	pub maj: OracleId,
+	/// The round constant `K_t`, repeated for every compression
+	pub round_const: OracleId,
+	/// In order: h + Σ1(e), + Ch, + K_t, + W_t (which is T1), Σ0(a) + Maj (which is T2),
+	/// d + T1 (the next `e`), T1 + T2 (the next `a`)
+	pub adds: [U32AddOracle; 7],
+}
+
+/// Oracles of the SHA-256 compression function applied to a batch of (state, block) inputs.
+#[derive(Debug)]
+pub struct Sha256CompressionOracle {
+	/// Batch of all committed 1-bit columns
+	pub batch_id: BatchId,
+	/// Input chaining value
+	pub state_in: [OracleId; 8],
+	/// Message block
+	pub message: [OracleId; 16],
+	pub schedule: [ScheduleOracle; 48],
+	pub rounds: [RoundOracle; 64],
+	/// Additions of the input chaining value and the final working variables
+	pub output_adds: [U32AddOracle; 8],
+	n_committed: usize,
+}
+
+impl Sha256CompressionOracle {
+	pub fn new<F: TowerField>(
+		builder: &mut ConstraintSystemBuilder<F>,
+		log_size: usize,
+	) -> Result<Self> {
+		ensure!(log_size >= LOG_U32_BITS, "trace must hold at least one 32-bit word per column");
+
+		// All committed columns are allocated in a single batch, so that they share one
+		// commitment: the inputs, then the sum and carry-out columns of every addition, and the Ch
+		// and Maj outputs.
+		let mut batch_scope = builder
+			.oracles_mut()
+			.build_committed_batch(log_size, BinaryField1b::TOWER_LEVEL);
+		let state_in = batch_scope.add_multiple::<8>();
+		let message = batch_scope.add_multiple::<16>();
+		let schedule_sums: [[[OracleId; 2]; 3]; 48] =
+			array::from_fn(|_| array::from_fn(|_| batch_scope.add_multiple::<2>()));
+		let round_ch_maj: [[OracleId; 2]; 64] = array::from_fn(|_| batch_scope.add_multiple::<2>());
+		let round_sums: [[[OracleId; 2]; 7]; 64] =
+			array::from_fn(|_| array::from_fn(|_| batch_scope.add_multiple::<2>()));
+		let output_sums: [[OracleId; 2]; 8] = array::from_fn(|_| batch_scope.add_multiple::<2>());
+		let batch_id = batch_scope.build();
+		let n_committed = builder.oracles().committed_batch(batch_id).n_polys;
+
+		// Message schedule words, either message words or sums of earlier schedule words.
+		let w = |t: usize| {
+			if t < 16 {
+				message[t]
+			} else {
+				schedule_sums[t - 16][2][0]
+			}
+		};
+		let schedule = array::try_from_fn(|i| {
+			let t = i + 16;
+			let sigma0 = XorShiftsOracle::new(builder, log_size, w(t - 15), SMALL_SIGMA_0)?;
+			let sigma1 = XorShiftsOracle::new(builder, log_size, w(t - 2), SMALL_SIGMA_1)?;
+			let [[z0, c0], [z1, c1], [z2, c2]] = schedule_sums[i];
+			let adds = [
+				U32AddOracle::new(builder, sigma1.out, w(t - 7), z0, c0)?,
+				U32AddOracle::new(builder, z0, sigma0.out, z1, c1)?,
+				U32AddOracle::new(builder, z1, w(t - 16), z2, c2)?,
+			];
+			Ok::<_, anyhow::Error>(ScheduleOracle {
+				sigma0,
+				sigma1,
+				adds,
+			})
+		})?;
+
+		// The working variables `a` and `e` after round `t`, where negative rounds refer to the
+		// input chaining value.
+		let a = |t: isize| {
+			if t < 0 {
+				state_in[(-1 - t) as usize]
+			} else {
+				round_sums[t as usize][6][0]
+			}
+		};
+		let e = |t: isize| {
+			if t < 0 {
+				state_in[4 + (-1 - t) as usize]
+			} else {
+				round_sums[t as usize][5][0]
+			}
+		};
+
+		let rounds = array::try_from_fn(|i| {
+			let t = i as isize;
+			let abcd = [a(t - 1), a(t - 2), a(t - 3), a(t - 4)];
+			let efgh = [e(t - 1), e(t - 2), e(t - 3), e(t - 4)];
+			let big_sigma0 = XorShiftsOracle::new(builder, log_size, abcd[0], BIG_SIGMA_0)?;
+			let big_sigma1 = XorShiftsOracle::new(builder, log_size, efgh[0], BIG_SIGMA_1)?;
+
+			// Ch(e, f, g) and Maj(a, b, c) of the working variables
+			let [ch, maj] = round_ch_maj[i];
+			let [e_var, f_var, g_var, _] = efgh.map(ArithExpr::Var);
+			builder.assert_zero(
+				"sha256.ch",
+				e_var.clone() * f_var + (ArithExpr::Const(F::ONE) - e_var) * g_var
+					- ArithExpr::Var(ch),
+			);
+			let [a_var, b_var, c_var, _] = abcd.map(ArithExpr::Var);
+			builder.assert_zero(
+				"sha256.maj",
+				a_var.clone() * b_var.clone() + a_var * c_var.clone() + b_var * c_var
+					- ArithExpr::Var(maj),
+			);
+
+			let round_const_single = builder.add_transparent(MultilinearExtensionTransparent(
+				MultilinearExtension::from_values(vec![PackedBinaryField32x1b::from(
+					ROUND_CONSTS_K[i],
+				)])?
+				.specialize::<F>(),
+			))?;
+			let round_const = builder
+				.oracles_mut()
+				.add_repeating(round_const_single, log_size - LOG_U32_BITS)?;
+
+			let [[z0, c0], [z1, c1], [z2, c2], [t1, c3], [t2, c4], [e_next, c5], [a_next, c6]] =
+				round_sums[i];
+			let adds = [
+				U32AddOracle::new(builder, efgh[3], big_sigma1.out, z0, c0)?,
+				U32AddOracle::new(builder, z0, ch, z1, c1)?,
+				U32AddOracle::new(builder, z1, round_const, z2, c2)?,
+				U32AddOracle::new(builder, z2, w(i), t1, c3)?,
+				U32AddOracle::new(builder, big_sigma0.out, maj, t2, c4)?,
+				U32AddOracle::new(builder, abcd[3], t1, e_next, c5)?,
+				U32AddOracle::new(builder, t1, t2, a_next, c6)?,
+			];
+			Ok::<_, anyhow::Error>(RoundOracle {
+				big_sigma0,
+				big_sigma1,
+				ch,
+				maj,
+				round_const,
+				adds,
+			})
+		})?;
+
+		let output_adds = array::try_from_fn(|i| {
+			let last = if i < 4 {
+				a(63 - i as isize)
+			} else {
+				e(67 - i as isize)
+			};
+			let [z, c_out] = output_sums[i];
+			U32AddOracle::new(builder, state_in[i], last, z, c_out)
+		})?;
+
+		Ok(Self {
+			batch_id,
+			state_in,
+			message,
+			schedule,
+			rounds,
+			output_adds,
+			n_committed,
+		})
+	}
+
+	/// The committed columns, in batch order.
+	pub fn iter_committed(&self) -> Range<OracleId> {
+		let first = self.state_in[0];
+		first..first + self.n_committed
+	}
+
+	/// Compress one message block and append the witness words of every column to `assignment`.
+	///
+	/// Returns the output chaining value.
+	pub fn assign(
+		&self,
+		state_in: [u32; 8],
+		message: [u32; 16],
+		assignment: &mut Vec<(OracleId, u32)>,
+	) -> [u32; 8] {
+		assignment.extend(iter::zip(self.state_in, state_in));
+		assignment.extend(iter::zip(self.message, message));
+
+		let mut w = [0u32; 64];
+		w[..16].copy_from_slice(&message);
+		for (i, schedule) in self.schedule.iter().enumerate() {
+			let t = i + 16;
+			let sigma0 = schedule.sigma0.assign(w[t - 15], assignment);
+			let sigma1 = schedule.sigma1.assign(w[t - 2], assignment);
+			let z0 = schedule.adds[0].assign(sigma1, w[t - 7], assignment);
+			let z1 = schedule.adds[1].assign(z0, sigma0, assignment);
+			w[t] = schedule.adds[2].assign(z1, w[t - 16], assignment);
+		}
+
+		// a and e after each round, preceded by the input values at indices 0..4
+		let mut a = [0u32; 68];
+		let mut e = [0u32; 68];
+		for t in 0..4 {
+			a[t] = state_in[3 - t];
+			e[t] = state_in[7 - t];
+		}
+		for (i, round) in self.rounds.iter().enumerate() {
+			let t = i + 4;
+			let big_sigma0 = round.big_sigma0.assign(a[t - 1], assignment);
+			let big_sigma1 = round.big_sigma1.assign(e[t - 1], assignment);
+			let ch = (e[t - 1] & e[t - 2]) ^ (!e[t - 1] & e[t - 3]);
+			let maj = (a[t - 1] & a[t - 2]) ^ (a[t - 1] & a[t - 3]) ^ (a[t - 2] & a[t - 3]);
+			assignment.extend([
+				(round.ch, ch),
+				(round.maj, maj),
+				(round.round_const, ROUND_CONSTS_K[i]),
+			]);
+
+			let z0 = round.adds[0].assign(e[t - 4], big_sigma1, assignment);
+			let z1 = round.adds[1].assign(z0, ch, assignment);
+			let z2 = round.adds[2].assign(z1, ROUND_CONSTS_K[i], assignment);
+			let t1 = round.adds[3].assign(z2, w[i], assignment);
+			let t2 = round.adds[4].assign(big_sigma0, maj, assignment);
+			e[t] = round.adds[5].assign(a[t - 4], t1, assignment);
+			a[t] = round.adds[6].assign(t1, t2, assignment);
+		}
+
+		array::from_fn(|i| {
+			let last = if i < 4 { a[67 - i] } else { e[71 - i] };
+			self.output_adds[i].assign(state_in[i], last, assignment)
+		})
+	}
+
+	/// Generate the witness for compressing each `(state, block)` pair in one word slot.
+	///
+	/// The number of inputs must fill the trace, that is be `2^(log_size - 5)`.
+	pub fn generate_witness<P>(
+		&self,
+		log_size: usize,
+		inputs: &[([u32; 8], [u32; 16])],
+	) -> Result<Sha256CompressionWitness<P>>
+	where
+		P: PackedField<Scalar = BinaryField1b> + Pod,
+	{
+		ensure!(
+			inputs.len() == 1 << (log_size - LOG_U32_BITS),
+			"number of compressions must be 2^(log_size - {LOG_U32_BITS})"
+		);
+
+		let assignments = inputs
+			.par_iter()
+			.map(|&(state_in, message)| {
+				let mut assignment = Vec::new();
+				let state_out = self.assign(state_in, message, &mut assignment);
+				(assignment, state_out)
+			})
+			.collect::<Vec<_>>();
+
+		let oracle_ids = assignments[0]
+			.0
+			.iter()
+			.map(|&(id, _)| id)
+			.collect::<Vec<_>>();
+		let columns = oracle_ids
+			.par_iter()
+			.enumerate()
+			.map(|(j, &id)| {
+				let mut values = vec![P::default(); 1 << (log_size - P::LOG_WIDTH)];
+				let words = must_cast_slice_mut::<_, u32>(&mut values);
+				for (word, (assignment, _)) in iter::zip(words, &assignments) {
+					*word = assignment[j].1;
+				}
+				(id, values)
+			})
+			.collect();
+
+		Ok(Sha256CompressionWitness {
+			columns,
+			state_out: assignments
+				.into_iter()
+				.map(|(_, state_out)| state_out)
+				.collect(),
+		})
+	}
+}
+
+/// Witness of [`Sha256CompressionOracle`], covering every oracle the gadget defines except the
+/// transparent round constants, which are instead given for their repeated oracles.
+pub struct Sha256CompressionWitness<P: PackedField> {
+	columns: BTreeMap<OracleId, Vec<P>>,
+	/// Output chaining values, one per compression
+	pub state_out: Vec<[u32; 8]>,
+}
+
+impl<P: PackedField> Sha256CompressionWitness<P> {
+	/// The witness of the gadget's constraint system, with the committed columns and the derived
+	/// columns that the trace generation already computed.
+	pub fn to_witness<PW>(&self, oracle: &Sha256CompressionOracle) -> ConstraintSystemWitness<P, PW>
+	where
+		PW: PackedField,
+		PW::Scalar: ExtensionField<P::Scalar>,
+	{
+		let committed = oracle.iter_committed();
+		let mut witness = ConstraintSystemWitness::new();
+		for (&id, values) in self.columns.iter() {
+			let poly = MultilinearExtension::from_values_slice(values.as_slice())
+				.expect("column length is a power of two");
+			if committed.contains(&id) {
+				witness.set_committed(id, poly);
+			} else {
+				witness.set(id, poly.specialize_arc_dyn());
+			}
+		}
+		witness
+	}
+}
@@ -10,32 +10,49 @@
 #![feature(array_try_map)]
 #![feature(step_trait)]
 
-use anyhow::{ensure, Result};
+use anyhow::{bail, ensure, Result};
 use binius_core::{
-	oracle::{MultilinearOracleSet, OracleId, ShiftVariant},
+	challenger::{CanGrind, CanObserve, CanSample, CanSampleBits, DomainSeparator, HashChallenger},
+	oracle::{BatchId, CompositePolyOracle, MultilinearOracleSet, OracleId, ShiftVariant},
+	poly_commit::{tensor_pcs, PolyCommitScheme},
 	polynomial::{
-		composition::{empty_mix_composition, index_composition},
+		composition::{empty_mix_composition, index_composition, IndexComposition, LinearFactor},
 		transparent::{
 			constant::Constant, multilinear_extension::MultilinearExtensionTransparent,
 			step_down::StepDown,
 		},
-		CompositionPoly, Error as PolynomialError, MultilinearComposite, MultilinearExtension,
-		MultilinearPoly,
+		CompositionPoly, Error as PolynomialError, EvaluationDomain, MultilinearComposite,
+		MultilinearExtension, MultilinearPoly,
 	},
+	protocols::{
+		evalcheck::SameQueryPcsClaim,
+		greedy_evalcheck,
+		greedy_evalcheck::{GreedyEvalcheckProof, GreedyEvalcheckProveOutput},
+		zerocheck::{self, ZerocheckClaim, ZerocheckProof, ZerocheckProveOutput},
+	},
+	serialization::{
+		from_versioned_bytes, to_versioned_bytes, DeserializeBytes, Error as SerializationError,
+		SerializeBytes,
+	},
+	witness::{find_constraint_violations, MultilinearWitnessIndex},
 };
 use binius_field::{
 	packed::set_packed_slice, AESTowerField128b, AESTowerField8b, BinaryField128b, BinaryField1b,
-	BinaryField8b, ExtensionField, Field, PackedAESBinaryField32x8b, PackedAESBinaryField64x8b,
-	PackedBinaryField16x8b, PackedBinaryField256x1b, PackedField, PackedFieldIndexable, TowerField,
+	BinaryField8b, ExtensionField, Field, PackedAESBinaryField16x8b, PackedAESBinaryField64x8b,
+	PackedBinaryField128x1b, PackedBinaryField16x8b, PackedBinaryField1x128b,
+	PackedBinaryField8x16b, PackedField, PackedFieldIndexable, TowerField,
 };
-use binius_hash::Groestl256Core;
+use binius_hash::{Groestl256Core, GroestlHasher};
 use binius_macros::composition_poly;
+use binius_utils::{
+	examples::get_log_trace_size, rayon::adjust_thread_pool, tracing::init_tracing,
+};
 use itertools::chain;
 use rand::thread_rng;
-use std::{array, env, iter, slice, sync::Arc};
-use tracing::instrument;
-use tracing_profile::{CsvLayer, PrintTreeConfig, PrintTreeLayer};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use std::{array, fmt::Debug, iter, slice, sync::Arc, time::Instant};
+use tracing::{info, instrument};
+
+const DOMAIN_TAG: &str = "binius.example.groestl";
 
 /// Number of rounds in a Grøstl-256 compression
 const N_ROUNDS: usize = 10;
@@ -67,25 +84,6 @@ const MIX_BYTES_VEC: [AESTowerField8b; 8] = [
 	AESTowerField8b::new(0x07),
 ];
 
-fn init_tracing() {
-	if let Ok(csv_path) = env::var("PROFILE_CSV_FILE") {
-		let _ = tracing_subscriber::registry()
-			.with(CsvLayer::new(csv_path))
-			.with(tracing_subscriber::fmt::layer())
-			.try_init();
-	} else {
-		let _ = tracing_subscriber::registry()
-			.with(PrintTreeLayer::new(PrintTreeConfig {
-				attention_above_percent: 25.0,
-				relevant_above_percent: 2.5,
-				hide_below_percent: 1.0,
-				display_unaccounted: false,
-			}))
-			.with(tracing_subscriber::fmt::layer())
-			.try_init();
-	}
-}
-
 fn p_round_consts() -> [Vec<PackedBinaryField16x8b>; 8] {
 	let mut p_round_consts = [PackedBinaryField16x8b::zero(); 8];
 	for i in 0..8 {
@@ -100,6 +98,11 @@ fn p_round_consts() -> [Vec<PackedBinaryField16x8b>; 8] {
 
 #[derive(Debug)]
 struct TraceOracle {
+	/// Batch of committed 1-bit columns
+	trace1b_batch_id: BatchId,
+	/// Batch of committed 8-bit columns
+	trace8b_batch_id: BatchId,
+
 	// Transparent columns
 	/// Single-bit selector of whether a round should link its output to the next input.
 	round_selector: OracleId,
@@ -157,14 +160,14 @@ impl TraceOracle {
 		let mut batch_scope_1b =
 			oracles.build_committed_batch(log_size, BinaryField1b::TOWER_LEVEL);
 		let p_sub_bytes_inv_bits = batch_scope_1b.add_multiple::<{ 64 * 8 }>();
-		let _trace1b_batch_id = batch_scope_1b.build();
+		let trace1b_batch_id = batch_scope_1b.build();
 
 		let mut batch_scope_8b =
 			oracles.build_committed_batch(log_size, BinaryField8b::TOWER_LEVEL);
 		let p_in = batch_scope_8b.add_multiple::<64>();
 		let p_out = batch_scope_8b.add_multiple::<64>();
 		let p_sub_bytes_prod = batch_scope_8b.add_multiple::<64>();
-		let _trace8b_batch_id = batch_scope_8b.build();
+		let trace8b_batch_id = batch_scope_8b.build();
 
 		// Virtual witness columns
 		let p_sub_bytes_inv = array::try_from_fn(|ij| {
@@ -193,6 +196,8 @@ impl TraceOracle {
 			p_in.try_map(|p_in_i| oracles.add_shifted(p_in_i, 1, 4, ShiftVariant::LogicalRight))?;
 
 		Ok(TraceOracle {
+			trace1b_batch_id,
+			trace8b_batch_id,
 			round_selector,
 			p_default_round_const,
 			p_round_consts,
@@ -278,6 +283,134 @@ where
 	fn binary_tower_level(&self) -> usize {
 		AESTowerField8b::TOWER_LEVEL
 	}
+
+	fn linear_factors(&self) -> Option<Vec<LinearFactor<F>>> {
+		let terms = iter::once((0, -F::ONE))
+			.chain(
+				self.mix_bytes
+					.iter()
+					.enumerate()
+					.map(|(i, &coeff)| (i + 1, F::from(coeff))),
+			)
+			.collect();
+		Some(vec![LinearFactor {
+			constant: F::ZERO,
+			terms,
+		}])
+	}
+}
+
+/// The trace constraints, grouped by kind, with one constraint per state byte in each group.
+///
+/// Every constraint is indexed into the columns of [`TraceOracle::iter_oracles`].
+struct TraceConstraints<F8b: Clone> {
+	/// Number of trace columns
+	n_columns: usize,
+	/// SubBytes product consistency
+	sub_bytes_product: [IndexComposition<SubBytesProductCheck, 4>; 64],
+	/// SubBytes: x * inv == 1 OR x == 0
+	sub_bytes_input_zero: [IndexComposition<ProductImpliesInputZero, 3>; 64],
+	/// SubBytes: x * inv == 1 OR inv == 0
+	sub_bytes_inverse_zero: [IndexComposition<ProductImpliesInverseZero, 2>; 64],
+	/// ShiftBytes + MixBytes
+	mix_column: [IndexComposition<MixColumn<F8b>, 9>; 64],
+	/// Consistency checks with next round
+	next_round_link: [IndexComposition<ConditionalEquality, 3>; 64],
+}
+
+impl<F8b> TraceConstraints<F8b>
+where
+	F8b: TowerField + From<AESTowerField8b>,
+{
+	fn new(trace_oracle: &TraceOracle) -> Result<Self> {
+		let zerocheck_column_ids = trace_oracle.iter_oracles().collect::<Vec<_>>();
+
+		let sub_bytes_product = array::try_from_fn(|ij| {
+			index_composition(
+				&zerocheck_column_ids,
+				[
+					trace_oracle.p_in[ij],
+					trace_oracle.p_sub_bytes_inv[ij],
+					trace_oracle.p_sub_bytes_prod[ij],
+					trace_oracle.p_round_const(ij),
+				],
+				SubBytesProductCheck,
+			)
+		})?;
+
+		let sub_bytes_input_zero = array::try_from_fn(|ij| {
+			index_composition(
+				&zerocheck_column_ids,
+				[
+					trace_oracle.p_in[ij],
+					trace_oracle.p_sub_bytes_prod[ij],
+					trace_oracle.p_round_const(ij),
+				],
+				ProductImpliesInputZero,
+			)
+		})?;
+
+		let sub_bytes_inverse_zero = array::try_from_fn(|ij| {
+			index_composition(
+				&zerocheck_column_ids,
+				[
+					trace_oracle.p_sub_bytes_inv[ij],
+					trace_oracle.p_sub_bytes_prod[ij],
+				],
+				ProductImpliesInverseZero,
+			)
+		})?;
+
+		let mix_column = array::try_from_fn(|ij| {
+			let i = ij / 8;
+			let j = ij % 8;
+
+			let mut oracle_ids = [OracleId::default(); 9];
+			oracle_ids[0] = trace_oracle.p_out[ij];
+			for k in 0..8 {
+				let j_prime = (j + k) % 8;
+				let i_prime = (i + j_prime) % 8;
+				oracle_ids[k + 1] = trace_oracle.p_sub_bytes_out[i_prime * 8 + j_prime];
+			}
+
+			index_composition(&zerocheck_column_ids, oracle_ids, MixColumn::<F8b>::default())
+		})?;
+
+		let next_round_link = array::try_from_fn(|ij| {
+			index_composition(
+				&zerocheck_column_ids,
+				[
+					trace_oracle.p_out[ij],
+					trace_oracle.p_next_in[ij],
+					trace_oracle.round_selector,
+				],
+				ConditionalEquality,
+			)
+		})?;
+
+		Ok(Self {
+			n_columns: zerocheck_column_ids.len(),
+			sub_bytes_product,
+			sub_bytes_input_zero,
+			sub_bytes_inverse_zero,
+			mix_column,
+			next_round_link,
+		})
+	}
+
+	/// Mix all constraints into a single composition with powers of `challenge`.
+	fn mix<FW>(self, challenge: FW) -> Result<impl CompositionPoly<FW>>
+	where
+		FW: TowerField + ExtensionField<F8b>,
+	{
+		let mix = empty_mix_composition(self.n_columns, challenge)
+			.include(self.sub_bytes_product)?
+			.include(self.sub_bytes_input_zero)?
+			.include(self.sub_bytes_inverse_zero)?
+			.include(self.mix_column)?
+			.include(self.next_round_link)?;
+		Ok(mix)
+	}
 }
 
 fn make_constraints<F8b, FW>(
@@ -288,79 +421,7 @@ where
 	F8b: TowerField + From<AESTowerField8b>,
 	FW: TowerField + ExtensionField<F8b>,
 {
-	let zerocheck_column_ids = trace_oracle.iter_oracles().collect::<Vec<_>>();
-
-	let mix = empty_mix_composition(zerocheck_column_ids.len(), challenge);
-
-	// SubBytes product consistency
-	let mix = mix.include(array::try_from_fn::<_, 64, _>(|ij| {
-		index_composition(
-			&zerocheck_column_ids,
-			[
-				trace_oracle.p_in[ij],
-				trace_oracle.p_sub_bytes_inv[ij],
-				trace_oracle.p_sub_bytes_prod[ij],
-				trace_oracle.p_round_const(ij),
-			],
-			SubBytesProductCheck,
-		)
-	})?)?;
-
-	// SubBytes: x * inv == 1 OR x == 0
-	let mix = mix.include(array::try_from_fn::<_, 64, _>(|ij| {
-		index_composition(
-			&zerocheck_column_ids,
-			[
-				trace_oracle.p_in[ij],
-				trace_oracle.p_sub_bytes_prod[ij],
-				trace_oracle.p_round_const(ij),
-			],
-			ProductImpliesInputZero,
-		)
-	})?)?;
-
-	// SubBytes: x * inv == 1 OR inv == 0
-	let mix = mix.include(array::try_from_fn::<_, 64, _>(|ij| {
-		index_composition(
-			&zerocheck_column_ids,
-			[
-				trace_oracle.p_sub_bytes_inv[ij],
-				trace_oracle.p_sub_bytes_prod[ij],
-			],
-			ProductImpliesInverseZero,
-		)
-	})?)?;
-
-	// ShiftBytes + MixBytes
-	let mix = mix.include(array::try_from_fn::<_, 64, _>(|ij| {
-		let i = ij / 8;
-		let j = ij % 8;
-
-		let mut oracle_ids = [OracleId::default(); 9];
-		oracle_ids[0] = trace_oracle.p_out[ij];
-		for k in 0..8 {
-			let j_prime = (j + k) % 8;
-			let i_prime = (i + j_prime) % 8;
-			oracle_ids[k + 1] = trace_oracle.p_sub_bytes_out[i_prime * 8 + j_prime];
-		}
-
-		index_composition(&zerocheck_column_ids, oracle_ids, MixColumn::<F8b>::default())
-	})?)?;
-
-	// consistency checks with next round
-	let mix = mix.include(array::try_from_fn::<_, 64, _>(|ij| {
-		index_composition(
-			&zerocheck_column_ids,
-			[
-				trace_oracle.p_out[ij],
-				trace_oracle.p_next_in[ij],
-				trace_oracle.round_selector,
-			],
-			ConditionalEquality,
-		)
-	})?)?;
-
-	Ok(mix)
+	TraceConstraints::<F8b>::new(trace_oracle)?.mix(challenge)
 }
 
 struct TraceWitness<P1b: PackedField, P8b: PackedField> {
@@ -413,6 +474,96 @@ impl<P1b: PackedField, P8b: PackedField> TraceWitness<P1b, P8b> {
 		});
 		chain!(fixed_polys_1b, fixed_polys_8b, trace_polys_1b, trace_polys_8b).collect()
 	}
+
+	/// Index the witness columns by oracle, in the same order as [`TraceOracle::iter_oracles`].
+	fn to_index<F>(&self, trace_oracle: &TraceOracle) -> Result<MultilinearWitnessIndex<'_, F>>
+	where
+		F: ExtensionField<P1b::Scalar> + ExtensionField<P8b::Scalar>,
+	{
+		let mut index = MultilinearWitnessIndex::new();
+		for (oracle, poly) in iter::zip(trace_oracle.iter_oracles(), self.all_polys()?) {
+			index.set(oracle, poly);
+		}
+		Ok(index)
+	}
+
+	fn commit_polys_1b(&self) -> Result<Vec<MultilinearExtension<P1b>>> {
+		self.p_sub_bytes_inv_bits
+			.iter()
+			.map(|values| Ok(MultilinearExtension::from_values_slice(values.as_slice())?))
+			.collect()
+	}
+}
+
+impl<P1b, P8b> TraceWitness<P1b, P8b>
+where
+	P1b: PackedField,
+	P8b: PackedFieldIndexable<Scalar = AESTowerField8b>,
+{
+	/// The committed 8-bit columns, mapped from the AES tower into the canonical tower, which is
+	/// the basis the oracles are defined in.
+	fn commit_polys_8b(
+		&self,
+	) -> Result<Vec<MultilinearExtension<'static, PackedBinaryField16x8b>>> {
+		chain!(self.p_in.iter(), self.p_out.iter(), self.p_sub_bytes_prod.iter())
+			.map(|values| {
+				let values = PackedFieldIndexable::unpack_scalars(values.as_slice())
+					.chunks(PackedBinaryField16x8b::WIDTH)
+					.map(|chunk| PackedBinaryField16x8b::from_fn(|i| chunk[i].into()))
+					.collect();
+				Ok(MultilinearExtension::from_values(values)?)
+			})
+			.collect()
+	}
+}
+
+struct Proof<F: Field, PCSComm1b, PCSProof1b, PCSComm8b, PCSProof8b> {
+	trace1b_comm: PCSComm1b,
+	trace8b_comm: PCSComm8b,
+	zerocheck_proof: ZerocheckProof<F>,
+	evalcheck_proof: GreedyEvalcheckProof<F>,
+	trace1b_open_proof: PCSProof1b,
+	trace8b_open_proof: PCSProof8b,
+}
+
+impl<F, PCSComm1b, PCSProof1b, PCSComm8b, PCSProof8b> SerializeBytes
+	for Proof<F, PCSComm1b, PCSProof1b, PCSComm8b, PCSProof8b>
+where
+	F: Field + SerializeBytes,
+	PCSComm1b: SerializeBytes,
+	PCSProof1b: SerializeBytes,
+	PCSComm8b: SerializeBytes,
+	PCSProof8b: SerializeBytes,
+{
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.trace1b_comm.serialize(write_buf);
+		self.trace8b_comm.serialize(write_buf);
+		self.zerocheck_proof.serialize(write_buf);
+		self.evalcheck_proof.serialize(write_buf);
+		self.trace1b_open_proof.serialize(write_buf);
+		self.trace8b_open_proof.serialize(write_buf);
+	}
+}
+
+impl<F, PCSComm1b, PCSProof1b, PCSComm8b, PCSProof8b> DeserializeBytes
+	for Proof<F, PCSComm1b, PCSProof1b, PCSComm8b, PCSProof8b>
+where
+	F: Field + DeserializeBytes,
+	PCSComm1b: DeserializeBytes,
+	PCSProof1b: DeserializeBytes,
+	PCSComm8b: DeserializeBytes,
+	PCSProof8b: DeserializeBytes,
+{
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		Ok(Self {
+			trace1b_comm: PCSComm1b::deserialize(read_buf)?,
+			trace8b_comm: PCSComm8b::deserialize(read_buf)?,
+			zerocheck_proof: ZerocheckProof::deserialize(read_buf)?,
+			evalcheck_proof: GreedyEvalcheckProof::deserialize(read_buf)?,
+			trace1b_open_proof: PCSProof1b::deserialize(read_buf)?,
+			trace8b_open_proof: PCSProof8b::deserialize(read_buf)?,
+		})
+	}
 }
 
 fn s_box(x: AESTowerField8b) -> AESTowerField8b {
@@ -583,37 +734,384 @@ where
 	witness
 }
 
-fn check_witness<FW, P1b: PackedField, P8b: PackedField>(
+/// Check that the witness satisfies every trace constraint, reporting the violations by name.
+fn check_witness<P1b: PackedField, P8b: PackedField>(
 	log_size: usize,
-	constraint: impl CompositionPoly<FW>,
+	oracles: &MultilinearOracleSet<BinaryField128b>,
+	trace_oracle: &TraceOracle,
 	witness: &TraceWitness<P1b, P8b>,
 ) -> Result<()>
 where
-	FW: ExtensionField<P1b::Scalar> + ExtensionField<P8b::Scalar>,
+	AESTowerField128b: ExtensionField<P1b::Scalar> + ExtensionField<P8b::Scalar>,
 {
-	let composite = MultilinearComposite::new(log_size, constraint, witness.all_polys::<FW>()?)?;
-	for z in 0..1 << log_size {
-		let constraint_eval = composite.evaluate_on_hypercube(z)?;
-		ensure!(constraint_eval == FW::ZERO);
+	const MAX_VIOLATIONS: usize = 10;
+
+	/// Name the constraints of a group by the state byte they constrain.
+	fn named<C>(name: &'static str, constraints: [C; 64]) -> impl Iterator<Item = (String, C)> {
+		constraints
+			.into_iter()
+			.enumerate()
+			.map(move |(ij, constraint)| (format!("{name}[{ij}]"), constraint))
 	}
+
+	let zerocheck_column_oracles = trace_oracle
+		.iter_oracles()
+		.map(|id| oracles.oracle(id))
+		.collect();
+	let oracle = CompositePolyOracle::new(
+		log_size,
+		zerocheck_column_oracles,
+		make_constraints::<BinaryField8b, _>(trace_oracle, BinaryField128b::ONE)?,
+	)?;
+	let witness_index = witness.to_index::<AESTowerField128b>(trace_oracle)?;
+	let constraints = TraceConstraints::<AESTowerField8b>::new(trace_oracle)?;
+
+	let violations = chain!(
+		find_constraint_violations(
+			&oracle,
+			named("sub_bytes_product", constraints.sub_bytes_product),
+			&witness_index,
+			MAX_VIOLATIONS,
+		)?,
+		find_constraint_violations(
+			&oracle,
+			named("sub_bytes_input_zero", constraints.sub_bytes_input_zero),
+			&witness_index,
+			MAX_VIOLATIONS,
+		)?,
+		find_constraint_violations(
+			&oracle,
+			named("sub_bytes_inverse_zero", constraints.sub_bytes_inverse_zero),
+			&witness_index,
+			MAX_VIOLATIONS,
+		)?,
+		find_constraint_violations(
+			&oracle,
+			named("mix_column", constraints.mix_column),
+			&witness_index,
+			MAX_VIOLATIONS,
+		)?,
+		find_constraint_violations(
+			&oracle,
+			named("next_round_link", constraints.next_round_link),
+			&witness_index,
+			MAX_VIOLATIONS,
+		)?,
+	)
+	.collect::<Vec<_>>();
+	ensure!(violations.is_empty(), "trace does not satisfy the constraints: {violations:?}");
+	Ok(())
+}
+
+/// Split the evalcheck output into the opening claims for the 1-bit and 8-bit trace batches.
+fn split_same_query_claims<F: Field>(
+	trace_oracle: &TraceOracle,
+	same_query_claims: Vec<(BatchId, SameQueryPcsClaim<F>)>,
+) -> Result<(SameQueryPcsClaim<F>, SameQueryPcsClaim<F>)> {
+	ensure!(same_query_claims.len() == 2, "expected one opening claim per committed batch");
+	let mut trace1b_claim = None;
+	let mut trace8b_claim = None;
+	for (batch_id, same_query_claim) in same_query_claims {
+		if batch_id == trace_oracle.trace1b_batch_id {
+			trace1b_claim = Some(same_query_claim);
+		} else if batch_id == trace_oracle.trace8b_batch_id {
+			trace8b_claim = Some(same_query_claim);
+		}
+	}
+	match (trace1b_claim, trace8b_claim) {
+		(Some(trace1b_claim), Some(trace8b_claim)) => Ok((trace1b_claim, trace8b_claim)),
+		_ => bail!("missing opening claim for a committed batch"),
+	}
+}
+
+type GroestlProof<PCS1b, PCS8b> = Proof<
+	BinaryField128b,
+	<PCS1b as PolyCommitScheme<PackedBinaryField128x1b, BinaryField128b>>::Commitment,
+	<PCS1b as PolyCommitScheme<PackedBinaryField128x1b, BinaryField128b>>::Proof,
+	<PCS8b as PolyCommitScheme<PackedBinaryField16x8b, BinaryField128b>>::Commitment,
+	<PCS8b as PolyCommitScheme<PackedBinaryField16x8b, BinaryField128b>>::Proof,
+>;
+
+/// Prove the trace of Grøstl-256 P permutations.
+///
+/// The oracles are defined over the canonical binary tower, while the prover computes over the
+/// isomorphic AES tower, in which the witness is generated.
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip_all)]
+fn prove<PCS1b, PCS8b, CH>(
+	log_size: usize,
+	oracles: &mut MultilinearOracleSet<BinaryField128b>,
+	trace_oracle: &TraceOracle,
+	pcs1b: &PCS1b,
+	pcs8b: &PCS8b,
+	mut challenger: CH,
+	witness: &TraceWitness<PackedBinaryField128x1b, PackedAESBinaryField16x8b>,
+) -> Result<GroestlProof<PCS1b, PCS8b>>
+where
+	PCS1b: PolyCommitScheme<PackedBinaryField128x1b, BinaryField128b, Error: Debug, Proof: 'static>,
+	PCS8b: PolyCommitScheme<PackedBinaryField16x8b, BinaryField128b, Error: Debug, Proof: 'static>,
+	CH: CanObserve<BinaryField128b>
+		+ CanObserve<PCS1b::Commitment>
+		+ CanObserve<PCS8b::Commitment>
+		+ CanSample<BinaryField128b>
+		+ CanSampleBits<usize>
+		+ CanGrind<BinaryField128b>
+		+ DomainSeparator
+		+ Clone,
+{
+	assert_eq!(pcs1b.n_vars(), log_size);
+	assert_eq!(pcs8b.n_vars(), log_size);
+
+	let mut witness_index = witness.to_index::<AESTowerField128b>(trace_oracle)?;
+
+	challenger.observe_domain_tag(DOMAIN_TAG, &(&*oracles, log_size));
+
+	// Round 1
+	let trace1b_commit_polys = witness.commit_polys_1b()?;
+	let (trace1b_comm, trace1b_committed) = pcs1b.commit(&trace1b_commit_polys)?;
+	challenger.observe(trace1b_comm.clone());
+
+	let trace8b_commit_polys = witness.commit_polys_8b()?;
+	let (trace8b_comm, trace8b_committed) = pcs8b.commit(&trace8b_commit_polys)?;
+	challenger.observe(trace8b_comm.clone());
+
+	// Zerocheck mixing
+	let mixing_challenge: BinaryField128b = challenger.sample();
+
+	let mix_composition_verifier =
+		make_constraints::<BinaryField8b, _>(trace_oracle, mixing_challenge)?;
+	let mix_composition_prover = make_constraints::<AESTowerField8b, _>(
+		trace_oracle,
+		AESTowerField128b::from(mixing_challenge),
+	)?;
+
+	let zerocheck_column_oracles = trace_oracle
+		.iter_oracles()
+		.map(|id| oracles.oracle(id))
+		.collect();
+	let zerocheck_claim = ZerocheckClaim {
+		poly: CompositePolyOracle::new(
+			log_size,
+			zerocheck_column_oracles,
+			mix_composition_verifier,
+		)?,
+	};
+
+	let zerocheck_witness = MultilinearComposite::new(
+		log_size,
+		mix_composition_prover,
+		witness.all_polys::<AESTowerField128b>()?,
+	)?;
+
+	// Zerocheck
+	let zerocheck_domain = EvaluationDomain::<AESTowerField8b>::new_isomorphic::<AESTowerField8b>(
+		zerocheck_claim.poly.max_individual_degree() + 1,
+	)?;
+
+	let switchover_fn = |extension_degree| match extension_degree {
+		128 => 5,
+		_ => 1,
+	};
+
+	let ZerocheckProveOutput {
+		evalcheck_claim,
+		zerocheck_proof,
+	} = zerocheck::prove::<BinaryField128b, AESTowerField128b, AESTowerField8b, _, _>(
+		&zerocheck_claim,
+		zerocheck_witness,
+		&zerocheck_domain,
+		&mut challenger,
+		switchover_fn,
+	)?;
+
+	// Evalcheck
+	let GreedyEvalcheckProveOutput {
+		same_query_claims,
+		proof: evalcheck_proof,
+	} = greedy_evalcheck::prove(
+		oracles,
+		&mut witness_index,
+		[evalcheck_claim],
+		switchover_fn,
+		&mut challenger,
+	)?;
+
+	let (trace1b_claim, trace8b_claim) = split_same_query_claims(trace_oracle, same_query_claims)?;
+
+	let trace1b_open_proof = pcs1b.prove_evaluation(
+		&mut challenger,
+		&trace1b_committed,
+		&trace1b_commit_polys,
+		&trace1b_claim.eval_point,
+	)?;
+	let trace8b_open_proof = pcs8b.prove_evaluation(
+		&mut challenger,
+		&trace8b_committed,
+		&trace8b_commit_polys,
+		&trace8b_claim.eval_point,
+	)?;
+
+	Ok(Proof {
+		trace1b_comm,
+		trace8b_comm,
+		zerocheck_proof,
+		evalcheck_proof,
+		trace1b_open_proof,
+		trace8b_open_proof,
+	})
+}
+
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip_all)]
+fn verify<PCS1b, PCS8b, CH>(
+	log_size: usize,
+	oracles: &mut MultilinearOracleSet<BinaryField128b>,
+	trace_oracle: &TraceOracle,
+	pcs1b: &PCS1b,
+	pcs8b: &PCS8b,
+	mut challenger: CH,
+	proof: GroestlProof<PCS1b, PCS8b>,
+) -> Result<()>
+where
+	PCS1b: PolyCommitScheme<PackedBinaryField128x1b, BinaryField128b, Error: Debug, Proof: 'static>,
+	PCS8b: PolyCommitScheme<PackedBinaryField16x8b, BinaryField128b, Error: Debug, Proof: 'static>,
+	CH: CanObserve<BinaryField128b>
+		+ CanObserve<PCS1b::Commitment>
+		+ CanObserve<PCS8b::Commitment>
+		+ CanSample<BinaryField128b>
+		+ CanSampleBits<usize>
+		+ DomainSeparator,
+{
+	let Proof {
+		trace1b_comm,
+		trace8b_comm,
+		zerocheck_proof,
+		evalcheck_proof,
+		trace1b_open_proof,
+		trace8b_open_proof,
+	} = proof;
+
+	challenger.observe_domain_tag(DOMAIN_TAG, &(&*oracles, log_size));
+
+	// Round 1
+	challenger.observe(trace1b_comm.clone());
+	challenger.observe(trace8b_comm.clone());
+
+	// Zerocheck mixing
+	let mixing_challenge: BinaryField128b = challenger.sample();
+	let mix_composition = make_constraints::<BinaryField8b, _>(trace_oracle, mixing_challenge)?;
+
+	// Zerocheck
+	let zerocheck_column_oracles = trace_oracle
+		.iter_oracles()
+		.map(|id| oracles.oracle(id))
+		.collect();
+	let zerocheck_claim = ZerocheckClaim {
+		poly: CompositePolyOracle::new(log_size, zerocheck_column_oracles, mix_composition)?,
+	};
+
+	let evalcheck_claim = zerocheck::verify(&zerocheck_claim, zerocheck_proof, &mut challenger)?;
+
+	// Evalcheck
+	let same_query_claims =
+		greedy_evalcheck::verify(oracles, [evalcheck_claim], evalcheck_proof, &mut challenger)?;
+
+	let (trace1b_claim, trace8b_claim) = split_same_query_claims(trace_oracle, same_query_claims)?;
+
+	pcs1b.verify_evaluation(
+		&mut challenger,
+		&trace1b_comm,
+		&trace1b_claim.eval_point,
+		trace1b_open_proof,
+		&trace1b_claim.evals,
+	)?;
+	pcs8b.verify_evaluation(
+		&mut challenger,
+		&trace8b_comm,
+		&trace8b_claim.eval_point,
+		trace8b_open_proof,
+		&trace8b_claim.evals,
+	)?;
+
 	Ok(())
 }
 
 fn main() {
+	const SECURITY_BITS: usize = 100;
+
+	adjust_thread_pool()
+		.as_ref()
+		.expect("failed to init thread pool");
+
 	init_tracing();
 
-	let log_size = 8;
+	// Values below 14 are rejected by `find_proof_size_optimal_pcs()`.
+	let log_size = get_log_trace_size().unwrap_or(14);
+	let log_inv_rate = 1;
+
+	info!(n_permutations = 1 << (log_size - LOG_COMPRESSION_BLOCK), "Grøstl-256 P permutation");
+
+	// Set up the public parameters
+	let pcs1b = tensor_pcs::find_proof_size_optimal_pcs::<
+		_,
+		PackedBinaryField128x1b,
+		_,
+		PackedBinaryField8x16b,
+		_,
+		PackedBinaryField8x16b,
+		_,
+		PackedBinaryField1x128b,
+	>(SECURITY_BITS, log_size, 64 * 8, log_inv_rate, false, 0)
+	.unwrap();
+	let pcs8b = tensor_pcs::find_proof_size_optimal_pcs::<
+		_,
+		PackedBinaryField16x8b,
+		_,
+		PackedBinaryField8x16b,
+		_,
+		PackedBinaryField8x16b,
+		_,
+		PackedBinaryField1x128b,
+	>(SECURITY_BITS, log_size, 64 * 3, log_inv_rate, false, 0)
+	.unwrap();
 
 	let mut oracles = MultilinearOracleSet::<BinaryField128b>::new();
 	let trace_oracle = TraceOracle::new(&mut oracles, log_size).unwrap();
 
-	let witness = generate_trace::<PackedBinaryField256x1b, PackedAESBinaryField32x8b>(log_size);
-
-	let mut rng = thread_rng();
-	let mix_challenge = <AESTowerField128b as Field>::random(&mut rng);
-	let prover_composition =
-		make_constraints::<AESTowerField8b, _>(&trace_oracle, mix_challenge).unwrap();
-
-	check_witness(log_size, prover_composition, &witness)
-		.expect("trace does not satisify the constraints");
+	let challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+
+	let witness = generate_trace::<PackedBinaryField128x1b, PackedAESBinaryField16x8b>(log_size);
+
+	check_witness(log_size, &oracles, &trace_oracle, &witness).unwrap();
+
+	let start = Instant::now();
+	let proof = prove(
+		log_size,
+		&mut oracles.clone(),
+		&trace_oracle,
+		&pcs1b,
+		&pcs8b,
+		challenger.clone(),
+		&witness,
+	)
+	.unwrap();
+	let prove_time = start.elapsed();
+
+	// Send the proof to the verifier as bytes
+	let proof_bytes = to_versioned_bytes(&proof);
+	info!(proof_size = proof_bytes.len(), ?prove_time, "Proved");
+	let proof = from_versioned_bytes(&proof_bytes).unwrap();
+
+	let start = Instant::now();
+	verify(
+		log_size,
+		&mut oracles.clone(),
+		&trace_oracle,
+		&pcs1b,
+		&pcs8b,
+		challenger.clone(),
+		proof,
+	)
+	.unwrap();
+	info!(verify_time = ?start.elapsed(), "Verified");
 }
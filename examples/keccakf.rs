@@ -6,7 +6,7 @@ extern crate core;
 
 use anyhow::Result;
 use binius_core::{
-	challenger::{CanObserve, CanSample, CanSampleBits, HashChallenger},
+	challenger::{CanGrind, CanObserve, CanSample, CanSampleBits, DomainSeparator, HashChallenger},
 	oracle::{BatchId, CompositePolyOracle, MultilinearOracleSet, OracleId, ShiftVariant},
 	poly_commit::{tensor_pcs, PolyCommitScheme},
 	polynomial::{
@@ -21,6 +21,10 @@ use binius_core::{
 		greedy_evalcheck::{GreedyEvalcheckProof, GreedyEvalcheckProveOutput},
 		zerocheck::{self, ZerocheckClaim, ZerocheckProof, ZerocheckProveOutput},
 	},
+	serialization::{
+		from_versioned_bytes, to_versioned_bytes, DeserializeBytes, Error as SerializationError,
+		SerializeBytes,
+	},
 	witness::MultilinearWitnessIndex,
 };
 use binius_field::{
@@ -123,6 +127,8 @@ impl<F: Field> CompositionPoly<F> for SumComposition {
 	}
 }
 
+const DOMAIN_TAG: &str = "binius.example.keccakf";
+
 composition_poly!(ChiComposition[a, b0, b1, b2] = a - (b0 + (1 - b1) * b2));
 composition_poly!(ChiIotaComposition[a, b0, b1, b2, rc] = a - (rc + b0 + (1 - b1) * b2));
 composition_poly!(RoundConsistency[state_out, next_state_in, select] = (state_out - next_state_in) * select);
@@ -361,6 +367,36 @@ struct Proof<F: Field, PCSComm, PCSProof> {
 	trace_open_proof: PCSProof,
 }
 
+impl<F, PCSComm, PCSProof> SerializeBytes for Proof<F, PCSComm, PCSProof>
+where
+	F: Field + SerializeBytes,
+	PCSComm: SerializeBytes,
+	PCSProof: SerializeBytes,
+{
+	fn serialize(&self, write_buf: &mut Vec<u8>) {
+		self.trace_comm.serialize(write_buf);
+		self.zerocheck_proof.serialize(write_buf);
+		self.evalcheck_proof.serialize(write_buf);
+		self.trace_open_proof.serialize(write_buf);
+	}
+}
+
+impl<F, PCSComm, PCSProof> DeserializeBytes for Proof<F, PCSComm, PCSProof>
+where
+	F: Field + DeserializeBytes,
+	PCSComm: DeserializeBytes,
+	PCSProof: DeserializeBytes,
+{
+	fn deserialize(read_buf: &mut &[u8]) -> Result<Self, SerializationError> {
+		Ok(Self {
+			trace_comm: PCSComm::deserialize(read_buf)?,
+			zerocheck_proof: ZerocheckProof::deserialize(read_buf)?,
+			evalcheck_proof: GreedyEvalcheckProof::deserialize(read_buf)?,
+			trace_open_proof: PCSProof::deserialize(read_buf)?,
+		})
+	}
+}
+
 #[instrument]
 #[allow(clippy::needless_range_loop)]
 fn generate_trace<P: PackedField + Pod>(log_size: usize) -> TraceWitness<P> {
@@ -483,15 +519,23 @@ fn prove<P, F, PW, FsStep, FS, PCS, CH>(
 ) -> Result<Proof<F, PCS::Commitment, PCS::Proof>>
 where
 	P: PackedField<Scalar = BinaryField1b> + Pod,
-	F: TowerField + From<PW> + Step,
+	F: TowerField + SerializeBytes + From<PW> + Step,
 	PW: TowerField + From<F> + ExtensionField<FS>,
 	FsStep: TowerField + Step,
 	FS: TowerField + From<FsStep>,
 	PCS: PolyCommitScheme<P, F, Error: Debug, Proof: 'static>,
-	CH: CanObserve<F> + CanObserve<PCS::Commitment> + CanSample<F> + CanSampleBits<usize> + Clone,
+	CH: CanObserve<F>
+		+ CanObserve<PCS::Commitment>
+		+ CanSample<F>
+		+ CanSampleBits<usize>
+		+ CanGrind<F>
+		+ DomainSeparator
+		+ Clone,
 {
 	let mut trace_witness = witness.to_index::<PW>(fixed_oracle, trace_oracle);
 
+	challenger.observe_domain_tag(DOMAIN_TAG, &(&*oracles, log_size));
+
 	// Round 1
 	let trace_commit_polys = witness.commit_polys().collect::<Vec<_>>();
 	let (trace_comm, trace_committed) = pcs.commit(&trace_commit_polys)?;
@@ -593,9 +637,13 @@ fn verify<P, F, PCS, CH>(
 ) -> Result<()>
 where
 	P: PackedField<Scalar = BinaryField1b>,
-	F: TowerField,
+	F: TowerField + SerializeBytes,
 	PCS: PolyCommitScheme<P, F, Error: Debug, Proof: 'static>,
-	CH: CanObserve<F> + CanObserve<PCS::Commitment> + CanSample<F> + CanSampleBits<usize>,
+	CH: CanObserve<F>
+		+ CanObserve<PCS::Commitment>
+		+ CanSample<F>
+		+ CanSampleBits<usize>
+		+ DomainSeparator,
 {
 	let Proof {
 		trace_comm,
@@ -604,6 +652,8 @@ where
 		trace_open_proof,
 	} = proof;
 
+	challenger.observe_domain_tag(DOMAIN_TAG, &(&*oracles, log_size));
+
 	// Round 1
 	challenger.observe(trace_comm.clone());
 
@@ -762,7 +812,7 @@ fn main() {
 		PackedBinaryField8x16b,
 		_,
 		PackedBinaryField1x128b,
-	>(SECURITY_BITS, log_size, 60, log_inv_rate, false)
+	>(SECURITY_BITS, log_size, 60, log_inv_rate, false, 0)
 	.unwrap();
 
 	let mut oracles = MultilinearOracleSet::new();
@@ -793,6 +843,11 @@ fn main() {
 	)
 	.unwrap();
 
+	// Send the proof to the verifier as bytes
+	let proof_bytes = to_versioned_bytes(&proof);
+	tracing::info!(proof_size = proof_bytes.len(), "Serialized proof");
+	let proof = from_versioned_bytes(&proof_bytes).unwrap();
+
 	verify(
 		log_size,
 		&mut oracles.clone(),
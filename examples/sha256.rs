@@ -0,0 +1,149 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! This an example SNARK for proving the SHA-256 compression function.
+//!
+//! The constraints are defined by the reusable [`Sha256CompressionOracle`] gadget, which proves
+//! many independent compressions of (chaining value, message block) pairs at once.
+
+#![feature(array_try_from_fn)]
+#![feature(step_trait)]
+
+mod gadgets;
+
+use binius_core::{
+	challenger::HashChallenger,
+	constraint_system::{self, ConstraintSystemBuilder},
+	poly_commit::tensor_pcs,
+	serialization::{from_versioned_bytes, to_versioned_bytes},
+};
+use binius_field::{
+	BinaryField128b, PackedBinaryField128x1b, PackedBinaryField1x128b, PackedBinaryField8x16b,
+};
+use binius_hash::GroestlHasher;
+use binius_utils::{
+	examples::get_log_trace_size, rayon::adjust_thread_pool, tracing::init_tracing,
+};
+use gadgets::{
+	sha256::{Sha256CompressionOracle, INIT_STATE},
+	LOG_U32_BITS,
+};
+use rand::{thread_rng, Rng};
+use sha2::{compress256, digest::generic_array::GenericArray};
+use tracing::{debug, info};
+
+// mod field_types is a selector of different sets of types which provide
+// equivalent functionality but may differ significantly in performance.
+#[cfg(feature = "aes-tower")]
+mod field_types {
+	pub type Field = binius_field::AESTowerField128b;
+	pub type DomainField = binius_field::AESTowerField8b;
+	pub type DomainFieldWithStep = binius_field::AESTowerField8b;
+}
+
+#[cfg(not(feature = "aes-tower"))]
+mod field_types {
+	pub type Field = binius_field::BinaryField128bPolyval;
+	pub type DomainField = binius_field::BinaryField128bPolyval;
+	pub type DomainFieldWithStep = binius_field::BinaryField128b;
+}
+
+/// Compress with the reference implementation from the `sha2` crate.
+fn reference_compress(state: [u32; 8], message: [u32; 16]) -> [u32; 8] {
+	let mut block = [0u8; 64];
+	for (bytes, word) in block.chunks_exact_mut(4).zip(message) {
+		bytes.copy_from_slice(&word.to_be_bytes());
+	}
+	let mut state = state;
+	compress256(&mut state, &[GenericArray::clone_from_slice(&block)]);
+	state
+}
+
+fn main() {
+	const SECURITY_BITS: usize = 100;
+
+	adjust_thread_pool()
+		.as_ref()
+		.expect("failed to init thread pool");
+
+	init_tracing();
+
+	// Values below 14 are rejected by `find_proof_size_optimal_pcs()`.
+	let log_size = get_log_trace_size().unwrap_or(14);
+	let log_inv_rate = 1;
+	let n_compressions = 1 << (log_size - LOG_U32_BITS);
+
+	debug!(n_compressions, "SHA-256 compression");
+
+	let mut builder = ConstraintSystemBuilder::<BinaryField128b>::new();
+	let oracle = Sha256CompressionOracle::new(&mut builder, log_size).unwrap();
+	let constraint_system = builder.build().unwrap();
+
+	let pcs = tensor_pcs::find_proof_size_optimal_pcs::<
+		_,
+		PackedBinaryField128x1b,
+		_,
+		PackedBinaryField8x16b,
+		_,
+		PackedBinaryField8x16b,
+		_,
+		PackedBinaryField1x128b,
+	>(
+		SECURITY_BITS,
+		log_size,
+		constraint_system
+			.oracles()
+			.committed_batch(oracle.batch_id)
+			.n_polys,
+		log_inv_rate,
+		false,
+		0,
+	)
+	.unwrap();
+
+	let challenger = <HashChallenger<_, GroestlHasher<_>>>::new();
+
+	// Random message blocks, compressed from the initial hash value or from random states
+	let mut rng = thread_rng();
+	let inputs = (0..n_compressions)
+		.map(|i| {
+			let state_in = if i == 0 { INIT_STATE } else { rng.gen() };
+			(state_in, rng.gen())
+		})
+		.collect::<Vec<_>>();
+
+	let trace = oracle
+		.generate_witness::<PackedBinaryField128x1b>(log_size, &inputs)
+		.unwrap();
+
+	// Check the trace against the reference implementation
+	for (&(state_in, message), state_out) in inputs.iter().zip(trace.state_out.iter()) {
+		assert_eq!(*state_out, reference_compress(state_in, message));
+	}
+
+	let witness = trace.to_witness::<field_types::Field>(&oracle);
+
+	info!("Proving");
+
+	let switchover_fn = |extension_degree| match extension_degree {
+		128 => 5,
+		_ => 1,
+	};
+	let proof = constraint_system::prove::<
+		_,
+		BinaryField128b,
+		field_types::Field,
+		field_types::DomainFieldWithStep,
+		field_types::DomainField,
+		_,
+		_,
+	>(&constraint_system, witness, &pcs, switchover_fn, challenger.clone())
+	.unwrap();
+
+	// Send the proof to the verifier as bytes
+	let proof_bytes = to_versioned_bytes(&proof);
+	info!(proof_size = proof_bytes.len(), "Serialized proof");
+	let proof = from_versioned_bytes(&proof_bytes).unwrap();
+
+	info!("Verifying");
+	constraint_system::verify(&constraint_system, &pcs, proof, challenger.clone()).unwrap();
+}